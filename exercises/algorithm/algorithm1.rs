@@ -1,9 +1,11 @@
 /*
 	single linked list merge
 	This problem requires you to merge two ordered singly linked lists into one ordered singly linked list
+	The nodes also keep a prev link so the list can be walked and popped from the back.
 */
 
 use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
 use std::ptr::NonNull;
 use std::vec::*;
 
@@ -11,6 +13,7 @@ use std::vec::*;
 struct Node<T> {
     val: T,
     next: Option<NonNull<Node<T>>>,
+    prev: Option<NonNull<Node<T>>>,
 }
 
 impl<T> Node<T> {
@@ -18,9 +21,22 @@ impl<T> Node<T> {
         Node {
             val: t,
             next: None,
+            prev: None,
         }
     }
 }
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexOutOfRange {
+    pub index: usize,
+    pub length: usize,
+}
+
+impl Display for IndexOutOfRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "index {} is out of range for a list of length {}", self.index, self.length)
+    }
+}
+
 #[derive(Debug)]
 struct LinkedList<T> {
     length: u32,
@@ -28,13 +44,13 @@ struct LinkedList<T> {
     end: Option<NonNull<Node<T>>>,
 }
 
-impl<T: std::cmp::PartialOrd> Default for LinkedList<T> {
+impl<T> Default for LinkedList<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: std::cmp::PartialOrd> LinkedList<T> {
+impl<T> LinkedList<T> {
     pub fn new() -> Self {
         Self {
             length: 0,
@@ -44,17 +60,225 @@ impl<T: std::cmp::PartialOrd> LinkedList<T> {
     }
 
     pub fn add(&mut self, obj: T) {
-        let mut node = Box::new(Node::new(obj));
+        let node = Box::new(Node::new(obj));
+        self.attach_back(unsafe { NonNull::new_unchecked(Box::into_raw(node)) });
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.start.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.end.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.start.map(|node_ptr| self.unlink(node_ptr))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.end.map(|node_ptr| self.unlink(node_ptr))
+    }
+
+    /// Insert value so that it ends up at index, index may equal the length
+    pub fn insert_at(&mut self, index: usize, value: T) -> Result<(), IndexOutOfRange> {
+        let length = self.length as usize;
+        if index > length {
+            return Err(IndexOutOfRange { index, length });
+        }
+        let prev = match self.node_at(index) {
+            Some(next_ptr) => unsafe { (*next_ptr.as_ptr()).prev },
+            None => self.end,
+        };
+        let node = Box::new(Node::new(value));
+        self.attach_after(prev, unsafe { NonNull::new_unchecked(Box::into_raw(node)) });
+        Ok(())
+    }
+
+    /// Remove the value at index, None if there is no such index
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        let node_ptr = self.node_at(index)?;
+        Some(self.unlink(node_ptr))
+    }
+
+    /// Keep [0, at) in self and return [at, len) as a new list, no values are moved
+    ///
+    /// Panics if at is greater than the length, like Vec::split_off
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let length = self.length as usize;
+        if at > length {
+            panic!("{}", IndexOutOfRange { index: at, length });
+        }
+        self.detach_tail(at)
+    }
+
+    // Find the node at index, walking from whichever end is closer
+    fn node_at(&self, index: usize) -> Option<NonNull<Node<T>>> {
+        let length = self.length as usize;
+        if index >= length {
+            return None;
+        }
+        if index <= length / 2 {
+            let mut node = self.start;
+            for _ in 0..index {
+                node = node.and_then(|n| unsafe { (*n.as_ptr()).next });
+            }
+            node
+        } else {
+            let mut node = self.end;
+            for _ in index + 1..length {
+                node = node.and_then(|n| unsafe { (*n.as_ptr()).prev });
+            }
+            node
+        }
+    }
+
+    // Take a node out of the chain and free it, returning its value
+    fn unlink(&mut self, node_ptr: NonNull<Node<T>>) -> T {
+        self.detach(node_ptr);
+        unsafe { Box::from_raw(node_ptr.as_ptr()) }.val
+    }
+
+    // Take a node out of the chain without freeing it, fixing up both neighbours and the ends
+    fn detach(&mut self, node_ptr: NonNull<Node<T>>) {
+        let node = unsafe { &mut *node_ptr.as_ptr() };
+        match node.prev {
+            None => self.start = node.next,
+            Some(prev_ptr) => unsafe { (*prev_ptr.as_ptr()).next = node.next },
+        }
+        match node.next {
+            None => self.end = node.prev,
+            Some(next_ptr) => unsafe { (*next_ptr.as_ptr()).prev = node.prev },
+        }
+        node.prev = None;
         node.next = None;
-        let node_ptr = Some(unsafe { NonNull::new_unchecked(Box::into_raw(node)) });
+        self.length -= 1;
+    }
+
+    // Hang a node that belongs to no list after the given node, or at the front for None
+    fn attach_after(&mut self, after: Option<NonNull<Node<T>>>, node_ptr: NonNull<Node<T>>) {
+        let next = match after {
+            None => self.start,
+            Some(after_ptr) => unsafe { (*after_ptr.as_ptr()).next },
+        };
+        unsafe {
+            (*node_ptr.as_ptr()).prev = after;
+            (*node_ptr.as_ptr()).next = next;
+        }
+        match after {
+            None => self.start = Some(node_ptr),
+            Some(after_ptr) => unsafe { (*after_ptr.as_ptr()).next = Some(node_ptr) },
+        }
+        match next {
+            None => self.end = Some(node_ptr),
+            Some(next_ptr) => unsafe { (*next_ptr.as_ptr()).prev = Some(node_ptr) },
+        }
+        self.length += 1;
+    }
+
+    // Hang a node that belongs to no list onto the back of this one
+    fn attach_back(&mut self, node_ptr: NonNull<Node<T>>) {
+        unsafe {
+            (*node_ptr.as_ptr()).next = None;
+            (*node_ptr.as_ptr()).prev = self.end;
+        }
         match self.end {
-            None => self.start = node_ptr,
-            Some(end_ptr) => unsafe { (*end_ptr.as_ptr()).next = node_ptr },
+            None => self.start = Some(node_ptr),
+            Some(end_ptr) => unsafe { (*end_ptr.as_ptr()).next = Some(node_ptr) },
         }
-        self.end = node_ptr;
+        self.end = Some(node_ptr);
         self.length += 1;
     }
 
+    // Move every node of other onto the back of this list in O(1)
+    fn splice_back(&mut self, other: &mut Self) {
+        match (self.end, other.start) {
+            (_, None) => return,
+            (None, _) => {
+                self.start = other.start;
+            }
+            (Some(end_ptr), Some(start_ptr)) => unsafe {
+                (*end_ptr.as_ptr()).next = Some(start_ptr);
+                (*start_ptr.as_ptr()).prev = Some(end_ptr);
+            },
+        }
+        self.end = other.end;
+        self.length += other.length;
+        other.start = None;
+        other.end = None;
+        other.length = 0;
+    }
+
+    // Cut the list before index at, returning the nodes [at, len) as a new list
+    fn detach_tail(&mut self, at: usize) -> Self {
+        let mut tail = Self::new();
+        let Some(node_ptr) = self.node_at(at) else {
+            return tail;
+        };
+        let prev = unsafe { (*node_ptr.as_ptr()).prev };
+        match prev {
+            None => self.start = None,
+            Some(prev_ptr) => unsafe { (*prev_ptr.as_ptr()).next = None },
+        }
+        unsafe { (*node_ptr.as_ptr()).prev = None };
+        tail.start = Some(node_ptr);
+        tail.end = self.end;
+        tail.length = self.length - at as u32;
+        self.end = prev;
+        self.length = at as u32;
+        tail
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.start,
+            remaining: self.length as usize,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            node: self.start,
+            remaining: self.length as usize,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|x| x == value)
+    }
+
+    // First value from the front that matches pred
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<&T> {
+        self.iter().find(|x| pred(x))
+    }
+
+    // Index of the first value from the front that matches pred
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    // Cursor starting on the first node, or on the ghost position if the list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.start,
+            list: self,
+        }
+    }
+
+    // Iterate from the last node back to the first one
+    pub fn iter_rev(&self) -> IterRev<'_, T> {
+        IterRev {
+            node: self.end,
+            remaining: self.length as usize,
+            marker: PhantomData,
+        }
+    }
+
     pub fn get(&mut self, index: i32) -> Option<&T> {
         self.get_ith_node(self.start, index)
     }
@@ -68,42 +292,206 @@ impl<T: std::cmp::PartialOrd> LinkedList<T> {
             },
         }
     }
+}
 
-    pub fn merge(mut list_a: Self, mut list_b: Self) -> Self
-    where
-        T: Clone,
-    {
-        let mut result = Self::new();
+impl<T: std::cmp::PartialOrd> LinkedList<T> {
 
-        let (mut a_idx, mut b_idx) = (0, 0);
-        let (mut a, mut b) = (list_a.get(0), list_b.get(0));
-        while a.is_some() && b.is_some() {
-            let a_val = a.unwrap();
-            let b_val = b.unwrap();
+    // Relinks the nodes of both lists, on ties the node from list_a goes first
+    pub fn merge(mut list_a: Self, mut list_b: Self) -> Self {
+        let mut result = Self::new();
 
-            if a_val < b_val {
-                result.add(a_val.clone());
-                a_idx = a_idx + 1;
-                a = list_a.get(a_idx);
+        while let (Some(a_ptr), Some(b_ptr)) = (list_a.start, list_b.start) {
+            let (a_val, b_val) = unsafe { (&(*a_ptr.as_ptr()).val, &(*b_ptr.as_ptr()).val) };
+            if b_val < a_val {
+                list_b.detach(b_ptr);
+                result.attach_back(b_ptr);
             } else {
-                result.add(b_val.clone());
-                b_idx = b_idx + 1;
-                b = list_b.get(b_idx);
+                list_a.detach(a_ptr);
+                result.attach_back(a_ptr);
             }
         }
 
-        while a.is_some() {
-            result.add(a.unwrap().clone());
-            a_idx = a_idx + 1;
-            a = list_a.get(a_idx);
-        }
+        result.splice_back(&mut list_a);
+        result.splice_back(&mut list_b);
+        result
+    }
+}
 
-        while b.is_some() {
-            result.add(b.unwrap().clone());
-            b_idx = b_idx + 1;
-            b = list_b.get(b_idx);
+impl<T: Ord> LinkedList<T> {
+    // Stable top-down merge sort, nodes are relinked and values never move
+    pub fn sort(&mut self) {
+        if self.length < 2 {
+            return;
         }
-        result
+        let mut back = self.detach_tail(self.length as usize / 2);
+        self.sort();
+        back.sort();
+        let front = std::mem::take(self);
+        *self = Self::merge(front, back);
+    }
+}
+
+// Free the nodes one at a time instead of recursing through the chain
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+// A position in the list that can edit around itself, current is None on the
+// ghost position that sits between the last and the first node
+pub struct CursorMut<'a, T> {
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut LinkedList<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    // Step towards the back, from the last node onto the ghost and from the ghost onto the first node
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            None => self.list.start,
+            Some(node_ptr) => unsafe { (*node_ptr.as_ptr()).next },
+        };
+    }
+
+    // Step towards the front, from the first node onto the ghost and from the ghost onto the last node
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            None => self.list.end,
+            Some(node_ptr) => unsafe { (*node_ptr.as_ptr()).prev },
+        };
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current.map(|node_ptr| unsafe { &mut (*node_ptr.as_ptr()).val })
+    }
+
+    // Insert after the current node, on the ghost position this inserts at the front
+    pub fn insert_after(&mut self, value: T) {
+        let node = Box::new(Node::new(value));
+        let node_ptr = unsafe { NonNull::new_unchecked(Box::into_raw(node)) };
+        self.list.attach_after(self.current, node_ptr);
+    }
+
+    // Remove the current node and move onto the one after it, None on the ghost position
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node_ptr = self.current?;
+        self.current = unsafe { (*node_ptr.as_ptr()).next };
+        Some(self.list.unlink(node_ptr))
+    }
+}
+
+pub struct Iter<'a, T> {
+    node: Option<NonNull<Node<T>>>,
+    remaining: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.node.map(|node_ptr| {
+            let node = unsafe { &*node_ptr.as_ptr() };
+            self.node = node.next;
+            self.remaining -= 1;
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+pub struct IterMut<'a, T> {
+    node: Option<NonNull<Node<T>>>,
+    remaining: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.node.map(|node_ptr| {
+            let node = unsafe { &mut *node_ptr.as_ptr() };
+            self.node = node.next;
+            self.remaining -= 1;
+            &mut node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+// Owning iterator, whatever is not consumed is freed by the list's Drop
+pub struct IntoIter<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.length as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+pub struct IterRev<'a, T> {
+    node: Option<NonNull<Node<T>>>,
+    remaining: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for IterRev<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.node.map(|node_ptr| {
+            let node = unsafe { &*node_ptr.as_ptr() };
+            self.node = node.prev;
+            self.remaining -= 1;
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
@@ -133,7 +521,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::LinkedList;
+    use super::{IndexOutOfRange, LinkedList};
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
     fn create_numeric_list() {
@@ -197,4 +587,434 @@ mod tests {
 			assert_eq!(target_vec[i],*list_c.get(i as i32).unwrap());
 		}
 	}
-}
\ No newline at end of file
+
+    // Walk the list through next and prev links, checking they agree
+    fn assert_links(list: &LinkedList<i32>, expected: &[i32]) {
+        let mut forward = vec![];
+        let mut node = list.start;
+        let mut prev = None;
+        while let Some(node_ptr) = node {
+            let current = unsafe { &*node_ptr.as_ptr() };
+            assert_eq!(current.prev, prev);
+            forward.push(current.val);
+            prev = node;
+            node = current.next;
+        }
+        assert_eq!(list.end, prev);
+        assert_eq!(forward, expected);
+        let backward: Vec<i32> = list.iter_rev().copied().collect();
+        let mut reversed = expected.to_vec();
+        reversed.reverse();
+        assert_eq!(backward, reversed);
+        assert_eq!(list.length as usize, expected.len());
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let mut list = LinkedList::<i32>::new();
+        assert_eq!(list.iter_rev().next(), None);
+        for i in 1..=5 {
+            list.add(i);
+        }
+        assert_eq!(list.iter_rev().size_hint(), (5, Some(5)));
+        assert_links(&list, &[1, 2, 3, 4, 5]);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&5));
+    }
+
+    #[test]
+    fn test_pop_both_ends() {
+        let mut list = LinkedList::<i32>::new();
+        for i in 1..=6 {
+            list.add(i);
+        }
+        assert_eq!(list.pop_front(), Some(1));
+        assert_links(&list, &[2, 3, 4, 5, 6]);
+        assert_eq!(list.pop_back(), Some(6));
+        assert_links(&list, &[2, 3, 4, 5]);
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_back(), Some(5));
+        assert_links(&list, &[3, 4]);
+        list.add(7);
+        assert_links(&list, &[3, 4, 7]);
+        assert_eq!(list.pop_back(), Some(7));
+        assert_eq!(list.pop_front(), Some(3));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_links(&list, &[]);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.back(), None);
+        list.add(8);
+        assert_links(&list, &[8]);
+    }
+
+    // Counts how many times values were dropped
+    #[derive(Clone, PartialEq, PartialOrd)]
+    struct DropCounter(u32, Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let mut list = LinkedList::<i32>::new();
+        for i in 0..1_000_000 {
+            list.add(i);
+        }
+        assert_eq!(list.length, 1_000_000);
+        drop(list);
+    }
+
+    #[test]
+    fn test_drop_each_node_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut list = LinkedList::new();
+        for i in 0..10 {
+            list.add(DropCounter(i, Rc::clone(&drops)));
+        }
+        let front = list.pop_front();
+        let back = list.pop_back();
+        assert_eq!(drops.get(), 0);
+        drop(list);
+        assert_eq!(drops.get(), 8);
+        drop(front);
+        drop(back);
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn test_drop_after_merge() {
+        let drops = Rc::new(Cell::new(0));
+        let mut list_a = LinkedList::new();
+        let mut list_b = LinkedList::new();
+        for i in 0..3 {
+            list_a.add(DropCounter(i * 2, Rc::clone(&drops)));
+            list_b.add(DropCounter(i * 2 + 1, Rc::clone(&drops)));
+        }
+        let merged = LinkedList::merge(list_a, list_b);
+        // merge moves the nodes, the emptied inputs free nothing
+        assert_eq!(drops.get(), 0);
+        assert_eq!(merged.length, 6);
+        drop(merged);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn test_insert_remove_empty() {
+        let mut list = LinkedList::<i32>::new();
+        assert_eq!(list.remove_at(0), None);
+        assert_eq!(list.insert_at(1, 5), Err(IndexOutOfRange { index: 1, length: 0 }));
+        assert_eq!(list.insert_at(0, 5), Ok(()));
+        assert_links(&list, &[5]);
+    }
+
+    #[test]
+    fn test_insert_remove_single() {
+        let mut list = LinkedList::<i32>::new();
+        list.add(2);
+        list.insert_at(0, 1).unwrap();
+        assert_links(&list, &[1, 2]);
+        assert_eq!(list.remove_at(1), Some(2));
+        assert_links(&list, &[1]);
+        assert_eq!(list.remove_at(1), None);
+        assert_eq!(list.remove_at(0), Some(1));
+        assert_links(&list, &[]);
+        list.insert_at(0, 3).unwrap();
+        list.insert_at(1, 4).unwrap();
+        assert_links(&list, &[3, 4]);
+    }
+
+    #[test]
+    fn test_insert_remove_many() {
+        let mut list = LinkedList::<i32>::new();
+        let mut reference = vec![];
+        for i in 0..10 {
+            list.add(i);
+            reference.push(i);
+        }
+        for (index, value) in [(0, 100), (11, 101), (5, 102), (8, 103), (3, 104)] {
+            list.insert_at(index, value).unwrap();
+            reference.insert(index, value);
+            assert_links(&list, &reference);
+        }
+        for index in [0, 13, 6, 9, 2, 4] {
+            assert_eq!(list.remove_at(index), Some(reference.remove(index)));
+            assert_links(&list, &reference);
+        }
+        let last = reference.len() - 1;
+        assert_eq!(list.remove_at(last), reference.pop());
+        assert_links(&list, &reference);
+        list.add(200);
+        reference.push(200);
+        assert_links(&list, &reference);
+        assert!(list.insert_at(100, 0).is_err());
+        assert_eq!(list.remove_at(100), None);
+    }
+
+    #[test]
+    fn test_sort_empty_and_single() {
+        let mut list = LinkedList::<i32>::new();
+        list.sort();
+        assert_links(&list, &[]);
+        list.add(7);
+        list.sort();
+        assert_links(&list, &[7]);
+    }
+
+    #[test]
+    fn test_sort_orders_values() {
+        let mut list = LinkedList::<i32>::new();
+        let mut reference = vec![];
+        let mut x = 12345u32;
+        for _ in 0..500 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let value = (x >> 16) as i32 % 100;
+            list.add(value);
+            reference.push(value);
+        }
+        list.sort();
+        reference.sort();
+        assert_links(&list, &reference);
+    }
+
+    // Ordered by key only, so equal keys can be told apart by index
+    #[derive(Debug, PartialEq, Eq)]
+    struct Keyed {
+        key: u8,
+        index: usize,
+    }
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        let keys = [3, 1, 2, 3, 1, 2, 3, 1, 0, 2, 2, 1, 3, 0];
+        let mut list = LinkedList::<Keyed>::new();
+        for (index, &key) in keys.iter().enumerate() {
+            list.add(Keyed { key, index });
+        }
+        list.sort();
+        let mut sorted: Vec<(u8, usize)> = list.iter_rev().map(|k| (k.key, k.index)).collect();
+        sorted.reverse();
+        let mut expected: Vec<(u8, usize)> = keys.iter().copied().zip(0..).collect();
+        expected.sort();
+        assert_eq!(sorted, expected);
+        assert_eq!(list.length as usize, keys.len());
+    }
+
+    #[test]
+    fn test_iter_by_reference() {
+        let mut list = LinkedList::<i32>::new();
+        assert_eq!(list.iter().next(), None);
+        for i in 1..=4 {
+            list.add(i);
+        }
+        let mut seen = vec![];
+        for x in &list {
+            seen.push(*x);
+        }
+        assert_eq!(seen, vec![1, 2, 3, 4]);
+        let mut iter = list.iter();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut list = LinkedList::<i32>::new();
+        for i in 1..=4 {
+            list.add(i);
+        }
+        for x in list.iter_mut() {
+            *x *= 10;
+        }
+        for x in &mut list {
+            *x += 1;
+        }
+        assert_links(&list, &[11, 21, 31, 41]);
+        assert_eq!(list.iter_mut().size_hint(), (4, Some(4)));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut list = LinkedList::<String>::new();
+        for s in ["a", "b", "c"] {
+            list.add(s.to_string());
+        }
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next().as_deref(), Some("a"));
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn test_into_iter_frees_the_rest() {
+        let drops = Rc::new(Cell::new(0));
+        let mut list = LinkedList::new();
+        for i in 0..5 {
+            list.add(DropCounter(i, Rc::clone(&drops)));
+        }
+        let mut iter = list.into_iter();
+        let first = iter.next().unwrap();
+        assert_eq!(first.0, 0);
+        drop(iter);
+        assert_eq!(drops.get(), 4);
+        drop(first);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn test_split_off_middle() {
+        let mut list = LinkedList::<i32>::new();
+        for i in 0..6 {
+            list.add(i);
+        }
+        let mut tail = list.split_off(2);
+        assert_links(&list, &[0, 1]);
+        assert_links(&tail, &[2, 3, 4, 5]);
+        list.add(10);
+        assert_eq!(tail.pop_front(), Some(2));
+        assert_eq!(tail.pop_back(), Some(5));
+        tail.add(11);
+        assert_links(&list, &[0, 1, 10]);
+        assert_links(&tail, &[3, 4, 11]);
+    }
+
+    #[test]
+    fn test_split_off_ends() {
+        let mut list = LinkedList::<i32>::new();
+        for i in 0..3 {
+            list.add(i);
+        }
+        let mut empty = list.split_off(3);
+        assert_links(&list, &[0, 1, 2]);
+        assert_links(&empty, &[]);
+        empty.add(7);
+        assert_links(&empty, &[7]);
+
+        let mut all = list.split_off(0);
+        assert_links(&list, &[]);
+        assert_links(&all, &[0, 1, 2]);
+        list.add(5);
+        assert_eq!(all.pop_back(), Some(2));
+        assert_links(&list, &[5]);
+        assert_links(&all, &[0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 is out of range for a list of length 3")]
+    fn test_split_off_past_the_end() {
+        let mut list = LinkedList::<i32>::new();
+        for i in 0..3 {
+            list.add(i);
+        }
+        list.split_off(4);
+    }
+
+    #[test]
+    fn test_search_empty() {
+        let list = LinkedList::<i32>::new();
+        assert!(!list.contains(&1));
+        assert_eq!(list.find(|_| true), None);
+        assert_eq!(list.position(|_| true), None);
+    }
+
+    #[test]
+    fn test_search_head_tail_absent() {
+        // NoDefault has neither Default nor Clone, search must not need them
+        #[derive(Debug, PartialEq)]
+        struct NoDefault(i32);
+        let mut list = LinkedList::new();
+        for i in [4, 8, 15, 16, 23, 42] {
+            list.add(NoDefault(i));
+        }
+        assert!(list.contains(&NoDefault(4)));
+        assert!(list.contains(&NoDefault(42)));
+        assert!(!list.contains(&NoDefault(5)));
+        assert_eq!(list.find(|x| x.0 % 4 == 0), Some(&NoDefault(4)));
+        assert_eq!(list.find(|x| x.0 > 30), Some(&NoDefault(42)));
+        assert_eq!(list.find(|x| x.0 > 100), None);
+        assert_eq!(list.position(|x| x.0 == 4), Some(0));
+        assert_eq!(list.position(|x| x.0 == 42), Some(5));
+        assert_eq!(list.position(|x| x.0 % 2 == 1), Some(2));
+        assert_eq!(list.position(|x| x.0 < 0), None);
+    }
+
+    #[test]
+    fn test_cursor_moves_and_edits() {
+        let mut list = LinkedList::<i32>::new();
+        {
+            let mut cursor = list.cursor_front_mut();
+            assert_eq!(cursor.current(), None);
+            assert_eq!(cursor.remove_current(), None);
+            cursor.insert_after(2);
+            cursor.insert_after(1);
+            cursor.move_next();
+            assert_eq!(cursor.current(), Some(&mut 1));
+            cursor.move_next();
+            cursor.insert_after(3);
+            cursor.move_next();
+            assert_eq!(cursor.current(), Some(&mut 3));
+            cursor.move_next();
+            assert_eq!(cursor.current(), None);
+            cursor.move_prev();
+            cursor.move_prev();
+            *cursor.current().unwrap() = 20;
+        }
+        assert_links(&list, &[1, 20, 3]);
+        {
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_prev();
+            cursor.move_prev();
+            assert_eq!(cursor.remove_current(), Some(3));
+            assert_eq!(cursor.current(), None);
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(1));
+            assert_eq!(cursor.current(), Some(&mut 20));
+        }
+        assert_links(&list, &[20]);
+        list.add(21);
+        assert_links(&list, &[20, 21]);
+    }
+
+    #[test]
+    fn test_cursor_josephus() {
+        // 7 people in a circle, every 3rd one leaves, the 4th survives
+        let mut list = LinkedList::<i32>::new();
+        for i in 1..=7 {
+            list.add(i);
+        }
+        let mut eliminated = vec![];
+        {
+            let mut cursor = list.cursor_front_mut();
+            while cursor.list.length > 1 {
+                for _ in 0..2 {
+                    cursor.move_next();
+                    if cursor.current().is_none() {
+                        cursor.move_next();
+                    }
+                }
+                eliminated.push(cursor.remove_current().unwrap());
+                if cursor.current().is_none() {
+                    cursor.move_next();
+                }
+            }
+        }
+        assert_eq!(eliminated, vec![3, 6, 2, 7, 5, 1]);
+        assert_links(&list, &[4]);
+    }
+}
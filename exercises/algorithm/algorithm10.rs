@@ -3,7 +3,7 @@
 	This problem requires you to implement a basic graph function
 */
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 #[derive(Debug, Clone)]
 pub struct NodeNotInGraph;
@@ -12,6 +12,16 @@ impl fmt::Display for NodeNotInGraph {
         write!(f, "accessing a node that is not in the graph")
     }
 }
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleError {
+    // a node that lies on a cycle
+    pub node: String,
+}
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the graph has a cycle through {}", self.node)
+    }
+}
 pub struct UndirectedGraph {
     adjacency_table: HashMap<String, Vec<(String, i32)>>,
 }
@@ -39,6 +49,90 @@ impl Graph for UndirectedGraph {
             .and_modify(|v| v.push((node1.to_string(), weight)));
     }
 }
+pub struct DirectedGraph {
+    adjacency_table: HashMap<String, Vec<(String, i32)>>,
+}
+impl Graph for DirectedGraph {
+    fn new() -> DirectedGraph {
+        DirectedGraph {
+            adjacency_table: HashMap::new(),
+        }
+    }
+    fn adjacency_table_mutable(&mut self) -> &mut HashMap<String, Vec<(String, i32)>> {
+        &mut self.adjacency_table
+    }
+    fn adjacency_table(&self) -> &HashMap<String, Vec<(String, i32)>> {
+        &self.adjacency_table
+    }
+    fn is_directed(&self) -> bool {
+        true
+    }
+    fn add_edge(&mut self, edge: (&str, &str, i32)) {
+        let (from, to, weight) = edge;
+        self.add_node(from);
+        self.add_node(to);
+        self.adjacency_table
+            .entry(from.to_string())
+            .and_modify(|v| v.push((to.to_string(), weight)));
+    }
+}
+impl DirectedGraph {
+    // Kahn's algorithm, ready nodes are taken in name order so the result is repeatable
+    pub fn topological_sort(&self) -> Result<Vec<String>, CycleError> {
+        let mut in_degree: HashMap<&String, usize> =
+            self.adjacency_table.keys().map(|node| (node, 0)).collect();
+        for neighbours in self.adjacency_table.values() {
+            for (to, _) in neighbours {
+                *in_degree.get_mut(to).unwrap() += 1;
+            }
+        }
+        let mut ready: Vec<&String> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&node, _)| node)
+            .collect();
+        ready.sort();
+        let mut ready: VecDeque<&String> = ready.into();
+        let mut order = Vec::new();
+        while let Some(node) = ready.pop_front() {
+            order.push(node.clone());
+            let mut unlocked = Vec::new();
+            for (to, _) in &self.adjacency_table[node] {
+                let degree = in_degree.get_mut(to).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    unlocked.push(to);
+                }
+            }
+            unlocked.sort();
+            ready.extend(unlocked);
+        }
+        if order.len() == self.adjacency_table.len() {
+            return Ok(order);
+        }
+        // every node left over still has a left over predecessor, walking back
+        // through them has to come round to a node twice, and that node is on a cycle
+        let mut node = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree > 0)
+            .map(|(&node, _)| node)
+            .min()
+            .unwrap();
+        let mut seen = HashSet::new();
+        while seen.insert(node) {
+            node = self
+                .adjacency_table
+                .iter()
+                .filter(|(from, neighbours)| {
+                    in_degree[from] > 0 && neighbours.iter().any(|(to, _)| to == node)
+                })
+                .map(|(from, _)| from)
+                .min()
+                .unwrap();
+        }
+        Err(CycleError { node: node.clone() })
+    }
+}
 pub trait Graph {
     fn new() -> Self;
     fn adjacency_table_mutable(&mut self) -> &mut HashMap<String, Vec<(String, i32)>>;
@@ -69,6 +163,61 @@ pub trait Graph {
         }
         edges
     }
+    // Undirected graphs store every edge in both adjacency lists
+    fn is_directed(&self) -> bool {
+        false
+    }
+    // Graphviz DOT source, nodes and edges sorted by name and weights as edge labels
+    fn to_dot(&self) -> String {
+        let (keyword, arrow) = if self.is_directed() { ("digraph", "->") } else { ("graph", "--") };
+        let mut dot = format!("{} {{\n", keyword);
+        let mut nodes: Vec<&String> = self.nodes().into_iter().collect();
+        nodes.sort();
+        for node in &nodes {
+            dot.push_str(&format!("    {};\n", dot_id(node)));
+        }
+        let mut edges = self.edges();
+        edges.sort();
+        let mut self_loops_seen = 0;
+        for (from, to, weight) in edges {
+            if !self.is_directed() {
+                // each undirected edge shows up once from either end, a self loop twice in one list
+                if from > to {
+                    continue;
+                }
+                if from == to {
+                    self_loops_seen += 1;
+                    if self_loops_seen % 2 == 0 {
+                        continue;
+                    }
+                }
+            }
+            dot.push_str(&format!(
+                "    {} {} {} [label=\"{}\"];\n",
+                dot_id(from),
+                arrow,
+                dot_id(to),
+                weight
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+// A quoted DOT id, non-ASCII characters become HTML entities which Graphviz renders
+fn dot_id(name: &str) -> String {
+    let mut id = String::from("\"");
+    for c in name.chars() {
+        match c {
+            '"' => id.push_str("\\\""),
+            '\\' => id.push_str("\\\\"),
+            '&' => id.push_str("&amp;"),
+            c if c.is_ascii() => id.push(c),
+            c => id.push_str(&format!("&#{};", c as u32)),
+        }
+    }
+    id.push('"');
+    id
 }
 #[cfg(test)]
 mod test_undirected_graph {
@@ -92,4 +241,159 @@ mod test_undirected_graph {
             assert_eq!(graph.edges().contains(edge), true);
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test_directed_graph {
+    use super::{CycleError, DirectedGraph, Graph};
+    use std::collections::HashMap;
+
+    // Every edge has to point from an earlier to a later position
+    fn assert_topological(graph: &DirectedGraph, order: &[String]) {
+        assert_eq!(order.len(), graph.nodes().len());
+        let position: HashMap<&String, usize> =
+            order.iter().enumerate().map(|(i, node)| (node, i)).collect();
+        for (from, to, _) in graph.edges() {
+            assert!(position[from] < position[to], "{} is not before {}", from, to);
+        }
+    }
+
+    #[test]
+    fn test_add_edge_is_one_way() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("a", "b", 5));
+        assert_eq!(graph.edges(), vec![(&String::from("a"), &String::from("b"), 5)]);
+        assert!(graph.contains("b"));
+    }
+
+    #[test]
+    fn test_topological_sort_diamond() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("top", "left", 1));
+        graph.add_edge(("top", "right", 1));
+        graph.add_edge(("left", "bottom", 1));
+        graph.add_edge(("right", "bottom", 1));
+        let order = graph.topological_sort().unwrap();
+        assert_topological(&graph, &order);
+    }
+
+    #[test]
+    fn test_topological_sort_disconnected() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("shirt", "tie", 1));
+        graph.add_edge(("tie", "jacket", 1));
+        graph.add_edge(("socks", "shoes", 1));
+        graph.add_node("watch");
+        let order = graph.topological_sort().unwrap();
+        assert_topological(&graph, &order);
+        assert!(order.contains(&String::from("watch")));
+        assert_eq!(DirectedGraph::new().topological_sort(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_topological_sort_self_loop() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("a", "a", 1));
+        assert_eq!(graph.topological_sort(), Err(CycleError { node: String::from("a") }));
+    }
+
+    #[test]
+    fn test_topological_sort_reports_a_cycle_node() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("start", "b", 1));
+        graph.add_edge(("b", "c", 1));
+        graph.add_edge(("c", "d", 1));
+        graph.add_edge(("d", "b", 1));
+        // "e" hangs off the cycle but is not part of it
+        graph.add_edge(("d", "e", 1));
+        let err = graph.topological_sort().unwrap_err();
+        assert!(["b", "c", "d"].contains(&err.node.as_str()), "{}", err.node);
+        assert_eq!(err.to_string(), format!("the graph has a cycle through {}", err.node));
+    }
+}
+
+#[cfg(test)]
+mod test_dot {
+    use super::{DirectedGraph, Graph, UndirectedGraph};
+
+    // Pull the quoted ids out of one DOT statement, undoing the escapes
+    fn quoted(line: &str) -> Vec<String> {
+        let mut ids = Vec::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c != '"' {
+                continue;
+            }
+            let mut id = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => id.push(chars.next().unwrap()),
+                    '"' => break,
+                    c => id.push(c),
+                }
+            }
+            ids.push(id);
+        }
+        ids
+    }
+
+    // Every edge statement as (from, to, label), sorted
+    fn dot_edges(dot: &str, arrow: &str) -> Vec<(String, String, String)> {
+        let mut edges: Vec<(String, String, String)> = dot
+            .lines()
+            .filter(|line| line.contains(arrow))
+            .map(|line| {
+                assert!(line.trim_end().ends_with("];"), "{}", line);
+                let ids = quoted(line);
+                assert_eq!(ids.len(), 3, "{}", line);
+                (ids[0].clone(), ids[1].clone(), ids[2].clone())
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn test_undirected_dot() {
+        let mut graph = UndirectedGraph::new();
+        graph.add_edge(("a", "b", 5));
+        graph.add_edge(("b", "c", 10));
+        graph.add_edge(("c", "a", 7));
+        graph.add_edge(("c", "c", 1));
+        graph.add_node("lonely");
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(!dot.contains("->"));
+        let got = dot_edges(&dot, "--");
+        let mut expected: Vec<(String, String, String)> =
+            [("a", "b", "5"), ("b", "c", "10"), ("a", "c", "7"), ("c", "c", "1")]
+                .iter()
+                .map(|(a, b, w)| (a.to_string(), b.to_string(), w.to_string()))
+                .collect();
+        expected.sort();
+        assert_eq!(got, expected);
+        assert!(dot.lines().any(|line| line.trim() == "\"lonely\";"));
+    }
+
+    #[test]
+    fn test_directed_dot_escapes_names() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("say \"hi\"", "b\\c", 2));
+        graph.add_edge(("b\\c", "say \"hi\"", 3));
+        graph.add_edge(("b\\c", "naïve", 4));
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.is_ascii());
+        assert!(dot.contains("\"na&#239;ve\""));
+        assert_eq!(
+            dot_edges(&dot, "->"),
+            vec![
+                ("b\\c".to_string(), "na&#239;ve".to_string(), "4".to_string()),
+                ("b\\c".to_string(), "say \"hi\"".to_string(), "3".to_string()),
+                ("say \"hi\"".to_string(), "b\\c".to_string(), "2".to_string()),
+            ]
+        );
+        // the three nodes each get a statement of their own
+        assert_eq!(dot.lines().filter(|line| line.trim_end().ends_with("\";")).count(), 3);
+    }
+}
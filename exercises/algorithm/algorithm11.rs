@@ -0,0 +1,205 @@
+/*
+	trie
+	This problem requires you to implement a prefix tree over string keys
+	Nodes are keyed by char, not by byte, so words in any script share their prefixes.
+*/
+
+// I AM NOT DONE
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+struct Node {
+	children: HashMap<char, Node>,
+	// whether a word ends here, and not only passes through
+	is_word: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Trie {
+	root: Node,
+	len: usize,
+}
+
+impl Trie {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Number of distinct words in the trie
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	// TODO: Add the word, counting it only if it wasn't there yet
+	pub fn insert(&mut self, word: &str) {
+		todo!()
+	}
+
+	// TODO: The node prefix leads to, if there is one
+	fn find(&self, prefix: &str) -> Option<&Node> {
+		todo!()
+	}
+
+	pub fn contains(&self, word: &str) -> bool {
+		self.find(word).is_some_and(|node| node.is_word)
+	}
+
+	// Every string starts with the empty prefix, even in an empty trie
+	pub fn starts_with(&self, prefix: &str) -> bool {
+		self.find(prefix).is_some()
+	}
+
+	// Returns whether the word was there. Nodes that no longer lead to
+	// any word are removed, the ones shared with other words are kept.
+	pub fn remove(&mut self, word: &str) -> bool {
+		let chars: Vec<char> = word.chars().collect();
+		let removed = Self::remove_from(&mut self.root, &chars);
+		if removed {
+			self.len -= 1;
+		}
+		removed
+	}
+
+	// TODO: Unmark the word below node, and remove the child on the way
+	// if it has become a leaf that isn't a word
+	fn remove_from(node: &mut Node, word: &[char]) -> bool {
+		todo!()
+	}
+
+	// Number of nodes below the root, to check that removal prunes
+	fn node_count(&self) -> usize {
+		fn count(node: &Node) -> usize {
+			node.children.values().map(|child| 1 + count(child)).sum()
+		}
+		count(&self.root)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_insert_and_contains() {
+		let mut trie = Trie::new();
+		trie.insert("rust");
+		trie.insert("rustlings");
+		assert!(trie.contains("rust"));
+		assert!(trie.contains("rustlings"));
+		assert!(!trie.contains("rus"));
+		assert!(!trie.contains("rustling"));
+		assert!(!trie.contains("crab"));
+		assert_eq!(trie.len(), 2);
+	}
+
+	#[test]
+	fn test_prefixes_of_each_other() {
+		let mut trie = Trie::new();
+		trie.insert("apple");
+		assert!(!trie.contains("app"));
+		assert!(trie.starts_with("app"));
+		trie.insert("app");
+		assert!(trie.contains("app"));
+		assert!(trie.contains("apple"));
+		assert!(trie.starts_with("appl"));
+		assert!(!trie.starts_with("apples"));
+	}
+
+	#[test]
+	fn test_insert_twice_counts_once() {
+		let mut trie = Trie::new();
+		trie.insert("word");
+		trie.insert("word");
+		assert_eq!(trie.len(), 1);
+		assert!(trie.remove("word"));
+		assert!(!trie.contains("word"));
+		assert!(trie.is_empty());
+	}
+
+	#[test]
+	fn test_remove_keeps_shared_prefix() {
+		let mut trie = Trie::new();
+		trie.insert("app");
+		trie.insert("apple");
+		trie.insert("apply");
+		assert!(trie.remove("apple"));
+		assert!(!trie.contains("apple"));
+		assert!(trie.contains("app"));
+		assert!(trie.contains("apply"));
+		assert!(!trie.starts_with("apple"));
+		assert!(trie.remove("app"));
+		assert!(trie.contains("apply"));
+		assert!(trie.starts_with("app"));
+		assert_eq!(trie.len(), 1);
+	}
+
+	#[test]
+	fn test_remove_prunes_empty_branches() {
+		let mut trie = Trie::new();
+		trie.insert("car");
+		trie.insert("cart");
+		trie.insert("dog");
+		assert_eq!(trie.node_count(), 7);
+		assert!(trie.remove("cart"));
+		assert_eq!(trie.node_count(), 6);
+		assert!(trie.remove("dog"));
+		assert_eq!(trie.node_count(), 3);
+		assert!(!trie.starts_with("d"));
+		assert!(trie.remove("car"));
+		assert_eq!(trie.node_count(), 0);
+		assert!(trie.is_empty());
+	}
+
+	#[test]
+	fn test_remove_missing_word() {
+		let mut trie = Trie::new();
+		trie.insert("apple");
+		assert!(!trie.remove("app"));
+		assert!(!trie.remove("apples"));
+		assert!(!trie.remove("banana"));
+		assert!(trie.contains("apple"));
+		assert_eq!(trie.node_count(), 5);
+		assert_eq!(trie.len(), 1);
+	}
+
+	#[test]
+	fn test_empty_string() {
+		let mut trie = Trie::new();
+		assert!(!trie.contains(""));
+		assert!(trie.starts_with(""));
+		assert!(!trie.remove(""));
+		trie.insert("");
+		assert!(trie.contains(""));
+		assert_eq!(trie.len(), 1);
+		assert_eq!(trie.node_count(), 0);
+		trie.insert("a");
+		assert!(trie.remove(""));
+		assert!(!trie.contains(""));
+		assert!(trie.contains("a"));
+	}
+
+	#[test]
+	fn test_non_ascii_words() {
+		let mut trie = Trie::new();
+		trie.insert("日本");
+		trie.insert("日本語");
+		trie.insert("naïve");
+		trie.insert("🦀🦀");
+		// one node per char, however many bytes it takes
+		assert_eq!(trie.node_count(), 3 + 5 + 2);
+		assert!(trie.contains("日本語"));
+		assert!(trie.starts_with("日"));
+		assert!(trie.starts_with("naï"));
+		assert!(!trie.starts_with("nai"));
+		assert!(trie.starts_with("🦀"));
+		assert!(!trie.contains("🦀"));
+		assert!(trie.remove("日本語"));
+		assert!(trie.contains("日本"));
+		assert_eq!(trie.node_count(), 2 + 5 + 2);
+	}
+}
@@ -0,0 +1,210 @@
+/*
+	lru cache
+	This problem requires you to implement a least recently used cache
+	A HashMap finds the entries and a doubly linked list keeps them in the order they were used,
+	so that get and put both take O(1). The list links entries by their index in a Vec,
+	and the slots of evicted entries are reused.
+*/
+
+// I AM NOT DONE
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct Entry<K, V> {
+	// shared with the map, so that K needs no Clone
+	key: Rc<K>,
+	value: V,
+	// towards the most recently used entry
+	prev: Option<usize>,
+	// towards the least recently used entry
+	next: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct LruCache<K: Hash + Eq, V> {
+	capacity: usize,
+	map: HashMap<Rc<K>, usize>,
+	entries: Vec<Entry<K, V>>,
+	// the most recently used entry
+	head: Option<usize>,
+	// the least recently used entry, evicted first
+	tail: Option<usize>,
+}
+
+impl<K: Hash + Eq, V> LruCache<K, V> {
+	pub fn new(capacity: usize) -> Self {
+		assert!(capacity > 0, "an LRU cache needs room for at least one entry");
+		LruCache {
+			capacity,
+			map: HashMap::with_capacity(capacity),
+			entries: Vec::with_capacity(capacity),
+			head: None,
+			tail: None,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	// TODO: The value of key, which becomes the most recently used entry
+	pub fn get(&mut self, key: &K) -> Option<&V> {
+		todo!()
+	}
+
+	// TODO: Insert or update key as the most recently used entry. A full
+	// cache evicts its least recently used entry first, and reuses its slot.
+	pub fn put(&mut self, key: K, value: V) {
+		todo!()
+	}
+
+	// The keys from the most to the least recently used
+	pub fn keys(&self) -> Vec<&K> {
+		let mut keys = Vec::with_capacity(self.len());
+		let mut current = self.head;
+		while let Some(idx) = current {
+			keys.push(&*self.entries[idx].key);
+			current = self.entries[idx].next;
+		}
+		keys
+	}
+
+	// TODO: Take the entry at idx out of the list
+	fn unlink(&mut self, idx: usize) {
+		todo!()
+	}
+
+	// TODO: Link the entry at idx in as the most recently used one
+	fn push_front(&mut self, idx: usize) {
+		todo!()
+	}
+
+	fn move_to_front(&mut self, idx: usize) {
+		if self.head != Some(idx) {
+			self.unlink(idx);
+			self.push_front(idx);
+		}
+	}
+
+	// Panic unless the list, walked both ways, and the map hold the same entries
+	fn assert_consistent(&self) {
+		let forward = self.keys();
+		assert_eq!(forward.len(), self.map.len(), "the list and the map disagree about the length");
+		let mut backward = Vec::new();
+		let mut current = self.tail;
+		while let Some(idx) = current {
+			backward.push(&*self.entries[idx].key);
+			current = self.entries[idx].prev;
+		}
+		backward.reverse();
+		assert!(forward.iter().zip(&backward).all(|(a, b)| std::ptr::eq(*a, *b)));
+		assert_eq!(forward.len(), backward.len());
+		for (rank, key) in forward.iter().enumerate() {
+			let idx = self.map[*key];
+			assert!(std::ptr::eq(&*self.entries[idx].key, *key), "entry {} is not where the map says", rank);
+		}
+		assert!(self.len() <= self.capacity);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_get_and_put() {
+		let mut cache = LruCache::new(2);
+		assert_eq!(cache.get(&1), None);
+		cache.put(1, "one");
+		cache.put(2, "two");
+		assert_eq!(cache.get(&1), Some(&"one"));
+		assert_eq!(cache.get(&2), Some(&"two"));
+		assert_eq!(cache.len(), 2);
+		cache.assert_consistent();
+	}
+
+	#[test]
+	fn test_eviction_order() {
+		let mut cache = LruCache::new(3);
+		cache.put("a", 1);
+		cache.put("b", 2);
+		cache.put("c", 3);
+		// "a" is used, so "b" is the least recently used now
+		assert_eq!(cache.get(&"a"), Some(&1));
+		cache.put("d", 4);
+		assert_eq!(cache.get(&"b"), None);
+		assert_eq!(cache.keys(), [&"d", &"a", &"c"]);
+		cache.assert_consistent();
+
+		assert_eq!(cache.get(&"c"), Some(&3));
+		cache.put("e", 5);
+		assert_eq!(cache.get(&"a"), None);
+		cache.put("f", 6);
+		assert_eq!(cache.get(&"d"), None);
+		assert_eq!(cache.keys(), [&"f", &"e", &"c"]);
+		cache.assert_consistent();
+	}
+
+	#[test]
+	fn test_updating_refreshes_recency() {
+		let mut cache = LruCache::new(2);
+		cache.put(1, 10);
+		cache.put(2, 20);
+		cache.put(1, 11);
+		assert_eq!(cache.len(), 2);
+		// updating 1 made 2 the least recently used
+		cache.put(3, 30);
+		assert_eq!(cache.get(&2), None);
+		assert_eq!(cache.get(&1), Some(&11));
+		assert_eq!(cache.get(&3), Some(&30));
+		cache.assert_consistent();
+	}
+
+	#[test]
+	fn test_capacity_one() {
+		let mut cache = LruCache::new(1);
+		cache.put("x", 1);
+		assert_eq!(cache.get(&"x"), Some(&1));
+		cache.put("x", 2);
+		assert_eq!(cache.get(&"x"), Some(&2));
+		cache.put("y", 3);
+		assert_eq!(cache.get(&"x"), None);
+		assert_eq!(cache.get(&"y"), Some(&3));
+		assert_eq!(cache.len(), 1);
+		cache.assert_consistent();
+	}
+
+	#[test]
+	fn test_keys_without_clone() {
+		// String keys are moved in, never cloned
+		let mut cache = LruCache::new(2);
+		cache.put(String::from("rust"), 1);
+		cache.put(String::from("crab"), 2);
+		cache.put(String::from("ferris"), 3);
+		assert_eq!(cache.get(&String::from("rust")), None);
+		assert_eq!(cache.get(&String::from("crab")), Some(&2));
+		assert!(!cache.is_empty());
+		cache.assert_consistent();
+	}
+
+	#[test]
+	fn test_map_and_list_agree() {
+		let mut cache = LruCache::new(4);
+		for i in 0..100u32 {
+			cache.put(i % 7, i);
+			cache.get(&(i % 3));
+			cache.get(&(i % 5));
+			cache.assert_consistent();
+			assert!(cache.len() <= 4);
+		}
+		assert_eq!(cache.len(), 4);
+		assert_eq!(cache.entries.len(), 4);
+	}
+}
@@ -0,0 +1,180 @@
+/*
+	union find
+	This problem requires you to implement a disjoint set union
+	find compresses the paths it walks and union hangs the shallower tree under the deeper one,
+	which together make both close to O(1).
+*/
+
+// I AM NOT DONE
+
+#[derive(Debug)]
+pub struct UnionFind {
+	parent: Vec<usize>,
+	// an upper bound of the height of the tree below each root
+	rank: Vec<u8>,
+	count: usize,
+}
+
+impl UnionFind {
+	// n sets, each holding one of the elements 0..n
+	pub fn new(n: usize) -> Self {
+		UnionFind {
+			parent: (0..n).collect(),
+			rank: vec![0; n],
+			count: n,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.parent.len()
+	}
+
+	// TODO: The representative of the set x is in. Every element on the way
+	// is hung directly under it, so the next find is shorter.
+	pub fn find(&mut self, x: usize) -> usize {
+		todo!()
+	}
+
+	// TODO: Merge the sets of a and b, returning false if they were one already
+	pub fn union(&mut self, a: usize, b: usize) -> bool {
+		todo!()
+	}
+
+	pub fn connected(&mut self, a: usize, b: usize) -> bool {
+		self.find(a) == self.find(b)
+	}
+
+	// The number of disjoint sets
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	// How many parents are above x, without compressing anything
+	fn depth(&self, mut x: usize) -> usize {
+		let mut depth = 0;
+		while self.parent[x] != x {
+			x = self.parent[x];
+			depth += 1;
+		}
+		depth
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// A small xorshift generator, so that the tests need no crates and
+	// still see the same unions every time
+	struct Random(u64);
+
+	impl Random {
+		fn below(&mut self, n: usize) -> usize {
+			self.0 ^= self.0 << 13;
+			self.0 ^= self.0 >> 7;
+			self.0 ^= self.0 << 17;
+			(self.0 % n as u64) as usize
+		}
+	}
+
+	#[test]
+	fn test_singletons() {
+		let mut sets = UnionFind::new(4);
+		assert_eq!(sets.count(), 4);
+		assert_eq!(sets.len(), 4);
+		for x in 0..4 {
+			assert_eq!(sets.find(x), x);
+		}
+		assert!(!sets.connected(0, 1));
+	}
+
+	#[test]
+	fn test_union_reports_merges() {
+		let mut sets = UnionFind::new(5);
+		assert!(sets.union(0, 1));
+		assert!(sets.union(1, 2));
+		assert!(!sets.union(0, 2));
+		assert!(!sets.union(3, 3));
+		assert_eq!(sets.count(), 3);
+		assert!(sets.connected(0, 2));
+		assert!(!sets.connected(2, 3));
+	}
+
+	#[test]
+	fn test_connected_components() {
+		// 0-1-2   3-4   5   6-7-8-9 closed into a cycle
+		let edges = [(0, 1), (1, 2), (3, 4), (6, 7), (7, 8), (8, 9), (9, 6), (2, 0)];
+		let mut sets = UnionFind::new(10);
+		let counts: Vec<usize> = edges
+			.iter()
+			.map(|&(a, b)| {
+				sets.union(a, b);
+				sets.count()
+			})
+			.collect();
+		assert_eq!(counts, [9, 8, 7, 6, 5, 4, 4, 4]);
+		assert!(sets.connected(6, 9));
+		assert!(!sets.connected(5, 4));
+	}
+
+	#[test]
+	fn test_find_compresses_paths() {
+		let mut sets = UnionFind::new(8);
+		for x in 0..7 {
+			sets.union(x, x + 1);
+		}
+		let root = sets.find(0);
+		for x in 0..8 {
+			sets.find(x);
+			assert!(sets.depth(x) <= 1, "{} is still {} below its root", x, sets.depth(x));
+			assert_eq!(sets.find(x), root);
+		}
+	}
+
+	#[test]
+	fn test_union_by_rank_keeps_trees_flat() {
+		// Growing one set an element at a time, whichever way round the
+		// elements are passed, the new one has to go under the big tree
+		let n = 1000;
+		let mut forward = UnionFind::new(n);
+		let mut backward = UnionFind::new(n);
+		for x in 0..n - 1 {
+			forward.union(x, x + 1);
+			backward.union(x + 1, x);
+		}
+		assert_eq!(forward.count(), 1);
+		assert_eq!(backward.count(), 1);
+		// depth doesn't compress, so this is the height union alone left
+		let deepest = (0..n).map(|x| forward.depth(x).max(backward.depth(x))).max().unwrap();
+		assert!(deepest <= 1, "an element is {} below its root", deepest);
+	}
+
+	#[test]
+	fn test_against_label_propagation() {
+		let n = 500;
+		let mut sets = UnionFind::new(n);
+		// every element carries the label of its set, relabeled on each merge
+		let mut labels: Vec<usize> = (0..n).collect();
+		let mut random = Random(0x2545f4914f6cdd1d);
+		for _ in 0..3000 {
+			let (a, b) = (random.below(n), random.below(n));
+			let merged = labels[a] != labels[b];
+			if merged {
+				let (from, to) = (labels[b], labels[a]);
+				for label in labels.iter_mut().filter(|label| **label == from) {
+					*label = to;
+				}
+			}
+			assert_eq!(sets.union(a, b), merged);
+			let (c, d) = (random.below(n), random.below(n));
+			assert_eq!(sets.connected(c, d), labels[c] == labels[d]);
+		}
+		let mut distinct = labels.clone();
+		distinct.sort_unstable();
+		distinct.dedup();
+		assert_eq!(sets.count(), distinct.len());
+		for x in 0..n {
+			assert!(sets.depth(x) <= 9);
+		}
+	}
+}
@@ -0,0 +1,178 @@
+/*
+	binary search
+	This problem requires you to implement binary search and its bounds, on sorted slices
+	and on sorted slices rotated at an unknown pivot. Every loop calls probe(), so that
+	the tests can tell a binary search from a linear scan.
+*/
+
+// I AM NOT DONE
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+thread_local! {
+	static PROBES: Cell<usize> = const { Cell::new(0) };
+}
+
+// Count one more look at the slice
+fn probe() {
+	PROBES.with(|probes| probes.set(probes.get() + 1));
+}
+
+// TODO: The first index whose element isn't less than target, len() if there is none
+fn lower_bound<T: Ord>(slice: &[T], target: &T) -> usize {
+	let (mut low, mut high) = (0, slice.len());
+	while low < high {
+		probe();
+		todo!()
+	}
+	low
+}
+
+// TODO: The first index whose element is greater than target, len() if there is none
+fn upper_bound<T: Ord>(slice: &[T], target: &T) -> usize {
+	let (mut low, mut high) = (0, slice.len());
+	while low < high {
+		probe();
+		todo!()
+	}
+	low
+}
+
+// TODO: The index of an element equal to target, any of them if there are several
+fn binary_search<T: Ord>(slice: &[T], target: &T) -> Option<usize> {
+	let (mut low, mut high) = (0, slice.len());
+	while low < high {
+		probe();
+		todo!()
+	}
+	None
+}
+
+// TODO: The index of target in a slice of distinct values that was sorted, then
+// rotated so that it starts somewhere in the middle, e.g. [4, 5, 6, 1, 2, 3]
+fn search_rotated(slice: &[i32], target: i32) -> Option<usize> {
+	let (mut low, mut high) = (0, slice.len());
+	while low < high {
+		probe();
+		todo!()
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LARGE: usize = 1 << 20;
+
+	// How many probes f takes
+	fn probes<R>(f: impl FnOnce() -> R) -> (R, usize) {
+		PROBES.with(|probes| probes.set(0));
+		let result = f();
+		(result, PROBES.with(Cell::get))
+	}
+
+	fn rotated(len: usize, by: usize) -> Vec<i32> {
+		let mut values: Vec<i32> = (0..len as i32).map(|x| x * 2).collect();
+		values.rotate_left(by);
+		values
+	}
+
+	#[test]
+	fn test_binary_search() {
+		let slice = [1, 3, 5, 7, 9, 11];
+		for (i, x) in slice.iter().enumerate() {
+			assert_eq!(binary_search(&slice, x), Some(i));
+		}
+		assert_eq!(binary_search(&slice, &4), None);
+		assert_eq!(binary_search(&slice, &0), None);
+		assert_eq!(binary_search(&slice, &12), None);
+		assert_eq!(binary_search(&["ant", "bee", "cat"], &"bee"), Some(1));
+	}
+
+	#[test]
+	fn test_empty_slice() {
+		let empty: [i32; 0] = [];
+		assert_eq!(binary_search(&empty, &1), None);
+		assert_eq!(lower_bound(&empty, &1), 0);
+		assert_eq!(upper_bound(&empty, &1), 0);
+		assert_eq!(search_rotated(&empty, 1), None);
+	}
+
+	#[test]
+	fn test_bounds_on_duplicates() {
+		let slice = [1, 2, 2, 2, 3, 5, 5, 8];
+		assert_eq!(lower_bound(&slice, &2), 1);
+		assert_eq!(upper_bound(&slice, &2), 4);
+		assert_eq!(lower_bound(&slice, &5), 5);
+		assert_eq!(upper_bound(&slice, &5), 7);
+		// a missing value has an empty run, where it would be inserted
+		assert_eq!(lower_bound(&slice, &4), 5);
+		assert_eq!(upper_bound(&slice, &4), 5);
+		assert!(matches!(binary_search(&slice, &2), Some(1..=3)));
+		let same = [7; 100];
+		assert_eq!(lower_bound(&same, &7), 0);
+		assert_eq!(upper_bound(&same, &7), 100);
+	}
+
+	#[test]
+	fn test_targets_outside_the_range() {
+		let slice = [10, 20, 30];
+		assert_eq!(lower_bound(&slice, &5), 0);
+		assert_eq!(upper_bound(&slice, &5), 0);
+		assert_eq!(lower_bound(&slice, &35), 3);
+		assert_eq!(upper_bound(&slice, &35), 3);
+		assert_eq!(lower_bound(&slice, &30), 2);
+		assert_eq!(upper_bound(&slice, &30), 3);
+	}
+
+	#[test]
+	fn test_search_rotated() {
+		let slice = [4, 5, 6, 7, 0, 1, 2];
+		for (i, &x) in slice.iter().enumerate() {
+			assert_eq!(search_rotated(&slice, x), Some(i));
+		}
+		assert_eq!(search_rotated(&slice, 3), None);
+		assert_eq!(search_rotated(&slice, 8), None);
+		assert_eq!(search_rotated(&slice, -1), None);
+		assert_eq!(search_rotated(&[1], 1), Some(0));
+		assert_eq!(search_rotated(&[2, 1], 1), Some(1));
+	}
+
+	#[test]
+	fn test_rotations_by_zero_and_len_minus_one() {
+		let len = 50;
+		for by in [0, 1, len / 2, len - 1] {
+			let slice = rotated(len, by);
+			for (i, &x) in slice.iter().enumerate() {
+				assert_eq!(search_rotated(&slice, x), Some(i), "rotated by {}", by);
+				assert_eq!(search_rotated(&slice, x + 1), None, "rotated by {}", by);
+			}
+		}
+	}
+
+	#[test]
+	fn test_large_slices_take_logarithmic_probes() {
+		// log2(LARGE) is 20, a linear scan would take up to a million
+		let limit = 21;
+		let sorted: Vec<u32> = (0..LARGE as u32).map(|x| x / 4).collect();
+		for target in [0, 1, 77_777, (LARGE / 4 - 1) as u32, LARGE as u32] {
+			let (_, taken) = probes(|| binary_search(&sorted, &target));
+			assert!(taken <= limit, "binary_search took {} probes", taken);
+			let (low, taken) = probes(|| lower_bound(&sorted, &target));
+			assert!(taken <= limit, "lower_bound took {} probes", taken);
+			let (high, taken) = probes(|| upper_bound(&sorted, &target));
+			assert!(taken <= limit, "upper_bound took {} probes", taken);
+			assert_eq!(high - low, if (target as usize) < LARGE / 4 { 4 } else { 0 });
+		}
+		for by in [0, 1, LARGE / 3, LARGE - 1] {
+			let slice = rotated(LARGE, by);
+			for target in [0, 2 * (LARGE as i32 - 1), 123_456, 123_457] {
+				let (found, taken) = probes(|| search_rotated(&slice, target));
+				assert!(taken <= limit, "search_rotated took {} probes", taken);
+				assert_eq!(found.map(|i| slice[i]), Some(target).filter(|t| t % 2 == 0));
+			}
+		}
+	}
+}
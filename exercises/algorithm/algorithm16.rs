@@ -0,0 +1,148 @@
+/*
+	kmp
+	This problem requires you to find every occurrence of a needle in a haystack with Knuth-Morris-Pratt
+	The failure function says how much of the needle still matches after a mismatch, so the scan
+	never goes back in the haystack and takes O(n + m). The bytes are Bytes, which count every
+	comparison made with them, so that the tests can tell it from the naive O(n * m) search.
+*/
+
+// I AM NOT DONE
+
+use std::cell::Cell;
+
+thread_local! {
+	static STEPS: Cell<usize> = const { Cell::new(0) };
+}
+
+mod counted {
+	// A byte that counts every comparison made with it. Its value is private
+	// to this module, so == is the only way to look at it
+	#[derive(Clone, Copy, Debug)]
+	pub struct Byte(u8);
+
+	impl PartialEq for Byte {
+		fn eq(&self, other: &Self) -> bool {
+			super::STEPS.with(|steps| steps.set(steps.get() + 1));
+			self.0 == other.0
+		}
+	}
+
+	pub fn bytes(s: &str) -> Vec<Byte> {
+		s.bytes().map(Byte).collect()
+	}
+}
+
+use counted::{bytes, Byte};
+
+// TODO: failure[i] is the length of the longest proper prefix of
+// needle[..=i] that is also a suffix of it
+fn build_failure(needle: &[Byte]) -> Vec<usize> {
+	todo!()
+}
+
+// TODO: The byte index of the start of every occurrence of needle in
+// haystack, overlapping ones included. UTF-8 never has the start of a char
+// in the middle of another, so matching bytes only ever finds whole chars.
+// Like str::match_indices, the empty needle is found at every char boundary.
+fn find_all(haystack: &str, needle: &str) -> Vec<usize> {
+	let (haystack_bytes, needle_bytes) = (bytes(haystack), bytes(needle));
+	todo!()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// How many steps f takes
+	fn steps<R>(f: impl FnOnce() -> R) -> (R, usize) {
+		STEPS.with(|steps| steps.set(0));
+		let result = f();
+		(result, STEPS.with(Cell::get))
+	}
+
+	#[test]
+	fn test_failure_function() {
+		assert_eq!(build_failure(&bytes("abcdabd")), [0, 0, 0, 0, 1, 2, 0]);
+		assert_eq!(build_failure(&bytes("aaaa")), [0, 1, 2, 3]);
+		assert_eq!(build_failure(&bytes("abab")), [0, 0, 1, 2]);
+		assert_eq!(build_failure(&bytes("aabaaab")), [0, 1, 0, 1, 2, 2, 3]);
+		assert_eq!(build_failure(&bytes("")), [0usize; 0]);
+	}
+
+	#[test]
+	fn test_find_all() {
+		assert_eq!(find_all("hello world, hello rust", "hello"), [0, 13]);
+		assert_eq!(find_all("abcabcabc", "cab"), [2, 5]);
+		assert_eq!(find_all("abc", "d"), [0usize; 0]);
+		assert_eq!(find_all("abc", "abc"), [0]);
+	}
+
+	#[test]
+	fn test_overlapping_matches() {
+		assert_eq!(find_all("aaaaa", "aaa"), [0, 1, 2]);
+		assert_eq!(find_all("abababa", "aba"), [0, 2, 4]);
+	}
+
+	#[test]
+	fn test_empty_needle() {
+		assert_eq!(find_all("abc", ""), [0, 1, 2, 3]);
+		assert_eq!(find_all("", ""), [0]);
+		// at the char boundaries, as str::match_indices does
+		let haystack = "aé日";
+		let expected: Vec<usize> = haystack.match_indices("").map(|(i, _)| i).collect();
+		assert_eq!(find_all(haystack, ""), expected);
+		assert_eq!(expected, [0, 1, 3, 6]);
+	}
+
+	#[test]
+	fn test_needle_longer_than_haystack() {
+		assert_eq!(find_all("ab", "abc"), [0usize; 0]);
+		assert_eq!(find_all("", "a"), [0usize; 0]);
+	}
+
+	#[test]
+	fn test_utf8_haystacks() {
+		let haystack = "日本語の日本、にほん日本";
+		let found = find_all(haystack, "日本");
+		assert_eq!(found, [0, 12, 30]);
+		for &i in &found {
+			assert!(haystack.is_char_boundary(i));
+			assert_eq!(&haystack[i..i + "日本".len()], "日本");
+		}
+		// é is 0xc3 0xa9, its second byte alone never matches a char
+		assert_eq!(find_all("éé", "é"), [0, 2]);
+		assert_eq!(find_all("🦀🦀🦀", "🦀🦀"), [0, 4]);
+	}
+
+	#[test]
+	fn test_agrees_with_std() {
+		let haystack = "abracadabra, abracadabra! cadabra";
+		for needle in ["a", "abra", "cad", "ra, a", "abracadabra", "z"] {
+			// match_indices skips overlapping matches, so compare with a naive search
+			let naive: Vec<usize> = (0..haystack.len())
+				.filter(|&i| haystack[i..].starts_with(needle))
+				.collect();
+			assert_eq!(find_all(haystack, needle), naive, "looking for {:?}", needle);
+		}
+	}
+
+	#[test]
+	fn test_large_repetitive_input_is_linear() {
+		// a...ab in a...a, where the naive search compares nearly the whole
+		// needle at every position
+		let haystack = "a".repeat(200_000);
+		let needle = format!("{}b", "a".repeat(999));
+		let (found, taken) = steps(|| find_all(&haystack, &needle));
+		assert!(found.is_empty());
+		// every byte of the haystack is compared at least once
+		let limit = 2 * (haystack.len() + needle.len());
+		assert!(taken >= haystack.len(), "{} steps for {} bytes, the Bytes weren't compared", taken, haystack.len());
+		assert!(taken <= limit, "{} steps for {} bytes, more than {}", taken, haystack.len(), limit);
+
+		let haystack = format!("{}b", "ab".repeat(100_000));
+		let (found, taken) = steps(|| find_all(&haystack, "abababab"));
+		assert_eq!(found.len(), 100_000 - 3);
+		assert!(taken >= haystack.len());
+		assert!(taken <= 2 * (haystack.len() + 8));
+	}
+}
@@ -0,0 +1,93 @@
+/*
+	sliding window maximum
+	This problem requires you to find the maximum of every window of k consecutive numbers
+	A monotonic deque of indices keeps the candidates for the maximum: their values
+	decrease from front to back, so the front is the maximum of the window.
+	Every index is pushed and popped at most once, which makes it O(n) for any k.
+*/
+
+// I AM NOT DONE
+
+use std::collections::VecDeque;
+
+// TODO: The maximum of nums[i..i + k] for every i, nums.len() - k + 1 of them
+fn max_sliding_window(nums: &[i32], k: usize) -> Vec<i32> {
+	assert!(k > 0, "a window holds at least one number");
+	if k > nums.len() {
+		return Vec::new();
+	}
+	let mut maxima: Vec<i32> = Vec::with_capacity(nums.len() - k + 1);
+	// indices into nums, whose values decrease from front to back
+	let mut candidates: VecDeque<usize> = VecDeque::new();
+	todo!()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn brute_force(nums: &[i32], k: usize) -> Vec<i32> {
+		nums.windows(k).map(|window| *window.iter().max().unwrap()).collect()
+	}
+
+	#[test]
+	fn test_example() {
+		assert_eq!(max_sliding_window(&[1, 3, -1, -3, 5, 3, 6, 7], 3), [3, 3, 5, 5, 6, 7]);
+	}
+
+	#[test]
+	fn test_window_of_one() {
+		let nums = [4, -2, 7, 0];
+		assert_eq!(max_sliding_window(&nums, 1), nums);
+	}
+
+	#[test]
+	fn test_window_of_everything() {
+		assert_eq!(max_sliding_window(&[2, 9, -4, 9, 1], 5), [9]);
+		assert_eq!(max_sliding_window(&[5], 1), [5]);
+	}
+
+	#[test]
+	fn test_window_larger_than_input() {
+		assert_eq!(max_sliding_window(&[1, 2], 3), [0i32; 0]);
+		assert_eq!(max_sliding_window(&[], 1), [0i32; 0]);
+	}
+
+	#[test]
+	fn test_decreasing_and_increasing() {
+		let decreasing: Vec<i32> = (0..10).rev().collect();
+		assert_eq!(max_sliding_window(&decreasing, 4), [9, 8, 7, 6, 5, 4, 3]);
+		let increasing: Vec<i32> = (0..10).collect();
+		assert_eq!(max_sliding_window(&increasing, 4), [3, 4, 5, 6, 7, 8, 9]);
+	}
+
+	#[test]
+	fn test_duplicates_of_the_maximum() {
+		assert_eq!(max_sliding_window(&[7, 7, 7, 7], 2), [7, 7, 7]);
+		assert_eq!(max_sliding_window(&[5, 1, 5, 1, 1, 1], 3), [5, 5, 5, 1]);
+		assert_eq!(max_sliding_window(&[i32::MIN, i32::MIN, -1], 2), [i32::MIN, -1]);
+	}
+
+	#[test]
+	fn test_large_random_input() {
+		// xorshift, for the same numbers every time without a crate
+		let mut state = 0x9e3779b97f4a7c15_u64;
+		let nums: Vec<i32> = (0..100_000)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 7;
+				state ^= state << 17;
+				(state % 2001) as i32 - 1000
+			})
+			.collect();
+		// a window this wide makes checking every window take 2.5 billion steps
+		let k = 25_000;
+		let maxima = max_sliding_window(&nums, k);
+		assert_eq!(maxima.len(), nums.len() - k + 1);
+		// so only every 1000th window is checked by brute force
+		for i in (0..maxima.len()).step_by(1000) {
+			assert_eq!(maxima[i], *nums[i..i + k].iter().max().unwrap(), "window at {}", i);
+		}
+		assert_eq!(max_sliding_window(&nums[..2000], 37), brute_force(&nums[..2000], 37));
+	}
+}
@@ -0,0 +1,242 @@
+/*
+	skip list
+	This problem requires you to implement a skip list, a sorted linked list with express lanes
+	Every node is on level 0, and each level above holds about half of the nodes of the one
+	below it, so a search skips ahead on the high levels first and takes O(log n) on average.
+	How many levels a new node gets is up to a random source handed in from outside, so
+	that the tests can use seeded ones.
+*/
+
+// I AM NOT DONE
+
+const MAX_LEVEL: usize = 32;
+
+#[derive(Debug)]
+struct Node<T> {
+	// None for the head, which comes before every value, and for free slots
+	value: Option<T>,
+	// the next node on each of the levels this node is on
+	next: Vec<Option<usize>>,
+}
+
+pub struct SkipList<T: Ord> {
+	// nodes[HEAD] is the head, the others are linked by their index
+	nodes: Vec<Node<T>>,
+	// slots of removed nodes, reused by the next insertions
+	free: Vec<usize>,
+	// how many levels are in use
+	level: usize,
+	len: usize,
+	// random bits, every bit set in a row promotes a new node one level more
+	random: Box<dyn FnMut() -> u64>,
+}
+
+const HEAD: usize = 0;
+
+impl<T: Ord> SkipList<T> {
+	pub fn new(random: Box<dyn FnMut() -> u64>) -> Self {
+		SkipList {
+			nodes: vec![Node { value: None, next: vec![None; MAX_LEVEL] }],
+			free: Vec::new(),
+			level: 1,
+			len: 0,
+			random,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	fn value(&self, idx: usize) -> &T {
+		self.nodes[idx].value.as_ref().expect("linked nodes hold a value")
+	}
+
+	// TODO: The last node before value on every level in use
+	fn predecessors(&self, value: &T) -> [usize; MAX_LEVEL] {
+		todo!()
+	}
+
+	fn random_level(&mut self) -> usize {
+		((self.random)().trailing_ones() as usize + 1).min(MAX_LEVEL)
+	}
+
+	// TODO: Link value in on random_level() levels, in a free slot if there
+	// is one. Returns false if the value was in the list already
+	pub fn insert(&mut self, value: T) -> bool {
+		todo!()
+	}
+
+	pub fn contains(&self, value: &T) -> bool {
+		let path = self.predecessors(value);
+		self.nodes[path[0]].next[0].is_some_and(|next| self.value(next) == value)
+	}
+
+	// TODO: Unlink value on every level it is on, free its slot and drop the
+	// levels that are left empty. Returns false if the value wasn't in the list
+	pub fn remove(&mut self, value: &T) -> bool {
+		todo!()
+	}
+
+	// The values in order
+	pub fn iter(&self) -> Iter<'_, T> {
+		Iter { list: self, next: self.nodes[HEAD].next[0] }
+	}
+
+	// The values on each level in use, from level 0 up
+	fn levels(&self) -> Vec<Vec<&T>> {
+		(0..self.level)
+			.map(|level| {
+				let mut values = Vec::new();
+				let mut current = self.nodes[HEAD].next[level];
+				while let Some(idx) = current {
+					values.push(self.value(idx));
+					current = self.nodes[idx].next[level];
+				}
+				values
+			})
+			.collect()
+	}
+}
+
+pub struct Iter<'a, T: Ord> {
+	list: &'a SkipList<T>,
+	next: Option<usize>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+	type Item = &'a T;
+
+	fn next(&mut self) -> Option<&'a T> {
+		let idx = self.next?;
+		self.next = self.list.nodes[idx].next[0];
+		Some(self.list.value(idx))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeSet;
+
+	// A seeded xorshift generator, the same list shape on every run
+	fn xorshift(seed: u64) -> impl FnMut() -> u64 {
+		let mut state = seed;
+		move || {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			state
+		}
+	}
+
+	fn shuffled(n: u32, seed: u64) -> Vec<u32> {
+		let mut keys: Vec<u32> = (0..n).collect();
+		let mut random = xorshift(seed);
+		for i in (1..keys.len()).rev() {
+			keys.swap(i, (random() % (i as u64 + 1)) as usize);
+		}
+		keys
+	}
+
+	// Every level but level 0 only holds values of the level below it, in the same order
+	fn assert_levels_are_subsequences<T: Ord + std::fmt::Debug>(list: &SkipList<T>) {
+		let levels = list.levels();
+		assert_eq!(levels[0].len(), list.len());
+		for (i, pair) in levels.windows(2).enumerate() {
+			let (below, above) = (&pair[0], &pair[1]);
+			assert!(!above.is_empty(), "level {} is in use but empty", i + 1);
+			let mut rest = below.iter();
+			for value in above {
+				assert!(rest.any(|v| v == value), "{:?} is on level {} but not on level {}", value, i + 1, i);
+			}
+		}
+	}
+
+	#[test]
+	fn test_insert_and_contains() {
+		let mut list = SkipList::new(Box::new(xorshift(1)));
+		assert!(list.is_empty());
+		assert!(list.insert(5));
+		assert!(list.insert(1));
+		assert!(list.insert(3));
+		assert!(!list.insert(3));
+		assert_eq!(list.len(), 3);
+		assert!(list.contains(&1));
+		assert!(list.contains(&5));
+		assert!(!list.contains(&2));
+		assert!(!list.contains(&6));
+		assert_eq!(list.iter().collect::<Vec<_>>(), [&1, &3, &5]);
+	}
+
+	#[test]
+	fn test_remove() {
+		let mut list = SkipList::new(Box::new(xorshift(2)));
+		for word in ["pear", "apple", "fig"] {
+			list.insert(word);
+		}
+		assert!(list.remove(&"apple"));
+		assert!(!list.remove(&"apple"));
+		assert!(!list.remove(&"kiwi"));
+		assert_eq!(list.iter().collect::<Vec<_>>(), [&"fig", &"pear"]);
+		assert!(list.remove(&"fig"));
+		assert!(list.remove(&"pear"));
+		assert!(list.is_empty());
+		assert_eq!(list.iter().next(), None);
+		assert_eq!(list.level, 1);
+	}
+
+	#[test]
+	fn test_levels_follow_the_random_source() {
+		// all bits set gives every node every level, none keeps them on level 0
+		let mut tall = SkipList::new(Box::new(|| u64::MAX));
+		let mut flat = SkipList::new(Box::new(|| 0));
+		for i in 0..10 {
+			tall.insert(i);
+			flat.insert(i);
+		}
+		assert_eq!(tall.levels().len(), MAX_LEVEL);
+		assert!(tall.levels().iter().all(|level| level.len() == 10));
+		assert_eq!(flat.levels().len(), 1);
+		assert_levels_are_subsequences(&tall);
+		assert_levels_are_subsequences(&flat);
+	}
+
+	#[test]
+	fn test_against_btreeset() {
+		let mut list = SkipList::new(Box::new(xorshift(0x853c49e6748fea9b)));
+		let mut reference = BTreeSet::new();
+		let keys = shuffled(4000, 7);
+		for &key in &keys {
+			assert_eq!(list.insert(key), reference.insert(key));
+		}
+		assert_eq!(list.len(), 4000);
+		assert!(list.iter().copied().eq(reference.iter().copied()));
+		assert_levels_are_subsequences(&list);
+		// about half the nodes are promoted each level up
+		let levels = list.levels();
+		assert!(levels.len() > 5 && levels[1].len() > 1500 && levels[1].len() < 2500);
+
+		for key in shuffled(4000, 11).into_iter().filter(|key| key % 2 == 0) {
+			assert_eq!(list.remove(&key), reference.remove(&key));
+		}
+		assert_eq!(list.len(), 2000);
+		for key in 0..4001 {
+			assert_eq!(list.contains(&key), reference.contains(&key), "contains({})", key);
+		}
+		assert!(list.iter().copied().eq(reference.iter().copied()));
+		assert_levels_are_subsequences(&list);
+
+		// the slots of the removed nodes are reused
+		for key in (0..4000).step_by(2) {
+			list.insert(key);
+		}
+		assert_eq!(list.nodes.len(), 4001);
+		assert!(list.iter().copied().eq(0..4000));
+		assert_levels_are_subsequences(&list);
+	}
+}
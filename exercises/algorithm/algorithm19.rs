@@ -0,0 +1,151 @@
+/*
+	fenwick tree
+	This problem requires you to implement a binary indexed tree, for prefix sums that can be updated
+	Ranges are half-open like the ranges of Rust: prefix_sum(i) is the sum of the first i elements,
+	the ones at 0..i, and range_sum(l, r) the sum of the ones at l..r, without r.
+*/
+
+// I AM NOT DONE
+
+#[derive(Debug, Clone)]
+pub struct FenwickTree {
+	// tree[i - 1] holds the sum of the elements at i - lowbit(i)..i, where
+	// lowbit(i) is the lowest bit set in i
+	tree: Vec<i64>,
+}
+
+fn lowbit(i: usize) -> usize {
+	i & i.wrapping_neg()
+}
+
+impl FenwickTree {
+	// n elements, all 0
+	pub fn new(n: usize) -> Self {
+		FenwickTree { tree: vec![0; n] }
+	}
+
+	// TODO: In O(n), by adding each sum to the one above it instead of adding
+	// every element on its own, which would take O(n log n)
+	pub fn from_slice(values: &[i64]) -> Self {
+		todo!()
+	}
+
+	pub fn len(&self) -> usize {
+		self.tree.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.tree.is_empty()
+	}
+
+	// TODO: Add delta to the element at i
+	pub fn add(&mut self, i: usize, delta: i64) {
+		assert!(i < self.len(), "index {} out of range for {} elements", i, self.len());
+		todo!()
+	}
+
+	// TODO: The sum of the first i elements, at 0..i
+	pub fn prefix_sum(&self, i: usize) -> i64 {
+		assert!(i <= self.len(), "prefix of {} out of range for {} elements", i, self.len());
+		todo!()
+	}
+
+	// The sum of the elements at l..r
+	pub fn range_sum(&self, l: usize, r: usize) -> i64 {
+		assert!(l <= r, "range {}..{} is backwards", l, r);
+		self.prefix_sum(r) - self.prefix_sum(l)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_empty_tree() {
+		let tree = FenwickTree::new(0);
+		assert!(tree.is_empty());
+		assert_eq!(tree.prefix_sum(0), 0);
+		assert_eq!(tree.range_sum(0, 0), 0);
+		assert!(FenwickTree::from_slice(&[]).is_empty());
+	}
+
+	#[test]
+	fn test_add_and_prefix_sum() {
+		let mut tree = FenwickTree::new(5);
+		tree.add(0, 3);
+		tree.add(2, 4);
+		tree.add(4, -2);
+		assert_eq!(tree.prefix_sum(0), 0);
+		assert_eq!(tree.prefix_sum(1), 3);
+		assert_eq!(tree.prefix_sum(3), 7);
+		assert_eq!(tree.prefix_sum(5), 5);
+	}
+
+	#[test]
+	fn test_first_and_last_index() {
+		let mut tree = FenwickTree::from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+		tree.add(0, 10);
+		tree.add(7, 100);
+		assert_eq!(tree.range_sum(0, 1), 11);
+		assert_eq!(tree.range_sum(7, 8), 108);
+		assert_eq!(tree.prefix_sum(8), 36 + 110);
+	}
+
+	#[test]
+	fn test_ranges_leave_out_their_end() {
+		let tree = FenwickTree::from_slice(&[5, 1, 4, 2, 3]);
+		// 1 + 4, the element at 3 isn't part of 1..3
+		assert_eq!(tree.range_sum(1, 3), 5);
+		assert_eq!(tree.range_sum(2, 2), 0);
+		assert_eq!(tree.range_sum(0, 5), 15);
+		assert_eq!(tree.range_sum(4, 5), 3);
+	}
+
+	#[test]
+	fn test_negative_deltas() {
+		let mut tree = FenwickTree::new(4);
+		tree.add(1, -7);
+		tree.add(3, 2);
+		tree.add(1, 3);
+		assert_eq!(tree.prefix_sum(2), -4);
+		assert_eq!(tree.range_sum(1, 4), -2);
+	}
+
+	#[test]
+	fn test_from_slice_matches_adding() {
+		let values: Vec<i64> = (0..1000).map(|i| (i * 7919 % 201) - 100).collect();
+		let built = FenwickTree::from_slice(&values);
+		let mut added = FenwickTree::new(values.len());
+		for (i, &value) in values.iter().enumerate() {
+			added.add(i, value);
+		}
+		assert_eq!(built.tree, added.tree);
+	}
+
+	#[test]
+	fn test_against_naive_prefix_sums() {
+		let n = 2000;
+		let mut values = vec![0i64; n];
+		let mut tree = FenwickTree::new(n);
+		let mut state = 0x2545f4914f6cdd1d_u64;
+		let mut random = |below: usize| {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			(state % below as u64) as usize
+		};
+		for _ in 0..5000 {
+			let i = random(n);
+			let delta = random(2001) as i64 - 1000;
+			values[i] += delta;
+			tree.add(i, delta);
+
+			let (a, b) = (random(n + 1), random(n + 1));
+			let (l, r) = (a.min(b), a.max(b));
+			assert_eq!(tree.range_sum(l, r), values[l..r].iter().sum::<i64>(), "range {}..{}", l, r);
+			let i = random(n + 1);
+			assert_eq!(tree.prefix_sum(i), values[..i].iter().sum::<i64>(), "prefix {}", i);
+		}
+	}
+}
@@ -0,0 +1,148 @@
+/*
+	bit manipulation
+	This problem requires you to implement a few functions with bitwise operators, without calling
+	the methods of the integer types that do the same, which the tests compare them with.
+*/
+
+// I AM NOT DONE
+
+// TODO: The number of bits set. Clear the lowest one until none is left,
+// so that it loops once per bit that's set rather than once per bit
+pub fn count_ones_manual(x: u32) -> u32 {
+	todo!()
+}
+
+// TODO: A power of two has exactly one bit set, and 0 has none
+pub fn is_power_of_two(x: u64) -> bool {
+	todo!()
+}
+
+// TODO: Swap the halves, then the bytes in each half, and so on down to
+// the neighbouring bits
+pub fn reverse_bits(x: u32) -> u32 {
+	todo!()
+}
+
+// TODO: Every number is there twice except one, find the single one
+pub fn single_number(numbers: &[i32]) -> i32 {
+	todo!()
+}
+
+// TODO: The smallest power of two that is at least x, or None if it doesn't
+// fit in a u64, which is once x is above 2^63. 0 gives 1, like the std method.
+pub fn next_power_of_two(x: u64) -> Option<u64> {
+	todo!()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// count values from a seeded xorshift generator
+	fn random_values(count: usize) -> Vec<u64> {
+		let mut state = 0x2545f4914f6cdd1d_u64;
+		(0..count)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 7;
+				state ^= state << 17;
+				// Some small values and some with only a few bits set, so that
+				// more than the middle of the range gets tested
+				match state % 4 {
+					0 => state % 1000,
+					1 => state >> (state % 64),
+					2 => 1 << (state % 64) | state >> 60,
+					_ => state,
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_count_ones() {
+		assert_eq!(count_ones_manual(0), 0);
+		assert_eq!(count_ones_manual(1), 1);
+		assert_eq!(count_ones_manual(0b1011_0100), 4);
+		assert_eq!(count_ones_manual(1 << 31), 1);
+		assert_eq!(count_ones_manual(u32::MAX), 32);
+	}
+
+	#[test]
+	fn test_is_power_of_two() {
+		assert!(!is_power_of_two(0));
+		assert!(is_power_of_two(1));
+		assert!(is_power_of_two(2));
+		assert!(!is_power_of_two(3));
+		assert!(is_power_of_two(1024));
+		assert!(!is_power_of_two(1023));
+		assert!(is_power_of_two(1 << 63));
+		assert!(!is_power_of_two(u64::MAX));
+		for shift in 0..64 {
+			assert!(is_power_of_two(1 << shift), "2^{}", shift);
+			let two_bits = 1 << shift | 1 << ((shift + 1) % 64);
+			assert!(!is_power_of_two(two_bits), "{:#x}", two_bits);
+		}
+	}
+
+	#[test]
+	fn test_reverse_bits() {
+		assert_eq!(reverse_bits(0), 0);
+		assert_eq!(reverse_bits(1), 1 << 31);
+		assert_eq!(reverse_bits(1 << 31), 1);
+		assert_eq!(reverse_bits(u32::MAX), u32::MAX);
+		assert_eq!(reverse_bits(0b1101), 0b1011 << 28);
+		assert_eq!(reverse_bits(0x1234_5678), 0x1e6a_2c48);
+	}
+
+	#[test]
+	fn test_single_number() {
+		assert_eq!(single_number(&[7]), 7);
+		assert_eq!(single_number(&[2, 2, 1]), 1);
+		assert_eq!(single_number(&[4, 1, 2, 1, 2]), 4);
+		assert_eq!(single_number(&[-3, 5, 5]), -3);
+		assert_eq!(single_number(&[0, i32::MIN, 9, i32::MIN, 9]), 0);
+		assert_eq!(single_number(&[i32::MAX, -1, i32::MAX]), -1);
+	}
+
+	#[test]
+	fn test_next_power_of_two() {
+		assert_eq!(next_power_of_two(0), Some(1));
+		assert_eq!(next_power_of_two(1), Some(1));
+		assert_eq!(next_power_of_two(2), Some(2));
+		assert_eq!(next_power_of_two(3), Some(4));
+		assert_eq!(next_power_of_two(1000), Some(1024));
+		assert_eq!(next_power_of_two(1024), Some(1024));
+		assert_eq!(next_power_of_two(1025), Some(2048));
+		// The top of the range
+		assert_eq!(next_power_of_two(1 << 63), Some(1 << 63));
+		assert_eq!(next_power_of_two((1 << 62) + 1), Some(1 << 63));
+		assert_eq!(next_power_of_two((1 << 63) + 1), None);
+		assert_eq!(next_power_of_two(u64::MAX), None);
+	}
+
+	#[test]
+	fn test_against_std() {
+		for x in random_values(10_000) {
+			let low = x as u32;
+			assert_eq!(count_ones_manual(low), low.count_ones(), "{:#x}", low);
+			assert_eq!(reverse_bits(low), low.reverse_bits(), "{:#x}", low);
+			assert_eq!(is_power_of_two(x), x.is_power_of_two(), "{:#x}", x);
+			assert_eq!(next_power_of_two(x), x.checked_next_power_of_two(), "{:#x}", x);
+		}
+	}
+
+	#[test]
+	fn test_single_number_shuffled() {
+		let values = random_values(1001);
+		let single = values[1000] as i32;
+		let mut numbers: Vec<i32> = values[..500].iter().map(|&x| x as i32).filter(|&x| x != single).collect();
+		numbers.extend(numbers.clone());
+		numbers.push(single);
+		// Shuffle with the rest of the random values
+		for i in (1..numbers.len()).rev() {
+			let j = (values[500 + i % 500] % (i as u64 + 1)) as usize;
+			numbers.swap(i, j);
+		}
+		assert_eq!(single_number(&numbers), single);
+	}
+}
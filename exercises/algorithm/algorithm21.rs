@@ -0,0 +1,153 @@
+/*
+	running median
+	This problem requires you to keep track of the median of a stream of numbers
+	The smaller half of them sits in a max-heap and the larger half in a min-heap, both of
+	them the Heap from algorithm9, so both middle values are always on top. The lower half
+	holds the extra one when the count is odd.
+*/
+
+// I AM NOT DONE
+
+#[allow(dead_code)]
+#[path = "algorithm9.rs"]
+mod heap;
+
+use heap::{Heap, MaxHeap, MinHeap};
+
+pub struct MedianTracker {
+	lower: Heap<i32>,
+	upper: Heap<i32>,
+}
+
+impl Default for MedianTracker {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl MedianTracker {
+	pub fn new() -> Self {
+		Self {
+			lower: MaxHeap::new(),
+			upper: MinHeap::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.lower.len() + self.upper.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.lower.is_empty()
+	}
+
+	// TODO: Add x in O(log n). Put it in the half it belongs in, then move
+	// the top of one half to the other if the lower half has more than one
+	// extra value, or the upper half has more values than the lower one.
+	pub fn add(&mut self, x: i32) {
+		todo!()
+	}
+
+	// TODO: The middle value, or the mean of the two middle values when the
+	// count is even. None until a value has been added.
+	pub fn median(&self) -> Option<f64> {
+		todo!()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// A seeded xorshift generator, so every run sees the same values
+	struct Rng(u64);
+
+	impl Rng {
+		fn next_u64(&mut self) -> u64 {
+			self.0 ^= self.0 << 13;
+			self.0 ^= self.0 >> 7;
+			self.0 ^= self.0 << 17;
+			self.0
+		}
+	}
+
+	// The median of values, found by sorting them
+	fn sorted_median(values: &[i32]) -> Option<f64> {
+		let mut sorted = values.to_vec();
+		sorted.sort();
+		let mid = sorted.len() / 2;
+		match sorted.len() {
+			0 => None,
+			len if len % 2 == 1 => Some(f64::from(sorted[mid])),
+			_ => Some((f64::from(sorted[mid - 1]) + f64::from(sorted[mid])) / 2.0),
+		}
+	}
+
+	// Adds every value and compares the median after each one
+	fn check_medians(values: &[i32]) {
+		let mut tracker = MedianTracker::new();
+		for (i, &value) in values.iter().enumerate() {
+			tracker.add(value);
+			assert_eq!(tracker.len(), i + 1);
+			assert_eq!(tracker.median(), sorted_median(&values[..=i]), "after {:?}", &values[..=i]);
+			let extra = tracker.lower.len() - tracker.upper.len();
+			assert!(extra <= 1, "halves of {} and {}", tracker.lower.len(), tracker.upper.len());
+		}
+	}
+
+	#[test]
+	fn test_median_empty() {
+		let tracker = MedianTracker::new();
+		assert!(tracker.is_empty());
+		assert_eq!(tracker.median(), None);
+	}
+
+	#[test]
+	fn test_median_small() {
+		let mut tracker = MedianTracker::default();
+		tracker.add(5);
+		assert_eq!(tracker.median(), Some(5.0));
+		tracker.add(2);
+		assert_eq!(tracker.median(), Some(3.5));
+		tracker.add(10);
+		assert_eq!(tracker.median(), Some(5.0));
+		tracker.add(-4);
+		assert_eq!(tracker.median(), Some(3.5));
+	}
+
+	#[test]
+	fn test_median_sorted_input() {
+		check_medians(&(1..=200).collect::<Vec<_>>());
+		check_medians(&(1..=200).rev().collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn test_median_duplicates() {
+		check_medians(&[7; 50]);
+		check_medians(&[3, 3, 1, 1, 3, 3, 1, 1, 2, 2, 2]);
+		let mut tracker = MedianTracker::new();
+		for _ in 0..10 {
+			tracker.add(4);
+		}
+		assert_eq!(tracker.median(), Some(4.0));
+	}
+
+	#[test]
+	fn test_median_extremes() {
+		// The mean is taken in f64, adding the two would overflow i32
+		let mut tracker = MedianTracker::new();
+		tracker.add(i32::MAX);
+		tracker.add(i32::MAX);
+		assert_eq!(tracker.median(), Some(f64::from(i32::MAX)));
+		tracker.add(i32::MIN);
+		tracker.add(i32::MIN);
+		assert_eq!(tracker.median(), Some(-0.5));
+	}
+
+	#[test]
+	fn test_median_random() {
+		let mut rng = Rng(0x0ddb_a11c_afe0_0042);
+		let values: Vec<i32> = (0..1000).map(|_| (rng.next_u64() % 201) as i32 - 100).collect();
+		check_medians(&values);
+	}
+}
@@ -2,39 +2,370 @@
 	sort
 	This problem requires you to implement a sorting algorithm
 	you can use bubble sorting, insertion sorting, heap sorting, etc.
+	Heap sort reuses the one from algorithm9.
 */
 
-fn sort<T: std::cmp::PartialOrd>(array: &mut [T]){
+#[allow(dead_code)]
+#[path = "algorithm9.rs"]
+mod heap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortAlgo {
+    Bubble,
+    Insertion,
+    Merge,
+    Quick,
+    Heap,
+}
+
+impl SortAlgo {
+    pub const ALL: [SortAlgo; 5] = [
+        SortAlgo::Bubble,
+        SortAlgo::Insertion,
+        SortAlgo::Merge,
+        SortAlgo::Quick,
+        SortAlgo::Heap,
+    ];
+
+    // Whether equal elements keep their original relative order
+    pub fn is_stable(self) -> bool {
+        matches!(self, SortAlgo::Bubble | SortAlgo::Insertion | SortAlgo::Merge)
+    }
+}
+
+// Work done by one sort, a rotation counts as the swaps it takes to do by reversals.
+// Heap sort only reports its comparisons.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: u64,
+    pub swaps: u64,
+}
+
+// The algorithms compare and move elements only through here, so every
+// comparison and swap is counted
+struct Counter {
+    stats: SortStats,
+}
+
+impl Counter {
+    fn less<T: Ord>(&mut self, a: &T, b: &T) -> bool {
+        self.stats.comparisons += 1;
+        a < b
+    }
+
+    fn swap<T>(&mut self, array: &mut [T], i: usize, j: usize) {
+        self.stats.swaps += 1;
+        array.swap(i, j);
+    }
+
+    fn reverse<T>(&mut self, array: &mut [T]) {
+        let len = array.len();
+        for i in 0..len / 2 {
+            self.swap(array, i, len - 1 - i);
+        }
+    }
+
+    // rotate_left done with three reversals
+    fn rotate_left<T>(&mut self, array: &mut [T], mid: usize) {
+        self.reverse(&mut array[..mid]);
+        self.reverse(&mut array[mid..]);
+        self.reverse(array);
+    }
+}
+
+// Sort in place, none of the algorithms allocate a second buffer
+pub fn sort<T: Ord>(array: &mut [T], algo: SortAlgo) {
+    sort_instrumented(array, algo);
+}
+
+// Sort like sort does and report how much work it took
+pub fn sort_instrumented<T: Ord>(array: &mut [T], algo: SortAlgo) -> SortStats {
+    let mut counter = Counter { stats: SortStats::default() };
+    match algo {
+        SortAlgo::Bubble => bubble_sort(array, &mut counter),
+        SortAlgo::Insertion => insertion_sort(array, &mut counter),
+        SortAlgo::Merge => merge_sort(array, &mut counter),
+        SortAlgo::Quick => quick_sort(array, &mut counter),
+        SortAlgo::Heap => {
+            // algorithm9 takes an Fn comparator, so count through a Cell. It
+            // swaps the elements itself, out of sight, so they aren't counted.
+            let comparisons = std::cell::Cell::new(0);
+            heap::heap_sort_by(array, |a, b| {
+                comparisons.set(comparisons.get() + 1);
+                a < b
+            });
+            counter.stats.comparisons = comparisons.get();
+        }
+    }
+    counter.stats
+}
+
+fn bubble_sort<T: Ord>(array: &mut [T], counter: &mut Counter) {
     let len = array.len();
     for _ in 0..len {
-        for j in 0..len - 1 {
-            if array[j] > array[j + 1] {
-                array.swap(j, j + 1);
+        for j in 0..len.saturating_sub(1) {
+            if counter.less(&array[j + 1], &array[j]) {
+                counter.swap(array, j, j + 1);
             }
         }
     }
 }
 
+fn insertion_sort<T: Ord>(array: &mut [T], counter: &mut Counter) {
+    for i in 1..array.len() {
+        let mut j = i;
+        // strictly less, so an element never moves in front of an equal one
+        while j > 0 && counter.less(&array[j], &array[j - 1]) {
+            counter.swap(array, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+// Top-down merge sort that merges by rotating, stable and without a buffer
+fn merge_sort<T: Ord>(array: &mut [T], counter: &mut Counter) {
+    if array.len() < 2 {
+        return;
+    }
+    let mid = array.len() / 2;
+    merge_sort(&mut array[..mid], counter);
+    merge_sort(&mut array[mid..], counter);
+    merge_in_place(array, mid, counter);
+}
+
+// Merge the sorted runs array[..mid] and array[mid..]. Cut the longer run in
+// half, find where its middle element goes in the other run, rotate the two
+// inner pieces past each other and merge the halves on either side.
+fn merge_in_place<T: Ord>(array: &mut [T], mid: usize, counter: &mut Counter) {
+    let (left_len, right_len) = (mid, array.len() - mid);
+    if left_len == 0 || right_len == 0 {
+        return;
+    }
+    if array.len() == 2 {
+        if counter.less(&array[1], &array[0]) {
+            counter.swap(array, 0, 1);
+        }
+        return;
+    }
+    let (left_cut, right_cut) = if left_len >= right_len {
+        let left_cut = left_len / 2;
+        // right elements equal to the pivot stay behind it
+        let right_cut = mid + partition_point(&array[mid..], |x| counter.less(x, &array[left_cut]));
+        (left_cut, right_cut)
+    } else {
+        let right_cut = mid + right_len / 2;
+        // left elements equal to the pivot stay in front of it
+        let left_cut = partition_point(&array[..mid], |x| !counter.less(&array[right_cut], x));
+        (left_cut, right_cut)
+    };
+    counter.rotate_left(&mut array[left_cut..right_cut], mid - left_cut);
+    let new_mid = left_cut + (right_cut - mid);
+    merge_in_place(&mut array[..new_mid], left_cut, counter);
+    merge_in_place(&mut array[new_mid..], right_cut - new_mid, counter);
+}
+
+// Number of leading elements of a partitioned slice for which pred holds
+fn partition_point<T>(array: &[T], mut pred: impl FnMut(&T) -> bool) -> usize {
+    let (mut low, mut high) = (0, array.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if pred(&array[mid]) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+// Quick sort with a three-way partition around the middle element, so sorted
+// and all-equal input stay O(n log n). Recursing into the smaller side only
+// keeps the stack depth O(log n).
+fn quick_sort<T: Ord>(mut array: &mut [T], counter: &mut Counter) {
+    while array.len() > 1 {
+        let (lt, gt) = partition3(array, counter);
+        let (smaller_left, rest) = array.split_at_mut(lt);
+        let larger_right = &mut rest[gt - lt..];
+        if smaller_left.len() < larger_right.len() {
+            quick_sort(smaller_left, counter);
+            array = larger_right;
+        } else {
+            quick_sort(larger_right, counter);
+            array = smaller_left;
+        }
+    }
+}
+
+// Afterwards [..lt] is below the pivot, [lt..gt] equal to it and [gt..] above it
+fn partition3<T: Ord>(array: &mut [T], counter: &mut Counter) -> (usize, usize) {
+    let middle = array.len() / 2;
+    counter.swap(array, 0, middle);
+    // the pivot travels with the equal block, lt always points at one copy of it
+    let (mut lt, mut i, mut gt) = (0, 1, array.len());
+    while i < gt {
+        if counter.less(&array[i], &array[lt]) {
+            counter.swap(array, i, lt);
+            lt += 1;
+            i += 1;
+        } else if counter.less(&array[lt], &array[i]) {
+            gt -= 1;
+            counter.swap(array, i, gt);
+        } else {
+            i += 1;
+        }
+    }
+    (lt, gt)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_sort_1() {
-        let mut vec = vec![37, 73, 57, 75, 91, 19, 46, 64];
-        sort(&mut vec);
-        assert_eq!(vec, vec![19, 37, 46, 57, 64, 73, 75, 91]);
+        for algo in SortAlgo::ALL {
+            let mut vec = vec![37, 73, 57, 75, 91, 19, 46, 64];
+            sort(&mut vec, algo);
+            assert_eq!(vec, vec![19, 37, 46, 57, 64, 73, 75, 91]);
+        }
     }
 	#[test]
     fn test_sort_2() {
-        let mut vec = vec![1];
-        sort(&mut vec);
-        assert_eq!(vec, vec![1]);
+        for algo in SortAlgo::ALL {
+            let mut vec = vec![1];
+            sort(&mut vec, algo);
+            assert_eq!(vec, vec![1]);
+        }
     }
 	#[test]
     fn test_sort_3() {
-        let mut vec = vec![99, 88, 77, 66, 55, 44, 33, 22, 11];
-        sort(&mut vec);
-        assert_eq!(vec, vec![11, 22, 33, 44, 55, 66, 77, 88, 99]);
+        for algo in SortAlgo::ALL {
+            let mut vec = vec![99, 88, 77, 66, 55, 44, 33, 22, 11];
+            sort(&mut vec, algo);
+            assert_eq!(vec, vec![11, 22, 33, 44, 55, 66, 77, 88, 99]);
+        }
+    }
+
+    struct Rng(u64);
+
+    impl Rng {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    // Every algorithm has to agree with the standard library
+    fn check_all(input: &[i32]) {
+        let mut expected = input.to_vec();
+        expected.sort();
+        for algo in SortAlgo::ALL {
+            let mut vec = input.to_vec();
+            sort(&mut vec, algo);
+            assert_eq!(vec, expected, "{:?} on {:?}", algo, input);
+        }
+    }
+
+    #[test]
+    fn test_sort_edge_inputs() {
+        check_all(&[]);
+        check_all(&[2, 1]);
+        let ascending: Vec<i32> = (0..300).collect();
+        check_all(&ascending);
+        let descending: Vec<i32> = (0..300).rev().collect();
+        check_all(&descending);
+        check_all(&[7; 300]);
+    }
+
+    #[test]
+    fn test_sort_random() {
+        let mut rng = Rng(0x853C49E6748FEA9B);
+        for len in [3, 10, 64, 257, 1000] {
+            for range in [2, 10, 1_000_000] {
+                let input: Vec<i32> = (0..len).map(|_| (rng.next_u64() % range) as i32).collect();
+                check_all(&input);
+            }
+        }
+    }
+
+    // Ordered by key only, index records where the element started out
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Keyed {
+        key: u8,
+        index: usize,
     }
-}
\ No newline at end of file
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn test_sort_stability() {
+        let mut rng = Rng(0x2545F4914F6CDD1D);
+        let input: Vec<Keyed> = (0..500)
+            .map(|index| Keyed { key: (rng.next_u64() % 8) as u8, index })
+            .collect();
+        let mut expected: Vec<(u8, usize)> = input.iter().map(|k| (k.key, k.index)).collect();
+        expected.sort();
+        for algo in SortAlgo::ALL.into_iter().filter(|algo| algo.is_stable()) {
+            let mut vec = input.clone();
+            sort(&mut vec, algo);
+            let got: Vec<(u8, usize)> = vec.iter().map(|k| (k.key, k.index)).collect();
+            assert_eq!(got, expected, "{:?} is not stable", algo);
+        }
+    }
+
+    #[test]
+    fn test_stats_insertion() {
+        let n = 1000u64;
+        let mut sorted: Vec<u64> = (0..n).collect();
+        let stats = sort_instrumented(&mut sorted, SortAlgo::Insertion);
+        assert_eq!(stats, SortStats { comparisons: n - 1, swaps: 0 });
+
+        let mut reversed: Vec<u64> = (0..n).rev().collect();
+        let stats = sort_instrumented(&mut reversed, SortAlgo::Insertion);
+        assert_eq!(stats.swaps, n * (n - 1) / 2);
+        assert_eq!(stats.comparisons, n * (n - 1) / 2);
+        assert!(reversed.into_iter().eq(0..n));
+    }
+
+    #[test]
+    fn test_stats_n_log_n() {
+        let n = 4096usize;
+        let bound = (n as f64 * (n as f64).log2()) as u64;
+        let mut rng = Rng(0x9E3779B97F4A7C15);
+        let input: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+        for algo in [SortAlgo::Merge, SortAlgo::Quick, SortAlgo::Heap] {
+            let mut vec = input.clone();
+            let stats = sort_instrumented(&mut vec, algo);
+            assert!(vec.windows(2).all(|w| w[0] <= w[1]));
+            assert!(stats.comparisons < 2 * bound, "{:?} made {:?}", algo, stats);
+            assert!(stats.comparisons > bound / 2, "{:?} made {:?}", algo, stats);
+        }
+        let mut vec = input.clone();
+        let stats = sort_instrumented(&mut vec, SortAlgo::Bubble);
+        assert_eq!(stats.comparisons, (n * (n - 1)) as u64);
+    }
+
+    #[test]
+    fn test_stats_count_every_algorithm() {
+        for algo in SortAlgo::ALL {
+            let mut vec = vec![3, 1, 2];
+            let stats = sort_instrumented(&mut vec, algo);
+            assert_eq!(vec, vec![1, 2, 3]);
+            assert!(stats.comparisons > 0, "{:?}", algo);
+            assert!(stats.swaps > 0 || algo == SortAlgo::Heap, "{:?}", algo);
+            assert_eq!(sort_instrumented(&mut Vec::<u8>::new(), algo), SortStats::default());
+        }
+    }
+}
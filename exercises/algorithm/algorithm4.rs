@@ -13,6 +13,8 @@ where
     T: Ord,
 {
     value: T,
+    // nodes on the longest path down to a leaf, a leaf has height 1
+    height: usize,
     left: Option<Box<TreeNode<T>>>,
     right: Option<Box<TreeNode<T>>>,
 }
@@ -32,6 +34,7 @@ where
     fn new(value: T) -> Self {
         TreeNode {
             value,
+            height: 1,
             left: None,
             right: None,
         }
@@ -47,6 +50,16 @@ where
         BinarySearchTree { root: None }
     }
 
+    // Height-balanced tree from strictly ascending values, each subtree is rooted
+    // at the middle of its part of the slice
+    fn from_sorted_slice(values: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        debug_assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        BinarySearchTree { root: TreeNode::build_balanced(values) }
+    }
+
     // Insert a value into the BST
     fn insert(&mut self, value: T) {
         match self.root {
@@ -60,6 +73,84 @@ where
         self.search_recursive(&self.root, value)
     }
 
+    // Values in ascending order, borrowed from the tree
+    fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+
+    // Consume the tree into its values in ascending order without cloning
+    fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::new();
+        let mut stack = Vec::new();
+        let mut current = self.root.take();
+        loop {
+            while let Some(mut node) = current {
+                current = node.left.take();
+                stack.push(node);
+            }
+            match stack.pop() {
+                Some(mut node) => {
+                    current = node.right.take();
+                    sorted.push(node.value);
+                }
+                None => return sorted,
+            }
+        }
+    }
+
+    // Largest value that is <= value
+    fn floor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut node = self.root.as_deref();
+        while let Some(current) = node {
+            match value.cmp(&current.value) {
+                Ordering::Equal => return Some(&current.value),
+                Ordering::Less => node = current.left.as_deref(),
+                Ordering::Greater => {
+                    best = Some(&current.value);
+                    node = current.right.as_deref();
+                }
+            }
+        }
+        best
+    }
+
+    // Smallest value that is >= value
+    fn ceil(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut node = self.root.as_deref();
+        while let Some(current) = node {
+            match value.cmp(&current.value) {
+                Ordering::Equal => return Some(&current.value),
+                Ordering::Greater => node = current.right.as_deref(),
+                Ordering::Less => {
+                    best = Some(&current.value);
+                    node = current.left.as_deref();
+                }
+            }
+        }
+        best
+    }
+
+    // Values in [low, high] in ascending order, subtrees outside the bounds are never entered
+    fn range<'a>(&'a self, low: &'a T, high: &'a T) -> Range<'a, T> {
+        let mut range = Range { stack: Vec::new(), low, high };
+        range.push_left_spine(self.root.as_deref());
+        range
+    }
+
+    // Remove a value from the BST, returns whether it was there
+    fn delete(&mut self, value: &T) -> bool {
+        TreeNode::delete(&mut self.root, value, false)
+    }
+
+    // Nodes on the longest root to leaf path, 0 for an empty tree
+    fn height(&self) -> usize {
+        TreeNode::height(&self.root)
+    }
+
     fn search_recursive(&self, node: &Option<Box<TreeNode<T>>>, value: T) -> bool {
         match node {
             Some(ref current_node) => {
@@ -80,25 +171,321 @@ impl<T> TreeNode<T>
 where
     T: Ord,
 {
-    // Insert a node into the tree
+    // Insert a node into the tree, walking down in a loop so a degenerate
+    // tree cannot overflow the stack
     fn insert(&mut self, value: T) {
-        if value == self.value {
-            return
+        // first find where the value goes, remembering the turns taken
+        let mut turns = Vec::new();
+        let mut node = &*self;
+        loop {
+            let child = match value.cmp(&node.value) {
+                Ordering::Equal => return,
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            };
+            turns.push(value < node.value);
+            match child {
+                Some(next) => node = next,
+                None => break,
+            }
+        }
+        // then take the same turns again, raising the heights to fit the new leaf
+        let depth = turns.len();
+        let mut node = self;
+        for (d, &left) in turns.iter().enumerate() {
+            node.height = node.height.max(depth - d + 1);
+            let child = if left { &mut node.left } else { &mut node.right };
+            match child {
+                Some(next) => node = next,
+                None => {
+                    *child = Some(Box::new(TreeNode::new(value)));
+                    return
+                }
+            }
+        }
+    }
+
+    fn build_balanced(values: &[T]) -> Option<Box<TreeNode<T>>>
+    where
+        T: Clone,
+    {
+        if values.is_empty() {
+            return None;
         }
-        if value < self.value {
-            match self.left {
-                Some(ref mut left) => left.insert(value),
-                None => self.left = Some(Box::new(TreeNode::new(value))),
+        let mid = values.len() / 2;
+        let mut node = Box::new(TreeNode::new(values[mid].clone()));
+        node.left = TreeNode::build_balanced(&values[..mid]);
+        node.right = TreeNode::build_balanced(&values[mid + 1..]);
+        node.update_height();
+        Some(node)
+    }
+
+    fn height(node: &Option<Box<TreeNode<T>>>) -> usize {
+        node.as_ref().map_or(0, |node| node.height)
+    }
+
+    fn update_height(&mut self) {
+        self.height = 1 + TreeNode::height(&self.left).max(TreeNode::height(&self.right));
+    }
+
+    // Left subtree height minus right subtree height
+    fn balance_factor(&self) -> isize {
+        TreeNode::height(&self.left) as isize - TreeNode::height(&self.right) as isize
+    }
+
+    // The left child becomes the root of this subtree
+    fn rotate_right(mut node: Box<TreeNode<T>>) -> Box<TreeNode<T>> {
+        let mut left = node.left.take().unwrap();
+        node.left = left.right.take();
+        node.update_height();
+        left.right = Some(node);
+        left.update_height();
+        left
+    }
+
+    // The right child becomes the root of this subtree
+    fn rotate_left(mut node: Box<TreeNode<T>>) -> Box<TreeNode<T>> {
+        let mut right = node.right.take().unwrap();
+        node.right = right.left.take();
+        node.update_height();
+        right.left = Some(node);
+        right.update_height();
+        right
+    }
+
+    // Restore the AVL property at a node whose children are already balanced
+    fn rebalance(mut node: Box<TreeNode<T>>) -> Box<TreeNode<T>> {
+        node.update_height();
+        let balance = node.balance_factor();
+        if balance > 1 {
+            if node.left.as_ref().unwrap().balance_factor() < 0 {
+                node.left = Some(TreeNode::rotate_left(node.left.take().unwrap()));
+            }
+            TreeNode::rotate_right(node)
+        } else if balance < -1 {
+            if node.right.as_ref().unwrap().balance_factor() > 0 {
+                node.right = Some(TreeNode::rotate_right(node.right.take().unwrap()));
             }
+            TreeNode::rotate_left(node)
         } else {
-            match self.right {
-                Some(ref mut right) => right.insert(value),
-                None => self.right = Some(Box::new(TreeNode::new(value))),
+            node
+        }
+    }
+
+    // Fix the height of the node in slot after one of its subtrees changed,
+    // rotating it back into balance as well for a balanced tree
+    fn repair(slot: &mut Option<Box<TreeNode<T>>>, balanced: bool) {
+        if let Some(mut node) = slot.take() {
+            if balanced {
+                node = TreeNode::rebalance(node);
+            } else {
+                node.update_height();
+            }
+            *slot = Some(node);
+        }
+    }
+
+    // AVL insert, recursion is fine here since the depth stays O(log n)
+    fn insert_balanced(slot: &mut Option<Box<TreeNode<T>>>, value: T) {
+        match slot {
+            None => *slot = Some(Box::new(TreeNode::new(value))),
+            Some(node) => match value.cmp(&node.value) {
+                Ordering::Equal => return,
+                Ordering::Less => TreeNode::insert_balanced(&mut node.left, value),
+                Ordering::Greater => TreeNode::insert_balanced(&mut node.right, value),
+            },
+        }
+        TreeNode::repair(slot, true);
+    }
+
+    // Remove value from the subtree hanging off slot, a node with two children
+    // takes the value of its in-order successor
+    fn delete(slot: &mut Option<Box<TreeNode<T>>>, value: &T, balanced: bool) -> bool {
+        let node = match slot {
+            Some(node) => node,
+            None => return false,
+        };
+        let removed = match value.cmp(&node.value) {
+            Ordering::Less => TreeNode::delete(&mut node.left, value, balanced),
+            Ordering::Greater => TreeNode::delete(&mut node.right, value, balanced),
+            Ordering::Equal => {
+                let mut node = slot.take().unwrap();
+                *slot = match (node.left.take(), node.right.take()) {
+                    (None, None) => None,
+                    (Some(child), None) | (None, Some(child)) => Some(child),
+                    (Some(left), Some(right)) => {
+                        let (successor, rest) = TreeNode::take_min(right, balanced);
+                        node.value = successor;
+                        node.left = Some(left);
+                        node.right = rest;
+                        Some(node)
+                    }
+                };
+                true
+            }
+        };
+        if removed {
+            TreeNode::repair(slot, balanced);
+        }
+        removed
+    }
+
+    // Split the smallest value off a subtree, returning it and what is left
+    fn take_min(mut node: Box<TreeNode<T>>, balanced: bool) -> (T, Option<Box<TreeNode<T>>>) {
+        match node.left.take() {
+            None => (node.value, node.right),
+            Some(left) => {
+                let (min, rest) = TreeNode::take_min(left, balanced);
+                node.left = rest;
+                let mut slot = Some(node);
+                TreeNode::repair(&mut slot, balanced);
+                (min, slot)
             }
         }
     }
 }
 
+// A BST that rebalances itself with AVL rotations on insert and delete, so
+// sorted input still gives O(log n) operations
+#[derive(Debug)]
+struct BalancedTree<T>
+where
+    T: Ord,
+{
+    tree: BinarySearchTree<T>,
+}
+
+impl<T> BalancedTree<T>
+where
+    T: Ord,
+{
+    fn new() -> Self {
+        BalancedTree { tree: BinarySearchTree::new() }
+    }
+
+    fn insert(&mut self, value: T) {
+        TreeNode::insert_balanced(&mut self.tree.root, value)
+    }
+
+    fn delete(&mut self, value: &T) -> bool {
+        TreeNode::delete(&mut self.tree.root, value, true)
+    }
+
+    fn search(&self, value: T) -> bool {
+        self.tree.search(value)
+    }
+
+    fn height(&self) -> usize {
+        self.tree.height()
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        self.tree.iter()
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        self.tree.floor(value)
+    }
+
+    fn ceil(&self, value: &T) -> Option<&T> {
+        self.tree.ceil(value)
+    }
+
+    fn range<'a>(&'a self, low: &'a T, high: &'a T) -> Range<'a, T> {
+        self.tree.range(low, high)
+    }
+}
+
+// In-order traversal with an explicit stack of the nodes still to visit
+struct Iter<'a, T>
+where
+    T: Ord,
+{
+    stack: Vec<&'a TreeNode<T>>,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: Ord,
+{
+    fn push_left_spine(&mut self, mut node: Option<&'a TreeNode<T>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some(&node.value)
+    }
+}
+
+// Like Iter, but skips left spines below low and stops at the first value above high
+struct Range<'a, T>
+where
+    T: Ord,
+{
+    stack: Vec<&'a TreeNode<T>>,
+    low: &'a T,
+    high: &'a T,
+}
+
+impl<'a, T> Range<'a, T>
+where
+    T: Ord,
+{
+    fn push_left_spine(&mut self, mut node: Option<&'a TreeNode<T>>) {
+        while let Some(current) = node {
+            if current.value < *self.low {
+                // everything to the left is smaller still
+                node = current.right.as_deref();
+            } else {
+                self.stack.push(current);
+                node = current.left.as_deref();
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for Range<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        if node.value > *self.high {
+            self.stack.clear();
+            return None;
+        }
+        self.push_left_spine(node.right.as_deref());
+        Some(&node.value)
+    }
+}
+
+// Free nodes from an explicit stack, the default recursive drop overflows on deep trees
+impl<T> Drop for BinarySearchTree<T>
+where
+    T: Ord,
+{
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<TreeNode<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -150,6 +537,288 @@ mod tests {
             None => panic!("Root should not be None after insertion"),
         }
     }
+
+    #[test]
+    fn test_delete_leaf_and_single_child() {
+        let mut bst = BinarySearchTree::new();
+        for v in [5, 3, 8, 2, 9] {
+            bst.insert(v);
+        }
+
+        assert_eq!(bst.delete(&2), true);
+        assert_eq!(bst.search(2), false);
+        assert_eq!(bst.search(3), true);
+
+        // 8 only has the right child 9
+        assert_eq!(bst.delete(&8), true);
+        assert_eq!(bst.search(8), false);
+        assert_eq!(bst.search(9), true);
+        assert_eq!(bst.root.as_ref().unwrap().right.as_ref().unwrap().value, 9);
+    }
+
+    #[test]
+    fn test_delete_two_children_and_root() {
+        let mut bst = BinarySearchTree::new();
+        for v in [50, 30, 70, 20, 40, 60, 80, 65] {
+            bst.insert(v);
+        }
+
+        // 70 has children 60 and 80 and is replaced by its successor 80
+        assert_eq!(bst.delete(&70), true);
+        assert_eq!(bst.root.as_ref().unwrap().right.as_ref().unwrap().value, 80);
+        assert_eq!(bst.search(70), false);
+        for v in [50, 30, 20, 40, 60, 80, 65] {
+            assert_eq!(bst.search(v), true);
+        }
+
+        // the root has two children and is replaced by its successor 60
+        assert_eq!(bst.delete(&50), true);
+        assert_eq!(bst.root.as_ref().unwrap().value, 60);
+        for v in [30, 20, 40, 60, 80, 65] {
+            assert_eq!(bst.search(v), true);
+        }
+        assert_eq!(bst.search(50), false);
+    }
+
+    #[test]
+    fn test_delete_missing() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(bst.delete(&1), false);
+        bst.insert(1);
+        assert_eq!(bst.delete(&2), false);
+        assert_eq!(bst.search(1), true);
+    }
+
+    #[test]
+    fn test_delete_until_empty() {
+        let mut bst = BinarySearchTree::new();
+        let values = [15, 6, 23, 4, 7, 71, 5, 50, 18, 19];
+        for v in values {
+            bst.insert(v);
+        }
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(bst.delete(v), true);
+            assert_eq!(bst.delete(v), false);
+            for gone in &values[..=i] {
+                assert_eq!(bst.search(*gone), false);
+            }
+            for kept in &values[i + 1..] {
+                assert_eq!(bst.search(*kept), true);
+            }
+        }
+        assert!(bst.root.is_none());
+    }
+
+    #[test]
+    fn test_iter_in_order() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(bst.iter().next(), None);
+        for v in [50, 30, 70, 20, 40, 60, 80, 65, 30] {
+            bst.insert(v);
+        }
+        let values: Vec<&i32> = bst.iter().collect();
+        assert_eq!(values, vec![&20, &30, &40, &50, &60, &65, &70, &80]);
+        assert_eq!(bst.into_sorted_vec(), vec![20, 30, 40, 50, 60, 65, 70, 80]);
+    }
+
+    #[test]
+    fn test_iter_does_not_clone() {
+        // String is not Copy, iter hands out references into the tree
+        let mut bst = BinarySearchTree::new();
+        for s in ["pear", "apple", "fig"] {
+            bst.insert(s.to_string());
+        }
+        let first: &String = bst.iter().next().unwrap();
+        assert_eq!(first, "apple");
+        assert_eq!(bst.into_sorted_vec(), vec!["apple", "fig", "pear"]);
+    }
+
+    #[test]
+    fn test_iter_degenerate_tree() {
+        // the shape inserting 0..n in ascending order produces, linked directly
+        // because n ascending inserts into an unbalanced tree take O(n^2)
+        let n = 100_000;
+        let mut bst = BinarySearchTree::new();
+        for v in (0..n - 1).rev() {
+            let mut node = Box::new(TreeNode::new(v));
+            node.right = bst.root.take();
+            bst.root = Some(node);
+        }
+        // one more insert has to walk the whole chain
+        bst.insert(n - 1);
+        assert!(bst.iter().copied().eq(0..n));
+        let sorted = bst.into_sorted_vec();
+        assert_eq!(sorted.len(), n as usize);
+        assert!(sorted.into_iter().eq(0..n));
+    }
+
+    // Check every stored height and, for balanced trees, every balance factor
+    fn assert_heights<T: Ord>(node: &Option<Box<TreeNode<T>>>, balanced: bool) -> usize {
+        match node {
+            None => 0,
+            Some(node) => {
+                let left = assert_heights(&node.left, balanced);
+                let right = assert_heights(&node.right, balanced);
+                assert_eq!(node.height, 1 + left.max(right));
+                if balanced {
+                    assert!(left.abs_diff(right) <= 1);
+                }
+                node.height
+            }
+        }
+    }
+
+    #[test]
+    fn test_height_of_plain_tree() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(bst.height(), 0);
+        for v in [1, 2, 3, 4] {
+            bst.insert(v);
+        }
+        assert_eq!(bst.height(), 4);
+        bst.insert(0);
+        bst.insert(3);
+        assert_eq!(bst.height(), 4);
+        assert_heights(&bst.root, false);
+        bst.delete(&4);
+        assert_eq!(bst.height(), 3);
+        bst.delete(&1);
+        assert_heights(&bst.root, false);
+        assert_eq!(bst.height(), 2);
+    }
+
+    #[test]
+    fn test_balanced_ascending_inserts() {
+        let n = 10_000;
+        let mut tree = BalancedTree::new();
+        for v in 0..n {
+            tree.insert(v);
+        }
+        let bound = 2.0 * (n as f64).log2() + 2.0;
+        assert!((tree.height() as f64) < bound, "height {}", tree.height());
+        assert_heights(&tree.tree.root, true);
+        for v in 0..n {
+            assert!(tree.search(v));
+        }
+        assert!(!tree.search(n));
+        assert!(tree.iter().copied().eq(0..n));
+    }
+
+    #[test]
+    fn test_balanced_delete_rebalances() {
+        let mut tree = BalancedTree::new();
+        for v in 0..1_000 {
+            tree.insert(v);
+        }
+        // dropping the whole left half forces rotations on the way up
+        for v in 0..500 {
+            assert!(tree.delete(&v));
+            assert_heights(&tree.tree.root, true);
+        }
+        assert!(!tree.delete(&0));
+        assert!(tree.height() <= 11);
+        for v in 0..1_000 {
+            assert_eq!(tree.search(v), v >= 500);
+        }
+        for v in (500..1_000).step_by(3) {
+            tree.delete(&v);
+        }
+        assert_heights(&tree.tree.root, true);
+        tree.insert(42);
+        tree.insert(42);
+        assert!(tree.search(42));
+        assert_heights(&tree.tree.root, true);
+    }
+
+    #[test]
+    fn test_floor_ceil_range_empty() {
+        let bst = BinarySearchTree::<i32>::new();
+        assert_eq!(bst.floor(&3), None);
+        assert_eq!(bst.ceil(&3), None);
+        assert_eq!(bst.range(&0, &10).next(), None);
+    }
+
+    #[test]
+    fn test_floor_ceil_range_bounds() {
+        let mut bst = BinarySearchTree::new();
+        for v in [20, 10, 30, 5, 15, 25, 35] {
+            bst.insert(v);
+        }
+        assert_eq!(bst.floor(&15), Some(&15));
+        assert_eq!(bst.floor(&14), Some(&10));
+        assert_eq!(bst.floor(&4), None);
+        assert_eq!(bst.floor(&100), Some(&35));
+        assert_eq!(bst.ceil(&16), Some(&20));
+        assert_eq!(bst.ceil(&36), None);
+        assert_eq!(bst.ceil(&-1), Some(&5));
+        assert_eq!(bst.range(&12, &27).copied().collect::<Vec<_>>(), vec![15, 20, 25]);
+        assert_eq!(bst.range(&-10, &100).count(), 7);
+        assert_eq!(bst.range(&36, &100).next(), None);
+        assert_eq!(bst.range(&-10, &4).next(), None);
+        assert_eq!(bst.range(&27, &12).next(), None);
+        assert_eq!(bst.range(&20, &20).copied().collect::<Vec<_>>(), vec![20]);
+    }
+
+    #[test]
+    fn test_floor_ceil_range_random() {
+        use std::collections::BTreeSet;
+        let mut seed = 0x2545F4914F6CDD1Du64;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % 2_000) as i32 - 1_000
+        };
+        let mut bst = BinarySearchTree::new();
+        let mut tree = BalancedTree::new();
+        let mut reference = BTreeSet::new();
+        for _ in 0..500 {
+            let v = next();
+            bst.insert(v);
+            tree.insert(v);
+            reference.insert(v);
+        }
+        for _ in 0..300 {
+            let (a, b) = (next(), next());
+            assert_eq!(bst.floor(&a), reference.range(..=a).next_back());
+            assert_eq!(tree.floor(&a), reference.range(..=a).next_back());
+            assert_eq!(bst.ceil(&a), reference.range(a..).next());
+            assert_eq!(tree.ceil(&a), reference.range(a..).next());
+            let expected: Vec<&i32> = if a <= b { reference.range(a..=b).collect() } else { vec![] };
+            assert_eq!(bst.range(&a, &b).collect::<Vec<_>>(), expected);
+            assert_eq!(tree.range(&a, &b).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_sorted_slice_height() {
+        for n in [0usize, 1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 31, 32, 33, 1023, 1024, 1025] {
+            let values: Vec<usize> = (0..n).collect();
+            let bst = BinarySearchTree::from_sorted_slice(&values);
+            // ceil(log2(n + 1)) is the bit length of n
+            let expected = (usize::BITS - n.leading_zeros()) as usize;
+            assert_eq!(bst.height(), expected, "n = {}", n);
+            assert_heights(&bst.root, true);
+        }
+    }
+
+    #[test]
+    fn test_from_sorted_slice_round_trip() {
+        let words = ["ant", "bee", "cat", "dog", "eel", "fox"].map(String::from);
+        let bst = BinarySearchTree::from_sorted_slice(&words);
+        assert!(bst.search("cat".to_string()));
+        assert_eq!(bst.into_sorted_vec(), words.to_vec());
+
+        // rebuilding a degenerate tree keeps the elements and fixes the height
+        let mut chain = BinarySearchTree::new();
+        for v in 0..100 {
+            chain.insert(v);
+        }
+        assert_eq!(chain.height(), 100);
+        let rebuilt = BinarySearchTree::from_sorted_slice(&chain.into_sorted_vec());
+        assert_eq!(rebuilt.height(), 7);
+        assert_eq!(rebuilt.into_sorted_vec(), (0..100).collect::<Vec<_>>());
+    }
 }
 
 
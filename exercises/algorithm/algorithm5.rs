@@ -1,13 +1,76 @@
 /*
 	bfs
 	This problem requires you to implement a basic BFS algorithm
+	Weighted shortest paths reuse the Heap from algorithm9 as the priority queue.
 */
 
 use std::collections::VecDeque;
 
-// Define a graph
+#[allow(dead_code)]
+#[path = "algorithm9.rs"]
+mod heap;
+
+use heap::{Handle, Heap};
+
+type NodeId = usize;
+
+// Disjoint sets over 0..n with path compression and union by rank
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    // Representative of the set holding x, every node on the way is pointed straight at it
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    // Merge the sets holding a and b, false if they already were one set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // hang the shallower tree under the deeper one
+        match self.rank[a].cmp(&self.rank[b]) {
+            std::cmp::Ordering::Less => self.parent[a] = b,
+            std::cmp::Ordering::Greater => self.parent[b] = a,
+            std::cmp::Ordering::Equal => {
+                self.parent[b] = a;
+                self.rank[a] += 1;
+            }
+        }
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+// Define a graph, every neighbour is stored with the weight of the edge to it
 struct Graph {
-    adj: Vec<Vec<usize>>,
+    adj: Vec<Vec<(NodeId, u64)>>,
+    // removed nodes keep their slot so the other ids stay valid
+    removed: Vec<bool>,
 }
 
 impl Graph {
@@ -15,18 +78,133 @@ impl Graph {
     fn new(n: usize) -> Self {
         Graph {
             adj: vec![vec![]; n],
+            removed: vec![false; n],
         }
     }
 
-    // Add an edge to the graph
+    fn contains_node(&self, node: NodeId) -> bool {
+        node < self.adj.len() && !self.removed[node]
+    }
+
+    // Number of edges, a self loop counts once
+    fn edge_count(&self) -> usize {
+        self.adj.iter().map(|edges| edges.len()).sum::<usize>() / 2
+    }
+
+    // Groups of nodes joined by paths, each group sorted and the groups ordered
+    // by their smallest node
+    fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut sets = UnionFind::new(self.adj.len());
+        for (node, edges) in self.adj.iter().enumerate() {
+            for &(to, _) in edges {
+                sets.union(node, to);
+            }
+        }
+        // nodes are visited in ascending order, so groups and members come out sorted
+        let mut group_of_root: Vec<Option<usize>> = vec![None; self.adj.len()];
+        let mut components: Vec<Vec<NodeId>> = Vec::new();
+        for node in (0..self.adj.len()).filter(|&node| self.contains_node(node)) {
+            let root = sets.find(node);
+            match group_of_root[root] {
+                Some(group) => components[group].push(node),
+                None => {
+                    group_of_root[root] = Some(components.len());
+                    components.push(vec![node]);
+                }
+            }
+        }
+        components
+    }
+
+    // Remove one edge between src and dest, false if there was none
+    fn remove_edge(&mut self, src: NodeId, dest: NodeId) -> bool {
+        if !self.contains_node(src) || !self.contains_node(dest) {
+            return false;
+        }
+        let Some(idx) = self.adj[src].iter().position(|&(to, _)| to == dest) else {
+            return false;
+        };
+        self.adj[src].remove(idx);
+        // a self loop is stored twice in the same list
+        let back = self.adj[dest].iter().position(|&(to, _)| to == src).unwrap();
+        self.adj[dest].remove(back);
+        true
+    }
+
+    // Remove a node and every edge touching it, false if it was not in the graph
+    fn remove_node(&mut self, node: NodeId) -> bool {
+        if !self.contains_node(node) {
+            return false;
+        }
+        for (other, _) in std::mem::take(&mut self.adj[node]) {
+            self.adj[other].retain(|&(to, _)| to != node);
+        }
+        self.removed[node] = true;
+        true
+    }
+
+    // Add an edge to the graph, unweighted edges count as weight 1
     fn add_edge(&mut self, src: usize, dest: usize) {
-        self.adj[src].push(dest);
-        self.adj[dest].push(src);
+        self.add_weighted_edge(src, dest, 1);
+    }
+
+    fn add_weighted_edge(&mut self, src: NodeId, dest: NodeId, weight: u64) {
+        self.adj[src].push((dest, weight));
+        self.adj[dest].push((src, weight));
+    }
+
+    // Dijkstra from src, returns the total weight and the nodes along the path
+    // or None if dst cannot be reached
+    fn shortest_path(&self, src: NodeId, dst: NodeId) -> Option<(u64, Vec<NodeId>)> {
+        if !self.contains_node(src) || !self.contains_node(dst) {
+            return None;
+        }
+        let mut dist: Vec<Option<u64>> = vec![None; self.adj.len()];
+        let mut prev: Vec<Option<NodeId>> = vec![None; self.adj.len()];
+        let mut handles: Vec<Option<Handle>> = vec![None; self.adj.len()];
+        let mut done = vec![false; self.adj.len()];
+        // (distance, node) pairs, the smallest distance on top
+        let mut queue = Heap::new_min();
+
+        dist[src] = Some(0);
+        handles[src] = Some(queue.push_with_handle((0, src)));
+        while let Some((d, n)) = queue.pop() {
+            if n == dst {
+                break;
+            }
+            done[n] = true;
+            for &(next, weight) in &self.adj[n] {
+                let candidate = d + weight;
+                // only a strictly shorter path relaxes, so zero weights and self loops settle
+                if done[next] || dist[next].map_or(false, |known| known <= candidate) {
+                    continue;
+                }
+                dist[next] = Some(candidate);
+                prev[next] = Some(n);
+                match handles[next] {
+                    Some(handle) => {
+                        queue.update(handle, (candidate, next));
+                    }
+                    None => handles[next] = Some(queue.push_with_handle((candidate, next))),
+                }
+            }
+        }
+
+        let total = dist[dst]?;
+        let mut path = vec![dst];
+        while let Some(p) = prev[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path.reverse();
+        Some((total, path))
     }
 
     // Perform a breadth-first search on the graph, return the order of visited nodes
     fn bfs_with_return(&self, start: usize) -> Vec<usize> {
         let mut visit_order = vec![];
+        if !self.contains_node(start) {
+            return visit_order;
+        }
         let mut visited = vec![false; self.adj.len()];
 
         let mut q = VecDeque::new();
@@ -36,7 +214,7 @@ impl Graph {
         while let Some(n) = q.pop_front() {
             visit_order.push(n);
             if let Some(nodes) = self.adj.get(n) {
-                for &node in nodes {
+                for &(node, _) in nodes {
                     if !visited[node] {
                         visited[node] = true;
                         q.push_back(node);
@@ -97,5 +275,190 @@ mod tests {
         let visited_order = graph.bfs_with_return(0);
         assert_eq!(visited_order, vec![0]);
     }
+
+    #[test]
+    fn test_shortest_path_weighted() {
+        let mut graph = Graph::new(5);
+        graph.add_weighted_edge(0, 1, 4);
+        graph.add_weighted_edge(0, 2, 1);
+        graph.add_weighted_edge(2, 1, 2);
+        graph.add_weighted_edge(1, 3, 5);
+        graph.add_weighted_edge(2, 3, 8);
+
+        assert_eq!(graph.shortest_path(0, 3), Some((8, vec![0, 2, 1, 3])));
+        assert_eq!(graph.shortest_path(3, 0), Some((8, vec![3, 1, 2, 0])));
+        assert_eq!(graph.shortest_path(0, 0), Some((0, vec![0])));
+        assert_eq!(graph.shortest_path(0, 4), None);
+        assert_eq!(graph.shortest_path(0, 9), None);
+    }
+
+    #[test]
+    fn test_shortest_path_zero_weights_and_self_loops() {
+        let mut graph = Graph::new(4);
+        graph.add_weighted_edge(0, 0, 0);
+        graph.add_weighted_edge(0, 1, 0);
+        graph.add_weighted_edge(1, 0, 0);
+        graph.add_weighted_edge(1, 1, 3);
+        graph.add_weighted_edge(1, 2, 0);
+        graph.add_weighted_edge(2, 3, 7);
+
+        assert_eq!(graph.shortest_path(0, 3), Some((7, vec![0, 1, 2, 3])));
+        assert_eq!(graph.shortest_path(2, 0), Some((0, vec![2, 1, 0])));
+    }
+
+    // Relax every edge n - 1 times, slow but obviously right
+    fn bellman_ford(graph: &Graph, src: NodeId) -> Vec<Option<u64>> {
+        let mut dist = vec![None; graph.adj.len()];
+        dist[src] = Some(0);
+        for _ in 1..graph.adj.len() {
+            for (n, edges) in graph.adj.iter().enumerate() {
+                for &(next, weight) in edges {
+                    if let Some(d) = dist[n] {
+                        if dist[next].map_or(true, |known| d + weight < known) {
+                            dist[next] = Some(d + weight);
+                        }
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_shortest_path_matches_bellman_ford() {
+        let mut seed = 0x9E3779B97F4A7C15u64;
+        let mut next = move |bound: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % bound
+        };
+        for _ in 0..50 {
+            let n = 2 + next(9) as usize;
+            let mut graph = Graph::new(n);
+            for _ in 0..next(2 * n as u64) {
+                let (u, v) = (next(n as u64) as usize, next(n as u64) as usize);
+                graph.add_weighted_edge(u, v, next(10));
+            }
+            let src = next(n as u64) as usize;
+            let expected = bellman_ford(&graph, src);
+            for dst in 0..n {
+                let found = graph.shortest_path(src, dst);
+                assert_eq!(found.as_ref().map(|(d, _)| *d), expected[dst]);
+                if let Some((d, path)) = found {
+                    // the path has to exist and add up to the reported distance
+                    assert_eq!(path.first(), Some(&src));
+                    assert_eq!(path.last(), Some(&dst));
+                    let total: u64 = path
+                        .windows(2)
+                        .map(|w| {
+                            graph.adj[w[0]]
+                                .iter()
+                                .filter(|(to, _)| *to == w[1])
+                                .map(|(_, weight)| *weight)
+                                .min()
+                                .unwrap()
+                        })
+                        .sum();
+                    assert_eq!(total, d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 2);
+        assert_eq!(graph.edge_count(), 4);
+
+        assert!(graph.remove_edge(1, 0));
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.remove_edge(0, 1));
+        assert!(!graph.remove_edge(0, 1));
+        assert!(graph.remove_edge(2, 2));
+        assert!(!graph.remove_edge(2, 2));
+        assert!(!graph.remove_edge(0, 7));
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.bfs_with_return(0), vec![0]);
+        assert_eq!(graph.bfs_with_return(2), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_remove_hub_node() {
+        // 0 is a hub joined to everyone, 3 and 4 also share an edge
+        let mut graph = Graph::new(5);
+        for n in 1..5 {
+            graph.add_edge(0, n);
+        }
+        graph.add_edge(3, 4);
+        graph.add_edge(0, 0);
+        assert_eq!(graph.edge_count(), 6);
+
+        assert!(graph.remove_node(0));
+        assert!(!graph.remove_node(0));
+        assert!(!graph.remove_node(5));
+        assert_eq!(graph.edge_count(), 1);
+        for n in 1..5 {
+            assert!(!graph.bfs_with_return(n).contains(&0));
+            assert!(graph.adj[n].iter().all(|&(to, _)| to != 0));
+        }
+        assert_eq!(graph.bfs_with_return(1), vec![1]);
+        assert_eq!(graph.bfs_with_return(3), vec![3, 4]);
+        assert_eq!(graph.bfs_with_return(0), vec![]);
+        assert_eq!(graph.shortest_path(1, 0), None);
+        assert!(!graph.remove_edge(0, 1));
+    }
+
+    #[test]
+    fn test_components_isolated() {
+        let mut graph = Graph::new(5);
+        graph.add_edge(3, 1);
+        assert_eq!(graph.connected_components(), vec![vec![0], vec![1, 3], vec![2], vec![4]]);
+        assert_eq!(Graph::new(0).connected_components(), Vec::<Vec<NodeId>>::new());
+    }
+
+    #[test]
+    fn test_components_one_big() {
+        let mut graph = Graph::new(6);
+        for (u, v) in [(5, 4), (4, 0), (2, 1), (1, 3), (3, 5)] {
+            graph.add_edge(u, v);
+        }
+        assert_eq!(graph.connected_components(), vec![vec![0, 1, 2, 3, 4, 5]]);
+        // cutting the bridge and dropping a node splits it again
+        graph.remove_edge(3, 5);
+        graph.remove_node(2);
+        assert_eq!(graph.connected_components(), vec![vec![0, 4, 5], vec![1, 3]]);
+    }
+
+    #[test]
+    fn test_union_find_incremental() {
+        let mut sets = UnionFind::new(6);
+        assert!(!sets.same_set(0, 5));
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 5));
+        assert!(!sets.same_set(1, 4));
+        assert!(sets.union(1, 2));
+        assert!(sets.union(2, 5));
+        assert!(sets.same_set(0, 4));
+        assert!(!sets.union(5, 0));
+        assert!(!sets.same_set(3, 0));
+        assert_eq!(sets.find(3), 3);
+    }
+
+    #[test]
+    fn test_union_find_long_chain() {
+        let n = 100_000;
+        let mut sets = UnionFind::new(n);
+        for i in 1..n {
+            sets.union(i - 1, i);
+        }
+        assert!(sets.same_set(0, n - 1));
+        // union by rank keeps the trees shallow
+        assert!(sets.rank.iter().all(|&rank| rank <= 17));
+    }
 }
 
@@ -36,11 +36,41 @@ impl Graph {
 
     // Perform a depth-first search on the graph, return the order of visited nodes
     fn dfs(&self, start: usize) -> Vec<usize> {
+        self.dfs_iterative(start)
+    }
+
+    // The original recursive search, one stack frame per node on the current path
+    fn dfs_recursive(&self, start: usize) -> Vec<usize> {
         let mut visited = HashSet::new();
         let mut visit_order = Vec::new();
         self.dfs_util(start, &mut visited, &mut visit_order);
         visit_order
     }
+
+    // Same visit order as dfs_recursive but with an explicit stack, so long paths
+    // cannot overflow the call stack
+    fn dfs_iterative(&self, start: usize) -> Vec<usize> {
+        let mut visited = HashSet::new();
+        let mut visit_order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(v) = stack.pop() {
+            // a node can be pushed by several neighbours before it is reached
+            if !visited.insert(v) {
+                continue;
+            }
+            visit_order.push(v);
+            if let Some(neighbors) = self.adj.get(v) {
+                // reversed so the first neighbour is popped, and visited, first
+                for &dest in neighbors.iter().rev() {
+                    if !visited.contains(&dest) {
+                        stack.push(dest);
+                    }
+                }
+            }
+        }
+        visit_order
+    }
 }
 
 #[cfg(test)]
@@ -82,5 +112,41 @@ mod tests {
         let visit_order_disconnected = graph.dfs(3);
         assert_eq!(visit_order_disconnected, vec![3, 4]);
     }
+
+    #[test]
+    fn test_dfs_long_chain() {
+        let n = 200_000;
+        let mut graph = Graph::new(n);
+        for i in 1..n {
+            graph.add_edge(i - 1, i);
+        }
+
+        let visit_order = graph.dfs(0);
+        assert_eq!(visit_order.len(), n);
+        assert!(visit_order.into_iter().eq(0..n));
+        let visit_order = graph.dfs(n / 2);
+        assert_eq!(&visit_order[..3], &[n / 2, n / 2 - 1, n / 2 - 2]);
+        assert_eq!(visit_order.len(), n);
+    }
+
+    #[test]
+    fn test_dfs_matches_recursive() {
+        let mut seed = 0xD1B54A32D192ED03u64;
+        let mut next = move |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed as usize % bound
+        };
+        for _ in 0..200 {
+            let n = 1 + next(12);
+            let mut graph = Graph::new(n);
+            for _ in 0..next(3 * n) {
+                graph.add_edge(next(n), next(n));
+            }
+            let start = next(n);
+            assert_eq!(graph.dfs_iterative(start), graph.dfs_recursive(start));
+        }
+    }
 }
 
@@ -101,22 +101,182 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 	}
 }
 
+// A stack that also knows its smallest element, mins holds the positions of the
+// running minima so min stays O(1) and T never needs to be cloned
+#[derive(Debug)]
+struct MinStack<T: Ord> {
+	stack: Stack<T>,
+	mins: Stack<usize>,
+}
+impl<T: Ord> MinStack<T> {
+	fn new() -> Self {
+		Self {
+			stack: Stack::new(),
+			mins: Stack::new(),
+		}
+	}
+	fn is_empty(&self) -> bool {
+		self.stack.is_empty()
+	}
+	fn len(&self) -> usize {
+		self.stack.len()
+	}
+	fn push(&mut self, val: T) {
+		// ties are pushed too, so popping one duplicate leaves the other as the minimum
+		let is_min = match self.min() {
+			Some(min) => val <= *min,
+			None => true,
+		};
+		if is_min {
+			self.mins.push(self.stack.len());
+		}
+		self.stack.push(val);
+	}
+	fn pop(&mut self) -> Option<T> {
+		let val = self.stack.pop()?;
+		if self.mins.peek() == Some(&self.stack.len()) {
+			self.mins.pop();
+		}
+		Some(val)
+	}
+	fn peek(&self) -> Option<&T> {
+		self.stack.peek()
+	}
+	fn min(&self) -> Option<&T> {
+		self.mins.peek().map(|&idx| &self.stack.data[idx])
+	}
+}
+
 fn bracket_match(bracket: &str) -> bool
+{
+	bracket_match_detailed(bracket, false).is_ok()
+}
+
+// Why a string failed to match, every index is the byte offset of the offending char
+#[derive(Debug, PartialEq)]
+enum MatchError {
+	// a closer with no opener left on the stack
+	UnexpectedCloser { index: usize, found: char },
+	// a closer that does not pair with the opener on top of the stack
+	Mismatched { index: usize, found: char, expected: char },
+	// an opener that was never closed
+	Unclosed { index: usize, found: char },
+	// a " that starts a string literal which never ends, only with skip_literals
+	UnterminatedString { index: usize },
+}
+
+impl std::fmt::Display for MatchError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			MatchError::UnexpectedCloser { index, found } => {
+				write!(f, "unexpected '{}' at byte {}", found, index)
+			}
+			MatchError::Mismatched { index, found, expected } => {
+				write!(f, "expected '{}' but found '{}' at byte {}", expected, found, index)
+			}
+			MatchError::Unclosed { index, found } => {
+				write!(f, "'{}' at byte {} is never closed", found, index)
+			}
+			MatchError::UnterminatedString { index } => {
+				write!(f, "the string at byte {} is never closed", index)
+			}
+		}
+	}
+}
+
+fn closer_for(opener: char) -> char {
+	match opener {
+		'(' => ')',
+		'[' => ']',
+		_ => '}',
+	}
+}
+
+// The length of the char literal at the start of rest, the part after a ',
+// up to and including the closing '. None if the ' doesn't start one, like
+// the ' of a lifetime or of an apostrophe.
+fn char_literal_len(rest: &str) -> Option<usize> {
+	let mut chars = rest.char_indices();
+	let end = match chars.next()? {
+		(_, '\\') => match chars.next()? {
+			// \u{...}, hex digits in braces
+			(_, 'u') => {
+				if chars.next()?.1 != '{' {
+					return None;
+				}
+				loop {
+					match chars.next()? {
+						(i, '}') => break i + 1,
+						(_, c) if c.is_ascii_hexdigit() => {}
+						_ => return None,
+					}
+				}
+			}
+			// \x and two hex digits
+			(_, 'x') => {
+				let (_, high) = chars.next()?;
+				let (i, low) = chars.next()?;
+				if !high.is_ascii_hexdigit() || !low.is_ascii_hexdigit() {
+					return None;
+				}
+				i + 1
+			}
+			(i, c) => i + c.len_utf8(),
+		},
+		(_, '\'') => return None,
+		(i, c) => i + c.len_utf8(),
+	};
+	rest[end..].starts_with('\'').then_some(end + 1)
+}
+
+// Like bracket_match but says where it went wrong. With skip_literals brackets
+// inside "..." strings (backslash escapes the next char), inside char literals
+// like '(' or '\u{7b}' and after a // line comment are ignored. A ' that
+// doesn't start a char literal is an ordinary char.
+fn bracket_match_detailed(bracket: &str, skip_literals: bool) -> Result<(), MatchError>
 {
 	let mut s = Stack::new();
-	for c in bracket.chars() {
+	let mut chars = bracket.char_indices().peekable();
+	while let Some((index, c)) = chars.next() {
 		match c {
-			'(' | '[' | '{' => s.push(c),
-            ')' => if s.pop() != Some('(') { return false; },
-            ']' => if s.pop() != Some('[') { return false; },
-            '}' => if s.pop() != Some('{') { return false; },
-            _ => continue,
+			'(' | '[' | '{' => s.push((index, c)),
+			')' | ']' | '}' => match s.pop() {
+				None => return Err(MatchError::UnexpectedCloser { index, found: c }),
+				Some((_, opener)) if closer_for(opener) != c => {
+					return Err(MatchError::Mismatched { index, found: c, expected: closer_for(opener) });
+				}
+				Some(_) => {}
+			},
+			'"' if skip_literals => loop {
+				match chars.next() {
+					None => return Err(MatchError::UnterminatedString { index }),
+					Some((_, '\\')) => {
+						chars.next();
+					}
+					Some((_, '"')) => break,
+					Some(_) => {}
+				}
+			},
+			'\'' if skip_literals => {
+				if let Some(len) = char_literal_len(&bracket[index + 1..]) {
+					let end = index + 1 + len;
+					while chars.next_if(|&(i, _)| i < end).is_some() {}
+				}
+			}
+			'/' if skip_literals && matches!(chars.peek(), Some((_, '/'))) => {
+				while let Some((_, inner)) = chars.next() {
+					if inner == '\n' {
+						break;
+					}
+				}
+			}
+			_ => continue,
 		}
 	}
-	if s.len() == 0 {
-		true
-	} else {
-		false
+	// the innermost unclosed opener is on top, report the outermost one instead
+	match s.into_iter().last() {
+		Some((index, found)) => Err(MatchError::Unclosed { index, found }),
+		None => Ok(()),
 	}
 }
 
@@ -154,4 +314,125 @@ mod tests {
 		let s = "";
 		assert_eq!(bracket_match(s),true);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn min_stack_empty(){
+		let mut s = MinStack::<i32>::new();
+		assert_eq!(s.min(), None);
+		assert_eq!(s.pop(), None);
+		assert!(s.is_empty());
+	}
+	#[test]
+	fn min_stack_reveals_previous_min(){
+		let mut s = MinStack::new();
+		for v in [5, 3, 7, 1, 4] {
+			s.push(v);
+		}
+		assert_eq!(s.min(), Some(&1));
+		assert_eq!(s.pop(), Some(4));
+		assert_eq!(s.min(), Some(&1));
+		assert_eq!(s.pop(), Some(1));
+		assert_eq!(s.min(), Some(&3));
+		assert_eq!(s.pop(), Some(7));
+		assert_eq!(s.pop(), Some(3));
+		assert_eq!(s.min(), Some(&5));
+		assert_eq!(s.peek(), Some(&5));
+		assert_eq!(s.len(), 1);
+	}
+	#[test]
+	fn min_stack_duplicate_mins(){
+		let mut s = MinStack::new();
+		for v in [2, 2, 1, 1] {
+			s.push(v);
+		}
+		assert_eq!(s.min(), Some(&1));
+		s.pop();
+		assert_eq!(s.min(), Some(&1));
+		s.pop();
+		assert_eq!(s.min(), Some(&2));
+		s.pop();
+		assert_eq!(s.min(), Some(&2));
+		s.pop();
+		assert_eq!(s.min(), None);
+	}
+
+	#[test]
+	fn bracket_detailed_ok(){
+		assert_eq!(bracket_match_detailed("fn f(a: [u8; 2]) { }", false), Ok(()));
+		assert_eq!(bracket_match_detailed("", true), Ok(()));
+	}
+	#[test]
+	fn bracket_detailed_unexpected_closer(){
+		let s = "héllo)";
+		let err = bracket_match_detailed(s, false).unwrap_err();
+		assert_eq!(err, MatchError::UnexpectedCloser { index: 6, found: ')' });
+		assert!(s.is_char_boundary(6));
+		assert_eq!(err.to_string(), "unexpected ')' at byte 6");
+	}
+	#[test]
+	fn bracket_detailed_mismatched(){
+		let s = "{ü(ø]}";
+		assert_eq!(
+			bracket_match_detailed(s, false),
+			Err(MatchError::Mismatched { index: 6, found: ']', expected: ')' })
+		);
+		assert_eq!(&s[6..7], "]");
+	}
+	#[test]
+	fn bracket_detailed_unclosed(){
+		let s = "日本(語[x]";
+		assert_eq!(
+			bracket_match_detailed(s, false),
+			Err(MatchError::Unclosed { index: 6, found: '(' })
+		);
+		assert_eq!(&s[6..7], "(");
+	}
+	#[test]
+	fn bracket_detailed_skips_literals(){
+		let s = "f(\"(\", ')', \"\\\"]\") // 🦀 ] {";
+		assert_eq!(bracket_match_detailed(s, true), Ok(()));
+		assert_eq!(
+			bracket_match_detailed(s, false),
+			Err(MatchError::Mismatched { index: 15, found: ']', expected: ')' })
+		);
+		let s = "🦀 { \"}\"\n// }\n]";
+		assert_eq!(
+			bracket_match_detailed(s, true),
+			Err(MatchError::Mismatched { index: 16, found: ']', expected: '}' })
+		);
+		assert!(s.is_char_boundary(16));
+		assert_eq!(bracket_match_detailed("a / b (c)", true), Ok(()));
+	}
+	#[test]
+	fn bracket_detailed_char_literals(){
+		let s = "m('{', '\\'', '\\u{7b}', '\\x28', '🦀')";
+		assert_eq!(bracket_match_detailed(s, true), Ok(()));
+		assert!(bracket_match_detailed(s, false).is_err());
+	}
+	#[test]
+	fn bracket_detailed_lifetimes_and_apostrophes(){
+		let s = "fn f<'a>(x: &'a [u8]) -> &'a u8 { &x[0] }";
+		assert_eq!(bracket_match_detailed(s, true), Ok(()));
+		assert_eq!(char_literal_len("a>(x"), None);
+		// the ' of don't must not hide the ( after it
+		let s = "don't (stop]";
+		assert_eq!(
+			bracket_match_detailed(s, true),
+			Err(MatchError::Mismatched { index: 11, found: ']', expected: ')' })
+		);
+		assert_eq!(bracket_match_detailed("it's [fine] isn't it", true), Ok(()));
+	}
+	#[test]
+	fn bracket_detailed_unterminated_string(){
+		let s = "f(\"abc)";
+		let err = bracket_match_detailed(s, true).unwrap_err();
+		assert_eq!(err, MatchError::UnterminatedString { index: 2 });
+		assert_eq!(err.to_string(), "the string at byte 2 is never closed");
+		// an escaped quote doesn't end it either
+		assert_eq!(
+			bracket_match_detailed("(\"\\\")", true),
+			Err(MatchError::UnterminatedString { index: 1 })
+		);
+		assert_eq!(bracket_match_detailed(s, false), Ok(()));
+	}
+}
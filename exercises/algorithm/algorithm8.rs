@@ -3,32 +3,148 @@
 	This question requires you to use queues to implement the functionality of the stack
 */
 
+// Growable circular buffer, the live elements are the len slots starting at head
+// and wrapping around the end of slots
+#[derive(Debug)]
+struct RingBuffer<T> {
+    slots: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    fn new() -> RingBuffer<T> {
+        RingBuffer {
+            slots: Vec::new(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // Slot holding the element that is offset places behind the front
+    fn physical(&self, offset: usize) -> usize {
+        (self.head + offset) % self.slots.len()
+    }
+
+    // Double the slots, unwrapping the elements so the front lands at slot 0
+    fn grow(&mut self) {
+        let capacity = (self.slots.len() * 2).max(4);
+        let mut slots = Vec::with_capacity(capacity);
+        for offset in 0..self.len {
+            let idx = self.physical(offset);
+            slots.push(self.slots[idx].take());
+        }
+        slots.resize_with(capacity, || None);
+        self.slots = slots;
+        self.head = 0;
+    }
+
+    fn push_back(&mut self, value: T) {
+        if self.len == self.slots.len() {
+            self.grow();
+        }
+        let idx = self.physical(self.len);
+        self.slots[idx] = Some(value);
+        self.len += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let value = self.slots[self.head].take();
+        self.head = self.physical(1);
+        self.len -= 1;
+        value
+    }
+
+    fn push_front(&mut self, value: T) {
+        if self.len == self.slots.len() {
+            self.grow();
+        }
+        // step head back one slot, wrapping to the end of slots
+        self.head = self.physical(self.slots.len() - 1);
+        self.slots[self.head] = Some(value);
+        self.len += 1;
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = self.physical(self.len - 1);
+        self.len -= 1;
+        self.slots[idx].take()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    // Element offset places behind the front
+    fn get(&self, offset: usize) -> Option<&T> {
+        if offset >= self.len {
+            return None;
+        }
+        self.slots[self.physical(offset)].as_ref()
+    }
+}
+
+// Walks a ring buffer from front to back
+pub struct Iter<'a, T> {
+    ring: &'a RingBuffer<T>,
+    offset: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let value = self.ring.get(self.offset)?;
+        self.offset += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.ring.len() - self.offset;
+        (remaining, Some(remaining))
+    }
+}
+
 #[derive(Debug)]
 pub struct Queue<T> {
-    elements: Vec<T>,
+    elements: RingBuffer<T>,
 }
 
 impl<T> Queue<T> {
     pub fn new() -> Queue<T> {
         Queue {
-            elements: Vec::new(),
+            elements: RingBuffer::new(),
         }
     }
 
     pub fn enqueue(&mut self, value: T) {
-        self.elements.push(value)
+        self.elements.push_back(value)
     }
 
     pub fn dequeue(&mut self) -> Result<T, &str> {
-        if !self.elements.is_empty() {
-            Ok(self.elements.remove(0usize))
-        } else {
-            Err("Queue is empty")
-        }
+        self.elements.pop_front().ok_or("Queue is empty")
     }
 
     pub fn peek(&self) -> Result<&T, &str> {
-        match self.elements.first() {
+        match self.elements.front() {
             Some(value) => Ok(value),
             None => Err("Queue is empty"),
         }
@@ -39,16 +155,73 @@ impl<T> Queue<T> {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.elements.is_empty()
+        self.elements.len() == 0
     }
 }
 
 impl<T> Default for Queue<T> {
     fn default() -> Queue<T> {
-        Queue {
-            elements: Vec::new(),
+        Queue::new()
+    }
+}
+
+// Double-ended queue on the same ring buffer, both ends are amortized O(1)
+#[derive(Debug)]
+pub struct Deque<T> {
+    elements: RingBuffer<T>,
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Deque<T> {
+        Deque {
+            elements: RingBuffer::new(),
         }
     }
+
+    pub fn push_front(&mut self, value: T) {
+        self.elements.push_front(value)
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.elements.push_back(value)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.elements.pop_front()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.elements.pop_back()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.elements.front()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.elements.back()
+    }
+
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.len() == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            ring: &self.elements,
+            offset: 0,
+        }
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Deque<T> {
+        Deque::new()
+    }
 }
 
 pub struct myStack<T>
@@ -115,4 +288,124 @@ mod tests {
         assert_eq!(s.pop(), Err("Stack is empty"));
         assert_eq!(s.is_empty(), true);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn test_ring_wraps_around(){
+		let mut q = Queue::new();
+		for i in 0..4 {
+			q.enqueue(i);
+		}
+		assert_eq!(q.elements.capacity(), 4);
+		assert_eq!(q.dequeue(), Ok(0));
+		assert_eq!(q.dequeue(), Ok(1));
+		q.enqueue(4);
+		q.enqueue(5);
+		// 4 and 5 went into the slots freed at the start
+		assert_eq!(q.elements.capacity(), 4);
+		assert_eq!(q.elements.head, 2);
+		assert_eq!(q.peek(), Ok(&2));
+		let drained: Vec<i32> = std::iter::from_fn(|| q.dequeue().ok()).collect();
+		assert_eq!(drained, vec![2, 3, 4, 5]);
+		assert_eq!(q.dequeue(), Err("Queue is empty"));
+		assert_eq!(q.peek(), Err("Queue is empty"));
+	}
+	#[test]
+	fn test_ring_grows_while_wrapped(){
+		let mut q = Queue::new();
+		for i in 0..4 {
+			q.enqueue(i);
+		}
+		q.dequeue().unwrap();
+		q.dequeue().unwrap();
+		q.dequeue().unwrap();
+		for i in 4..10 {
+			q.enqueue(i);
+		}
+		assert_eq!(q.elements.capacity(), 8);
+		assert_eq!(q.size(), 7);
+		let drained: Vec<i32> = std::iter::from_fn(|| q.dequeue().ok()).collect();
+		assert_eq!(drained, vec![3, 4, 5, 6, 7, 8, 9]);
+		assert!(q.is_empty());
+	}
+	#[test]
+	fn test_queue_many_items(){
+		let mut q = Queue::new();
+		for i in 0..100_000u32 {
+			q.enqueue(i);
+			if i % 3 == 0 {
+				q.dequeue().unwrap();
+			}
+		}
+		let mut expected = 33_334;
+		while let Ok(v) = q.dequeue() {
+			assert_eq!(v, expected);
+			expected += 1;
+		}
+		assert_eq!(expected, 100_000);
+	}
+	#[test]
+	fn test_stack_many_items(){
+		let mut s = myStack::new();
+		for i in 0..2_000 {
+			s.push(i);
+		}
+		for i in (0..2_000).rev() {
+			assert_eq!(s.pop(), Ok(i));
+		}
+		assert!(s.is_empty());
+	}
+
+	#[test]
+	fn test_deque_empty(){
+		let mut d = Deque::<i32>::new();
+		assert_eq!(d.pop_front(), None);
+		assert_eq!(d.pop_back(), None);
+		assert_eq!(d.front(), None);
+		assert_eq!(d.back(), None);
+		assert_eq!(d.iter().next(), None);
+		d.push_front(1);
+		assert_eq!(d.back(), Some(&1));
+		assert_eq!(d.pop_back(), Some(1));
+		assert!(d.is_empty());
+	}
+	#[test]
+	fn test_deque_alternating_growth(){
+		let mut d = Deque::new();
+		let mut expected = std::collections::VecDeque::new();
+		for i in 0..19 {
+			if i % 2 == 0 {
+				d.push_front(i);
+				expected.push_front(i);
+			} else {
+				d.push_back(i);
+				expected.push_back(i);
+			}
+			assert_eq!(d.front(), expected.front());
+			assert_eq!(d.back(), expected.back());
+		}
+		assert!(d.elements.capacity() >= 19);
+		assert_eq!(d.iter().size_hint(), (19, Some(19)));
+		assert!(d.iter().eq(expected.iter()));
+		while !expected.is_empty() {
+			assert_eq!(d.pop_front(), expected.pop_front());
+			assert_eq!(d.pop_back(), expected.pop_back());
+		}
+		assert!(d.is_empty());
+		assert_eq!(d.size(), 0);
+	}
+	#[test]
+	fn test_deque_iter_across_wrap(){
+		let mut d = Deque::new();
+		for i in 0..4 {
+			d.push_back(i);
+		}
+		d.pop_front();
+		d.pop_front();
+		d.push_back(4);
+		d.push_front(1);
+		assert_eq!(d.elements.capacity(), 4);
+		assert_eq!(d.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+		d.push_front(0);
+		assert_eq!(d.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+	}
+}
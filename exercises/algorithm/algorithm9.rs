@@ -1,92 +1,439 @@
 /*
 	heap
 	This question requires you to implement a binary heap function
+
+	Building with `--cfg 'feature="serde"'` (and serde passed via --extern)
+	adds Serialize support and deserialize helpers for Heap.
 */
 
 use std::cmp::Ord;
-use std::default::Default;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
 
-pub struct Heap<T>
-where
-    T: Default,
-{
-    count: usize,
+// Returns true when the first argument belongs above the second one
+type Comparator<T> = Rc<dyn Fn(&T, &T) -> bool>;
+
+/// Identifies an element added with `Heap::push_with_handle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle(usize);
+
+/// A D-ary heap, every node has up to D children. The default of two
+/// gives the usual binary heap.
+pub struct Heap<T, const D: usize = 2> {
     items: Vec<T>,
-    comparator: fn(&T, &T) -> bool,
+    comparator: Comparator<T>,
+    // The handle of the element in the same slot of items, if it has one
+    slot_handles: Vec<Option<Handle>>,
+    // Where each live handle currently sits in items
+    positions: HashMap<Handle, usize>,
+    next_handle: usize,
+    // Maximum number of elements kept, see with_limit
+    limit: Option<usize>,
 }
 
-impl<T> Heap<T>
-where
-    T: Default,
-{
-    pub fn new(comparator: fn(&T, &T) -> bool) -> Self {
-        Self {
-            count: 0,
-            items: vec![T::default()],
-            comparator,
-        }
+// Constructors for the binary heap, see with_arity for other arities
+impl<T> Heap<T> {
+    pub fn new(comparator: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        Self::from_vec(Vec::new(), comparator)
+    }
+
+    /// Create an empty heap with room for at least capacity elements
+    pub fn with_capacity(capacity: usize, comparator: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        Self::from_vec(Vec::with_capacity(capacity), comparator)
+    }
+
+    /// Create a heap that never holds more than limit elements. Adding to
+    /// a full heap either drops the new value, when it belongs above the
+    /// top, or replaces the top. A limited min-heap so keeps the `limit`
+    /// largest values seen.
+    pub fn with_limit(limit: usize, comparator: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        let mut heap = Self::with_capacity(limit, comparator);
+        heap.limit = Some(limit);
+        heap
+    }
+
+    /// Build a heap from existing items in O(n) by sifting down
+    /// every parent, starting from the last one
+    pub fn from_vec(items: Vec<T>, comparator: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        Self::from_vec_with_arity(items, comparator)
+    }
+}
+
+impl<T, const D: usize> Heap<T, D> {
+    // Referenced by the constructors so a bad arity fails to compile
+    const ARITY_CHECK: () = assert!(D >= 2, "a heap node needs room for at least two children");
+
+    /// Create an empty heap with the arity given as D,
+    /// e.g. `Heap::<u32, 4>::with_arity(|a, b| a < b)`
+    pub fn with_arity(comparator: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        Self::from_vec_with_arity(Vec::new(), comparator)
+    }
+
+    /// Same as from_vec, with the arity given as D
+    pub fn from_vec_with_arity(items: Vec<T>, comparator: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ARITY_CHECK;
+        let mut slot_handles = Vec::with_capacity(items.capacity());
+        slot_handles.resize(items.len(), None);
+        let mut heap = Self {
+            slot_handles,
+            items,
+            comparator: Rc::new(comparator),
+            positions: HashMap::new(),
+            next_handle: 0,
+            limit: None,
+        };
+        heap.heapify();
+        heap
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
     }
 
     pub fn len(&self) -> usize {
-        self.count
+        self.items.len()
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    // Number of slots actually stored, popped elements must not linger
+    #[cfg(test)]
+    fn storage_len(&self) -> usize {
+        debug_assert_eq!(self.items.len(), self.slot_handles.len());
+        self.items.len()
+    }
+
+    /// Number of elements the heap can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Reserve room for at least additional more elements
+    pub fn reserve(&mut self, additional: usize) {
+        self.items.reserve(additional);
+        self.slot_handles.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.items.shrink_to_fit();
+        self.slot_handles.shrink_to_fit();
+    }
+
+    /// Drop every element, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.slot_handles.clear();
+        self.positions.clear();
+    }
+
+    /// Iterate over the elements in arbitrary (internal) order
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Consume the heap, yielding its elements in arbitrary order
+    pub fn into_iter_unordered(self) -> std::vec::IntoIter<T> {
+        self.items.into_iter()
+    }
+
+    /// Returns the top element without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    /// Returns a guard giving mutable access to the top element,
+    /// the heap order is restored when the guard is dropped
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, D>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
     pub fn add(&mut self, value: T) {
-        // Increment count
-        self.count += 1;
+        self.push_slot(value, None);
+    }
+
+    /// Add an element whose value can later be changed with `update`
+    pub fn push_with_handle(&mut self, value: T) -> Handle {
+        let handle = Handle(self.next_handle);
+        self.next_handle += 1;
+        self.push_slot(value, Some(handle));
+        handle
+    }
+
+    /// Replace the value behind handle and move it to its new place in
+    /// O(log n). Returns the old value, or None if the handle is stale
+    /// because its element was already removed from the heap.
+    pub fn update(&mut self, handle: Handle, new_value: T) -> Option<T> {
+        let idx = *self.positions.get(&handle)?;
+        let old_value = std::mem::replace(&mut self.items[idx], new_value);
+        // Only one of these will actually move the element
+        let idx = self.sift_up(idx);
+        self.sift_down(idx);
+        Some(old_value)
+    }
+
+    /// Returns the current value behind handle, if it is still in the heap
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.positions.get(&handle).map(|&idx| &self.items[idx])
+    }
+
+    /// Remove and return the top element
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // Move the last element to the top and take the old top out
+        let top = self.swap_remove_slot(0);
+
+        // Perform down-heap bubbling
+        self.sift_down(0);
 
-        // If the vector isn't large enough to hold the new item, resize it
-        if self.count >= self.items.len() {
-            self.items.push(value);
+        Some(top)
+    }
+
+    /// Push value and pop the top in one sift pass. When value would
+    /// come out first anyway it is returned straight away.
+    pub fn push_pop(&mut self, value: T) -> T {
+        match self.items.first() {
+            Some(top) if (self.comparator)(top, &value) => {}
+            _ => return value,
+        }
+        self.replace_root(value)
+    }
+
+    /// Pop the top and push value in one sift pass. On an empty heap
+    /// this is the same as add and returns None.
+    pub fn replace_top(&mut self, value: T) -> Option<T> {
+        if self.is_empty() {
+            self.add(value);
+            None
         } else {
-            self.items[self.count] = value;
+            Some(self.replace_root(value))
+        }
+    }
+
+    // Swap value in at the top of a non-empty heap and sift it down
+    fn replace_root(&mut self, value: T) -> T {
+        let old = std::mem::replace(&mut self.items[0], value);
+        if let Some(handle) = self.slot_handles[0].take() {
+            self.positions.remove(&handle);
+        }
+        self.sift_down(0);
+        old
+    }
+
+    /// Remove the first element (in internal order) matching pred
+    pub fn remove_where(&mut self, pred: impl Fn(&T) -> bool) -> Option<T> {
+        let idx = self.items.iter().position(pred)?;
+        let value = self.swap_remove_slot(idx);
+        // The element moved into idx may belong above or below it
+        if idx < self.len() {
+            let idx = self.sift_up(idx);
+            self.sift_down(idx);
+        }
+        Some(value)
+    }
+
+    /// Keep only the elements matching pred, re-heapifying once at the end
+    pub fn retain(&mut self, mut pred: impl FnMut(&T) -> bool) {
+        let items = std::mem::take(&mut self.items);
+        let slot_handles = std::mem::take(&mut self.slot_handles);
+        self.positions.clear();
+        for (value, handle) in items.into_iter().zip(slot_handles) {
+            if pred(&value) {
+                if let Some(handle) = handle {
+                    self.positions.insert(handle, self.items.len());
+                }
+                self.items.push(value);
+                self.slot_handles.push(handle);
+            }
+        }
+        self.heapify();
+    }
+
+    /// Consume the heap, returning its elements in comparator order
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(value) = self.pop() {
+            sorted.push(value);
         }
+        sorted
+    }
+
+    /// Pop the elements in comparator order, the heap is left empty
+    /// even when the iterator is dropped before it is exhausted
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, D> {
+        DrainSorted { heap: self }
+    }
 
-        // Perform up-heap bubbling
-        let mut current_idx = self.count;
-        while current_idx > 1 && (self.comparator)(&self.items[current_idx], &self.items[self.parent_idx(current_idx)]) {
+    /// Move all elements of other into self, leaving other empty.
+    /// The result is ordered by self's comparator, other's is ignored.
+    /// Handles from other are not valid in self, those elements are
+    /// kept but can no longer be updated.
+    pub fn append(&mut self, other: &mut Heap<T, D>) {
+        self.slot_handles.extend(other.items.iter().map(|_| None));
+        self.items.append(&mut other.items);
+        other.slot_handles.clear();
+        other.positions.clear();
+        self.heapify();
+        if let Some(limit) = self.limit {
+            while self.len() > limit {
+                self.pop();
+            }
+        }
+    }
+
+    // Restore the heap order of the whole items vector
+    fn heapify(&mut self) {
+        for idx in (0..self.len() / 2).rev() {
+            self.sift_down(idx);
+        }
+    }
+
+    // Append a new element at the bottom and bubble it up
+    fn push_slot(&mut self, value: T, handle: Option<Handle>) {
+        if self.limit.is_some_and(|limit| self.len() >= limit) {
+            self.push_full(value, handle);
+            return;
+        }
+        self.items.push(value);
+        self.slot_handles.push(handle);
+        let idx = self.len() - 1;
+        if let Some(handle) = handle {
+            self.positions.insert(handle, idx);
+        }
+        self.sift_up(idx);
+    }
+
+    // Add to a heap that reached its limit by replacing the top
+    fn push_full(&mut self, value: T, handle: Option<Handle>) {
+        match self.items.first() {
+            Some(top) if (self.comparator)(top, &value) => {}
+            // Rejected, or the limit is zero
+            _ => return,
+        }
+        if let Some(Some(old)) = self.slot_handles.first() {
+            self.positions.remove(old);
+        }
+        self.items[0] = value;
+        self.slot_handles[0] = handle;
+        if let Some(handle) = handle {
+            self.positions.insert(handle, 0);
+        }
+        self.sift_down(0);
+    }
+
+    // Remove the element at idx, filling the hole with the last element
+    fn swap_remove_slot(&mut self, idx: usize) -> T {
+        let value = self.items.swap_remove(idx);
+        if let Some(handle) = self.slot_handles.swap_remove(idx) {
+            self.positions.remove(&handle);
+        }
+        if let Some(Some(moved)) = self.slot_handles.get(idx) {
+            self.positions.insert(*moved, idx);
+        }
+        value
+    }
+
+    // Every move of an element goes through here to keep positions in sync
+    fn swap(&mut self, a: usize, b: usize) {
+        self.items.swap(a, b);
+        self.slot_handles.swap(a, b);
+        for idx in [a, b] {
+            if let Some(handle) = self.slot_handles[idx] {
+                self.positions.insert(handle, idx);
+            }
+        }
+    }
+
+    // Perform up-heap bubbling starting at idx, returns the final index
+    fn sift_up(&mut self, idx: usize) -> usize {
+        let mut current_idx = idx;
+        while current_idx > 0 && (self.comparator)(&self.items[current_idx], &self.items[self.parent_idx(current_idx)]) {
             let pid = self.parent_idx(current_idx);
-            self.items.swap(current_idx, pid);
-            current_idx = self.parent_idx(current_idx);
+            self.swap(current_idx, pid);
+            current_idx = pid;
         }
+        current_idx
     }
 
-    fn parent_idx(&self, idx: usize) -> usize {
-        idx / 2
+    // Perform down-heap bubbling starting at idx
+    fn sift_down(&mut self, idx: usize) {
+        let mut current_idx = idx;
+        while self.children_present(current_idx) {
+            let smallest_child_idx = self.smallest_child_idx(current_idx);
+            if (self.comparator)(&self.items[smallest_child_idx], &self.items[current_idx]) {
+                self.swap(smallest_child_idx, current_idx);
+                current_idx = smallest_child_idx;
+            } else {
+                break;
+            }
+        }
     }
 
-    fn children_present(&self, idx: usize) -> bool {
-        self.left_child_idx(idx) <= self.count
+    /// Panic unless every parent belongs above each of its children and
+    /// the handle bookkeeping matches the stored elements
+    #[cfg(any(test, debug_assertions))]
+    fn assert_valid(&self) {
+        for child_idx in 1..self.len() {
+            let parent_idx = self.parent_idx(child_idx);
+            assert!(
+                !(self.comparator)(&self.items[child_idx], &self.items[parent_idx]),
+                "child {} belongs above its parent {}",
+                child_idx,
+                parent_idx
+            );
+        }
+        assert_eq!(self.items.len(), self.slot_handles.len());
+        let tracked = self.slot_handles.iter().filter(|h| h.is_some()).count();
+        assert_eq!(tracked, self.positions.len());
+        for (&handle, &idx) in &self.positions {
+            assert_eq!(self.slot_handles[idx], Some(handle));
+        }
+        if let Some(limit) = self.limit {
+            assert!(self.len() <= limit);
+        }
     }
 
-    fn left_child_idx(&self, idx: usize) -> usize {
-        idx * 2
+    fn parent_idx(&self, idx: usize) -> usize {
+        (idx - 1) / D
+    }
+
+    fn children_present(&self, idx: usize) -> bool {
+        self.first_child_idx(idx) < self.len()
     }
 
-    fn right_child_idx(&self, idx: usize) -> usize {
-        self.left_child_idx(idx) + 1
+    fn first_child_idx(&self, idx: usize) -> usize {
+        idx * D + 1
     }
 
     fn smallest_child_idx(&self, idx: usize) -> usize {
-        let left_idx = self.left_child_idx(idx);
-        let right_idx = self.right_child_idx(idx);
+        let first_idx = self.first_child_idx(idx);
+        let end_idx = (first_idx + D).min(self.len());
 
-        // Check if right child exists and is smaller than left child
-        if right_idx <= self.count && (self.comparator)(&self.items[right_idx], &self.items[left_idx]) {
-            right_idx
-        } else {
-            left_idx
+        // Pick the child that belongs highest among the existing ones
+        let mut smallest_idx = first_idx;
+        for child_idx in first_idx + 1..end_idx {
+            if (self.comparator)(&self.items[child_idx], &self.items[smallest_idx]) {
+                smallest_idx = child_idx;
+            }
         }
+        smallest_idx
     }
 }
 
 impl<T> Heap<T>
 where
-    T: Default + Ord,
+    T: Ord,
 {
     /// Create a new MinHeap
     pub fn new_min() -> Self {
@@ -99,38 +446,215 @@ where
     }
 }
 
-impl<T> Iterator for Heap<T>
-where
-    T: Default,
-{
+// Shows the elements in their internal array order
+impl<T: fmt::Debug, const D: usize> fmt::Debug for Heap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.items.iter()).finish()
+    }
+}
+
+impl<T: Clone, const D: usize> Clone for Heap<T, D> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            comparator: Rc::clone(&self.comparator),
+            slot_handles: self.slot_handles.clone(),
+            positions: self.positions.clone(),
+            next_handle: self.next_handle,
+            limit: self.limit,
+        }
+    }
+}
+
+// Heaps are equal when they hold the same elements, however they are laid out
+impl<T: Ord, const D: usize> PartialEq for Heap<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let mut ours: Vec<&T> = self.items.iter().collect();
+        let mut theirs: Vec<&T> = other.items.iter().collect();
+        ours.sort();
+        theirs.sort();
+        ours == theirs
+    }
+}
+
+impl<T: Ord, const D: usize> Eq for Heap<T, D> {}
+
+// Kept for backward compatibility, prefer pop or drain_sorted
+impl<T, const D: usize> Iterator for Heap<T, D> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
-        if self.is_empty() {
-            return None;
+        self.pop()
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for &'a Heap<T, D> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Draining iterator returned by `Heap::drain_sorted`
+pub struct DrainSorted<'a, T, const D: usize = 2> {
+    heap: &'a mut Heap<T, D>,
+}
+
+impl<T, const D: usize> Iterator for DrainSorted<'_, T, D> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, const D: usize> Drop for DrainSorted<'_, T, D> {
+    fn drop(&mut self) {
+        // Items that were not consumed are removed as well
+        self.heap.clear();
+    }
+}
+
+impl<T, const D: usize> Extend<T> for Heap<T, D> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
         }
+    }
+}
 
-        // Swap the top element with the last element
-        self.items.swap(1, self.count);
+/// Mutable access to the top element of a `Heap`, see `Heap::peek_mut`
+pub struct PeekMut<'a, T, const D: usize = 2> {
+    heap: &'a mut Heap<T, D>,
+}
 
-        // Decrement the count
-        self.count -= 1;
+impl<T, const D: usize> Deref for PeekMut<'_, T, D> {
+    type Target = T;
 
-        // Perform down-heap bubbling
-        let mut current_idx = 1;
-        while self.children_present(current_idx) {
-            let smallest_child_idx = self.smallest_child_idx(current_idx);
-            if (self.comparator)(&self.items[smallest_child_idx], &self.items[current_idx]) {
-                self.items.swap(smallest_child_idx, current_idx);
-                current_idx = smallest_child_idx;
-            } else {
-                break;
-            }
+    fn deref(&self) -> &T {
+        &self.heap.items[0]
+    }
+}
+
+impl<T, const D: usize> DerefMut for PeekMut<'_, T, D> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.items[0]
+    }
+}
+
+impl<T, const D: usize> Drop for PeekMut<'_, T, D> {
+    fn drop(&mut self) {
+        // The top element may have changed, move it back into place
+        self.heap.sift_down(0);
+    }
+}
+
+/// Collect an iterator straight into a heap, e.g. `(0..10).collect_min()`.
+/// `FromIterator` alone can't tell which ordering the heap should use.
+pub trait CollectHeap: Iterator + Sized
+where
+    Self::Item: Ord,
+{
+    fn collect_min(self) -> Heap<Self::Item> {
+        Heap::from_vec(self.collect(), |a, b| a < b)
+    }
+
+    fn collect_max(self) -> Heap<Self::Item> {
+        Heap::from_vec(self.collect(), |a, b| a > b)
+    }
+}
+
+impl<I> CollectHeap for I
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+}
+
+// Function pointers can't be serialized, so only the elements are written
+// out in array order and the comparator is supplied again on the way back.
+#[cfg(feature = "serde")]
+mod serde_support {
+    use super::Heap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl<T: Serialize, const D: usize> Serialize for Heap<T, D> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.items.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, const D: usize> Heap<T, D> {
+        /// Deserialize the elements and re-heapify them with comparator
+        pub fn deserialize_with<De: Deserializer<'de>>(
+            deserializer: De,
+            comparator: impl Fn(&T, &T) -> bool + 'static,
+        ) -> Result<Self, De::Error> {
+            let items = Vec::deserialize(deserializer)?;
+            Ok(Self::from_vec_with_arity(items, comparator))
+        }
+    }
+
+    impl<'de, T: Deserialize<'de> + Ord> Heap<T> {
+        pub fn deserialize_min<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+            Self::deserialize_with(deserializer, |a, b| a < b)
+        }
+
+        pub fn deserialize_max<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+            Self::deserialize_with(deserializer, |a, b| a > b)
         }
+    }
+}
+
+/// Sort slice in place in ascending order
+pub fn heap_sort<T: Ord>(slice: &mut [T]) {
+    heap_sort_by(slice, |a, b| a < b);
+}
 
-        // Return the popped item
-        Some(std::mem::replace(&mut self.items[self.count + 1], T::default()))
+/// Sort slice in place into the order a Heap with the same comparator
+/// would pop the elements, e.g. `|a, b| a > b` sorts descending
+pub fn heap_sort_by<T>(slice: &mut [T], comparator: impl Fn(&T, &T) -> bool) {
+    // Build a heap with the reversed comparator so the element that
+    // belongs last sits on top, then move the top behind the heap
+    let above = |a: &T, b: &T| comparator(b, a);
+    for idx in (0..slice.len() / 2).rev() {
+        sift_down_slice(slice, idx, &above);
+    }
+    for end in (1..slice.len()).rev() {
+        slice.swap(0, end);
+        sift_down_slice(&mut slice[..end], 0, &above);
+    }
+}
 
+// The down-heap bubbling of Heap::sift_down over a plain binary heap slice
+fn sift_down_slice<T>(items: &mut [T], idx: usize, above: &impl Fn(&T, &T) -> bool) {
+    let mut current_idx = idx;
+    loop {
+        let left_idx = current_idx * 2 + 1;
+        if left_idx >= items.len() {
+            break;
+        }
+        let right_idx = left_idx + 1;
+        let child_idx = if right_idx < items.len() && above(&items[right_idx], &items[left_idx]) {
+            right_idx
+        } else {
+            left_idx
+        };
+        if above(&items[child_idx], &items[current_idx]) {
+            items.swap(child_idx, current_idx);
+            current_idx = child_idx;
+        } else {
+            break;
+        }
     }
 }
 
@@ -140,7 +664,7 @@ impl MinHeap {
     #[allow(clippy::new_ret_no_self)]
     pub fn new<T>() -> Heap<T>
     where
-        T: Default + Ord,
+        T: Ord,
     {
         Heap::new(|a, b| a < b)
     }
@@ -152,15 +676,19 @@ impl MaxHeap {
     #[allow(clippy::new_ret_no_self)]
     pub fn new<T>() -> Heap<T>
     where
-        T: Default + Ord,
+        T: Ord,
     {
         Heap::new(|a, b| a > b)
     }
 }
 
+// Other exercises include this file as a module to use the heap, and are
+// built with `--cfg included` so that these tests aren't theirs, too
 #[cfg(test)]
+#[cfg(not(included))]
 mod tests {
     use super::*;
+    use std::collections::BinaryHeap;
     #[test]
     fn test_empty_heap() {
         let mut heap = MaxHeap::new::<i32>();
@@ -196,4 +724,786 @@ mod tests {
         heap.add(1);
         assert_eq!(heap.next(), Some(2));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_peek_empty_heap() {
+        let mut heap = MinHeap::new::<i32>();
+        assert_eq!(heap.peek(), None);
+        assert!(heap.peek_mut().is_none());
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut heap = MaxHeap::new();
+        heap.add(4);
+        heap.add(11);
+        heap.add(2);
+        assert_eq!(heap.peek(), Some(&11));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.next(), Some(11));
+        assert_eq!(heap.peek(), Some(&4));
+    }
+
+    #[test]
+    fn test_peek_mut_sinks_to_leaf() {
+        let mut heap = MinHeap::new();
+        for value in [5, 1, 8, 3, 9, 2, 7, 4, 6] {
+            heap.add(value);
+        }
+        if let Some(mut top) = heap.peek_mut() {
+            assert_eq!(*top, 1);
+            *top = 100;
+        }
+        assert_eq!(heap.peek(), Some(&2));
+        assert_eq!(heap.len(), 9);
+        let popped: Vec<i32> = heap.by_ref().collect();
+        assert_eq!(popped, vec![2, 3, 4, 5, 6, 7, 8, 9, 100]);
+    }
+
+    #[test]
+    fn test_peek_mut_unchanged() {
+        let mut heap = MaxHeap::new();
+        heap.add(3);
+        heap.add(7);
+        heap.add(5);
+        if let Some(mut top) = heap.peek_mut() {
+            *top += 1;
+        }
+        assert_eq!(heap.next(), Some(8));
+        assert_eq!(heap.next(), Some(5));
+        assert_eq!(heap.next(), Some(3));
+    }
+
+    // Deliberately has no Default impl
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct TaskId(u32);
+
+    #[test]
+    fn test_heap_without_default() {
+        let mut heap = MinHeap::new();
+        heap.add(TaskId(7));
+        heap.add(TaskId(3));
+        heap.add(TaskId(5));
+        assert_eq!(heap.peek(), Some(&TaskId(3)));
+        assert_eq!(heap.next(), Some(TaskId(3)));
+        assert_eq!(heap.next(), Some(TaskId(5)));
+        assert_eq!(heap.next(), Some(TaskId(7)));
+        assert_eq!(heap.next(), None);
+    }
+
+    // Small xorshift generator so the tests don't need external crates
+    struct Rng(u64);
+
+    impl Rng {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn shuffle<T>(&mut self, items: &mut [T]) {
+            for i in (1..items.len()).rev() {
+                let j = (self.next_u64() % (i as u64 + 1)) as usize;
+                items.swap(i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_vec_shuffled() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut items: Vec<u32> = (0..5000).collect();
+        rng.shuffle(&mut items);
+
+        let heap = Heap::from_vec(items.clone(), |a, b| a < b);
+        assert_eq!(heap.len(), 5000);
+        let popped: Vec<u32> = heap.collect();
+        assert_eq!(popped, (0..5000).collect::<Vec<u32>>());
+
+        let heap = Heap::from_vec(items, |a, b| a > b);
+        let popped: Vec<u32> = heap.collect();
+        assert_eq!(popped, (0..5000).rev().collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_from_vec_empty_and_single() {
+        let mut heap = Heap::from_vec(Vec::<i32>::new(), |a, b| a < b);
+        assert!(heap.is_empty());
+        assert_eq!(heap.next(), None);
+
+        let mut heap = Heap::from_vec(vec![42], |a, b| a < b);
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.next(), Some(42));
+        assert_eq!(heap.next(), None);
+    }
+
+    #[test]
+    fn test_collect_min_max() {
+        let heap = (0..1000).rev().collect_min();
+        assert_eq!(heap.peek(), Some(&0));
+        assert_eq!(heap.len(), 1000);
+
+        let mut heap = [3, 9, 1, 7].into_iter().collect_max();
+        assert_eq!(heap.next(), Some(9));
+        assert_eq!(heap.next(), Some(7));
+    }
+
+    #[test]
+    fn test_capturing_comparator() {
+        // Indices into the priority table, lowest priority first
+        let priorities: Vec<u64> = vec![50, 10, 40, 20, 30];
+        let table = priorities.clone();
+        let mut heap = Heap::new(move |a: &usize, b: &usize| table[*a] < table[*b]);
+        for idx in 0..priorities.len() {
+            heap.add(idx);
+        }
+        let order: Vec<usize> = heap.collect();
+        assert_eq!(order, vec![1, 3, 4, 2, 0]);
+
+        let offset = 3;
+        let heap = Heap::from_vec(vec![1, 2, 3, 4, 5], move |a: &i32, b: &i32| {
+            (a - offset).abs() < (b - offset).abs()
+        });
+        assert_eq!(heap.peek(), Some(&3));
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut heap = MinHeap::new();
+        heap.add(5);
+        heap.extend(vec![3, 8, 1]);
+        assert_eq!(heap.len(), 4);
+        let popped: Vec<i32> = heap.collect();
+        assert_eq!(popped, vec![1, 3, 5, 8]);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut heap = MinHeap::new();
+        heap.extend(vec![9, 4, 7]);
+        let mut other = MinHeap::new();
+        other.extend(vec![1, 8, 2]);
+        heap.append(&mut other);
+        assert!(other.is_empty());
+        let popped: Vec<i32> = heap.collect();
+        assert_eq!(popped, vec![1, 2, 4, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_append_empty() {
+        let mut heap = MaxHeap::new();
+        heap.extend(vec![2, 6]);
+        let mut empty = MaxHeap::new();
+        heap.append(&mut empty);
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.peek(), Some(&6));
+
+        let mut empty = MaxHeap::new();
+        empty.append(&mut heap);
+        assert!(heap.is_empty());
+        let popped: Vec<i32> = empty.collect();
+        assert_eq!(popped, vec![6, 2]);
+    }
+
+    #[test]
+    fn test_append_keeps_own_comparator() {
+        let mut heap = MaxHeap::new();
+        heap.extend(vec![3, 1]);
+        let mut other = MinHeap::new();
+        other.extend(vec![4, 2]);
+        heap.append(&mut other);
+        let popped: Vec<i32> = heap.collect();
+        assert_eq!(popped, vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_into_sorted_vec() {
+        let heap = [4, 1, 3, 2].into_iter().collect_max();
+        assert_eq!(heap.into_sorted_vec(), vec![4, 3, 2, 1]);
+        assert_eq!(MinHeap::new::<i32>().into_sorted_vec(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_drain_sorted() {
+        let mut heap = [5, 2, 9, 1].into_iter().collect_min();
+        let drained: Vec<i32> = heap.drain_sorted().collect();
+        assert_eq!(drained, vec![1, 2, 5, 9]);
+        assert!(heap.is_empty());
+        heap.add(3);
+        assert_eq!(heap.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_drain_sorted_dropped_early() {
+        let mut heap = (0..10).collect_min();
+        {
+            let mut drain = heap.drain_sorted();
+            assert_eq!(drain.size_hint(), (10, Some(10)));
+            assert_eq!(drain.next(), Some(0));
+            assert_eq!(drain.next(), Some(1));
+        }
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_update_sift_down() {
+        let mut heap = MinHeap::new();
+        let a = heap.push_with_handle(1);
+        heap.push_with_handle(5);
+        heap.extend(vec![3, 7]);
+        assert_eq!(heap.update(a, 6), Some(1));
+        assert_eq!(heap.get(a), Some(&6));
+        assert_eq!(heap.into_sorted_vec(), vec![3, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_update_sift_up() {
+        let mut heap = MinHeap::new();
+        heap.extend(vec![2, 4, 6]);
+        let d = heap.push_with_handle(8);
+        let e = heap.push_with_handle(10);
+        assert_eq!(heap.update(e, 1), Some(10));
+        assert_eq!(heap.peek(), Some(&1));
+        // Moving e must not confuse where d lives
+        assert_eq!(heap.update(d, 3), Some(8));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn test_update_stale_handle() {
+        let mut heap = MinHeap::new();
+        let a = heap.push_with_handle(1);
+        let b = heap.push_with_handle(2);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.get(a), None);
+        assert_eq!(heap.update(a, 0), None);
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.update(b, 9), Some(2));
+        assert_eq!(heap.pop(), Some(9));
+        assert_eq!(heap.update(b, 0), None);
+    }
+
+    #[test]
+    fn test_handles_survive_many_moves() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let mut heap = MinHeap::new();
+        let handles: Vec<(Handle, u64)> = (0..200)
+            .map(|_| {
+                let value = rng.next_u64() % 1000;
+                (heap.push_with_handle(value), value)
+            })
+            .collect();
+        for &(handle, value) in &handles {
+            assert_eq!(heap.get(handle), Some(&value));
+        }
+        for &(handle, value) in &handles {
+            let new_value = rng.next_u64() % 1000;
+            assert_eq!(heap.update(handle, new_value), Some(value));
+        }
+        let sorted = heap.into_sorted_vec();
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_remove_root() {
+        let mut heap = [4, 1, 7, 3].into_iter().collect_min();
+        assert_eq!(heap.remove_where(|&x| x == 1), Some(1));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![3, 4, 7]);
+    }
+
+    #[test]
+    fn test_remove_leaf() {
+        let mut heap = (1..=7).collect_min();
+        assert_eq!(heap.remove_where(|&x| x == 6), Some(6));
+        assert_eq!(heap.remove_where(|&x| x == 42), None);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn test_remove_moves_last_up() {
+        // The last element 4 lands under 50 and has to bubble up
+        let mut heap = Heap::from_vec(vec![1, 50, 2, 60, 70, 3, 4], |a, b| a < b);
+        assert_eq!(heap.remove_where(|&x| x == 70), Some(70));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4, 50, 60]);
+    }
+
+    #[test]
+    fn test_remove_last_element() {
+        let mut heap = MaxHeap::new();
+        let handle = heap.push_with_handle(5);
+        assert_eq!(heap.remove_where(|_| true), Some(5));
+        assert!(heap.is_empty());
+        assert_eq!(heap.get(handle), None);
+        assert_eq!(heap.remove_where(|_| true), None);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut heap = (0..20).collect_max();
+        let handle = heap.push_with_handle(100);
+        heap.retain(|x| x % 2 == 0);
+        assert_eq!(heap.get(handle), Some(&100));
+        assert_eq!(heap.update(handle, -1), Some(100));
+        let mut expected: Vec<i32> = (0..20).filter(|x| x % 2 == 0).rev().collect();
+        expected.push(-1);
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_retain_removes_everything() {
+        let mut heap = (0..10).collect_min();
+        heap.retain(|_| false);
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+        heap.add(3);
+        assert_eq!(heap.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_with_capacity_and_reserve() {
+        let mut heap = Heap::with_capacity(16, |a: &i32, b: &i32| a < b);
+        let capacity = heap.capacity();
+        assert!(capacity >= 16);
+        for value in 0..16 {
+            heap.add(value);
+        }
+        assert_eq!(heap.capacity(), capacity);
+
+        heap.reserve(100);
+        let capacity = heap.capacity();
+        assert!(capacity >= 116);
+        for value in 16..116 {
+            heap.add(value);
+        }
+        assert_eq!(heap.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_clear_keeps_capacity() {
+        let mut heap = (0..100).collect_min();
+        let handle = heap.push_with_handle(-1);
+        let capacity = heap.capacity();
+        heap.clear();
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.get(handle), None);
+        assert_eq!(heap.capacity(), capacity);
+
+        heap.extend(vec![3, 1, 2]);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut heap = Heap::with_capacity(1000, |a: &i32, b: &i32| a > b);
+        heap.extend(vec![1, 2, 3]);
+        heap.shrink_to_fit();
+        assert!(heap.capacity() < 1000);
+        assert!(heap.capacity() >= 3);
+        assert_eq!(heap.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_debug() {
+        let heap = [3, 1, 2].into_iter().collect_min();
+        let printed = format!("{:?}", heap);
+        assert!(printed.starts_with('[') && printed.ends_with(']'));
+        for value in ["1", "2", "3"] {
+            assert!(printed.contains(value));
+        }
+        assert_eq!(format!("{:?}", MinHeap::new::<i32>()), "[]");
+    }
+
+    #[test]
+    fn test_eq_ignores_layout() {
+        let mut a = MinHeap::new();
+        a.extend(vec![1, 2, 3, 4, 5, 5]);
+        let mut b = MinHeap::new();
+        b.extend(vec![5, 4, 5, 3, 2, 1]);
+        assert_eq!(a, b);
+        b.pop();
+        assert_ne!(a, b);
+        b.add(6);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let mut original = MaxHeap::new();
+        let handle = original.push_with_handle(10);
+        original.extend(vec![4, 7]);
+        let mut copy = original.clone();
+        assert_eq!(original, copy);
+
+        copy.update(handle, 1);
+        copy.add(20);
+        assert_eq!(original.get(handle), Some(&10));
+        assert_eq!(original.into_sorted_vec(), vec![10, 7, 4]);
+        assert_eq!(copy.into_sorted_vec(), vec![20, 7, 4, 1]);
+    }
+
+    #[test]
+    fn test_limit_keeps_largest() {
+        let mut rng = Rng(0xdead_beef_cafe_f00d);
+        let mut heap = Heap::with_limit(10, |a: &u64, b: &u64| a < b);
+        assert_eq!(heap.limit(), Some(10));
+        let mut all = Vec::new();
+        for _ in 0..100_000 {
+            let value = rng.next_u64();
+            all.push(value);
+            heap.add(value);
+            assert!(heap.len() <= 10);
+        }
+        all.sort_unstable();
+        let expected = all[all.len() - 10..].to_vec();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_limit_rejects_and_replaces() {
+        let mut heap = Heap::with_limit(3, |a: &i32, b: &i32| a > b);
+        heap.extend(vec![5, 9, 7]);
+        // A max-heap with a limit keeps the smallest values
+        let rejected = heap.push_with_handle(10);
+        assert_eq!(heap.get(rejected), None);
+        let kept = heap.push_with_handle(1);
+        assert_eq!(heap.get(kept), Some(&1));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![7, 5, 1]);
+    }
+
+    #[test]
+    fn test_limit_zero_and_append() {
+        let mut heap = Heap::with_limit(0, |a: &i32, b: &i32| a < b);
+        heap.add(1);
+        assert!(heap.is_empty());
+
+        let mut heap = Heap::with_limit(2, |a: &i32, b: &i32| a < b);
+        let mut other = (0..5).collect_min();
+        heap.append(&mut other);
+        assert_eq!(heap.into_sorted_vec(), vec![3, 4]);
+        assert_eq!(MinHeap::new::<i32>().limit(), None);
+    }
+
+    fn pop_order<const D: usize>(items: &[u64]) -> Vec<u64> {
+        let mut heap = Heap::<u64, D>::with_arity(|a, b| a < b);
+        heap.extend(items.iter().copied());
+        let mut popped = Vec::new();
+        while let Some(value) = heap.pop() {
+            popped.push(value);
+        }
+        popped
+    }
+
+    #[test]
+    fn test_arity_same_pop_order() {
+        let mut rng = Rng(0x1234_5678_9abc_def1);
+        let items: Vec<u64> = (0..2000).map(|_| rng.next_u64() % 500).collect();
+        let binary = pop_order::<2>(&items);
+        assert_eq!(binary, pop_order::<3>(&items));
+        assert_eq!(binary, pop_order::<4>(&items));
+        let mut sorted = items.clone();
+        sorted.sort_unstable();
+        assert_eq!(binary, sorted);
+    }
+
+    #[test]
+    fn test_arity_other_operations() {
+        let mut heap = Heap::<i32, 4>::from_vec_with_arity((0..50).rev().collect(), |a, b| a < b);
+        let handle = heap.push_with_handle(100);
+        heap.update(handle, -5);
+        assert_eq!(heap.remove_where(|&x| x == 25), Some(25));
+        if let Some(mut top) = heap.peek_mut() {
+            *top = 1000;
+        }
+        let drained: Vec<i32> = heap.drain_sorted().collect();
+        let mut expected: Vec<i32> = (0..50).filter(|&x| x != 25).collect();
+        expected.push(1000);
+        assert_eq!(drained, expected);
+    }
+
+    #[test]
+    fn test_pop_releases_slots() {
+        let mut heap = MinHeap::new();
+        for i in 0..1000 {
+            heap.add(format!("item {:04}", i));
+        }
+        assert_eq!(heap.storage_len(), 1000);
+        for remaining in (500..1000).rev() {
+            heap.pop();
+            assert_eq!(heap.storage_len(), remaining);
+        }
+        heap.drain_sorted().take(10).for_each(drop);
+        assert_eq!(heap.storage_len(), 0);
+    }
+
+    #[test]
+    fn test_popped_values_are_not_kept() {
+        let shared = Rc::new(String::from("shared"));
+        let mut heap = Heap::new(|a: &Rc<String>, b: &Rc<String>| a < b);
+        for _ in 0..10 {
+            heap.add(Rc::clone(&shared));
+        }
+        assert_eq!(Rc::strong_count(&shared), 11);
+        for _ in 0..10 {
+            heap.pop();
+        }
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn test_interleaved_add_pop_strings() {
+        let mut heap = MaxHeap::new();
+        heap.add(String::from("b"));
+        heap.add(String::from("d"));
+        assert_eq!(heap.pop().as_deref(), Some("d"));
+        heap.add(String::from("a"));
+        heap.add(String::from("c"));
+        assert_eq!(heap.storage_len(), 3);
+        assert_eq!(heap.pop().as_deref(), Some("c"));
+        assert_eq!(heap.pop().as_deref(), Some("b"));
+        heap.add(String::from("e"));
+        assert_eq!(heap.pop().as_deref(), Some("e"));
+        assert_eq!(heap.pop().as_deref(), Some("a"));
+        assert_eq!(heap.storage_len(), 0);
+    }
+
+    #[test]
+    fn test_iter_does_not_consume() {
+        let heap = [4, 8, 1, 6, 1].into_iter().collect_max();
+        let mut seen: Vec<i32> = heap.iter().copied().collect();
+        assert_eq!(heap.len(), 5);
+        seen.sort_unstable();
+        assert_eq!(seen, vec![1, 1, 4, 6, 8]);
+
+        let mut total = 0;
+        for value in &heap {
+            total += value;
+        }
+        assert_eq!(total, 20);
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some(&8));
+    }
+
+    #[test]
+    fn test_into_iter_unordered() {
+        let mut heap = MinHeap::new();
+        heap.extend(vec![String::from("b"), String::from("a"), String::from("c")]);
+        let mut owned: Vec<String> = heap.into_iter_unordered().collect();
+        owned.sort();
+        assert_eq!(owned, vec!["a", "b", "c"]);
+        assert_eq!(MinHeap::new::<i32>().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap = MinHeap::new();
+        assert_eq!(heap.push_pop(5), 5);
+        assert!(heap.is_empty());
+
+        heap.extend(vec![3, 6, 9]);
+        // 1 would be popped right away
+        assert_eq!(heap.push_pop(1), 1);
+        assert_eq!(heap.push_pop(7), 3);
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![6, 7, 9]);
+    }
+
+    #[test]
+    fn test_push_pop_equal_to_top() {
+        let mut heap = [4, 4, 8].into_iter().collect_min();
+        assert_eq!(heap.push_pop(4), 4);
+        assert_eq!(heap.into_sorted_vec(), vec![4, 4, 8]);
+    }
+
+    #[test]
+    fn test_replace_top() {
+        let mut heap = MaxHeap::new();
+        assert_eq!(heap.replace_top(2), None);
+        assert_eq!(heap.len(), 1);
+        heap.extend(vec![10, 6]);
+        let handle = heap.push_with_handle(8);
+        assert_eq!(heap.replace_top(1), Some(10));
+        assert_eq!(heap.get(handle), Some(&8));
+        assert_eq!(heap.into_sorted_vec(), vec![8, 6, 2, 1]);
+    }
+
+    #[test]
+    fn test_replace_top_drops_handle() {
+        let mut heap = MinHeap::new();
+        let top = heap.push_with_handle(1);
+        heap.extend(vec![5, 3]);
+        assert_eq!(heap.replace_top(4), Some(1));
+        assert_eq!(heap.get(top), None);
+        assert_eq!(heap.update(top, 0), None);
+        assert_eq!(heap.into_sorted_vec(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_heap_sort_edge_cases() {
+        let mut empty: Vec<i32> = vec![];
+        heap_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![7];
+        heap_sort(&mut single);
+        assert_eq!(single, vec![7]);
+
+        let mut equal = vec![3; 50];
+        heap_sort(&mut equal);
+        assert_eq!(equal, vec![3; 50]);
+
+        let mut sorted: Vec<i32> = (0..100).collect();
+        heap_sort(&mut sorted);
+        assert_eq!(sorted, (0..100).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn test_heap_sort_random() {
+        let mut rng = Rng(0x0bad_cafe_1234_5678);
+        for size in [2, 3, 10, 17, 100, 1000, 4097] {
+            let mut items: Vec<u64> = (0..size).map(|_| rng.next_u64() % 100).collect();
+            let mut expected = items.clone();
+            expected.sort();
+            heap_sort(&mut items);
+            assert_eq!(items, expected);
+        }
+    }
+
+    #[test]
+    fn test_heap_sort_by() {
+        let mut words = vec!["pear", "fig", "banana", "apple"];
+        heap_sort_by(&mut words, |a, b| a.len() > b.len());
+        assert_eq!(words, vec!["banana", "apple", "pear", "fig"]);
+
+        let mut numbers = vec![5, 1, 4, 2, 3];
+        heap_sort_by(&mut numbers, |a, b| a > b);
+        assert_eq!(numbers, vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_random_against_binary_heap() {
+        let mut rng = Rng(0x5eed_0f_4ea9);
+        let mut heap = MaxHeap::new();
+        let mut reference = BinaryHeap::new();
+        for _ in 0..5000 {
+            let value = rng.next_u64() % 1000;
+            match rng.next_u64() % 5 {
+                0 | 1 => {
+                    heap.add(value);
+                    reference.push(value);
+                }
+                2 => assert_eq!(heap.pop(), reference.pop()),
+                3 => {
+                    reference.push(value);
+                    assert_eq!(heap.push_pop(value), reference.pop().unwrap());
+                }
+                _ => {
+                    let expected = reference.pop();
+                    reference.push(value);
+                    assert_eq!(heap.replace_top(value), expected);
+                }
+            }
+            heap.assert_valid();
+            assert_eq!(heap.len(), reference.len());
+            assert_eq!(heap.peek(), reference.peek());
+        }
+        assert_eq!(heap.into_sorted_vec(), reference.into_sorted_vec().into_iter().rev().collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_random_handles_and_removal() {
+        let mut rng = Rng(0xfeed_face_dead_beef);
+        for round in 0..2 {
+            let mut heap = MinHeap::new();
+            let mut handles = Vec::new();
+            for step in 0..3000 {
+                let value = rng.next_u64() % 500;
+                match rng.next_u64() % 6 {
+                    0 => handles.push(heap.push_with_handle(value)),
+                    1 => heap.add(value),
+                    2 => {
+                        heap.pop();
+                    }
+                    3 if !handles.is_empty() => {
+                        let handle = handles[(rng.next_u64() as usize) % handles.len()];
+                        heap.update(handle, value);
+                    }
+                    4 => {
+                        heap.remove_where(|&x| x == value);
+                    }
+                    _ => heap.add(value),
+                }
+                if step % 500 == 499 {
+                    heap.retain(|&x| x % 3 != round);
+                }
+                heap.assert_valid();
+            }
+            let sorted = heap.into_sorted_vec();
+            assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn test_random_dary() {
+        let mut rng = Rng(0xabad_1dea_0000_0001);
+        let mut heap = Heap::<u64, 3>::with_arity(|a, b| a > b);
+        let mut reference = BinaryHeap::new();
+        for _ in 0..3000 {
+            let value = rng.next_u64() % 100;
+            if rng.next_u64() % 3 == 0 {
+                assert_eq!(heap.pop(), reference.pop());
+            } else {
+                heap.add(value);
+                reference.push(value);
+            }
+            heap.assert_valid();
+        }
+    }
+}
+
+// Needs serde and serde_json, e.g. in a cargo project with the feature on
+#[cfg(all(test, feature = "serde", not(included)))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn test_min_heap_round_trip() {
+        let heap = [5, 3, 8, 1].into_iter().collect_min();
+        let json = serde_json::to_string(&heap).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let restored = Heap::deserialize_min(&mut de).unwrap();
+        assert_eq!(heap, restored);
+        assert_eq!(restored.into_sorted_vec(), vec![1, 3, 5, 8]);
+    }
+
+    #[test]
+    fn test_max_heap_round_trip() {
+        let heap = [5, 3, 8, 1].into_iter().collect_max();
+        let json = serde_json::to_string(&heap).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let restored = Heap::<i32>::deserialize_max(&mut de).unwrap();
+        assert_eq!(restored.into_sorted_vec(), vec![8, 5, 3, 1]);
+    }
+
+    #[test]
+    fn test_empty_heap_round_trip() {
+        let heap = MinHeap::new::<String>();
+        let json = serde_json::to_string(&heap).unwrap();
+        assert_eq!(json, "[]");
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let restored = Heap::<String>::deserialize_min(&mut de).unwrap();
+        assert!(restored.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_reheapifies() {
+        // The stored order is not a valid min-heap, it must be rebuilt
+        let mut de = serde_json::Deserializer::from_str("[9, 7, 5, 3, 1]");
+        let heap = Heap::<u8>::deserialize_with(&mut de, |a: &u8, b: &u8| a < b).unwrap();
+        assert_eq!(heap.into_sorted_vec(), vec![1, 3, 5, 7, 9]);
+    }
+}
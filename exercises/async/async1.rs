@@ -0,0 +1,109 @@
+// async1.rs
+//
+// Calling an `async fn` doesn't run its body. It returns a future, a value
+// that does the work when something polls it. Inside an async fn, `.await`
+// polls another future until it's done. Something still has to poll the
+// outermost future, and that is the job of an executor. Real programs get
+// their executor from a crate like tokio. This exercise comes with the
+// smallest one possible, `block_on`, so it doesn't need any crates.
+//
+// `word_count` is an ordinary function that blocks until it's done, so
+// `count_all` can't await it. Make it asynchronous.
+//
+// Execute `rustlings hint async1` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+thread_local! {
+    // How many texts word_count has counted so far
+    static COUNTED: Cell<usize> = const { Cell::new(0) };
+}
+
+// TODO: Turn this into an `async fn`.
+fn word_count(text: &str) -> usize {
+    COUNTED.with(|counted| counted.set(counted.get() + 1));
+    text.split_whitespace().count()
+}
+
+async fn count_all(texts: &[&str]) -> usize {
+    let mut total = 0;
+    for text in texts {
+        total += word_count(text).await;
+    }
+    total
+}
+
+// The smallest executor there is: it polls the future and, while the future
+// isn't ready, waits for its waker to say that another poll is worthwhile.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let signal = Arc::new(Signal::default());
+    let waker = Waker::from(Arc::clone(&signal));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => signal.wait(),
+        }
+    }
+}
+
+// What the waker of block_on sets, and block_on waits for
+#[derive(Default)]
+struct Signal {
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Signal {
+    fn wait(&self) {
+        let mut woken = self.woken.lock().unwrap();
+        while !*woken {
+            woken = self.condvar.wait(woken).unwrap();
+        }
+        *woken = false;
+    }
+}
+
+impl Wake for Signal {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        *self.woken.lock().unwrap() = true;
+        self.condvar.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_one_text() {
+        assert_eq!(block_on(word_count("the quick brown fox")), 4);
+        assert_eq!(block_on(word_count("")), 0);
+    }
+
+    #[test]
+    fn counts_all_texts() {
+        let texts = ["one", "two words", "  and three more  "];
+        assert_eq!(block_on(count_all(&texts)), 6);
+    }
+
+    #[test]
+    fn futures_do_nothing_until_awaited() {
+        COUNTED.with(|counted| counted.set(0));
+        let future = count_all(&["a b", "c"]);
+        assert_eq!(COUNTED.with(Cell::get), 0);
+        assert_eq!(block_on(future), 3);
+        assert_eq!(COUNTED.with(Cell::get), 2);
+    }
+}
@@ -0,0 +1,137 @@
+// async2.rs
+//
+// Awaiting futures one after the other runs them one after the other, too:
+// the second doesn't start before the first is done. To run them
+// concurrently, something has to poll all of them, and keep polling the ones
+// that aren't done yet, until every one of them is. Crates provide this as
+// `join_all`. Here it's yours to write.
+//
+// The results have to come back in the order of the futures, no matter which
+// of them finished first.
+//
+// Execute `rustlings hint async2` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+use std::cell::RefCell;
+use std::future::{poll_fn, Future};
+use std::pin::{pin, Pin};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+// TODO: Poll every future until all of them are ready, and return their
+// outputs in the order of `futures`. A future that is ready must not be
+// polled again.
+async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    todo!()
+}
+
+// A future that isn't ready the first time it's polled, to give the others a
+// turn. Before returning Pending, it wakes its task, so that it's polled again.
+fn yield_now() -> impl Future<Output = ()> {
+    let mut yielded = false;
+    poll_fn(move |cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+}
+
+// Takes `turns` polls to finish, and writes down its name once it has
+async fn task(name: &'static str, turns: usize, finished: &RefCell<Vec<&'static str>>) -> String {
+    for _ in 0..turns {
+        yield_now().await;
+    }
+    finished.borrow_mut().push(name);
+    name.to_uppercase()
+}
+
+// The smallest executor there is: it polls the future and, while the future
+// isn't ready, waits for its waker to say that another poll is worthwhile.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let signal = Arc::new(Signal::default());
+    let waker = Waker::from(Arc::clone(&signal));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => signal.wait(),
+        }
+    }
+}
+
+// What the waker of block_on sets, and block_on waits for
+#[derive(Default)]
+struct Signal {
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Signal {
+    fn wait(&self) {
+        let mut woken = self.woken.lock().unwrap();
+        while !*woken {
+            woken = self.condvar.wait(woken).unwrap();
+        }
+        *woken = false;
+    }
+}
+
+impl Wake for Signal {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        *self.woken.lock().unwrap() = true;
+        self.condvar.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::ready;
+
+    #[test]
+    fn joins_nothing() {
+        let futures: Vec<Pin<Box<dyn Future<Output = u8>>>> = Vec::new();
+        assert_eq!(block_on(join_all(futures)), []);
+    }
+
+    #[test]
+    fn joins_ready_futures() {
+        let futures = vec![ready(1), ready(2), ready(3)];
+        assert_eq!(block_on(join_all(futures)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn runs_the_futures_concurrently() {
+        let finished = RefCell::new(Vec::new());
+        let futures = vec![
+            task("slow", 5, &finished),
+            task("fast", 1, &finished),
+            task("medium", 3, &finished),
+        ];
+        let results = block_on(join_all(futures));
+        // They finished by how many turns they took...
+        assert_eq!(*finished.borrow(), ["fast", "medium", "slow"]);
+        // ...but the results are in the order of the futures.
+        assert_eq!(results, ["SLOW", "FAST", "MEDIUM"]);
+    }
+
+    #[test]
+    fn works_for_futures_of_different_types() {
+        let finished = RefCell::new(Vec::new());
+        let futures: Vec<Pin<Box<dyn Future<Output = String> + '_>>> = vec![
+            Box::pin(task("task", 2, &finished)),
+            Box::pin(async { String::from("block") }),
+        ];
+        assert_eq!(block_on(join_all(futures)), ["TASK", "block"]);
+    }
+}
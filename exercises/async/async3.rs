@@ -0,0 +1,130 @@
+// async3.rs
+//
+// Under every `.await` there's a type that implements `Future` by hand. Its
+// `poll` either returns `Poll::Ready` with the output, or `Poll::Pending`.
+// Pending comes with a promise: the future has arranged for the waker in the
+// `Context` to be called once polling again makes sense. An executor never
+// polls a future again until that happens. A future that breaks the promise
+// is never polled again, and whatever is waiting for it hangs.
+//
+// `Countdown` is ready after it has been polled `polls` times. Implement
+// `Future` for it, keeping the promise.
+//
+// Execute `rustlings hint async3` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct Countdown {
+    // How many more polls until it's ready
+    polls: usize,
+}
+
+impl Countdown {
+    fn new(polls: usize) -> Self {
+        Countdown { polls }
+    }
+}
+
+// TODO: Return `Poll::Ready(())` once the last poll has been counted down,
+// and `Poll::Pending` before that, after waking the waker of `cx`.
+impl Future for Countdown {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        todo!()
+    }
+}
+
+// Counts the times its waker is woken
+#[derive(Default)]
+struct WakeCounter {
+    wakes: AtomicUsize,
+}
+
+impl WakeCounter {
+    // How often it was woken since the last time this was called
+    fn take(&self) -> usize {
+        self.wakes.swap(0, Ordering::SeqCst)
+    }
+}
+
+impl Wake for WakeCounter {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// Runs the future like block_on from async1 does, but all the futures here
+// wake their waker before they return Pending. A future that doesn't would
+// make block_on wait forever, so this panics instead. It returns the output
+// with the number of polls it took.
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let mut future = pin!(future);
+    let counter = Arc::new(WakeCounter::default());
+    let waker = Waker::from(Arc::clone(&counter));
+    let mut cx = Context::from_waker(&waker);
+    let mut polls = 0;
+    loop {
+        polls += 1;
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return (output, polls),
+            Poll::Pending => assert!(
+                counter.take() > 0,
+                "poll {} returned Pending without waking the waker, so nothing would poll the future again",
+                polls
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ready_after_n_polls() {
+        assert_eq!(block_on(Countdown::new(1)), ((), 1));
+        assert_eq!(block_on(Countdown::new(4)), ((), 4));
+    }
+
+    #[test]
+    fn zero_polls_is_ready_at_once() {
+        assert_eq!(block_on(Countdown::new(0)), ((), 1));
+    }
+
+    #[test]
+    fn wakes_before_every_pending() {
+        let counter = Arc::new(WakeCounter::default());
+        let waker = Waker::from(Arc::clone(&counter));
+        let mut cx = Context::from_waker(&waker);
+        let mut countdown = Countdown::new(3);
+        for _ in 0..2 {
+            assert_eq!(Pin::new(&mut countdown).poll(&mut cx), Poll::Pending);
+            assert_eq!(counter.take(), 1);
+        }
+        assert_eq!(Pin::new(&mut countdown).poll(&mut cx), Poll::Ready(()));
+        assert_eq!(counter.take(), 0);
+    }
+
+    #[test]
+    fn can_be_awaited() {
+        let twice = async {
+            Countdown::new(2).await;
+            Countdown::new(3).await;
+            "done"
+        };
+        // The poll that finishes the first countdown goes on to poll the
+        // second one for the first time.
+        assert_eq!(block_on(twice), ("done", 4));
+    }
+}
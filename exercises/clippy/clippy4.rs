@@ -0,0 +1,20 @@
+// clippy4.rs
+//
+// Not every lint clippy knows is turned on by default. The `pedantic` ones
+// are stricter than most projects want, but some of them are worth the
+// trouble. This exercise is checked with `clippy::explicit_iter_loop` denied,
+// and every other lint allowed. Loop over the vector the way it suggests!
+//
+// Execute `rustlings hint clippy4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+fn main() {
+    let scores = vec![7, 9, 4];
+    let mut total = 0;
+    for score in scores.iter() {
+        total += score;
+    }
+    println!("The scores add up to {total}");
+}
@@ -0,0 +1,98 @@
+// cow_str.rs
+//
+// A function that sometimes has to change a string, and often doesn't, can
+// return a `Cow<str>` (see cow1). When nothing needs to change, it hands back
+// the input as `Cow::Borrowed`, and only allocates a new `String` for a
+// `Cow::Owned` when something does. Either way, the caller gets something
+// that derefs to a `&str`.
+//
+// A normalized string here is all lowercase, and has its words separated by
+// exactly one space, with no whitespace before the first word or after the
+// last one.
+//
+// Execute `rustlings hint cow_str` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::borrow::Cow;
+
+// TODO: Return the input as it is if it's normalized already, and a normalized
+// copy of it if it isn't.
+fn normalize(input: &str) -> Cow<'_, str> {
+    todo!()
+}
+
+// TODO: Normalize every entry, and count how many of them had to be copied
+// to do so.
+fn normalize_all(entries: &[String]) -> (Vec<Cow<'_, str>>, usize) {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_borrowed(cow: &Cow<'_, str>) -> bool {
+        matches!(cow, Cow::Borrowed(_))
+    }
+
+    #[test]
+    fn normalized_input_is_borrowed() {
+        let input = "already normal";
+        let output = normalize(input);
+        assert!(is_borrowed(&output));
+        assert_eq!(output, "already normal");
+        // Not a copy, the very same bytes
+        assert_eq!(output.as_ptr(), input.as_ptr());
+    }
+
+    #[test]
+    fn empty_and_single_words_are_borrowed() {
+        assert!(is_borrowed(&normalize("")));
+        assert!(is_borrowed(&normalize("word")));
+        assert!(is_borrowed(&normalize("naïve café")));
+        assert!(is_borrowed(&normalize("route 66")));
+    }
+
+    #[test]
+    fn uppercase_is_lowered() {
+        let output = normalize("Hello World");
+        assert!(matches!(output, Cow::Owned(_)));
+        assert_eq!(output, "hello world");
+        assert_eq!(normalize("ÉTÉ"), "été");
+    }
+
+    #[test]
+    fn whitespace_is_collapsed() {
+        for input in ["two  spaces", " leading", "trailing ", "a\ttab", "new\nline", "  all \t of\n them  "] {
+            let output = normalize(input);
+            assert!(matches!(output, Cow::Owned(_)), "{:?} isn't normalized", input);
+            assert!(!output.contains("  ") && !output.starts_with(' ') && !output.ends_with(' '));
+        }
+        assert_eq!(normalize("  all \t of\n them  "), "all of them");
+        assert_eq!(normalize(" \t\n "), "");
+    }
+
+    #[test]
+    fn counts_the_copies() {
+        let entries: Vec<String> = ["fine", "Not Fine", "", "two  spaces", "still fine"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (normalized, copied) = normalize_all(&entries);
+        assert_eq!(normalized, ["fine", "not fine", "", "two spaces", "still fine"]);
+        assert_eq!(copied, 2);
+        let borrowed: Vec<bool> = normalized.iter().map(is_borrowed).collect();
+        assert_eq!(borrowed, [true, false, true, false, true]);
+    }
+
+    #[test]
+    fn nothing_to_copy() {
+        let entries = vec![String::from("a b c"), String::new()];
+        let (normalized, copied) = normalize_all(&entries);
+        assert_eq!(copied, 0);
+        assert!(normalized.iter().all(is_borrowed));
+        assert_eq!(normalize_all(&[]), (Vec::new(), 0));
+    }
+}
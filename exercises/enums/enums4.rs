@@ -0,0 +1,176 @@
+// enums4.rs
+//
+// Patterns can do more than name a variant:
+//
+// - `n @ 1..=9` matches a range, and binds the value to `n`
+// - `x if x < 0` is a guard, the arm only matches if it's true
+// - `A | B` matches either pattern
+// - `Shape::Rect { size: Size { w, h } }` looks inside the struct inside the
+//   variant, and `..` leaves out the fields an arm doesn't need
+//
+// The first arm that matches wins, so when two arms overlap, the one that's
+// more specific has to come first. And `let ... else` is a `let` with a
+// pattern that might not match, and says what to do when it doesn't.
+//
+// Execute `rustlings hint enums4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Size {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Circle { radius: u32 },
+    Rect { size: Size },
+    Triangle { base: u32, height: u32 },
+    Line(u32),
+}
+
+// TODO: Write the arms:
+// - 0 is "zero"
+// - 1 to 9 are "digit 7"
+// - 42 is "the answer"
+// - 10 to 99 are "15 has two digits"
+// - negative even numbers are "-4 is negative and even"
+// - other negative numbers are "-3 is negative"
+// - 100, 1000 and 10000 are "1000 is round"
+// - anything else is "12345 is big"
+fn describe_number(n: i32) -> String {
+    match n {
+        _ => todo!(),
+    }
+}
+
+// TODO: Write the arms:
+// - rectangles with a width or a height of 0 are "empty rectangle"
+// - other rectangles are "3x3 square" if both sides are the same, and
+//   "3x4 rectangle" if they aren't
+// - a circle with a radius of 0 and a line with a length of 0 are both "point"
+// - other circles are "circle of radius 5", and other lines "line of length 5"
+// - triangles are "triangle of base 3 and height 4"
+fn describe_shape(shape: &Shape) -> String {
+    match shape {
+        _ => todo!(),
+    }
+}
+
+// TODO: Return how many corners the shape has: 4 for a rectangle, 3 for a
+// triangle, and None for shapes without corners.
+fn corners(shape: &Shape) -> Option<u32> {
+    match shape {
+        _ => todo!(),
+    }
+}
+
+// TODO: Read a size like "3x4", with `let ... else` to return None as soon as
+// something doesn't fit: when there's no 'x', or when a side isn't a number.
+fn parse_size(s: &str) -> Option<Size> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_and_zero() {
+        assert_eq!(describe_number(0), "zero");
+        assert_eq!(describe_number(1), "digit 1");
+        assert_eq!(describe_number(9), "digit 9");
+    }
+
+    #[test]
+    fn two_digits() {
+        assert_eq!(describe_number(10), "10 has two digits");
+        assert_eq!(describe_number(99), "99 has two digits");
+        // 42 is in 10 to 99 too, its own arm has to come first
+        assert_eq!(describe_number(42), "the answer");
+        assert_eq!(describe_number(41), "41 has two digits");
+    }
+
+    #[test]
+    fn negative_numbers() {
+        assert_eq!(describe_number(-4), "-4 is negative and even");
+        assert_eq!(describe_number(-3), "-3 is negative");
+        assert_eq!(describe_number(-1), "-1 is negative");
+        assert_eq!(describe_number(i32::MIN), "-2147483648 is negative and even");
+    }
+
+    #[test]
+    fn big_numbers() {
+        assert_eq!(describe_number(100), "100 is round");
+        assert_eq!(describe_number(1000), "1000 is round");
+        assert_eq!(describe_number(10_000), "10000 is round");
+        assert_eq!(describe_number(101), "101 is big");
+        assert_eq!(describe_number(12345), "12345 is big");
+        assert_eq!(describe_number(i32::MAX), "2147483647 is big");
+    }
+
+    #[test]
+    fn rectangles() {
+        let rect = |w, h| Shape::Rect { size: Size { w, h } };
+        assert_eq!(describe_shape(&rect(3, 4)), "3x4 rectangle");
+        assert_eq!(describe_shape(&rect(3, 3)), "3x3 square");
+        assert_eq!(describe_shape(&rect(0, 4)), "empty rectangle");
+        assert_eq!(describe_shape(&rect(4, 0)), "empty rectangle");
+        // Its sides are the same, but it's empty first
+        assert_eq!(describe_shape(&rect(0, 0)), "empty rectangle");
+    }
+
+    #[test]
+    fn other_shapes() {
+        assert_eq!(describe_shape(&Shape::Circle { radius: 5 }), "circle of radius 5");
+        assert_eq!(describe_shape(&Shape::Line(2)), "line of length 2");
+        assert_eq!(describe_shape(&Shape::Circle { radius: 0 }), "point");
+        assert_eq!(describe_shape(&Shape::Line(0)), "point");
+        assert_eq!(
+            describe_shape(&Shape::Triangle { base: 3, height: 4 }),
+            "triangle of base 3 and height 4"
+        );
+        assert_eq!(
+            describe_shape(&Shape::Triangle { base: 0, height: 0 }),
+            "triangle of base 0 and height 0"
+        );
+    }
+
+    // One shape of every variant
+    fn one_of_each() -> [Shape; 4] {
+        let shapes = [
+            Shape::Circle { radius: 1 },
+            Shape::Rect { size: Size { w: 1, h: 2 } },
+            Shape::Line(1),
+            Shape::Triangle { base: 1, height: 1 },
+        ];
+        // There's no `_` arm here, so this stops compiling if `Shape` gets a
+        // variant that's not in the list
+        for shape in &shapes {
+            match shape {
+                Shape::Circle { .. } | Shape::Rect { .. } | Shape::Line(_) | Shape::Triangle { .. } => {}
+            }
+        }
+        shapes
+    }
+
+    #[test]
+    fn no_variant_is_left_to_a_catch_all() {
+        let counts: Vec<Option<u32>> = one_of_each().iter().map(corners).collect();
+        assert_eq!(counts, [None, Some(4), None, Some(3)]);
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(parse_size("3x4"), Some(Size { w: 3, h: 4 }));
+        assert_eq!(parse_size("0x10"), Some(Size { w: 0, h: 10 }));
+        assert_eq!(parse_size("3"), None);
+        assert_eq!(parse_size("3x"), None);
+        assert_eq!(parse_size("x4"), None);
+        assert_eq!(parse_size("3x4x5"), None);
+        assert_eq!(parse_size("-3x4"), None);
+        assert_eq!(parse_size("threexfour"), None);
+    }
+}
@@ -0,0 +1,59 @@
+// env1.rs
+//
+// Programs are often configured through environment variables, so that the
+// same binary can behave differently on a laptop and on a server. When
+// rustlings runs the tests of this exercise, it sets `APP_MODE` to "test" and
+// `APP_RETRIES` to "3" for them (see the `env` of env1 in info.toml).
+//
+// Variables that aren't set should fall back to a default, but a variable that
+// is set to something that doesn't make sense is an error.
+//
+// Execute `rustlings hint env1` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+use std::env;
+
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Development,
+    Test,
+    Production,
+}
+
+#[derive(Debug, PartialEq)]
+struct Config {
+    mode: Mode,
+    retries: u32,
+}
+
+impl Config {
+    // TODO: Read the mode from `APP_MODE` ("development", "test" or
+    // "production", "development" when it isn't set) and the number of retries
+    // from `APP_RETRIES` (0 when it isn't set).
+    fn from_env() -> Result<Config, String> {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_environment() {
+        assert_eq!(
+            Config::from_env(),
+            Ok(Config {
+                mode: Mode::Test,
+                retries: 3
+            })
+        );
+    }
+
+    #[test]
+    fn sees_what_rustlings_sets() {
+        assert_eq!(env::var("APP_MODE").as_deref(), Ok("test"));
+        assert_eq!(env::var("APP_RETRIES").as_deref(), Ok("3"));
+    }
+}
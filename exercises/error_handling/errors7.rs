@@ -0,0 +1,120 @@
+// errors7.rs
+//
+// An error type of our own can do everything `Box<dyn Error>` can, and still
+// let callers match on what went wrong. For that, it implements `Display` for
+// the message, and `std::error::Error`, whose `source()` method gives callers
+// the lower-level error that caused it. `From` conversions let `?` turn the
+// lower-level errors into ours.
+//
+// `load_config` reads a file with one port number on each line, skipping
+// empty lines. Make it compile, and make the tests pass.
+//
+// Execute `rustlings hint errors7` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+struct Config {
+    ports: Vec<u16>,
+}
+
+#[derive(Debug)]
+enum ConfigError {
+    Io(io::Error),
+    // `line` counts from 1, as editors do
+    Parse { line: usize, source: ParseIntError },
+}
+
+// TODO: Implement `Display` for `ConfigError`, with the messages the tests
+// expect. The message shouldn't repeat the one of the source, callers that
+// want both can get the source with `source()`.
+
+// TODO: Implement `Error` for `ConfigError`, with a `source()` that returns
+// the error it was made from.
+
+// TODO: Implement `From<io::Error>` for `ConfigError`.
+
+fn load_config(path: &str) -> Result<Config, ConfigError> {
+    let text = fs::read_to_string(path)?;
+    let mut ports = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // TODO: The error needs the number of the line.
+        ports.push(line.trim().parse::<u16>()?);
+    }
+    Ok(Config { ports })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::IntErrorKind;
+    use std::path::PathBuf;
+
+    // Writes a config file of its own for every test, as they run at the same time
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rustlings-errors7-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn load(name: &str, contents: &str) -> Result<Config, ConfigError> {
+        let path = write_config(name, contents);
+        let config = load_config(path.to_str().unwrap());
+        fs::remove_file(path).unwrap();
+        config
+    }
+
+    #[test]
+    fn loads_a_config() {
+        let config = load("good", "8080\n\n  443\n");
+        assert_eq!(config.unwrap(), Config { ports: vec![8080, 443] });
+        assert_eq!(load("empty", "").unwrap(), Config { ports: vec![] });
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = load_config("/this/config/does/not/exist").unwrap_err();
+        assert!(matches!(err, ConfigError::Io(_)));
+        assert_eq!(err.to_string(), "couldn't read the config");
+        let source = err.source().expect("the io::Error is the source");
+        let io_err = source.downcast_ref::<io::Error>().expect("the source is an io::Error");
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn malformed_middle_line() {
+        let err = load("middle", "80\n\nhttp\n443\n").unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { line: 3, .. }), "{:?}", err);
+        assert_eq!(err.to_string(), "line 3 isn't a port number");
+        let source = err.source().unwrap().downcast_ref::<ParseIntError>().unwrap();
+        assert_eq!(source.kind(), &IntErrorKind::InvalidDigit);
+    }
+
+    #[test]
+    fn port_out_of_range() {
+        let err = load("range", "70000").unwrap_err();
+        assert_eq!(err.to_string(), "line 1 isn't a port number");
+        let source = err.source().unwrap().downcast_ref::<ParseIntError>().unwrap();
+        assert_eq!(source.kind(), &IntErrorKind::PosOverflow);
+    }
+
+    #[test]
+    fn works_as_a_boxed_error() -> Result<(), Box<dyn Error>> {
+        let err: Box<dyn Error> = load("boxed", "1\n2\nthree").unwrap_err().into();
+        assert_eq!(err.to_string(), "line 3 isn't a port number");
+        assert!(err.source().unwrap().is::<ParseIntError>());
+        let config = load("question_mark", "22")?;
+        assert_eq!(config.ports, [22]);
+        Ok(())
+    }
+}
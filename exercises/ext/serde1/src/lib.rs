@@ -0,0 +1,63 @@
+// serde1.rs
+//
+// Serde turns Rust values into formats such as JSON and back, and the
+// derive macros of the `serde` crate write the code doing it for your own
+// types. This exercise is a package of its own, have a look at its Cargo.toml
+// to see the crates it depends on.
+//
+// Make the tests pass by letting `Exercise` be read from and written to JSON.
+// Note that the JSON calls the field `time_secs` "time".
+//
+// Execute `rustlings hint serde1` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+// TODO: Derive the traits serde needs
+#[derive(Debug, PartialEq)]
+pub struct Exercise {
+    pub name: String,
+    pub done: bool,
+    pub time_secs: u32,
+}
+
+pub fn from_json(json: &str) -> serde_json::Result<Exercise> {
+    serde_json::from_str(json)
+}
+
+pub fn to_json(exercise: &Exercise) -> String {
+    serde_json::to_string(exercise).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_json() {
+        let exercise = from_json(r#"{"name": "serde1", "done": false, "time": 90}"#).unwrap();
+        assert_eq!(
+            exercise,
+            Exercise {
+                name: "serde1".to_string(),
+                done: false,
+                time_secs: 90,
+            }
+        );
+    }
+
+    #[test]
+    fn writes_json() {
+        let exercise = Exercise {
+            name: "serde1".to_string(),
+            done: true,
+            time_secs: 30,
+        };
+        assert_eq!(to_json(&exercise), r#"{"name":"serde1","done":true,"time":30}"#);
+    }
+
+    #[test]
+    fn rejects_missing_fields() {
+        assert!(from_json(r#"{"name": "serde1"}"#).is_err());
+    }
+}
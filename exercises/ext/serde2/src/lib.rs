@@ -0,0 +1,179 @@
+// serde2.rs
+//
+// The derives of serde can be told a lot with attributes: how enum variants
+// are spelled, what to do with fields they don't know, and which function
+// reads a field whose format serde doesn't know by itself. Here, the date a
+// config was started on is a string like "2024-03-01" in JSON, but a `Date`
+// in Rust.
+//
+// Make the tests pass. JSON with a field that `Config` doesn't have is a
+// mistake, so it should be rejected, and so is a date that doesn't exist.
+//
+// Execute `rustlings hint serde2` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+// TODO: Reject unknown fields, and read `started` with `deserialize_date`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub name: String,
+    pub level: Level,
+    pub mentor: Option<String>,
+    #[serde(serialize_with = "serialize_date")]
+    pub started: Date,
+}
+
+// TODO: Derive the traits serde needs. The variants are spelled in lowercase
+// in JSON: "beginner" and "advanced".
+#[derive(Debug, PartialEq)]
+pub enum Level {
+    Beginner,
+    Advanced,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// TODO: Parse a date written as YYYY-MM-DD, with exactly 4, 2 and 2 digits.
+// The month has to be 1 to 12, and the day has to be in the month.
+pub fn parse_date(s: &str) -> Result<Date, String> {
+    todo!()
+}
+
+fn serialize_date<S: Serializer>(date: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{:04}-{:02}-{:02}", date.year, date.month, date.day))
+}
+
+// TODO: Read a string, and turn it into a `Date` with `parse_date`.
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Date, D::Error> {
+    todo!()
+}
+
+pub fn load(json: &str) -> Result<Config, serde_json::Error> {
+    todo!()
+}
+
+pub fn save(config: &Config) -> String {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::error::Category;
+
+    fn config() -> Config {
+        Config {
+            name: String::from("rustlings"),
+            level: Level::Advanced,
+            mentor: Some(String::from("ferris")),
+            started: Date { year: 2024, month: 3, day: 1 },
+        }
+    }
+
+    #[test]
+    fn saves_json() {
+        assert_eq!(
+            save(&config()),
+            r#"{"name":"rustlings","level":"advanced","mentor":"ferris","started":"2024-03-01"}"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(load(&save(&config())).unwrap(), config());
+        let without_mentor = Config { mentor: None, level: Level::Beginner, ..config() };
+        assert_eq!(load(&save(&without_mentor)).unwrap(), without_mentor);
+    }
+
+    #[test]
+    fn mentor_is_optional() {
+        let config = load(r#"{"name": "solo", "level": "beginner", "started": "2023-12-31"}"#).unwrap();
+        assert_eq!(config.mentor, None);
+        assert_eq!(config.level, Level::Beginner);
+        assert_eq!(config.started, Date { year: 2023, month: 12, day: 31 });
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert_eq!(load(r#"{"name": "rustlings", "#).unwrap_err().classify(), Category::Eof);
+        assert_eq!(load(r#"{"name" "rustlings"}"#).unwrap_err().classify(), Category::Syntax);
+        let wrong_type = load(r#"{"name": 1, "level": "beginner", "started": "2024-01-01"}"#);
+        assert_eq!(wrong_type.unwrap_err().classify(), Category::Data);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let err = load(r#"{"name": "x", "level": "beginner", "started": "2024-01-01", "colour": "red"}"#).unwrap_err();
+        assert_eq!(err.classify(), Category::Data);
+        assert!(err.to_string().contains("unknown field `colour`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_unknown_levels() {
+        let err = load(r#"{"name": "x", "level": "Beginner", "started": "2024-01-01"}"#).unwrap_err();
+        assert_eq!(err.classify(), Category::Data);
+    }
+
+    #[test]
+    fn rejects_bad_dates() {
+        let err = load(r#"{"name": "x", "level": "beginner", "started": "2023-02-29"}"#).unwrap_err();
+        assert_eq!(err.classify(), Category::Data);
+        let err = load(r#"{"name": "x", "level": "beginner", "started": 20240101}"#).unwrap_err();
+        assert_eq!(err.classify(), Category::Data);
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse_date("2024-03-01"), Ok(Date { year: 2024, month: 3, day: 1 }));
+        assert_eq!(parse_date("0001-01-01"), Ok(Date { year: 1, month: 1, day: 1 }));
+        assert_eq!(parse_date("9999-12-31"), Ok(Date { year: 9999, month: 12, day: 31 }));
+    }
+
+    #[test]
+    fn parses_leap_days() {
+        assert!(parse_date("2024-02-29").is_ok());
+        assert!(parse_date("2000-02-29").is_ok());
+        assert!(parse_date("2023-02-29").is_err());
+        assert!(parse_date("1900-02-29").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        for s in [
+            "",
+            "2024",
+            "2024-03",
+            "2024-3-01",
+            "2024-03-1",
+            "24-03-01",
+            "2024/03/01",
+            "2024-03-01 ",
+            "+024-03-01",
+            "2024-+3-01",
+            "2024-00-10",
+            "2024-13-01",
+            "2024-04-31",
+            "2024-01-00",
+            "２０２４-03-01",
+        ] {
+            assert!(parse_date(s).is_err(), "{:?} isn't a date", s);
+        }
+    }
+}
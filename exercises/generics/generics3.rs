@@ -0,0 +1,97 @@
+// generics3.rs
+//
+// Types can be generic over values, too. `[T; N]` is one: an array of 3 and
+// an array of 4 are different types. With a `const N: usize` parameter, a
+// function or a struct can work with arrays of any length, and the length is
+// still known when the program is compiled, so nothing has to check it while
+// it runs.
+//
+// Execute `rustlings hint generics3` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+// TODO: Make this work for two arrays of any length, as long as it's the same
+// length for both.
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+#[derive(Debug)]
+struct Buffer<T, const N: usize> {
+    items: [T; N],
+}
+
+impl<T, const N: usize> Buffer<T, N> {
+    fn from_array(items: [T; N]) -> Self {
+        Buffer { items }
+    }
+
+    // TODO: Return the length, without looking at `items`.
+    fn len(&self) -> usize {
+        todo!()
+    }
+
+    // TODO: Return the first K items. Asking for more than there are should
+    // fail to compile: an `assert!` in a `const { ... }` block is checked by
+    // the compiler, and can use K and N.
+    fn first_chunk<const K: usize>(&self) -> &[T; K] {
+        todo!()
+    }
+}
+
+impl<T: Default, const N: usize> Buffer<T, N> {
+    // TODO: Make a buffer of N default values.
+    fn new() -> Self {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_products() {
+        assert_eq!(dot([1.0, 2.0, 3.0], [4.0, 5.0, 6.0]), 32.0);
+        assert_eq!(dot([2.0], [0.5]), 1.0);
+        assert_eq!(dot([1.0, -1.0, 1.0, -1.0, 1.0], [1.0; 5]), 1.0);
+        assert_eq!(dot([], []), 0.0);
+    }
+
+    #[test]
+    fn new_buffers() {
+        let numbers = Buffer::<i32, 4>::new();
+        assert_eq!(numbers.items, [0; 4]);
+        assert_eq!(numbers.len(), 4);
+        let words: Buffer<String, 2> = Buffer::new();
+        assert_eq!(words.items, ["", ""]);
+    }
+
+    #[test]
+    fn empty_buffers() {
+        let empty = Buffer::<u8, 0>::new();
+        assert_eq!(empty.len(), 0);
+        let chunk: &[u8; 0] = empty.first_chunk();
+        assert!(chunk.is_empty());
+    }
+
+    #[test]
+    fn length_comes_from_the_type() {
+        assert_eq!(Buffer::from_array([1, 2, 3]).len(), 3);
+        assert_eq!(Buffer::from_array(["a"; 100]).len(), 100);
+    }
+
+    #[test]
+    fn first_chunks() {
+        let buffer = Buffer::from_array([10, 20, 30]);
+        // The chunks are arrays, their length is part of their type
+        let two: &[i32; 2] = buffer.first_chunk();
+        assert_eq!(two, &[10, 20]);
+        assert_eq!(buffer.first_chunk::<3>(), &[10, 20, 30]);
+        assert_eq!(buffer.first_chunk::<0>(), &[]);
+        let [first] = buffer.first_chunk::<1>();
+        assert_eq!(*first, 10);
+        // buffer.first_chunk::<4>() doesn't compile, try it!
+    }
+}
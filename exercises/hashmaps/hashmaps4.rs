@@ -0,0 +1,302 @@
+// hashmaps4.rs
+//
+// Time to build a hash map of our own. `MyHashMap` keeps its entries in a
+// `Vec` of slots, and a key's hash picks the slot it should go in. When that
+// slot is taken by another key, it goes in the next free one after it, and
+// the one after that, wrapping around at the end: this is called open
+// addressing with linear probing. Looking a key up follows the same path,
+// until it finds the key or an empty slot.
+//
+// Removing a key can't just empty its slot, as that would cut the path to
+// the keys that were put after it. The slot gets a tombstone instead, which
+// lookups step over, and inserts can reuse.
+//
+// Execute `rustlings hint hashmaps4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+const INITIAL_CAPACITY: usize = 8;
+// Grow once more than this part of the slots would be in use
+const MAX_LOAD: f64 = 0.7;
+
+enum Slot<K, V> {
+    Empty,
+    Tombstone,
+    Full(K, V),
+}
+
+pub struct MyHashMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
+    // How many slots are full
+    len: usize,
+    // How many slots are full or tombstones, the ones a lookup steps over
+    used: usize,
+    hasher: S,
+}
+
+impl<K: Hash + Eq, V> MyHashMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> MyHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        MyHashMap {
+            slots: empty_slots(INITIAL_CAPACITY),
+            len: 0,
+            used: 0,
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Full(key, value) => Some((key, value)),
+            _ => None,
+        })
+    }
+
+    // The slot the key should go in, where its path starts
+    fn home(&self, key: &K) -> usize {
+        (self.hasher.hash_one(key) % self.slots.len() as u64) as usize
+    }
+
+    // TODO: Return the index of the slot that holds the key, if there is one.
+    // Start at its home, step over tombstones and other keys, and stop at the
+    // first empty slot.
+    fn find(&self, key: &K) -> Option<usize> {
+        todo!()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        todo!()
+    }
+
+    // TODO: Replace the value if the key is already there, and return the old
+    // one. Otherwise, first `grow` if `used + 1` slots would be more than
+    // `MAX_LOAD` of the capacity, and then put the key in the first tombstone
+    // or empty slot on its path.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        todo!()
+    }
+
+    // TODO: Put a tombstone in the slot of the key, and return its value.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        todo!()
+    }
+
+    // TODO: Double the number of slots, and put every key in its new home. A
+    // key's home changes with the capacity, so `slots` can't just get longer.
+    // The tombstones are left behind.
+    fn grow(&mut self) {
+        todo!()
+    }
+}
+
+fn empty_slots<K, V>(n: usize) -> Vec<Slot<K, V>> {
+    (0..n).map(|_| Slot::Empty).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    // Hashes every key to the same value, so that they all have the same home
+    // and have to be found by probing
+    #[derive(Default)]
+    struct CollidingHasher;
+
+    impl Hasher for CollidingHasher {
+        fn finish(&self) -> u64 {
+            3
+        }
+
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    type Colliding = BuildHasherDefault<CollidingHasher>;
+
+    fn colliding_map<K: Hash + Eq, V>() -> MyHashMap<K, V, Colliding> {
+        MyHashMap::with_hasher(Colliding::default())
+    }
+
+    fn sorted_contents<K: Hash + Eq + Ord + Clone, V: Clone, S: BuildHasher>(map: &MyHashMap<K, V, S>) -> Vec<(K, V)> {
+        let mut contents: Vec<(K, V)> = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        contents.sort_by(|a, b| a.0.cmp(&b.0));
+        contents
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut map = MyHashMap::new();
+        assert!(map.is_empty());
+        assert_eq!(map.insert("one", 1), None);
+        assert_eq!(map.insert("two", 2), None);
+        assert_eq!(map.get(&"one"), Some(&1));
+        assert_eq!(map.get(&"two"), Some(&2));
+        assert_eq!(map.get(&"three"), None);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn overwriting() {
+        let mut map = MyHashMap::new();
+        assert_eq!(map.insert(String::from("key"), 1), None);
+        assert_eq!(map.insert(String::from("key"), 2), Some(1));
+        assert_eq!(map.insert(String::from("key"), 3), Some(2));
+        assert_eq!(map.get(&String::from("key")), Some(&3));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn removing() {
+        let mut map = MyHashMap::new();
+        map.insert(1, 'a');
+        map.insert(2, 'b');
+        assert_eq!(map.remove(&1), Some('a'));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.remove(&3), None);
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.get(&2), Some(&'b'));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn grows_past_the_load_factor() {
+        let mut map = MyHashMap::new();
+        for i in 0..5 {
+            map.insert(i, i);
+        }
+        // 5 of 8 is less than 0.7, 6 of 8 would be more
+        assert_eq!(map.capacity(), 8);
+        map.insert(5, 5);
+        assert_eq!(map.capacity(), 16);
+        for i in 6..100 {
+            map.insert(i, i * 10);
+        }
+        assert!(map.capacity() >= 32, "grew only to {}", map.capacity());
+        assert!(map.len() as f64 <= MAX_LOAD * map.capacity() as f64);
+        assert_eq!(map.len(), 100);
+        for i in 6..100 {
+            assert_eq!(map.get(&i), Some(&(i * 10)), "key {}", i);
+        }
+    }
+
+    #[test]
+    fn colliding_keys_are_probed() {
+        let mut map = colliding_map();
+        for i in 0..5 {
+            assert_eq!(map.insert(i, i * i), None);
+        }
+        for i in 0..5 {
+            assert_eq!(map.get(&i), Some(&(i * i)));
+        }
+        assert_eq!(map.get(&5), None);
+        // Paths wrap around at the end of the slots, and survive growing
+        for i in 5..20 {
+            map.insert(i, i * i);
+        }
+        assert_eq!(sorted_contents(&map), (0..20).map(|i| (i, i * i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn tombstones_keep_paths_open() {
+        let mut map = colliding_map();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("c", 3);
+        // "c" comes after "b" on the path, and has to be found past its
+        // tombstone
+        assert_eq!(map.remove(&"b"), Some(2));
+        assert_eq!(map.get(&"c"), Some(&3));
+        assert_eq!(map.get(&"b"), None);
+        assert_eq!(map.insert("b", 20), None);
+        assert_eq!(map.get(&"b"), Some(&20));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn keys_past_a_tombstone_are_replaced() {
+        let mut map = colliding_map();
+        map.insert(1, "one");
+        map.insert(2, "two");
+        map.insert(3, "three");
+        map.remove(&1);
+        // The tombstone of 1 comes first on the path, but 3 is already there,
+        // further on
+        assert_eq!(map.insert(3, "THREE"), Some("three"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.remove(&3), Some("THREE"));
+        assert_eq!(map.get(&3), None);
+        assert_eq!(sorted_contents(&map), [(2, "two")]);
+    }
+
+    #[test]
+    fn reinserting_removed_keys() {
+        let mut map = MyHashMap::new();
+        for round in 0..10 {
+            for i in 0..50 {
+                assert_eq!(map.insert(i, round), None);
+            }
+            for i in 0..50 {
+                assert_eq!(map.remove(&i), Some(round));
+            }
+            assert!(map.is_empty());
+        }
+        // Growing clears out the tombstones, so they don't pile up
+        assert!(map.capacity() <= 256, "grew to {}", map.capacity());
+    }
+
+    // Runs the same random operations on a MyHashMap and a HashMap
+    fn random_operations<S: BuildHasher>(mut map: MyHashMap<u32, u32, S>, operations: usize, keys: u64) {
+        let mut reference = HashMap::new();
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        let mut random = |below: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % below
+        };
+        for i in 0..operations as u32 {
+            let key = random(keys) as u32;
+            match random(3) {
+                0 => assert_eq!(map.remove(&key), reference.remove(&key), "removing {}", key),
+                1 => assert_eq!(map.get(&key), reference.get(&key), "getting {}", key),
+                _ => assert_eq!(map.insert(key, i), reference.insert(key, i), "inserting {}", key),
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+        let mut expected: Vec<(u32, u32)> = reference.into_iter().collect();
+        expected.sort();
+        assert_eq!(sorted_contents(&map), expected);
+    }
+
+    #[test]
+    fn agrees_with_std() {
+        random_operations(MyHashMap::new(), 20_000, 500);
+    }
+
+    #[test]
+    fn agrees_with_std_when_colliding() {
+        random_operations(colliding_map(), 2000, 60);
+    }
+}
@@ -0,0 +1,168 @@
+// iterators6.rs
+//
+// `map`, `filter` and `take` don't do any work when they're called. They wrap
+// the iterator they're called on in a struct, which only pulls items out of it
+// when something asks for the next one. In this exercise, you'll write such
+// adapters yourself, and add them to every iterator with a trait of our own.
+//
+// Execute `rustlings hint iterators6` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+struct MyMap<I, F> {
+    iter: I,
+    f: F,
+}
+
+struct MyFilter<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+struct MyTake<I> {
+    iter: I,
+    // How many more items it may return
+    remaining: usize,
+}
+
+// TODO: Return `f` applied to the next item of `iter`.
+impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for MyMap<I, F> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        todo!()
+    }
+}
+
+// TODO: Return the next item of `iter` that `predicate` is true for.
+impl<I: Iterator, P: FnMut(&I::Item) -> bool> Iterator for MyFilter<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        todo!()
+    }
+}
+
+// TODO: Return the next item of `iter` while there are any `remaining`. Once
+// there aren't, don't ask `iter` for any more items.
+impl<I: Iterator> Iterator for MyTake<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        todo!()
+    }
+
+    // TODO: Return the bounds on how many more items `next` returns, from the
+    // ones of `iter` and how many are `remaining`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        todo!()
+    }
+}
+
+// The adapters for every iterator, like the methods of `Iterator`
+trait MyIteratorExt: Iterator + Sized {
+    // TODO: Wrap `self` in the adapters.
+    fn my_map<B, F: FnMut(Self::Item) -> B>(self, f: F) -> MyMap<Self, F> {
+        todo!()
+    }
+
+    fn my_filter<P: FnMut(&Self::Item) -> bool>(self, predicate: P) -> MyFilter<Self, P> {
+        todo!()
+    }
+
+    fn my_take(self, n: usize) -> MyTake<Self> {
+        todo!()
+    }
+}
+
+impl<I: Iterator> MyIteratorExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn each_adapter() {
+        assert_eq!((1..4).my_map(|x| x * 10).collect::<Vec<_>>(), [10, 20, 30]);
+        assert_eq!((1..10).my_filter(|x| x % 3 == 0).collect::<Vec<_>>(), [3, 6, 9]);
+        assert_eq!((1..10).my_take(2).collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn chained() {
+        let result: Vec<String> = (1..)
+            .my_filter(|x| x % 2 == 1)
+            .my_map(|x| format!("<{}>", x * x))
+            .my_take(4)
+            .collect();
+        assert_eq!(result, ["<1>", "<9>", "<25>", "<49>"]);
+    }
+
+    #[test]
+    fn empty_source() {
+        let empty = std::iter::empty::<i32>;
+        assert_eq!(empty().my_map(|x| x + 1).next(), None);
+        assert_eq!(empty().my_filter(|_| true).next(), None);
+        assert_eq!(empty().my_take(3).next(), None);
+        assert_eq!(empty().my_take(3).size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn lazy() {
+        let mapped = Cell::new(0);
+        let tested = Cell::new(0);
+        let mut iter = (1..)
+            .my_map(|x| {
+                mapped.set(mapped.get() + 1);
+                x * 2
+            })
+            .my_filter(|x| {
+                tested.set(tested.get() + 1);
+                x % 3 == 0
+            })
+            .my_take(2);
+        // Nothing has happened yet
+        assert_eq!((mapped.get(), tested.get()), (0, 0));
+        assert_eq!(iter.next(), Some(6));
+        assert_eq!((mapped.get(), tested.get()), (3, 3));
+        assert_eq!(iter.next(), Some(12));
+        // Taking no more than 2 doesn't look at a third one
+        assert_eq!(iter.next(), None);
+        assert_eq!((mapped.get(), tested.get()), (6, 6));
+    }
+
+    #[test]
+    fn take_size_hint() {
+        let mut iter = (0..10).my_take(3);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!((0..2).my_take(5).size_hint(), (2, Some(2)));
+        assert_eq!((0..).my_take(4).size_hint(), (4, Some(4)));
+        assert_eq!((0..10).my_take(0).size_hint(), (0, Some(0)));
+        // The filter could drop any of them
+        let (lower, upper) = (0..10).my_filter(|x| x % 2 == 0).my_take(3).size_hint();
+        assert_eq!((lower, upper), (0, Some(3)));
+    }
+
+    #[test]
+    fn agrees_with_std() {
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        let values: Vec<u64> = (0..1000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state % 1000
+            })
+            .collect();
+        for n in [0, 1, 10, 100, 1000, 2000] {
+            let ours: Vec<u64> = values.iter().my_map(|x| x * 3).my_filter(|x| x % 2 == 0).my_take(n).collect();
+            let std: Vec<u64> = values.iter().map(|x| x * 3).filter(|x| x % 2 == 0).take(n).collect();
+            assert_eq!(ours, std, "taking {}", n);
+            assert_eq!(values.iter().my_take(n).size_hint(), values.iter().take(n).size_hint());
+        }
+    }
+}
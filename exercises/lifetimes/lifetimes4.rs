@@ -0,0 +1,142 @@
+// lifetimes4.rs
+//
+// A parser that borrows its input doesn't have to copy any of it: every token
+// it finds can be a slice of the input. Those slices borrow from the input,
+// not from the parser, so they are still there after the parser is gone. The
+// lifetime annotations are what tell the compiler so.
+//
+// Words are runs of letters, digits and '_', numbers are words made of digits
+// only, and every other char is a symbol. Whitespace and ',' separate them.
+//
+// Make this compile by adding the lifetime annotations. Nothing else needs to
+// change.
+//
+// Execute `rustlings hint lifetimes4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(&str),
+    Number(&str),
+    Symbol(&str),
+}
+
+struct Parser {
+    input: &str,
+    // Where to look for the next token
+    pos: usize,
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || c == ','
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl Parser {
+    fn new(input: &str) -> Parser {
+        Parser { input, pos: 0 }
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        let rest = self.input[self.pos..].trim_start_matches(is_delimiter);
+        let start = self.input.len() - rest.len();
+        self.pos = start;
+        let first = rest.chars().next()?;
+        let len = if is_word_char(first) {
+            rest.find(|c| !is_word_char(c)).unwrap_or(rest.len())
+        } else {
+            first.len_utf8()
+        };
+        let text = &self.input[start..start + len];
+        self.pos += len;
+        Some(if text.bytes().all(|b| b.is_ascii_digit()) {
+            Token::Number(text)
+        } else if is_word_char(first) {
+            Token::Word(text)
+        } else {
+            Token::Symbol(text)
+        })
+    }
+}
+
+impl Iterator for Parser {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.next_token()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let tokens: Vec<Token> = Parser::new("x1 = 42+y;").collect();
+        assert_eq!(
+            tokens,
+            [
+                Token::Word("x1"),
+                Token::Symbol("="),
+                Token::Number("42"),
+                Token::Symbol("+"),
+                Token::Word("y"),
+                Token::Symbol(";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokens_outlive_the_parser() {
+        let input = String::from("let answer = 42");
+        let first;
+        let rest: Vec<Token>;
+        {
+            let mut parser = Parser::new(&input);
+            first = parser.next_token();
+            rest = parser.collect();
+        }
+        assert_eq!(first, Some(Token::Word("let")));
+        assert_eq!(rest, [Token::Word("answer"), Token::Symbol("="), Token::Number("42")]);
+    }
+
+    #[test]
+    fn tokens_are_slices_of_the_input() {
+        let input = "copy nothing";
+        let Some(Token::Word(second)) = Parser::new(input).nth(1) else {
+            panic!("the second token is a word");
+        };
+        assert_eq!(second.as_ptr(), input[5..].as_ptr());
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(Parser::new("").next_token(), None);
+        assert_eq!(Parser::new(" ,\t, \n").next_token(), None);
+    }
+
+    #[test]
+    fn trailing_delimiters() {
+        let mut parser = Parser::new("a, b,  ");
+        assert_eq!(parser.next_token(), Some(Token::Word("a")));
+        assert_eq!(parser.next_token(), Some(Token::Word("b")));
+        assert_eq!(parser.next_token(), None);
+        // and it stays done
+        assert_eq!(parser.next_token(), None);
+    }
+
+    #[test]
+    fn chars_that_take_more_than_a_byte() {
+        let tokens: Vec<Token> = Parser::new("café∑ 1,über").collect();
+        assert_eq!(
+            tokens,
+            [Token::Word("café"), Token::Symbol("∑"), Token::Number("1"), Token::Word("über")]
+        );
+    }
+}
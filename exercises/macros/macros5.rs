@@ -0,0 +1,125 @@
+// macros5.rs
+//
+// A macro can have several rules, and use the first one that matches what it
+// was called with. Rules can also match something repeated: `$($x:expr),*`
+// matches any number of expressions separated by commas, and `$(...)*` in
+// the expansion repeats its contents once for each of them.
+//
+// Complete `my_vec!`, which should make a `Vec` like `vec!` does, and
+// `hashmap!`, which should make a `HashMap` of `key => value` pairs.
+//
+// Execute `rustlings hint macros5` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::collections::HashMap;
+
+#[rustfmt::skip]
+macro_rules! my_vec {
+    () => {
+        Vec::new()
+    };
+    // TODO: Add a rule for `my_vec![value; n]`, which makes a `Vec` of n
+    // clones of the value, and one for `my_vec![a, b, c]`, which should also
+    // accept a comma after the last element.
+}
+
+#[rustfmt::skip]
+macro_rules! hashmap {
+    () => {
+        HashMap::new()
+    };
+    // TODO: Add a rule for `key => value` pairs, separated by commas, with or
+    // without one after the last pair.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    // A value that counts how often it's made
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counted(u32);
+
+    thread_local! {
+        static MADE: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn make(n: u32) -> Counted {
+        MADE.with(|made| made.set(made.get() + 1));
+        Counted(n)
+    }
+
+    fn made() -> u32 {
+        MADE.with(Cell::get)
+    }
+
+    #[test]
+    fn empty_vec() {
+        let v: Vec<i32> = my_vec![];
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn listed_elements() {
+        assert_eq!(my_vec![1], [1]);
+        assert_eq!(my_vec![1, 2, 3], [1, 2, 3]);
+        assert_eq!(my_vec!["a", "b",], ["a", "b"]);
+        assert_eq!(my_vec![2 + 2, 10 / 2], [4, 5]);
+    }
+
+    #[test]
+    fn listed_elements_are_made_once_each() {
+        let v = my_vec![make(1), make(2), make(3),];
+        assert_eq!(v, [Counted(1), Counted(2), Counted(3)]);
+        assert_eq!(made(), 3);
+    }
+
+    #[test]
+    fn repeated_element() {
+        assert_eq!(my_vec![0; 4], [0, 0, 0, 0]);
+        assert_eq!(my_vec![String::from("hi"); 2], ["hi", "hi"]);
+        assert!(my_vec![1u8; 0].is_empty());
+    }
+
+    #[test]
+    fn repeated_element_is_made_once() {
+        let v = my_vec![make(7); 5];
+        assert_eq!(v, vec![Counted(7); 5]);
+        // The other four are clones of the one that was made
+        assert_eq!(made(), 1);
+        let mut n = 0;
+        let v = my_vec![make(8); { n += 1; 3 }];
+        assert_eq!(v.len(), 3);
+        assert_eq!((made(), n), (2, 1));
+    }
+
+    #[test]
+    fn empty_hashmap() {
+        let map: HashMap<&str, i32> = hashmap! {};
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn hashmap_pairs() {
+        let map = hashmap! {
+            "one" => 1,
+            "two" => 2,
+            "three" => 1 + 2,
+        };
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["one"], 1);
+        assert_eq!(map["three"], 3);
+        let map = hashmap! { 'x' => "ex" };
+        assert_eq!(map.get(&'x'), Some(&"ex"));
+    }
+
+    #[test]
+    fn later_pairs_win() {
+        let map = hashmap! { "key" => 1, "key" => 2 };
+        assert_eq!(map.len(), 1);
+        assert_eq!(map["key"], 2);
+    }
+}
@@ -0,0 +1,30 @@
+// modules4/main.rs
+//
+// Real projects split their code over several files. This exercise is made of
+// two: this file and `shapes.rs` next to it. The line `mod shapes;` tells the
+// compiler to look for the contents of the `shapes` module in `shapes.rs`.
+// Make the tests pass without moving any code between the files!
+//
+// Execute `rustlings hint modules4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+mod shapes;
+
+#[cfg(test)]
+mod tests {
+    use super::shapes::Rectangle;
+
+    #[test]
+    fn area() {
+        let rectangle = Rectangle::new(3, 4);
+        assert_eq!(rectangle.area(), 12);
+    }
+
+    #[test]
+    fn squares() {
+        assert!(Rectangle::square(5).is_square());
+        assert!(!Rectangle::new(2, 3).is_square());
+    }
+}
@@ -0,0 +1,27 @@
+// modules4/shapes.rs
+//
+// The `shapes` module of modules4. This file is a module of its own, so
+// everything in it is private to it unless marked otherwise.
+
+pub struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    fn new(width: u32, height: u32) -> Self {
+        Rectangle { width, height }
+    }
+
+    fn square(side: u32) -> Self {
+        Rectangle::new(side, side)
+    }
+
+    fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+}
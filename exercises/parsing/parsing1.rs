@@ -0,0 +1,46 @@
+// parsing1.rs
+//
+// This program reads a shopping list from its standard input, one item per
+// line as `<name>: <quantity>`, and adds up the quantities. When rustlings runs
+// it, its standard input is `exercises/parsing/input.txt`, have a look at it!
+//
+// Blank lines should be skipped, and lines that can't be parsed should be
+// reported and skipped too, without stopping the program.
+//
+// Execute `rustlings hint parsing1` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::io::{self, BufRead};
+
+#[derive(Debug, PartialEq)]
+struct Item {
+    name: String,
+    quantity: u32,
+}
+
+// TODO: Parse a line such as "apples: 3" into an `Item`, and return an
+// error message for lines such as "apples" or "apples: many".
+fn parse_item(line: &str) -> Result<Item, String> {
+    todo!()
+}
+
+fn main() {
+    let mut total = 0;
+    for line in io::stdin().lock().lines() {
+        let line = line.expect("Failed to read the standard input");
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_item(&line) {
+            Ok(item) => {
+                println!("{} x {}", item.quantity, item.name);
+                total += item.quantity;
+            }
+            Err(e) => println!("Skipping {line:?}: {e}"),
+        }
+    }
+    println!("{total} items in total");
+    assert_eq!(total, 10);
+}
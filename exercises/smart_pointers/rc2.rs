@@ -0,0 +1,202 @@
+// rc2.rs
+//
+// `Rc` isn't magic, it's a struct from the standard library, built on a bit
+// of unsafe code. In this exercise, you'll write a simpler one yourself: a
+// `MyRc<T>` points at a value on the heap, next to a count of how many
+// `MyRc`s point at it. Cloning one adds one to the count, dropping one takes
+// one away, and when the last one is dropped, the value is dropped and its
+// memory is freed.
+//
+// `Box::into_raw` turns a `Box` into a raw pointer, and doesn't free the memory
+// when it goes out of scope anymore. `Box::from_raw` turns the pointer back
+// into a `Box`, which frees the memory again when it's dropped. `NonNull` is a
+// raw pointer that's never null.
+//
+// Execute `rustlings hint rc2` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+struct Inner<T> {
+    // How many `MyRc`s point at this
+    strong: Cell<usize>,
+    value: T,
+}
+
+pub struct MyRc<T> {
+    ptr: NonNull<Inner<T>>,
+    // Tells the compiler that a `MyRc<T>` owns an `Inner<T>`, and may drop it
+    phantom: PhantomData<Inner<T>>,
+}
+
+impl<T> MyRc<T> {
+    // TODO: Put the value on the heap, with a count of 1.
+    pub fn new(value: T) -> Self {
+        todo!()
+    }
+
+    // TODO: Return a reference to the `Inner` that `ptr` points at. It's alive
+    // as long as `self` is, so the reference can live as long as `&self`.
+    fn inner(&self) -> &Inner<T> {
+        todo!()
+    }
+
+    // TODO: Return how many `MyRc`s point at the value of `this`. Like the one
+    // of `Rc`, it's called as `MyRc::strong_count(&rc)`, so that it doesn't
+    // hide a method of `T` with the same name.
+    pub fn strong_count(this: &Self) -> usize {
+        todo!()
+    }
+}
+
+// TODO: Add one to the count, and return another `MyRc` to the same value.
+impl<T> Clone for MyRc<T> {
+    fn clone(&self) -> Self {
+        todo!()
+    }
+}
+
+// TODO: Take one away from the count. If it's the last one, free the `Inner`,
+// and the value in it.
+impl<T> Drop for MyRc<T> {
+    fn drop(&mut self) {
+        // Panicking here while another panic is unwinding aborts all tests,
+        // so this doesn't call `todo!()`, and forgets the value instead.
+    }
+}
+
+// TODO: Give access to the value.
+impl<T> Deref for MyRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        todo!()
+    }
+}
+
+// `MyRc` must not be sent to another thread: two threads could change the
+// count at the same time, as it isn't atomic. `NonNull` isn't `Send` or
+// `Sync`, so `MyRc` isn't either, and this doesn't compile:
+//
+//     let rc = MyRc::new(1);
+//     std::thread::spawn(move || println!("{}", *rc));
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    // Counts how often it's dropped
+    struct DropCounter<'a>(&'a Cell<u32>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn counts() {
+        let a = MyRc::new(5);
+        assert_eq!(MyRc::strong_count(&a), 1);
+        let b = a.clone();
+        assert_eq!(MyRc::strong_count(&a), 2);
+        {
+            let c = b.clone();
+            assert_eq!(MyRc::strong_count(&c), 3);
+            let d = c.clone();
+            assert_eq!(MyRc::strong_count(&a), 4);
+            drop(d);
+            assert_eq!(MyRc::strong_count(&c), 3);
+        }
+        assert_eq!(MyRc::strong_count(&b), 2);
+        drop(a);
+        assert_eq!(MyRc::strong_count(&b), 1);
+        assert_eq!(*b, 5);
+    }
+
+    #[test]
+    fn deref() {
+        let a = MyRc::new(String::from("shared"));
+        let b = a.clone();
+        assert_eq!(a.len(), 6);
+        assert_eq!(*b, "shared");
+        // Both point at the same value
+        assert!(std::ptr::eq(&*a, &*b));
+        assert!(!std::ptr::eq(&*a, &*MyRc::new(String::from("shared"))));
+    }
+
+    #[test]
+    fn value_is_dropped_once() {
+        let drops = Cell::new(0);
+        let a = MyRc::new(DropCounter(&drops));
+        let b = a.clone();
+        {
+            let c = a.clone();
+            let _d = c.clone();
+            drop(a);
+        }
+        assert_eq!(drops.get(), 0);
+        assert_eq!(MyRc::strong_count(&b), 1);
+        drop(b);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn unshared_value_is_dropped() {
+        let drops = Cell::new(0);
+        {
+            let _rc = MyRc::new(DropCounter(&drops));
+            assert_eq!(drops.get(), 0);
+        }
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn forgotten_clones_keep_the_value_alive() {
+        let drops = Cell::new(0);
+        let a = MyRc::new(DropCounter(&drops));
+        // Never decreases the count, so the value leaks. That's not unsafe,
+        // only wasteful, and `Rc` does the same.
+        std::mem::forget(a.clone());
+        drop(a);
+        assert_eq!(drops.get(), 0);
+    }
+
+    #[test]
+    fn rcs_in_rcs() {
+        let inner = MyRc::new(7);
+        let outer = MyRc::new(vec![inner.clone(), inner.clone()]);
+        let outer2 = outer.clone();
+        assert_eq!(MyRc::strong_count(&inner), 3);
+        drop(outer);
+        assert_eq!(MyRc::strong_count(&inner), 3);
+        drop(outer2);
+        assert_eq!(MyRc::strong_count(&inner), 1);
+    }
+
+    #[test]
+    fn as_small_as_a_pointer() {
+        assert_eq!(size_of::<MyRc<u64>>(), size_of::<usize>());
+        // The pointer is never null, so `None` can be stored as null
+        assert_eq!(size_of::<Option<MyRc<u64>>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn not_send_or_sync() {
+        // `some_item` can only be found if just one of the impls applies to
+        // `MyRc`, so this only compiles if `MyRc` is neither `Send` nor `Sync`.
+        trait AmbiguousIfSendOrSync<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfSendOrSync<()> for T {}
+        impl<T: ?Sized + Send> AmbiguousIfSendOrSync<u8> for T {}
+        impl<T: ?Sized + Sync> AmbiguousIfSendOrSync<u16> for T {}
+        <MyRc<i32> as AmbiguousIfSendOrSync<_>>::some_item();
+    }
+}
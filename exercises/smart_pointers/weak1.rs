@@ -0,0 +1,137 @@
+// weak1.rs
+//
+// In this exercise, we want to model a tree where every node owns its
+// children and knows its parent. If the links to the parents were `Rc`s too,
+// every parent and child would keep each other alive, and no node of the
+// tree would ever be freed. So a child only holds a `Weak` reference to its
+// parent, which doesn't keep the parent alive, and has to be upgraded to an
+// `Rc` to be used.
+//
+// The nodes change after they've been made, when children are added, so they
+// live in a `RefCell`, which checks the borrowing rules while the program
+// runs, and panics when they're broken.
+//
+// Execute `rustlings hint weak1` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: Weak<RefCell<Node>>,
+    children: Vec<Rc<RefCell<Node>>>,
+}
+
+// TODO: Make a node without a parent or children.
+fn new_node(name: &str) -> Rc<RefCell<Node>> {
+    todo!()
+}
+
+// TODO: Make `child` a child of `parent`, and `parent` the parent of `child`.
+fn add_child(parent: &Rc<RefCell<Node>>, child: &Rc<RefCell<Node>>) {
+    todo!()
+}
+
+// TODO: Return the name of the parent of `node`, if it has a parent that is
+// still alive.
+fn parent_name(node: &Rc<RefCell<Node>>) -> Option<String> {
+    todo!()
+}
+
+// Takes `child` away from its parent, if it has one, and adds it to the
+// children of `new_parent`.
+//
+// TODO: This panics with "already borrowed". Find out why and fix it.
+fn reparent(child: &Rc<RefCell<Node>>, new_parent: &Rc<RefCell<Node>>) {
+    if let Some(old_parent) = child.borrow().parent.upgrade() {
+        old_parent.borrow_mut().children.retain(|c| !Rc::ptr_eq(c, child));
+        child.borrow_mut().parent = Weak::new();
+    }
+    add_child(new_parent, child);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_nodes_are_alone() {
+        let node = new_node("root");
+        assert_eq!(node.borrow().name, "root");
+        assert!(node.borrow().children.is_empty());
+        assert_eq!(parent_name(&node), None);
+        assert_eq!(Rc::strong_count(&node), 1);
+        assert_eq!(Rc::weak_count(&node), 0);
+    }
+
+    #[test]
+    fn children_know_their_parent() {
+        let root = new_node("root");
+        let leaf = new_node("leaf");
+        add_child(&root, &leaf);
+        assert_eq!(parent_name(&leaf), Some(String::from("root")));
+        assert_eq!(root.borrow().children.len(), 1);
+        assert!(Rc::ptr_eq(&root.borrow().children[0], &leaf));
+        // The root owns the leaf, but the leaf only points back at the root
+        assert_eq!(Rc::strong_count(&leaf), 2);
+        assert_eq!(Rc::weak_count(&leaf), 0);
+        assert_eq!(Rc::strong_count(&root), 1);
+        assert_eq!(Rc::weak_count(&root), 1);
+    }
+
+    #[test]
+    fn dropping_the_parent_frees_it() {
+        let root = new_node("root");
+        let leaf = new_node("leaf");
+        add_child(&root, &leaf);
+        let weak_root = Rc::downgrade(&root);
+        drop(root);
+        assert!(weak_root.upgrade().is_none());
+        assert!(leaf.borrow().parent.upgrade().is_none());
+        assert_eq!(parent_name(&leaf), None);
+        // The children of the root went away with it
+        assert_eq!(Rc::strong_count(&leaf), 1);
+    }
+
+    #[test]
+    fn grandchildren() {
+        let root = new_node("root");
+        let branch = new_node("branch");
+        let leaf = new_node("leaf");
+        add_child(&root, &branch);
+        add_child(&branch, &leaf);
+        assert_eq!(parent_name(&leaf), Some(String::from("branch")));
+        let parent = leaf.borrow().parent.upgrade().unwrap();
+        assert_eq!(parent_name(&parent), Some(String::from("root")));
+        drop(parent);
+        assert_eq!(Rc::strong_count(&branch), 2);
+        assert_eq!(Rc::weak_count(&branch), 1);
+    }
+
+    #[test]
+    fn reparenting_moves_the_child() {
+        let old = new_node("old");
+        let new = new_node("new");
+        let leaf = new_node("leaf");
+        add_child(&old, &leaf);
+        reparent(&leaf, &new);
+        assert_eq!(parent_name(&leaf), Some(String::from("new")));
+        assert!(old.borrow().children.is_empty());
+        assert_eq!(new.borrow().children.len(), 1);
+        assert_eq!(Rc::strong_count(&leaf), 2);
+        assert_eq!(Rc::weak_count(&old), 0);
+        assert_eq!(Rc::weak_count(&new), 1);
+    }
+
+    #[test]
+    fn reparenting_a_node_without_a_parent() {
+        let root = new_node("root");
+        let leaf = new_node("leaf");
+        reparent(&leaf, &root);
+        assert_eq!(parent_name(&leaf), Some(String::from("root")));
+        assert_eq!(root.borrow().children.len(), 1);
+    }
+}
@@ -0,0 +1,134 @@
+// strings5.rs
+//
+// Strings in Rust are UTF-8: a character can take from 1 to 4 bytes. `len()`
+// counts the bytes, and slicing like `&s[..n]` takes the first n bytes, which
+// panics if byte n is in the middle of a character. `chars()` goes over the
+// characters instead, and `char_indices()` tells where each one starts.
+//
+// Even a `char` isn't always what a reader sees as one character: "é" can be
+// one `char`, or an "e" followed by a combining accent, which is two.
+//
+// Execute `rustlings hint strings5` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::collections::HashMap;
+
+// TODO: Return the first `max` characters of `s`, or all of `s` if it doesn't
+// have that many. Don't copy them, return a slice of `s`.
+fn truncate_chars(s: &str, max: usize) -> &str {
+    todo!()
+}
+
+// TODO: Return the characters of `s` in reverse order.
+fn reverse_graphemes_naive(s: &str) -> String {
+    todo!()
+}
+
+// TODO: Count how often every character is in `s`, except for ASCII
+// whitespace like spaces, tabs and newlines.
+fn char_frequencies(s: &str) -> HashMap<char, usize> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_ascii() {
+        assert_eq!(truncate_chars("hello", 3), "hel");
+        assert_eq!(truncate_chars("hello", 5), "hello");
+        assert_eq!(truncate_chars("hello", 10), "hello");
+        assert_eq!(truncate_chars("hello", 0), "");
+        assert_eq!(truncate_chars("", 3), "");
+    }
+
+    #[test]
+    fn truncate_in_the_middle_of_a_character() {
+        // "é" is 2 bytes, so `&s[..2]` would end in the middle of it, and panic
+        assert_eq!(truncate_chars("héllo", 2), "hé");
+        assert_eq!(truncate_chars("🦀🦀🦀", 1), "🦀");
+        assert_eq!(truncate_chars("你好世界", 2), "你好");
+    }
+
+    #[test]
+    fn truncate_counts_characters() {
+        // 5 characters, but 8 bytes
+        let s = "🦀rust";
+        assert_eq!(truncate_chars(s, 5), s);
+        assert_eq!(truncate_chars(s, 4), "🦀rus");
+        assert_eq!(truncate_chars("日本語", 3), "日本語");
+    }
+
+    #[test]
+    fn truncate_returns_a_slice() {
+        let s = String::from("crabs 🦀 and snakes 🐍");
+        let truncated = truncate_chars(&s, 7);
+        assert_eq!(truncated, "crabs 🦀");
+        assert_eq!(truncated.as_ptr(), s.as_ptr());
+    }
+
+    #[test]
+    fn truncate_combining_accents() {
+        // The accent is a character of its own, after the "e"
+        let decomposed = "e\u{301}cole";
+        assert_eq!(truncate_chars(decomposed, 1), "e");
+        assert_eq!(truncate_chars(decomposed, 2), "e\u{301}");
+        assert_eq!(truncate_chars("école", 1), "é");
+    }
+
+    #[test]
+    fn reverse() {
+        assert_eq!(reverse_graphemes_naive("stressed"), "desserts");
+        assert_eq!(reverse_graphemes_naive(""), "");
+        assert_eq!(reverse_graphemes_naive("你好"), "好你");
+        assert_eq!(reverse_graphemes_naive("🦀 and 🐍"), "🐍 dna 🦀");
+        assert_eq!(reverse_graphemes_naive("héllo"), "olléh");
+    }
+
+    #[test]
+    fn reverse_moves_accents() {
+        // "éa" becomes "áe": the accent now comes after the "a", and goes on
+        // it instead. That's what "naive" is about.
+        assert_eq!(reverse_graphemes_naive("e\u{301}a"), "a\u{301}e");
+    }
+
+    #[test]
+    fn frequencies() {
+        let counts = char_frequencies("hello world");
+        assert_eq!(counts[&'l'], 3);
+        assert_eq!(counts[&'o'], 2);
+        assert_eq!(counts[&'h'], 1);
+        assert_eq!(counts.get(&' '), None);
+        assert_eq!(counts.len(), 7);
+        assert!(char_frequencies("").is_empty());
+        assert!(char_frequencies(" \t\r\n ").is_empty());
+    }
+
+    #[test]
+    fn frequencies_of_wide_characters() {
+        let counts = char_frequencies("🦀 🦀\n你好 你");
+        assert_eq!(counts[&'🦀'], 2);
+        assert_eq!(counts[&'你'], 2);
+        assert_eq!(counts[&'好'], 1);
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn frequencies_only_skip_ascii_whitespace() {
+        // An ideographic space isn't ASCII, so it's counted
+        let counts = char_frequencies("你\u{3000}好 ");
+        assert_eq!(counts[&'\u{3000}'], 1);
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn frequencies_of_accents() {
+        let counts = char_frequencies("école e\u{301}cole");
+        assert_eq!(counts[&'é'], 1);
+        assert_eq!(counts[&'e'], 3);
+        assert_eq!(counts[&'\u{301}'], 1);
+    }
+}
@@ -0,0 +1,163 @@
+// structs4.rs
+//
+// A builder makes a struct step by step, with a method for each field that
+// can be set. Some fields can't be left out, though, and a request without a
+// URL doesn't make sense. A plain builder would find out when `build` is
+// called, while the program runs. Here, the builder is generic over the
+// state it's in instead: `RequestBuilder<NoUrl>` doesn't have a `build`
+// method at all, only `RequestBuilder<HasUrl>` does, so forgetting the URL
+// is found by the compiler.
+//
+// `NoUrl` and `HasUrl` are never used as values, they have no fields and
+// take no memory. `PhantomData<State>` tells the compiler that the builder
+// uses its `State` parameter anyway.
+//
+// Execute `rustlings hint structs4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+// The states of the builder
+struct NoUrl;
+struct HasUrl;
+
+struct RequestBuilder<State> {
+    // Empty until `url` is called
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    state: PhantomData<State>,
+}
+
+impl RequestBuilder<NoUrl> {
+    fn new() -> Self {
+        RequestBuilder {
+            url: String::new(),
+            headers: Vec::new(),
+            timeout: None,
+            state: PhantomData,
+        }
+    }
+
+    // TODO: Set the URL, and return a builder in the `HasUrl` state, with
+    // everything else that was set so far.
+    fn url(self, url: &str) -> RequestBuilder<HasUrl> {
+        todo!()
+    }
+}
+
+// These can be called before and after the URL is set
+impl<State> RequestBuilder<State> {
+    // TODO: Add a header, after the ones that were added before.
+    fn header(mut self, name: &str, value: &str) -> Self {
+        todo!()
+    }
+
+    // TODO: Set the timeout. If it's set twice, the second one counts.
+    fn timeout(mut self, timeout: Duration) -> Self {
+        todo!()
+    }
+}
+
+// TODO: Build the request, with `DEFAULT_TIMEOUT` if no timeout was set. If
+// this works, the following shouldn't compile, try it:
+//
+//     let request = RequestBuilder::new().header("Accept", "*/*").build();
+//
+impl RequestBuilder<HasUrl> {
+    fn build(self) -> Request {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    #[test]
+    fn only_a_url() {
+        let request = RequestBuilder::new().url("https://rust-lang.org").build();
+        assert_eq!(
+            request,
+            Request {
+                url: String::from("https://rust-lang.org"),
+                headers: Vec::new(),
+                timeout: DEFAULT_TIMEOUT,
+            }
+        );
+    }
+
+    #[test]
+    fn headers_are_kept_in_order() {
+        let request = RequestBuilder::new()
+            .url("https://example.com")
+            .header("Accept", "text/html")
+            .header("User-Agent", "rustlings")
+            .header("Accept", "text/plain")
+            .build();
+        assert_eq!(
+            request.headers,
+            [
+                (String::from("Accept"), String::from("text/html")),
+                (String::from("User-Agent"), String::from("rustlings")),
+                (String::from("Accept"), String::from("text/plain")),
+            ]
+        );
+        assert_eq!(request.timeout, DEFAULT_TIMEOUT);
+    }
+
+    #[test]
+    fn timeouts() {
+        let request = RequestBuilder::new()
+            .url("https://example.com")
+            .timeout(Duration::from_secs(5))
+            .build();
+        assert_eq!(request.timeout, Duration::from_secs(5));
+        assert!(request.headers.is_empty());
+
+        let request = RequestBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .url("https://example.com")
+            .timeout(Duration::from_millis(250))
+            .build();
+        assert_eq!(request.timeout, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn options_before_the_url() {
+        let request = RequestBuilder::new()
+            .header("Accept", "*/*")
+            .timeout(Duration::from_secs(1))
+            .url("https://example.com/a")
+            .header("Connection", "close")
+            .build();
+        assert_eq!(request.url, "https://example.com/a");
+        assert_eq!(request.headers.len(), 2);
+        assert_eq!(request.headers[0].0, "Accept");
+        assert_eq!(request.headers[1].1, "close");
+        assert_eq!(request.timeout, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn states_take_no_memory() {
+        assert_eq!(size_of::<NoUrl>(), 0);
+        assert_eq!(size_of::<HasUrl>(), 0);
+        assert_eq!(
+            size_of::<RequestBuilder<NoUrl>>(),
+            size_of::<RequestBuilder<HasUrl>>()
+        );
+    }
+}
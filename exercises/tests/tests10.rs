@@ -0,0 +1,167 @@
+// tests10.rs
+//
+// A test with examples only checks the examples someone thought of. A
+// property test checks something that has to be true for every input, like
+// "decoding what was encoded gives back the input", on lots of random inputs.
+// When it finds an input that breaks the property, it tries smaller and
+// smaller versions of it, and reports the smallest one that still breaks it.
+//
+// The tests module has a tiny harness for property tests. Write the
+// properties in the tests with TODOs. One of the functions has a bug that the
+// example tests don't find, but one of your properties will: fix it too.
+//
+// Execute `rustlings hint tests10` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+/// Sorts the numbers, smallest first.
+pub fn sort(mut numbers: Vec<i32>) -> Vec<i32> {
+    // Insertion sort
+    for i in 1..numbers.len() {
+        let mut j = i;
+        while j > 0 && numbers[j - 1] > numbers[j] {
+            numbers.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+    numbers
+}
+
+/// Turns every run of the same byte into a (byte, length) pair. Runs that are
+/// longer than 255 are split into several pairs.
+pub fn encode(data: &[u8]) -> Vec<(u8, u8)> {
+    let mut pairs: Vec<(u8, u8)> = Vec::new();
+    for &byte in data {
+        match pairs.last_mut() {
+            Some((last, count)) if *last == byte => {
+                if *count == u8::MAX {
+                    pairs.push((byte, 0));
+                } else {
+                    *count += 1;
+                }
+            }
+            _ => pairs.push((byte, 1)),
+        }
+    }
+    pairs
+}
+
+/// Turns the pairs of `encode` back into the bytes.
+pub fn decode(pairs: &[(u8, u8)]) -> Vec<u8> {
+    pairs
+        .iter()
+        .flat_map(|&(byte, count)| std::iter::repeat(byte).take(usize::from(count)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+
+    // A random number generator (xorshift). It always starts from the same
+    // seed, so the tests try the same inputs every time.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    // Up to 40 numbers from -10 to 10, so that some of them are the same
+    fn numbers(rng: &mut Rng) -> Vec<i32> {
+        let len = rng.below(40);
+        (0..len).map(|_| rng.below(21) as i32 - 10).collect()
+    }
+
+    // A few runs of the same byte, some of them long
+    fn runs(rng: &mut Rng) -> Vec<u8> {
+        let mut data = Vec::new();
+        for _ in 0..rng.below(6) {
+            let byte = rng.below(3) as u8;
+            let len = if rng.below(4) == 0 { rng.below(1000) } else { rng.below(10) };
+            data.extend(std::iter::repeat(byte).take(len as usize));
+        }
+        data
+    }
+
+    // Smaller versions of the input: one half of it, or all but its first or
+    // its last item
+    fn shrink<T: Clone>(input: &[T]) -> Vec<Vec<T>> {
+        let mut smaller = Vec::new();
+        if input.len() >= 2 {
+            let half = input.len() / 2;
+            smaller.push(input[..half].to_vec());
+            smaller.push(input[half..].to_vec());
+        }
+        if !input.is_empty() {
+            smaller.push(input[1..].to_vec());
+            smaller.push(input[..input.len() - 1].to_vec());
+        }
+        smaller
+    }
+
+    // Checks that the property holds for 500 inputs made by `generate`. If it
+    // doesn't hold for one of them, panics with the smallest input it can
+    // find that it doesn't hold for.
+    fn check<T: Clone + Debug>(generate: fn(&mut Rng) -> Vec<T>, property: impl Fn(&[T]) -> bool) {
+        let mut rng = Rng(0x2545f4914f6cdd1d);
+        for _ in 0..500 {
+            let mut input = generate(&mut rng);
+            if property(&input) {
+                continue;
+            }
+            while let Some(smaller) = shrink(&input).into_iter().find(|smaller| !property(smaller)) {
+                input = smaller;
+            }
+            panic!("the property doesn't hold for {} items: {:?}", input.len(), input);
+        }
+    }
+
+    #[test]
+    fn sort_examples() {
+        assert_eq!(sort(vec![3, 1, 2]), [1, 2, 3]);
+        assert_eq!(sort(vec![]), []);
+        assert_eq!(sort(vec![5, -5, 5, 0]), [-5, 0, 5, 5]);
+    }
+
+    #[test]
+    fn encode_examples() {
+        assert_eq!(encode(b"aaab"), [(b'a', 3), (b'b', 1)]);
+        assert_eq!(encode(b"abba"), [(b'a', 1), (b'b', 2), (b'a', 1)]);
+        assert_eq!(encode(b""), []);
+        assert_eq!(decode(&[(b'x', 2), (b'y', 1)]), b"xxy");
+    }
+
+    #[test]
+    fn sorted_numbers_are_in_order() {
+        check(numbers, |numbers| {
+            let sorted = sort(numbers.to_vec());
+            // TODO: Is every number in `sorted` at most as big as the next one?
+            todo!()
+        });
+    }
+
+    #[test]
+    fn sorted_numbers_are_the_same_numbers() {
+        check(numbers, |numbers| {
+            let sorted = sort(numbers.to_vec());
+            // TODO: Is every number as often in `sorted` as in `numbers`, and
+            // nothing else in it?
+            todo!()
+        });
+    }
+
+    #[test]
+    fn decoding_gives_back_the_input() {
+        check(runs, |data| {
+            // TODO: Is decoding the encoded `data` the same as `data`?
+            todo!()
+        });
+    }
+}
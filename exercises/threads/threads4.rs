@@ -0,0 +1,112 @@
+// threads4.rs
+//
+// A pipeline: producer threads send work items over one channel, worker
+// threads take them from it, square them and send the squares over another
+// channel, and the thread that started it all collects the results.
+//
+// Receiving from a channel only stops once every `Sender` of it is gone, so
+// a `Sender` that's kept around by mistake makes the receiving end wait
+// forever. If that happens, rustlings stops the tests after a while and
+// reports them as timed out.
+//
+// Execute `rustlings hint threads4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+// Sends the items from id * count up to (id + 1) * count
+fn produce(id: u32, count: u32, work: Sender<u32>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        for item in id * count..(id + 1) * count {
+            work.send(item).unwrap();
+        }
+    })
+}
+
+// Squares the items of the work channel until there are none left, or until it
+// has handled `quota` of them. Returns how many it handled.
+fn work(work: Arc<Mutex<Receiver<u32>>>, results: Sender<u64>, quota: Option<usize>) -> JoinHandle<usize> {
+    thread::spawn(move || {
+        let mut handled = 0;
+        while quota.map_or(true, |quota| handled < quota) {
+            // The lock is only held while taking an item, not while working on it
+            let item = work.lock().unwrap().recv();
+            let Ok(item) = item else {
+                break;
+            };
+            results.send(u64::from(item) * u64::from(item)).unwrap();
+            handled += 1;
+        }
+        handled
+    })
+}
+
+// Runs `producers` producers of `count` items each, and a worker for every one
+// of the `quotas`
+fn pipeline(producers: u32, count: u32, quotas: &[Option<usize>]) -> Vec<u64> {
+    let (work_tx, work_rx) = mpsc::channel();
+    let work_rx = Arc::new(Mutex::new(work_rx));
+    let (results_tx, results_rx) = mpsc::channel();
+
+    // TODO: Every producer needs a Sender of its own.
+    let producers: Vec<JoinHandle<()>> = (0..producers)
+        .map(|id| produce(id, count, work_tx))
+        .collect();
+
+    // TODO: Start a worker for every quota.
+    let workers: Vec<JoinHandle<usize>> = Vec::new();
+
+    // TODO: This only stops receiving once every Sender of the results is
+    // gone, and the workers only stop once every Sender of the work is.
+    let results: Vec<u64> = results_rx.iter().collect();
+
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    let handled: usize = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+    assert_eq!(handled, results.len(), "results got lost on the way");
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut results: Vec<u64>) -> Vec<u64> {
+        results.sort_unstable();
+        results
+    }
+
+    fn squares(n: u64) -> Vec<u64> {
+        (0..n).map(|i| i * i).collect()
+    }
+
+    #[test]
+    fn one_of_each() {
+        assert_eq!(sorted(pipeline(1, 5, &[None])), [0, 1, 4, 9, 16]);
+    }
+
+    #[test]
+    fn every_item_comes_back_once() {
+        let results = pipeline(4, 250, &[None, None, None]);
+        assert_eq!(results.len(), 1000);
+        assert_eq!(sorted(results), squares(1000));
+    }
+
+    #[test]
+    fn no_producers_means_no_results() {
+        assert_eq!(pipeline(0, 10, &[None, None]), []);
+    }
+
+    #[test]
+    fn workers_that_finish_early_lose_nothing() {
+        // The first worker quits after one item and the last one doesn't take
+        // any, the one in the middle has to handle all the others
+        let results = pipeline(3, 100, &[Some(1), None, Some(0)]);
+        assert_eq!(sorted(results), squares(300));
+    }
+}
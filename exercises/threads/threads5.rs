@@ -0,0 +1,168 @@
+// threads5.rs
+//
+// Starting a thread for every little job is slow. A thread pool starts a few
+// threads once, and hands them jobs over a channel: whichever worker is free
+// takes the next one. When the pool is dropped, it tells the workers to stop,
+// once they've run the jobs that are already queued, and waits for them.
+//
+// The jobs are closures. Every closure has a type of its own, so to send
+// different ones over the same channel, they're boxed as `dyn FnOnce()`.
+//
+// Execute `rustlings hint threads5` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+// TODO: A message is either a job to run, or the news that there won't be any
+// more jobs, and the worker should stop.
+enum Message {}
+
+struct Worker {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(receiver: Arc<Mutex<Receiver<Message>>>) -> Worker {
+        let handle = thread::spawn(move || {
+            // TODO: Receive messages and run the jobs, until the worker is
+            // told to stop.
+        });
+        Worker {
+            handle: Some(handle),
+        }
+    }
+}
+
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: Sender<Message>,
+}
+
+impl ThreadPool {
+    /// Makes a pool of `size` threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0, as nothing would ever run the jobs.
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0, "a thread pool needs at least one thread");
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size).map(|_| Worker::new(Arc::clone(&receiver))).collect();
+        ThreadPool { workers, sender }
+    }
+
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        self.sender.send(Message::Job(Box::new(job))).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // The jobs that are queued come before these, so they're run first
+        for _ in &self.workers {
+            self.sender.send(Message::Terminate).unwrap();
+        }
+        for worker in &mut self.workers {
+            if let Some(handle) = worker.handle.take() {
+                handle.join().unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::time::Duration;
+
+    #[test]
+    fn runs_every_job() {
+        let pool = ThreadPool::new(4);
+        let done = Arc::new(AtomicUsize::new(0));
+        for _ in 0..50 {
+            let done = Arc::clone(&done);
+            pool.execute(move || {
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(done.load(Ordering::SeqCst), 50);
+    }
+
+    #[test]
+    fn dropping_waits_for_the_jobs() {
+        let pool = ThreadPool::new(2);
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&finished);
+        pool.execute(move || {
+            thread::sleep(Duration::from_millis(200));
+            flag.store(true, Ordering::SeqCst);
+        });
+        for _ in 0..5 {
+            pool.execute(|| thread::sleep(Duration::from_millis(20)));
+        }
+        drop(pool);
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn jobs_run_at_the_same_time() {
+        // Every job waits until all 3 have started, so this only finishes if
+        // 3 workers run them at once
+        let pool = ThreadPool::new(3);
+        let barrier = Arc::new(Barrier::new(3));
+        let done = Arc::new(AtomicUsize::new(0));
+        for _ in 0..3 {
+            let barrier = Arc::clone(&barrier);
+            let done = Arc::clone(&done);
+            pool.execute(move || {
+                barrier.wait();
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(done.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn jobs_send_results() {
+        let pool = ThreadPool::new(3);
+        let (tx, rx) = mpsc::channel();
+        for n in 1..=10u64 {
+            let tx = tx.clone();
+            pool.execute(move || tx.send(n * n).unwrap());
+        }
+        drop(tx);
+        let mut squares: Vec<u64> = rx.iter().collect();
+        squares.sort();
+        assert_eq!(squares, [1, 4, 9, 16, 25, 36, 49, 64, 81, 100]);
+    }
+
+    #[test]
+    fn one_thread() {
+        let pool = ThreadPool::new(1);
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for i in 0..5 {
+            let order = Arc::clone(&order);
+            pool.execute(move || order.lock().unwrap().push(i));
+        }
+        drop(pool);
+        // One worker runs them in the order they were queued
+        assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one thread")]
+    fn no_threads() {
+        ThreadPool::new(0);
+    }
+}
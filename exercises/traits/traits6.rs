@@ -0,0 +1,151 @@
+// traits6.rs
+//
+// Operators like `+`, `*` and `[]` are traits from `std::ops` too, and
+// implementing them for a type of our own makes it work with them. Here that
+// type is a matrix of `f64`s. The tests need these implementations:
+//
+// - `Index<(usize, usize)>` for `Matrix`, so that `m[(row, col)]` works
+// - `Add<&Matrix>` and `Add<Matrix>` for `&Matrix`, adding element by element
+// - `Mul<f64>` for `&Matrix`, multiplying every element
+// - `Mul<&Matrix>` for `&Matrix`, the matrix product
+// - `AddAssign<&Matrix>` for `Matrix`, so that `m += &other` works
+//
+// Implementing them for references means the tests never have to clone a
+// matrix to use it in an expression: `&a + &b` leaves `a` and `b` as they are.
+//
+// Adding matrices of different sizes, multiplying matrices whose sizes don't
+// fit together and indexing outside of a matrix are bugs in the program, so
+// they panic, like indexing outside of a `Vec` does. The `should_panic` tests
+// show the messages they panic with.
+//
+// Execute `rustlings hint traits6` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::ops::{Add, AddAssign, Index, Mul};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Matrix {
+    rows: usize,
+    cols: usize,
+    // The elements row by row, row r starts at data[r * cols]
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    fn new(rows: usize, cols: usize, data: Vec<f64>) -> Self {
+        assert_eq!(data.len(), rows * cols, "a {}x{} matrix has {} elements", rows, cols, rows * cols);
+        Matrix { rows, cols, data }
+    }
+
+    fn identity(n: usize) -> Self {
+        let mut data = vec![0.0; n * n];
+        for i in 0..n {
+            data[i * n + i] = 1.0;
+        }
+        Matrix::new(n, n, data)
+    }
+}
+
+// TODO: Implement the traits listed above.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a() -> Matrix {
+        Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0])
+    }
+
+    fn b() -> Matrix {
+        Matrix::new(2, 2, vec![0.5, 0.0, -1.0, 2.0])
+    }
+
+    #[test]
+    fn indexing() {
+        let m = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(m[(0, 0)], 1.0);
+        assert_eq!(m[(0, 2)], 3.0);
+        assert_eq!(m[(1, 0)], 4.0);
+        assert_eq!(m[(1, 2)], 6.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "(2, 0) is outside of a 2x3 matrix")]
+    fn indexing_past_the_last_row() {
+        let m = Matrix::new(2, 3, vec![0.0; 6]);
+        let _ = m[(2, 0)];
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 3) is outside of a 2x3 matrix")]
+    fn indexing_past_the_last_column() {
+        // (0, 3) would be the element at (1, 0) if only the data was checked
+        let m = Matrix::new(2, 3, vec![0.0; 6]);
+        let _ = m[(0, 3)];
+    }
+
+    #[test]
+    fn adding() {
+        let (a, b) = (a(), b());
+        assert_eq!(&a + &b, Matrix::new(2, 2, vec![1.5, 2.0, 2.0, 6.0]));
+        // Both are still there
+        assert_eq!(&b + &a, &a + &b);
+    }
+
+    #[test]
+    fn scaling() {
+        assert_eq!(&a() * 2.0, Matrix::new(2, 2, vec![2.0, 4.0, 6.0, 8.0]));
+        assert_eq!(&a() * 0.0, Matrix::new(2, 2, vec![0.0; 4]));
+    }
+
+    #[test]
+    fn chained_expressions() {
+        let (a, b) = (a(), b());
+        // `*` goes first: this is &a + (&b * 2.0)
+        assert_eq!(&a + &b * 2.0, Matrix::new(2, 2, vec![2.0, 2.0, 1.0, 8.0]));
+        assert_eq!(&(&a + &b) * 2.0, &(&a * 2.0) + &b * 2.0);
+    }
+
+    #[test]
+    fn multiplying() {
+        let (a, b) = (a(), b());
+        assert_eq!(&a * &b, Matrix::new(2, 2, vec![-1.5, 4.0, -2.5, 8.0]));
+        assert_eq!(&b * &a, Matrix::new(2, 2, vec![0.5, 1.0, 5.0, 6.0]));
+        assert_eq!(&a * &Matrix::identity(2), a);
+    }
+
+    #[test]
+    fn multiplying_different_shapes() {
+        let row = Matrix::new(1, 3, vec![1.0, 2.0, 3.0]);
+        let column = Matrix::new(3, 1, vec![4.0, 5.0, 6.0]);
+        assert_eq!(&row * &column, Matrix::new(1, 1, vec![32.0]));
+        let outer = &column * &row;
+        assert_eq!((outer.rows, outer.cols), (3, 3));
+        assert_eq!(outer[(2, 1)], 12.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't multiply a 2x3 matrix by a 2x3 matrix")]
+    fn multiplying_sizes_that_dont_fit() {
+        let m = Matrix::new(2, 3, vec![0.0; 6]);
+        let _ = &m * &m;
+    }
+
+    #[test]
+    #[should_panic(expected = "can't add a 1x2 matrix to a 2x1 matrix")]
+    fn adding_different_sizes() {
+        let _ = &Matrix::new(2, 1, vec![0.0; 2]) + &Matrix::new(1, 2, vec![0.0; 2]);
+    }
+
+    #[test]
+    fn add_assign() {
+        let mut sum = Matrix::new(2, 2, vec![0.0; 4]);
+        let (a, b) = (a(), b());
+        sum += &a;
+        sum += &b;
+        sum += &a;
+        assert_eq!(sum, Matrix::new(2, 2, vec![2.5, 4.0, 5.0, 10.0]));
+    }
+}
@@ -0,0 +1,182 @@
+// traits7.rs
+//
+// A `Vec` holds values of one type only. To keep circles and rectangles in
+// the same `Vec`, they can be boxed as `Box<dyn Shape>`: a pointer to some
+// value that implements `Shape`, together with a table of its methods. Which
+// method is called is then decided while the program runs, instead of when
+// it's compiled, which is called dynamic dispatch.
+//
+// Not every trait can be used with `dyn`. The table needs an entry for every
+// method, so a trait with a generic method, which would need one for every
+// type it's called with, can't be.
+//
+// Execute `rustlings hint traits7` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+use std::any::Any;
+use std::f64::consts::PI;
+
+// TODO: `Vec<Box<dyn Shape>>` doesn't compile, because of `bigger_than`. Fix
+// it, so that it still works for every pair of shapes in the tests.
+trait Shape {
+    fn area(&self) -> f64;
+    fn name(&self) -> &str;
+
+    fn bigger_than<S: Shape>(&self, other: &S) -> bool {
+        self.area() > other.area()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Circle {
+    radius: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Square {
+    side: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn name(&self) -> &str {
+        "circle"
+    }
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    fn name(&self) -> &str {
+        "rectangle"
+    }
+}
+
+// TODO: Implement `Shape` for `Square`.
+
+// TODO: Add up the areas of all the shapes.
+fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    todo!()
+}
+
+// TODO: Describe the shape like "circle with an area of 3.14", with two
+// decimals.
+fn describe(shape: &dyn Shape) -> String {
+    todo!()
+}
+
+// The same with static dispatch: the compiler makes a copy of this function
+// for every type it's called with
+fn describe_static<S: Shape>(shape: &S) -> String {
+    format!("{} with an area of {:.2}", shape.name(), shape.area())
+}
+
+// TODO: `Any` is a trait for values whose type can be checked while the
+// program runs. Return the circles among the values, and leave out the rest,
+// with `Box<dyn Any>::downcast`.
+fn circles(values: Vec<Box<dyn Any>>) -> Vec<Circle> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shapes() -> Vec<Box<dyn Shape>> {
+        vec![
+            Box::new(Circle { radius: 1.0 }),
+            Box::new(Rectangle { width: 2.0, height: 3.0 }),
+            Box::new(Square { side: 2.0 }),
+        ]
+    }
+
+    #[test]
+    fn names() {
+        let shapes = shapes();
+        // Each name comes from the method of the type in the box
+        let names: Vec<&str> = shapes.iter().map(|shape| shape.name()).collect();
+        assert_eq!(names, ["circle", "rectangle", "square"]);
+    }
+
+    #[test]
+    fn areas() {
+        let areas: Vec<f64> = shapes().iter().map(|shape| shape.area()).collect();
+        assert_eq!(areas, [PI, 6.0, 4.0]);
+        assert_eq!(total_area(&shapes()), PI + 10.0);
+        assert_eq!(total_area(&[]), 0.0);
+    }
+
+    #[test]
+    fn dynamic_and_static_dispatch_agree() {
+        let circle = Circle { radius: 2.0 };
+        let square = Square { side: 1.5 };
+        assert_eq!(describe(&circle), describe_static(&circle));
+        assert_eq!(describe(&square), describe_static(&square));
+        assert_eq!(describe(&circle), "circle with an area of 12.57");
+        assert_eq!(describe(&square), "square with an area of 2.25");
+
+        let static_total = circle.area() + square.area();
+        let boxed: Vec<Box<dyn Shape>> = vec![Box::new(circle), Box::new(square)];
+        assert_eq!(total_area(&boxed), static_total);
+    }
+
+    #[test]
+    fn describe_boxes() {
+        let descriptions: Vec<String> = shapes().iter().map(|shape| describe(shape.as_ref())).collect();
+        assert_eq!(
+            descriptions,
+            [
+                "circle with an area of 3.14",
+                "rectangle with an area of 6.00",
+                "square with an area of 4.00",
+            ]
+        );
+    }
+
+    #[test]
+    fn comparing_shapes() {
+        let circle = Circle { radius: 1.0 };
+        let square = Square { side: 2.0 };
+        let rectangle = Rectangle { width: 1.0, height: 3.0 };
+        assert!(square.bigger_than(&circle));
+        assert!(circle.bigger_than(&rectangle));
+        assert!(!rectangle.bigger_than(&square));
+        assert!(!square.bigger_than(&Square { side: 2.0 }));
+    }
+
+    #[test]
+    fn downcasting() {
+        let values: Vec<Box<dyn Any>> = vec![
+            Box::new(Circle { radius: 1.0 }),
+            Box::new(String::from("not a shape")),
+            Box::new(Square { side: 1.0 }),
+            Box::new(Circle { radius: 0.5 }),
+            Box::new(42),
+        ];
+        assert_eq!(circles(values), [Circle { radius: 1.0 }, Circle { radius: 0.5 }]);
+        assert_eq!(circles(Vec::new()), []);
+    }
+
+    #[test]
+    fn downcasting_references() {
+        let value: Box<dyn Any> = Box::new(Rectangle { width: 4.0, height: 0.5 });
+        // A reference can be downcast too, and stays where it is
+        let rectangle = value.downcast_ref::<Rectangle>().unwrap();
+        assert_eq!(rectangle.area(), 2.0);
+        assert!(value.downcast_ref::<Circle>().is_none());
+        assert!(value.is::<Rectangle>());
+    }
+}
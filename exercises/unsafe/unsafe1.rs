@@ -0,0 +1,92 @@
+// unsafe1.rs
+//
+// Raw pointers, `*const T` and `*mut T`, are references without the rules:
+// they may dangle, be null, or point at the same value as a `&mut`. Making one
+// is safe, but reading or writing through it is only allowed in an `unsafe`
+// block, where it's up to us to make sure that it points at a live value of
+// the right type. Every `unsafe` block gets a `// SAFETY:` comment saying
+// why that's the case.
+//
+// `#![deny(unsafe_op_in_unsafe_fn)]` makes that true inside of `unsafe fn`s,
+// too: their bodies need `unsafe` blocks of their own.
+//
+// Execute `rustlings hint unsafe1` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+/// Reads the element `i` places after `ptr`.
+///
+/// # Safety
+///
+/// `ptr` and the `i` elements after it have to be part of the same live
+/// slice or array.
+unsafe fn read_at(ptr: *const i32, i: usize) -> i32 {
+    // TODO: Read the element, in an `unsafe` block with a `// SAFETY:` comment.
+    todo!()
+}
+
+// TODO: Add up the elements by calling `read_at` with `values.as_ptr()`.
+fn sum(values: &[i32]) -> i32 {
+    todo!()
+}
+
+// TODO: Return the last element, read from a pointer to it, or `None` if
+// there is none.
+fn last(values: &[i32]) -> Option<i32> {
+    todo!()
+}
+
+// TODO: Double every element, by writing through `values.as_mut_ptr()`.
+fn double_all(values: &mut [i32]) {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_through_a_pointer() {
+        let values = [7, 8, 9];
+        // SAFETY: 0 and 2 are inside of `values`.
+        unsafe {
+            assert_eq!(read_at(values.as_ptr(), 0), 7);
+            assert_eq!(read_at(values.as_ptr(), 2), 9);
+        }
+    }
+
+    #[test]
+    fn sums() {
+        assert_eq!(sum(&[1, 2, 3, 4]), 10);
+        assert_eq!(sum(&[-5]), -5);
+        assert_eq!(sum(&[]), 0);
+    }
+
+    #[test]
+    fn last_elements() {
+        assert_eq!(last(&[1, 2, 3]), Some(3));
+        assert_eq!(last(&[42]), Some(42));
+        assert_eq!(last(&[]), None);
+    }
+
+    #[test]
+    fn doubles_in_place() {
+        let mut values = vec![1, -2, 3];
+        double_all(&mut values);
+        assert_eq!(values, [2, -4, 6]);
+        let mut nothing: [i32; 0] = [];
+        double_all(&mut nothing);
+    }
+
+    #[test]
+    fn works_on_part_of_a_slice() {
+        let mut values = [1, 2, 3, 4, 5];
+        double_all(&mut values[1..4]);
+        assert_eq!(values, [1, 4, 6, 8, 5]);
+        assert_eq!(sum(&values[3..]), 13);
+        assert_eq!(last(&values[..1]), Some(1));
+    }
+}
@@ -0,0 +1,100 @@
+// unsafe2.rs
+//
+// Some things the borrow checker can't prove safe are safe all the same.
+// Borrowing two halves of a slice mutably at once is one of them: they never
+// overlap, but to the compiler they're both borrowed from the same slice.
+// Written by hand, it takes `unsafe` code inside of a safe function, which has
+// to make sure that no caller can make the unsafe part misbehave, whatever
+// arguments they pass.
+//
+// The standard library has this as `<[T]>::split_at_mut`. Write it yourself
+// here, without calling it.
+//
+// Execute `rustlings hint unsafe2` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use std::slice;
+
+// Returns the elements before `mid` and the ones from `mid` on. Panics if
+// `mid` is larger than the length of the slice.
+//
+// TODO: Make both halves with `slice::from_raw_parts_mut`, starting at
+// `slice.as_mut_ptr()`.
+fn split_at_mut<T>(slice: &mut [T], mid: usize) -> (&mut [T], &mut [T]) {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_in_the_middle() {
+        let mut values = [1, 2, 3, 4, 5];
+        let (left, right) = split_at_mut(&mut values, 2);
+        assert_eq!(left, [1, 2]);
+        assert_eq!(right, [3, 4, 5]);
+    }
+
+    #[test]
+    fn both_halves_can_be_changed_at_once() {
+        let mut values = [1, 2, 3, 4];
+        let (left, right) = split_at_mut(&mut values, 2);
+        left[0] = 10;
+        right[1] = 40;
+        std::mem::swap(&mut left[1], &mut right[0]);
+        assert_eq!(values, [10, 3, 2, 40]);
+    }
+
+    #[test]
+    fn split_at_the_start() {
+        let mut values = [1, 2, 3];
+        let (left, right) = split_at_mut(&mut values, 0);
+        assert!(left.is_empty());
+        assert_eq!(right, [1, 2, 3]);
+    }
+
+    #[test]
+    fn split_at_the_end() {
+        let mut values = [1, 2, 3];
+        let (left, right) = split_at_mut(&mut values, 3);
+        assert_eq!(left, [1, 2, 3]);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn split_an_empty_slice() {
+        let (left, right) = split_at_mut::<u8>(&mut [], 0);
+        assert!(left.is_empty() && right.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_past_the_end() {
+        split_at_mut(&mut [1, 2, 3], 4);
+    }
+
+    #[test]
+    fn works_for_any_type() {
+        let mut words = vec![String::from("unsafe"), String::from("but"), String::from("sound")];
+        let (left, right) = split_at_mut(&mut words, 1);
+        left[0].push_str(" code");
+        right[1].make_ascii_uppercase();
+        assert_eq!(words, ["unsafe code", "but", "SOUND"]);
+    }
+
+    #[test]
+    fn agrees_with_std() {
+        let mut ours: Vec<u32> = (0..10).collect();
+        let mut theirs = ours.clone();
+        for mid in 0..=10 {
+            let (a, b) = split_at_mut(&mut ours, mid);
+            let (c, d) = theirs.split_at_mut(mid);
+            assert_eq!((&*a, &*b), (&*c, &*d), "split at {}", mid);
+        }
+    }
+}
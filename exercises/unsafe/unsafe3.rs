@@ -0,0 +1,73 @@
+// unsafe3.rs
+//
+// Rust can call functions written in other languages, such as the ones of the
+// C standard library, which every Rust program on Linux or macOS is linked to.
+// An `extern "C"` block declares them. The compiler can't check anything the
+// foreign code does, so calling it is unsafe. A safe wrapper around such a
+// call has to make sure that the call is fine for any argument the wrapper
+// accepts, by going through what the C function expects of its arguments.
+//
+// C's `abs` is undefined for the smallest `int`, whose absolute value doesn't
+// fit in an `int`. `strlen` counts the bytes up to the first nul byte, and
+// reads past the end of the string if there is none.
+//
+// Execute `rustlings hint unsafe3` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use std::ffi::{c_char, c_int, CStr};
+
+extern "C" {
+    fn abs(input: c_int) -> c_int;
+    fn strlen(s: *const c_char) -> usize;
+}
+
+// TODO: Return the absolute value of `x` from C's `abs`, or `None` if it
+// doesn't have one that fits in an `i32`.
+fn c_abs(x: i32) -> Option<i32> {
+    todo!()
+}
+
+// TODO: Return the length of `s` from C's `strlen`. Why is `&CStr` the right
+// type to accept here, and `&str` wouldn't be?
+fn c_strlen(s: &CStr) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn absolute_values() {
+        assert_eq!(c_abs(-5), Some(5));
+        assert_eq!(c_abs(5), Some(5));
+        assert_eq!(c_abs(0), Some(0));
+    }
+
+    #[test]
+    fn absolute_values_at_the_edges() {
+        assert_eq!(c_abs(i32::MAX), Some(i32::MAX));
+        assert_eq!(c_abs(-i32::MAX), Some(i32::MAX));
+        assert_eq!(c_abs(i32::MIN), None);
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(c_strlen(&CString::new("hello").unwrap()), 5);
+        assert_eq!(c_strlen(&CString::new("").unwrap()), 0);
+        // bytes, not chars
+        assert_eq!(c_strlen(&CString::new("日本").unwrap()), 6);
+    }
+
+    #[test]
+    fn stops_at_the_nul() {
+        let s = CStr::from_bytes_until_nul(b"abc\0def\0").unwrap();
+        assert_eq!(c_strlen(s), 3);
+        assert_eq!(c_strlen(s), s.to_bytes().len());
+    }
+}
@@ -0,0 +1,117 @@
+// unsafe4.rs
+//
+// Functions can go the other way, too: with `extern "C"`, a Rust function can
+// be called from C, and `#[no_mangle]` keeps its name as it is, so that C
+// code can find it. The structs they pass around have to be laid out the way
+// C lays them out, and that's what `#[repr(C)]` does. Without it, Rust is free
+// to reorder fields, and does.
+//
+// C code can pass any pointer at all, including a null one, so the function
+// has to deal with null pointers, and is `unsafe` for all the others: it can't
+// check them, its callers have to promise that they're fine.
+//
+// Execute `rustlings hint unsafe4` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+// TODO: Make both structs use the layout of C.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Labeled {
+    pub label: u8,
+    pub point: Point,
+}
+
+// TODO: Declare C's `hypot`, which takes two `f64`s and returns the length of
+// the hypotenuse, without overflowing where squaring them would.
+
+/// Returns how far `*p` is from the origin, or NaN if `p` is null.
+///
+/// # Safety
+///
+/// `p` has to be null, or point at a live `Point`.
+//
+// TODO: Return the distance, with `hypot`. A panic can't unwind out of an
+// `extern "C"` function, and aborts the whole program instead, so this one
+// returns a wrong answer rather than calling `todo!()`.
+#[no_mangle]
+pub unsafe extern "C" fn point_norm(p: *const Point) -> f64 {
+    0.0
+}
+
+// TODO: Call `point_norm` for Rust code, which can't pass it a bad pointer.
+pub fn norm(p: &Point) -> f64 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, offset_of, size_of};
+    use std::ptr;
+
+    #[test]
+    fn point_layout() {
+        assert_eq!(size_of::<Point>(), 16);
+        assert_eq!(align_of::<Point>(), 8);
+        assert_eq!(offset_of!(Point, x), 0);
+        assert_eq!(offset_of!(Point, y), 8);
+    }
+
+    #[test]
+    fn labeled_layout() {
+        // C keeps the fields in order, with padding after the label, so that
+        // the point starts at a multiple of 8
+        assert_eq!(offset_of!(Labeled, label), 0);
+        assert_eq!(offset_of!(Labeled, point), 8);
+        assert_eq!(size_of::<Labeled>(), 24);
+    }
+
+    #[test]
+    fn called_through_a_pointer() {
+        let p = Point { x: 3.0, y: 4.0 };
+        // SAFETY: The pointer comes from a reference to a live Point.
+        let distance = unsafe { point_norm(&p as *const Point) };
+        assert_eq!(distance, 5.0);
+    }
+
+    #[test]
+    fn called_as_a_c_function() {
+        let f: unsafe extern "C" fn(*const Point) -> f64 = point_norm;
+        let points = [Point { x: 0.0, y: 0.0 }, Point { x: -6.0, y: 8.0 }];
+        // SAFETY: Both pointers point into `points`.
+        unsafe {
+            assert_eq!(f(points.as_ptr()), 0.0);
+            assert_eq!(f(points.as_ptr().add(1)), 10.0);
+        }
+    }
+
+    #[test]
+    fn null_pointers_give_nan() {
+        // SAFETY: Null pointers are allowed.
+        assert!(unsafe { point_norm(ptr::null()) }.is_nan());
+    }
+
+    #[test]
+    fn safe_wrapper() {
+        assert_eq!(norm(&Point { x: -5.0, y: 12.0 }), 13.0);
+        let labeled = Labeled { label: b'a', point: Point { x: 8.0, y: 15.0 } };
+        assert_eq!(norm(&labeled.point), 17.0);
+    }
+
+    #[test]
+    fn huge_points() {
+        // Squaring 1e200 overflows to infinity, hypot doesn't square it
+        let distance = norm(&Point { x: 1e200, y: 1e200 });
+        assert!(distance.is_finite());
+        assert!((distance / 1e200 - std::f64::consts::SQRT_2).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::exercise::Exercise;
+use crate::progress::PROGRESS_FILE;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// Run git in `dir`, returning its output if it exited successfully
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("git could not be run: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Commit `files`, relative to `dir`, and nothing else, as `message`.
+// Refuses to when `dir` isn't in a git repository, or when anything
+// but `files` is staged already, as committing would sweep that in or
+// leave it out without the user knowing.
+fn commit_in(dir: &Path, files: &[PathBuf], message: &str) -> Result<(), String> {
+    git(dir, &["rev-parse", "--is-inside-work-tree"]).map_err(|_| "this isn't a git repository".to_string())?;
+    let prefix = git(dir, &["rev-parse", "--show-prefix"])?;
+    let ours: Vec<String> = files
+        .iter()
+        .map(|file| format!("{}{}", prefix.trim(), file.display()))
+        .collect();
+    let staged = git(dir, &["diff", "--cached", "--name-only"])?;
+    let others: Vec<&str> = staged.lines().filter(|file| !ours.iter().any(|ours| ours == file)).collect();
+    if !others.is_empty() {
+        return Err(format!("other changes are staged already: {}", others.join(", ")));
+    }
+
+    let paths: Vec<&str> = files.iter().filter_map(|file| file.to_str()).collect();
+    let mut add = vec!["add", "--"];
+    add.extend(&paths);
+    git(dir, &add)?;
+    let mut commit = vec!["commit", "--quiet", "-m", message, "--"];
+    commit.extend(&paths);
+    git(dir, &commit)?;
+    Ok(())
+}
+
+/// Commit the files of an exercise that was just completed, and the
+/// progress file if git doesn't ignore it, as `rustlings: complete <name>`.
+/// Returns why nothing was committed otherwise.
+pub fn commit(exercise: &Exercise) -> Result<(), String> {
+    let dir = Path::new(".");
+    let mut files: Vec<PathBuf> = exercise.files().map(Path::to_path_buf).collect();
+    let state_ignored = git(dir, &["check-ignore", "--quiet", PROGRESS_FILE]).is_ok();
+    if Path::new(PROGRESS_FILE).exists() && !state_ignored {
+        files.push(PathBuf::from(PROGRESS_FILE));
+    }
+    commit_in(dir, &files, &format!("rustlings: complete {}", exercise.name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn commits_only_the_exercise() {
+        let dir = std::env::temp_dir().join(format!("rustlings_autocommit_{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("exercises")).unwrap();
+        let files = [PathBuf::from("exercises/intro2.rs")];
+        assert_eq!(commit_in(&dir, &files, "first"), Err("this isn't a git repository".to_string()));
+
+        for args in [&["init", "--quiet"][..], &["config", "user.name", "rustlings"], &["config", "user.email", "r@example.com"]] {
+            git(&dir, args).unwrap();
+        }
+        fs::write(dir.join("exercises/intro2.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("notes.txt"), "mine\n").unwrap();
+        git(&dir, &["add", "notes.txt"]).unwrap();
+        let error = commit_in(&dir, &files, "rustlings: complete intro2").unwrap_err();
+        assert_eq!(error, "other changes are staged already: notes.txt");
+
+        git(&dir, &["rm", "--cached", "--quiet", "notes.txt"]).unwrap();
+        commit_in(&dir, &files, "rustlings: complete intro2").unwrap();
+        let committed = git(&dir, &["show", "--name-only", "--format=%s"]).unwrap();
+        let status = git(&dir, &["status", "--porcelain"]).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(committed.trim(), "rustlings: complete intro2\n\nexercises/intro2.rs");
+        assert_eq!(status, "?? notes.txt\n");
+    }
+}
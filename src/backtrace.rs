@@ -0,0 +1,152 @@
+// The backtraces of panicking exercises, trimmed down to the frames of the
+// exercise itself unless `--full-backtrace` asks for all of them
+
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static FULL: AtomicBool = AtomicBool::new(false);
+
+// The frames of these crates are left out, as nothing in them is where
+// the exercise went wrong
+const LIBRARY_PREFIXES: &[&str] = &["std::", "core::", "alloc::", "test::", "__rustc::", "__rust_", "rust_begin_unwind"];
+
+/// Show backtraces as they are from now on
+pub fn set_full(full: bool) {
+    FULL.store(full, Ordering::Relaxed);
+}
+
+/// What RUST_BACKTRACE is set to for the exercises that run, unless the
+/// user set it already, in which case it is left as it is
+pub fn rust_backtrace() -> Option<&'static str> {
+    if env::var_os("RUST_BACKTRACE").is_some() {
+        None
+    } else if FULL.load(Ordering::Relaxed) {
+        Some("full")
+    } else {
+        Some("1")
+    }
+}
+
+/// Whether backtraces in the output of the exercises are to be condensed,
+/// which is only done to those rustlings asked for itself
+pub fn condensing() -> bool {
+    rust_backtrace() == Some("1")
+}
+
+// A frame of the standard library, the test harness or the glue between
+// them, such as `<alloc::vec::Vec<T> as core::ops::index::Index<I>>::index`
+// or `<fn() as core::ops::function::FnOnce<()>>::call_once`
+fn is_library(symbol: &str) -> bool {
+    let symbol = match symbol.strip_prefix('<') {
+        // The type the trait is implemented for: a primitive, a function
+        // or something in a library
+        Some(implementation) => {
+            let Some((ty, _)) = implementation.split_once(" as ") else { return false };
+            let ty = ["&mut ", "&", "*const ", "*mut ", "dyn "]
+                .iter()
+                .fold(ty, |ty, pointer| ty.strip_prefix(pointer).unwrap_or(ty));
+            if !ty.contains("::") || ty.starts_with(['[', '(']) || ty.starts_with("fn(") {
+                return true;
+            }
+            ty
+        }
+        None => symbol,
+    };
+    LIBRARY_PREFIXES.iter().any(|prefix| symbol.starts_with(prefix))
+}
+
+/// `output` with the frames of the standard library and the test harness
+/// left out of every backtrace in it, so that the first frame shown is
+/// the function of the exercise that panicked
+pub fn condense(output: &str) -> String {
+    let mut condensed = String::new();
+    let mut lines = output.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.trim_end() != "stack backtrace:" {
+            condensed.push_str(line);
+            condensed.push('\n');
+            continue;
+        }
+        condensed.push_str("stack backtrace (without the frames of std and the test harness, --full-backtrace shows them):\n");
+        let mut previous: Option<&str> = None;
+        while let Some(frame) = lines.next_if(|line| is_frame(line)) {
+            let symbol = frame.split_once(':').map_or("", |(_, symbol)| symbol.trim());
+            // The closure the test harness calls a test through
+            let wrapper = symbol.strip_suffix("::{{closure}}").is_some_and(|inner| previous == Some(inner));
+            let keep = !is_library(symbol) && !wrapper;
+            if keep {
+                condensed.push_str(frame);
+                condensed.push('\n');
+            }
+            while let Some(at) = lines.next_if(|line| line.trim_start().starts_with("at ")) {
+                if keep {
+                    condensed.push_str(at);
+                    condensed.push('\n');
+                }
+            }
+            previous = Some(symbol);
+        }
+        // It suggests RUST_BACKTRACE=full, which rustlings decides on
+        lines.next_if(|line| line.starts_with("note: Some details are omitted"));
+    }
+    if !output.ends_with('\n') {
+        condensed.pop();
+    }
+    condensed
+}
+
+// A line such as `  12: options1::maybe_icecream`
+fn is_frame(line: &str) -> bool {
+    line.trim_start()
+        .split_once(": ")
+        .is_some_and(|(number, _)| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PANIC: &str = "---- tests::check stdout ----
+
+thread 'tests::check' (23757) panicked at exercises/options/options1.rs:1:37:
+called `Option::unwrap()` on a `None` value
+stack backtrace:
+   0: __rustc::rust_begin_unwind
+             at /rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/std/src/panicking.rs:689:5
+   1: core::panicking::panic_fmt
+             at /rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/core/src/panicking.rs:80:14
+   2: core::option::unwrap_failed
+   3: <usize as core::slice::index::SliceIndex<[T]>>::index
+   4: <options1::Shelf as core::ops::index::Index<usize>>::index
+   5: options1::maybe_icecream::{{closure}}
+   6: options1::maybe_icecream
+   7: options1::tests::check
+   8: options1::tests::check::{{closure}}
+   9: core::ops::function::FnOnce::call_once
+  10: <fn() -> core::result::Result<(), alloc::string::String> as core::ops::function::FnOnce<()>>::call_once
+             at /rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/core/src/ops/function.rs:250:5
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.
+
+failures:
+";
+
+    #[test]
+    fn only_the_frames_of_the_exercise_are_left() {
+        let condensed = condense(PANIC);
+        let frames: Vec<&str> = condensed.lines().filter(|line| is_frame(line)).map(str::trim).collect();
+        assert_eq!(
+            frames,
+            [
+                "4: <options1::Shelf as core::ops::index::Index<usize>>::index",
+                "5: options1::maybe_icecream::{{closure}}",
+                "6: options1::maybe_icecream",
+                "7: options1::tests::check",
+            ]
+        );
+        assert!(!condensed.contains("/rustc/"));
+        assert!(!condensed.contains("RUST_BACKTRACE=full"));
+        assert!(condensed.contains("called `Option::unwrap()` on a `None` value\nstack backtrace (without"));
+        assert!(condensed.ends_with("\nfailures:\n"));
+        assert_eq!(condense("no panic here"), "no panic here");
+    }
+}
@@ -0,0 +1,327 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+// The shells `rustlings completions` can write a script for
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            "powershell" => Ok(Shell::Powershell),
+            _ => Err(format!(
+                "unknown shell `{s}`, expected `bash`, `zsh`, `fish` or `powershell`"
+            )),
+        }
+    }
+}
+
+/// The options of rustlings itself, before the subcommand
+pub const GLOBAL_OPTIONS: &[&str] = &["--nocapture", "--version", "--timeout", "--color", "--no-color", "--lang", "--temp-dir", "--exercise-dir", "--context", "--output-limit", "--full-backtrace", "--help"];
+
+/// Every subcommand with its description and long options.
+/// Keep this in sync with the argh definitions in main.rs.
+pub const COMMANDS: &[(&str, &str, &[&str])] = &[
+    (
+        "verify",
+        "Verifies all exercises according to the recommended order",
+        &[
+            "--jobs",
+            "--force-recheck",
+            "--from",
+            "--only",
+            "--tag",
+            "--ci",
+            "--quiet",
+            "--interactive",
+            "--keep-going",
+            "--help",
+        ],
+    ),
+    (
+        "watch",
+        "Reruns `verify` when files were edited",
+        &["--success-hints", "--force-recheck", "--notify", "--no-clear", "--strict-order", "--git-autocommit", "--help"],
+    ),
+    ("run", "Runs/Tests a single exercise", &["--nocapture", "--release", "--help"]),
+    ("edit", "Opens an exercise in $VISUAL or $EDITOR", &["--help"]),
+    ("reset", "Restores exercises to their original state", &["--all", "--force", "--help"]),
+    ("hint", "Returns a hint for the given exercise", &["--all", "--help"]),
+    (
+        "list",
+        "Lists the exercises available in Rustlings",
+        &[
+            "--paths",
+            "--names",
+            "--filter",
+            "--unsolved",
+            "--solved",
+            "--topic",
+            "--sort",
+            "--json",
+            "--tag",
+            "--max-difficulty",
+            "--prerequisites",
+            "--points",
+            "--interactive",
+            "--help",
+        ],
+    ),
+    ("lsp", "Enable rust-analyzer for exercises", &["--help"]),
+    ("solution", "Shows the reference solution of the given exercise", &["--diff", "--help"]),
+    ("score", "Verifies every exercise and reports the result as JSON", &["--output", "--help"]),
+    ("report", "Writes the recorded progress as a Markdown or HTML report", &["--format", "--output", "--verify", "--help"]),
+    ("quiz", "Asks for solved exercises again, in a random order", &["--count", "--topic", "--seed", "--help"]),
+    ("stats", "Shows which exercises took the most attempts", &["--help"]),
+    ("clean", "Removes compiled exercises left behind by interrupted runs", &["--progress", "--help"]),
+    ("dev", "Tools for the authors of exercises", &["--help"]),
+    ("cicvverify", "cicvverify", &["--help"]),
+    ("completions", "Prints a shell completion script", &["--help"]),
+];
+
+// The options of rustlings itself that take a value
+const VALUE_OPTIONS: &[&str] = &["--timeout", "--color", "--lang", "--temp-dir", "--exercise-dir", "--context", "--output-limit"];
+
+// The subcommands whose positional argument is the name of an exercise
+const TAKE_EXERCISE: &[&str] = &["run", "edit", "hint", "reset", "solution"];
+
+// The options whose value is the name of an exercise
+const EXERCISE_OPTIONS: &[&str] = &["--from", "--prerequisites"];
+
+// Prints one exercise name per line, and nothing at all outside of
+// a rustlings directory
+const LIST_NAMES: &str = "rustlings list --names";
+
+/// The completion script for `shell`. Exercise names are completed
+/// at the time of typing from the output of `rustlings list --names`.
+pub fn script(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+        Shell::Powershell => powershell(),
+    }
+}
+
+fn command_names() -> Vec<&'static str> {
+    COMMANDS.iter().map(|(name, _, _)| *name).collect()
+}
+
+fn bash() -> String {
+    let mut cases = String::new();
+    for (name, _, options) in COMMANDS {
+        let _ = writeln!(cases, "        {name}) options=\"{}\" ;;", options.join(" "));
+    }
+    format!(
+        r#"_rustlings() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local command="" word skip=""
+    for word in "${{COMP_WORDS[@]:1:COMP_CWORD-1}}"; do
+        if [[ -n "$skip" ]]; then
+            skip=""
+        elif [[ " {value_options} " == *" $word "* ]]; then
+            skip=1
+        elif [[ "$word" != -* ]]; then
+            command="$word"
+            break
+        fi
+    done
+
+    if [[ " {exercise_options} " == *" $prev "* ]] ||
+        [[ " {take_exercise} " == *" $command "* && "$cur" != -* ]]; then
+        COMPREPLY=($(compgen -W "$({list_names} 2>/dev/null)" -- "$cur"))
+        return
+    fi
+
+    local options
+    case "$command" in
+        "") options="{global} {commands}" ;;
+{cases}        *) options="" ;;
+    esac
+    COMPREPLY=($(compgen -W "$options" -- "$cur"))
+}}
+complete -F _rustlings rustlings
+"#,
+        value_options = VALUE_OPTIONS.join(" "),
+        exercise_options = EXERCISE_OPTIONS.join(" "),
+        take_exercise = TAKE_EXERCISE.join(" "),
+        list_names = LIST_NAMES,
+        global = GLOBAL_OPTIONS.join(" "),
+        commands = command_names().join(" "),
+    )
+}
+
+fn zsh() -> String {
+    let mut descriptions = String::new();
+    let mut cases = String::new();
+    for (name, about, options) in COMMANDS {
+        let _ = writeln!(descriptions, "        '{name}:{}'", about.replace('\'', "'\\''"));
+        let _ = writeln!(cases, "        {name}) compadd -- {} ;;", options.join(" "));
+    }
+    format!(
+        r#"#compdef rustlings
+
+_rustlings() {{
+    local -a commands
+    commands=(
+{descriptions}    )
+    local command=""
+    local i
+    for (( i = 2; i < CURRENT; i++ )); do
+        if [[ " {value_options} " != *" ${{words[i-1]}} "* && ${{words[i]}} != -* ]]; then
+            command=${{words[i]}}
+            break
+        fi
+    done
+
+    if [[ -z $command ]]; then
+        if [[ ${{words[CURRENT]}} == -* ]]; then
+            compadd -- {global}
+        else
+            _describe -t commands 'rustlings command' commands
+        fi
+        return
+    fi
+
+    if [[ " {exercise_options} " == *" ${{words[CURRENT-1]}} "* ]] ||
+        [[ " {take_exercise} " == *" $command "* && ${{words[CURRENT]}} != -* ]]; then
+        local -a names
+        names=(${{(f)"$({list_names} 2>/dev/null)"}})
+        compadd -a names
+        return
+    fi
+
+    case $command in
+{cases}    esac
+}}
+
+if [[ $zsh_eval_context[-1] == loadautofunc ]]; then
+    _rustlings "$@"
+else
+    compdef _rustlings rustlings
+fi
+"#,
+        value_options = VALUE_OPTIONS.join(" "),
+        exercise_options = EXERCISE_OPTIONS.join(" "),
+        take_exercise = TAKE_EXERCISE.join(" "),
+        list_names = LIST_NAMES,
+        global = GLOBAL_OPTIONS.join(" "),
+    )
+}
+
+fn fish() -> String {
+    let names = format!("({LIST_NAMES} 2>/dev/null)");
+    let mut out = String::from("complete -c rustlings -f\n");
+    for option in GLOBAL_OPTIONS {
+        let _ = writeln!(out, "complete -c rustlings -n __fish_use_subcommand -l {}", &option[2..]);
+    }
+    for (name, about, options) in COMMANDS {
+        let _ = writeln!(
+            out,
+            "complete -c rustlings -n __fish_use_subcommand -a {name} -d '{}'",
+            about.replace('\'', "\\'")
+        );
+        for option in options.iter() {
+            let value = if EXERCISE_OPTIONS.contains(option) {
+                format!(" -r -a '{names}'")
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                out,
+                "complete -c rustlings -n '__fish_seen_subcommand_from {name}' -l {}{value}",
+                &option[2..]
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "complete -c rustlings -n '__fish_seen_subcommand_from {}' -a '{names}'",
+        TAKE_EXERCISE.join(" ")
+    );
+    out
+}
+
+fn powershell() -> String {
+    let quote = |words: &[&str]| {
+        words
+            .iter()
+            .map(|w| format!("'{w}'"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut cases = String::new();
+    for (name, _, options) in COMMANDS {
+        let _ = writeln!(cases, "        '{name}' {{ @({}) }}", quote(options));
+    }
+    let global: Vec<&str> = GLOBAL_OPTIONS.iter().copied().chain(command_names()).collect();
+    format!(
+        r#"Register-ArgumentCompleter -Native -CommandName rustlings -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements | ForEach-Object {{ $_.ToString() }})
+    $count = $words.Count
+    if ($wordToComplete) {{ $count -= 1 }}
+    $typed = @($words | Select-Object -Skip 1 -First ([Math]::Max($count - 1, 0)))
+    $command = $null
+    for ($i = 0; $i -lt $typed.Count; $i++) {{
+        if (@({value_options}) -contains $typed[$i]) {{ $i++; continue }}
+        if (-not $typed[$i].StartsWith('-')) {{ $command = $typed[$i]; break }}
+    }}
+    $previous = if ($typed.Count) {{ $typed[-1] }} else {{ '' }}
+
+    if ((@({exercise_options}) -contains $previous) -or
+        ((@({take_exercise}) -contains $command) -and -not $wordToComplete.StartsWith('-'))) {{
+        $candidates = @({list_names} 2>$null)
+    }} else {{
+        $candidates = switch ($command) {{
+        $null {{ @({global}) }}
+{cases}        default {{ @() }}
+        }}
+    }}
+    $candidates | Where-Object {{ $_ -like "$wordToComplete*" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }}
+}}
+"#,
+        value_options = quote(VALUE_OPTIONS),
+        exercise_options = quote(EXERCISE_OPTIONS),
+        take_exercise = quote(TAKE_EXERCISE),
+        list_names = LIST_NAMES,
+        global = quote(&global),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scripts_cover_every_command() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::Powershell] {
+            let script = script(shell);
+            for (name, _, options) in COMMANDS {
+                assert!(script.contains(name), "{shell:?} misses {name}");
+                for option in options.iter() {
+                    assert!(script.contains(&option[2..]), "{shell:?} misses {name} {option}");
+                }
+            }
+            assert!(script.contains(LIST_NAMES), "{shell:?} doesn't complete exercise names");
+        }
+    }
+
+    #[test]
+    fn parses_shell_names() {
+        assert_eq!("powershell".parse(), Ok(Shell::Powershell));
+        assert!("tcsh".parse::<Shell>().is_err());
+    }
+}
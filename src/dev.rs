@@ -0,0 +1,362 @@
+use crate::exercise::Mode;
+use crate::test_blocks::tests_hash;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A mistake in an info.toml, or in the exercises it points at
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub file: PathBuf,
+    // 1-based, `None` when it is about the file as a whole
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}: {}", self.file.display(), self.message),
+            None => write!(f, "{}: {}", self.file.display(), self.message),
+        }
+    }
+}
+
+// The `[[exercises]]` entries of one info.toml, with where they start
+struct Entries<'a> {
+    file: &'a Path,
+    lines: Vec<&'a str>,
+    // The index in `lines` of the header of every entry
+    headers: Vec<usize>,
+}
+
+impl<'a> Entries<'a> {
+    fn new(file: &'a Path, source: &'a str) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        let headers = (0..lines.len()).filter(|&i| lines[i].trim() == "[[exercises]]").collect();
+        Entries { file, lines, headers }
+    }
+
+    // The line of `key` in the `i`th entry, or of its header
+    // if the entry doesn't set it
+    fn line(&self, i: usize, key: &str) -> Option<usize> {
+        let start = *self.headers.get(i)?;
+        let end = self.headers.get(i + 1).copied().unwrap_or(self.lines.len());
+        let is_key = |line: &&str| {
+            let line = line.trim_start();
+            line.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with('='))
+        };
+        let offset = self.lines[start..end].iter().position(is_key).unwrap_or(0);
+        Some(start + offset + 1)
+    }
+
+    fn violation(&self, i: usize, key: &str, message: String) -> Violation {
+        Violation {
+            file: self.file.to_path_buf(),
+            line: self.line(i, key),
+            message,
+        }
+    }
+}
+
+// Everything the exercise files are checked against
+#[derive(Default)]
+struct Checker {
+    violations: Vec<Violation>,
+    // Where every name was first defined
+    names: HashMap<String, (PathBuf, Option<usize>)>,
+    // How often every file is the `path` of an exercise
+    paths: HashMap<PathBuf, usize>,
+    // The files exercises use besides their `path`
+    additional: Vec<PathBuf>,
+    // The info.toml files already checked, to stop at cycles
+    visited: Vec<PathBuf>,
+}
+
+impl Checker {
+    fn check_file(&mut self, info: &Path) {
+        let canonical = info.canonicalize().unwrap_or_else(|_| info.to_path_buf());
+        if self.visited.contains(&canonical) {
+            return;
+        }
+        self.visited.push(canonical);
+        let whole_file = |message: String| Violation {
+            file: info.to_path_buf(),
+            line: None,
+            message,
+        };
+        let source = match fs::read_to_string(info) {
+            Ok(source) => source,
+            Err(e) => return self.violations.push(whole_file(format!("could not be read: {e}"))),
+        };
+        let value: toml::Value = match toml::from_str(&source) {
+            Ok(value) => value,
+            Err(e) => return self.violations.push(whole_file(format!("is invalid: {e}"))),
+        };
+        let dir = info.parent().unwrap_or_else(|| Path::new(""));
+        let entries = Entries::new(info, &source);
+        let exercises = value.get("exercises").and_then(toml::Value::as_array);
+        for (i, entry) in exercises.into_iter().flatten().enumerate() {
+            self.check_entry(&entries, i, entry, dir);
+        }
+        let includes = value.get("include").and_then(toml::Value::as_array);
+        for include in includes.into_iter().flatten().filter_map(toml::Value::as_str) {
+            self.check_file(&dir.join(include));
+        }
+    }
+
+    fn check_entry(&mut self, entries: &Entries, i: usize, entry: &toml::Value, dir: &Path) {
+        let name_pattern = Regex::new("^[a-z0-9_]+$").unwrap();
+        let name = match entry.get("name").and_then(toml::Value::as_str) {
+            Some(name) => {
+                let here = (entries.file.to_path_buf(), entries.line(i, "name"));
+                if !name_pattern.is_match(name) {
+                    let message = format!("the name `{name}` may only contain a-z, 0-9 and _");
+                    self.violations.push(entries.violation(i, "name", message));
+                }
+                if let Some((file, line)) = self.names.get(name) {
+                    let line = line.map(|line| format!(":{line}")).unwrap_or_default();
+                    let message = format!("{name} is already defined at {}{line}", file.display());
+                    self.violations.push(entries.violation(i, "name", message));
+                } else {
+                    self.names.insert(name.to_string(), here);
+                }
+                name.to_string()
+            }
+            None => {
+                self.violations.push(entries.violation(i, "name", "the exercise has no name".into()));
+                format!("number {}", i + 1)
+            }
+        };
+
+        let mode = match entry.get("mode").map(|mode| mode.clone().try_into::<Mode>()) {
+            Some(Ok(mode)) => Some(mode),
+            Some(Err(_)) => {
+                let message = format!("{name} has an unknown mode, expected compile, test, clippy, buildscript or cargo-test");
+                self.violations.push(entries.violation(i, "mode", message));
+                None
+            }
+            None => {
+                self.violations.push(entries.violation(i, "mode", format!("{name} has no mode")));
+                None
+            }
+        };
+
+        match entry.get("hint").and_then(toml::Value::as_str) {
+            Some(hint) if !hint.trim().is_empty() => {}
+            _ => self.violations.push(entries.violation(i, "hint", format!("{name} has no hint"))),
+        }
+        if entry.get("points").is_some_and(|points| points.as_integer().is_none_or(|p| p < 1 || p > u32::MAX.into())) {
+            let message = format!("{name} must be worth a whole number of points, at least 1");
+            self.violations.push(entries.violation(i, "points", message));
+        }
+
+        let additional: Vec<PathBuf> = entry
+            .get("additional_files")
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+            .map(|file| normalize(&dir.join(file)))
+            .collect();
+        for file in additional.iter().filter(|file| !file.exists()) {
+            let message = format!("the additional file {} of {name} doesn't exist", file.display());
+            self.violations.push(entries.violation(i, "additional_files", message));
+        }
+
+        let path = match entry.get("path").and_then(toml::Value::as_str) {
+            Some(path) => normalize(&dir.join(path)),
+            None => {
+                self.violations.push(entries.violation(i, "path", format!("{name} has no path")));
+                return;
+            }
+        };
+        if path.extension().is_none_or(|extension| extension != "rs") {
+            let message = format!("the path {} of {name} doesn't end in .rs", path.display());
+            self.violations.push(entries.violation(i, "path", message));
+        }
+        if !path.exists() {
+            let message = format!("the path {} of {name} doesn't exist", path.display());
+            self.violations.push(entries.violation(i, "path", message));
+        } else {
+            self.check_undeclared_files(&name, &path, &additional);
+        }
+        if path.exists() && mode == Some(Mode::Test) {
+            let has_test = std::iter::once(&path)
+                .chain(&additional)
+                .any(|file| fs::read_to_string(file).is_ok_and(|source| source.contains("#[test]")));
+            if !has_test {
+                let message = format!("{name} is a test exercise, but {} has no #[test]", path.display());
+                self.violations.push(entries.violation(i, "mode", message));
+            }
+        }
+        // Build script exercises share the build.rs next to them
+        if mode == Some(Mode::BuildScript) {
+            self.additional.push(path.with_file_name("build.rs"));
+        }
+        *self.paths.entry(path).or_default() += 1;
+        self.additional.extend(additional);
+    }
+
+    // Files read through `#[path]` or `include!` change the exercise like its own
+    // source does, so they have to be in its `additional_files`
+    fn check_undeclared_files(&mut self, name: &str, path: &Path, additional: &[PathBuf]) {
+        let pattern = Regex::new(r#"#\[path\s*=\s*"([^"]+)"\s*\]|include(?:_str|_bytes)?!\s*\(\s*"([^"]+)"\s*\)"#).unwrap();
+        let Ok(source) = fs::read_to_string(path) else { return };
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for (line, text) in source.lines().enumerate() {
+            for captures in pattern.captures_iter(text) {
+                let Some(file) = captures.get(1).or_else(|| captures.get(2)) else { continue };
+                let file = normalize(&dir.join(file.as_str()));
+                if !additional.contains(&file) {
+                    self.violations.push(Violation {
+                        file: path.to_path_buf(),
+                        line: Some(line + 1),
+                        message: format!("{name} reads {}, which isn't in its additional_files", file.display()),
+                    });
+                }
+            }
+        }
+    }
+
+    // Every exercise file under `exercises` belongs to exactly one exercise
+    fn check_exercise_files(&mut self, exercises: &Path) {
+        let mut files = Vec::new();
+        rust_files(exercises, &mut files);
+        files.sort();
+        for file in files {
+            let message = match self.paths.get(&file).copied().unwrap_or_default() {
+                0 if self.additional.contains(&file) => continue,
+                0 => "isn't the path of any exercise in info.toml".to_string(),
+                1 => continue,
+                n => format!("is the path of {n} exercises in info.toml"),
+            };
+            self.violations.push(Violation {
+                file,
+                line: None,
+                message,
+            });
+        }
+    }
+}
+
+// Drop the `./` components, so that paths can be compared
+fn normalize(path: &Path) -> PathBuf {
+    path.components().filter(|c| c.as_os_str() != ".").collect()
+}
+
+// The .rs files in `dir` and its subdirectories
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            // Build products of the package exercises
+            if !path.ends_with("target") {
+                rust_files(&path, files);
+            }
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(normalize(&path));
+        }
+    }
+}
+
+/// Check the info.toml at `info`, the files it includes and the `exercises`
+/// directory next to it, returning every mistake found
+pub fn check(info: &Path) -> Vec<Violation> {
+    let mut checker = Checker::default();
+    checker.check_file(info);
+    let dir = info.parent().unwrap_or_else(|| Path::new(""));
+    checker.check_exercise_files(&normalize(&dir.join("exercises")));
+    checker.violations
+}
+
+/// The source of src/test_hashes.rs: the hash of the test block of every
+/// file under `exercises` that has one, keyed by its path with `/` separators
+pub fn test_hashes(exercises: &Path) -> String {
+    let mut files = Vec::new();
+    rust_files(exercises, &mut files);
+    files.sort();
+    let mut source = String::from(
+        "// The hash of the test block of every exercise file that has one, keyed by its\n\
+         // path with `/` separators. Written by `rustlings dev hash-tests`, don't edit it.\n\
+         pub const TEST_HASHES: &[(&str, u64)] = &[\n",
+    );
+    for file in files {
+        let Some(hash) = fs::read_to_string(&file).ok().and_then(|source| tests_hash(&source)) else {
+            continue;
+        };
+        let key: Vec<String> = file.components().map(|c| c.as_os_str().to_string_lossy().into_owned()).collect();
+        let _ = writeln!(source, "    ({:?}, {hash:#018x}),", key.join("/"));
+    }
+    source.push_str("];\n");
+    source
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn reports_every_violation_with_its_line() {
+        let dir = std::env::temp_dir().join(format!("rustlings_dev_check_{}", process::id()));
+        fs::create_dir_all(dir.join("exercises")).unwrap();
+        fs::write(dir.join("exercises/good.rs"), "#[test]\nfn works() {}\n").unwrap();
+        fs::write(dir.join("exercises/untested.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("exercises/forgotten.rs"), "fn main() {}\n").unwrap();
+        let info = "[[exercises]]\nname = \"good\"\npath = \"exercises/good.rs\"\nmode = \"test\"\nhint = \"h\"\n\n\
+            [[exercises]]\nname = \"Untested\"\npath = \"exercises/untested.rs\"\nmode = \"test\"\nhint = \"\"\npoints = 0\n\n\
+            [[exercises]]\nname = \"good\"\npath = \"exercises/missing.txt\"\nmode = \"run\"\nhint = \"h\"\n";
+        fs::write(dir.join("info.toml"), info).unwrap();
+
+        let violations: Vec<String> = check(&dir.join("info.toml"))
+            .iter()
+            .map(|v| v.to_string().replace(&format!("{}/", dir.display()), ""))
+            .collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            violations,
+            [
+                "info.toml:8: the name `Untested` may only contain a-z, 0-9 and _",
+                "info.toml:11: Untested has no hint",
+                "info.toml:12: Untested must be worth a whole number of points, at least 1",
+                "info.toml:10: Untested is a test exercise, but exercises/untested.rs has no #[test]",
+                "info.toml:15: good is already defined at info.toml:2",
+                "info.toml:17: good has an unknown mode, expected compile, test, clippy, buildscript or cargo-test",
+                "info.toml:16: the path exercises/missing.txt of good doesn't end in .rs",
+                "info.toml:16: the path exercises/missing.txt of good doesn't exist",
+                "exercises/forgotten.rs: isn't the path of any exercise in info.toml",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_files_read_without_being_declared() {
+        let dir = std::env::temp_dir().join(format!("rustlings_dev_check_includes_{}", process::id()));
+        fs::create_dir_all(dir.join("exercises")).unwrap();
+        fs::write(dir.join("exercises/shared.rs"), "pub fn f() {}\n").unwrap();
+        fs::write(dir.join("exercises/input.txt"), "1 2 3\n").unwrap();
+        let reader = "#[path = \"shared.rs\"]\nmod shared;\n\nfn main() {\n    include_str!(\"input.txt\");\n}\n";
+        fs::write(dir.join("exercises/declared.rs"), reader).unwrap();
+        fs::write(dir.join("exercises/undeclared.rs"), reader).unwrap();
+        let info = "[[exercises]]\nname = \"declared\"\npath = \"exercises/declared.rs\"\nmode = \"compile\"\nhint = \"h\"\n\
+            additional_files = [\"exercises/shared.rs\", \"exercises/input.txt\"]\n\n\
+            [[exercises]]\nname = \"undeclared\"\npath = \"exercises/undeclared.rs\"\nmode = \"compile\"\nhint = \"h\"\n";
+        fs::write(dir.join("info.toml"), info).unwrap();
+
+        let violations: Vec<String> = check(&dir.join("info.toml"))
+            .iter()
+            .map(|v| v.to_string().replace(&format!("{}/", dir.display()), ""))
+            .collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            violations,
+            [
+                "exercises/undeclared.rs:1: undeclared reads exercises/shared.rs, which isn't in its additional_files",
+                "exercises/undeclared.rs:5: undeclared reads exercises/input.txt, which isn't in its additional_files",
+            ]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use console::style;
+use std::fmt::Write;
+
+// How many unchanged lines to show around every change
+const CONTEXT: usize = 3;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Op {
+    Keep,
+    Delete,
+    Insert,
+}
+
+// The line by line edit script turning `old` into `new`,
+// based on their longest common subsequence
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Op> {
+    // lcs[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push(Op::Keep);
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(Op::Delete);
+            i += 1;
+        } else {
+            ops.push(Op::Insert);
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// A unified diff from `old` to `new`, empty if they are the same.
+/// Removed lines are red and added lines green whenever
+/// the terminal supports colors.
+pub fn unified(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let ops = edit_script(&old, &new);
+    if ops.iter().all(|&op| op == Op::Keep) {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", style(format!("--- {old_name}")).red().bold());
+    let _ = writeln!(out, "{}", style(format!("+++ {new_name}")).green().bold());
+
+    // Line numbers in old and new before every op
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut i, mut j) = (0, 0);
+    for op in &ops {
+        positions.push((i, j));
+        match op {
+            Op::Keep => {
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+    positions.push((i, j));
+
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k] != Op::Keep).collect();
+    let mut next_change = 0;
+    while next_change < changes.len() {
+        // Grow the hunk while the next change is close enough
+        // for their contexts to touch
+        let start = changes[next_change].saturating_sub(CONTEXT);
+        let mut end = changes[next_change] + 1;
+        next_change += 1;
+        while next_change < changes.len() && changes[next_change] <= end + 2 * CONTEXT {
+            end = changes[next_change] + 1;
+            next_change += 1;
+        }
+        let end = (end + CONTEXT).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        // Empty ranges are numbered after the line they follow
+        let range = |start: usize, len: usize| match len {
+            0 => format!("{start},0"),
+            _ => format!("{},{len}", start + 1),
+        };
+        let _ = writeln!(
+            out,
+            "{}",
+            style(format!(
+                "@@ -{} +{} @@",
+                range(old_start, old_end - old_start),
+                range(new_start, new_end - new_start)
+            ))
+            .cyan()
+        );
+        for k in start..end {
+            let (i, j) = positions[k];
+            let _ = match ops[k] {
+                Op::Keep => writeln!(out, " {}", old[i]),
+                Op::Delete => writeln!(out, "{}", style(format!("-{}", old[i])).red()),
+                Op::Insert => writeln!(out, "{}", style(format!("+{}", new[j])).green()),
+            };
+        }
+    }
+    out
+}
+
+// The largest edit script `word_diff` is willing to compute, in tokens squared.
+// Anything bigger is better read in the raw output anyway.
+const MAX_WORD_DIFF: usize = 1 << 22;
+
+// Split a value into words, runs of whitespace and single punctuation marks,
+// e.g. `Point { x: 1 }` into `Point`, ` `, `{`, ` `, `x`, `:`, ` `, `1`, ` `, `}`
+fn words(s: &str) -> Vec<&str> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Punctuation,
+    }
+    let class = |c: char| match c {
+        c if c.is_alphanumeric() || c == '_' => Class::Word,
+        c if c.is_whitespace() => Class::Space,
+        _ => Class::Punctuation,
+    };
+
+    let mut words = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        if class(c) != Class::Punctuation {
+            while let Some(&(i, next)) = chars.peek() {
+                if class(next) != class(c) {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+        }
+        words.push(&s[start..end]);
+    }
+    words
+}
+
+/// The `left` and `right` values of every failed `assert_eq!`
+/// in the output of a test harness, in both the format of current
+/// and of older Rust versions, which quoted them in backticks.
+pub fn assertions(output: &str) -> Vec<(String, String)> {
+    let value = |line: &str, label: &str| -> Option<String> {
+        let value = line.trim_start().strip_prefix(label)?.trim();
+        let value = match value.strip_prefix('`') {
+            // followed by `,` or the quote closing the panic message
+            Some(quoted) => &quoted[..quoted.rfind('`')?],
+            None => value,
+        };
+        Some(value.to_string())
+    };
+    let lines: Vec<&str> = output.lines().collect();
+    lines
+        .windows(2)
+        .filter_map(|pair| Some((value(pair[0], "left:")?, value(pair[1], "right:")?)))
+        .collect()
+}
+
+/// A word level diff of the two sides of a failed assertion:
+/// the left value with the words missing on the right side in red,
+/// followed by the right value with the words it added in green.
+/// `None` if the values are the same or too large to compare.
+pub fn word_diff(left: &str, right: &str) -> Option<String> {
+    let old = words(left);
+    let new = words(right);
+    if old == new || old.len().saturating_mul(new.len()) > MAX_WORD_DIFF {
+        return None;
+    }
+
+    let (mut minus, mut plus) = (String::new(), String::new());
+    let (mut i, mut j) = (0, 0);
+    for op in edit_script(&old, &new) {
+        match op {
+            Op::Keep => {
+                minus.push_str(old[i]);
+                plus.push_str(new[j]);
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => {
+                let _ = write!(minus, "{}", style(old[i]).red().bold().underlined());
+                i += 1;
+            }
+            Op::Insert => {
+                let _ = write!(plus, "{}", style(new[j]).green().bold().underlined());
+                j += 1;
+            }
+        }
+    }
+    Some(format!(
+        "{} {minus}\n{} {plus}\n",
+        style(" left:").red(),
+        style("right:").green()
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn plain(old: &str, new: &str) -> String {
+        console::set_colors_enabled(false);
+        unified(old, new, "a.rs", "b.rs")
+    }
+
+    #[test]
+    fn identical_files_have_no_diff() {
+        assert_eq!(plain("fn main() {}\n", "fn main() {}\n"), "");
+    }
+
+    #[test]
+    fn changed_line_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+        assert_eq!(
+            plain(old, new),
+            "--- a.rs\n+++ b.rs\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let old: String = (1..=20).map(|i| format!("{i}\n")).collect();
+        let new: String = (1..=20)
+            .filter(|&i| i != 19)
+            .map(|i| if i == 2 { "two\n".to_string() } else { format!("{i}\n") })
+            .collect();
+        let diff = plain(&old, &new);
+        assert_eq!(diff.matches("@@ ").count(), 2, "{diff}");
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n 1\n-2\n+two\n"), "{diff}");
+        assert!(diff.contains("@@ -16,5 +16,4 @@\n 16\n 17\n 18\n-19\n 20\n"), "{diff}");
+    }
+
+    #[test]
+    fn insertion_into_empty_file() {
+        assert_eq!(plain("", "fn main() {}\n"), "--- a.rs\n+++ b.rs\n@@ -0,0 +1,1 @@\n+fn main() {}\n");
+    }
+
+    #[test]
+    fn splits_values_into_words() {
+        assert_eq!(
+            words("Point { x: 10 }"),
+            ["Point", " ", "{", " ", "x", ":", " ", "10", " ", "}"]
+        );
+        assert_eq!(words("[[1]]"), ["[", "[", "1", "]", "]"]);
+    }
+
+    #[test]
+    fn finds_assertions_in_test_output() {
+        let current = "thread 'tests::t' panicked at src/lib.rs:5:9:\n\
+                       assertion `left == right` failed\n  left: [1, 2]\n right: [1, 3]\n";
+        assert_eq!(assertions(current), [("[1, 2]".to_string(), "[1, 3]".to_string())]);
+
+        let old = "thread 'tests::t' panicked at 'assertion failed: `(left == right)`\n  \
+                   left: `\"a b\"`,\n right: `\"a c\"`', src/lib.rs:5:9\n";
+        assert_eq!(assertions(old), [("\"a b\"".to_string(), "\"a c\"".to_string())]);
+
+        assert!(assertions("test result: FAILED. 0 passed; 1 failed\n").is_empty());
+    }
+
+    #[test]
+    fn word_diff_keeps_unchanged_words() {
+        console::set_colors_enabled(false);
+        assert_eq!(
+            word_diff("Point { x: 1, y: 2 }", "Point { x: 1, y: 3 }").as_deref(),
+            Some(" left: Point { x: 1, y: 2 }\nright: Point { x: 1, y: 3 }\n")
+        );
+        assert_eq!(word_diff("Some(1)", "Some(1)"), None);
+    }
+}
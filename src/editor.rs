@@ -0,0 +1,71 @@
+use std::env;
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+// Editors that understand `+LINE` in front of the file to open
+const PLUS_LINE_EDITORS: &[&str] = &[
+    "vi", "vim", "nvim", "gvim", "nano", "pico", "emacs", "emacsclient", "micro", "kak", "joe", "ne", "mg",
+];
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+// The editor configured in $VISUAL or $EDITOR, split into the program
+// and its arguments, such as `code --wait`. `None` if neither is set.
+fn configured() -> Option<Vec<String>> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(env::var_os)
+        .map(|editor| editor.to_string_lossy().split_whitespace().map(String::from).collect::<Vec<_>>())
+        .find(|words| !words.is_empty())
+}
+
+// The command opening `path` in `editor`, at `line` if the editor
+// can be told to
+fn command(editor: &[String], path: &Path, line: Option<usize>) -> Command {
+    let mut command = Command::new(&editor[0]);
+    command.args(&editor[1..]);
+    let program = Path::new(&editor[0])
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    match line {
+        Some(line) if PLUS_LINE_EDITORS.contains(&program) => {
+            command.arg(format!("+{line}"));
+        }
+        _ => {}
+    }
+    command.arg(path);
+    command
+}
+
+/// Open `path` in the user's editor, or the platform's default one
+/// if none is configured, and wait for it to be closed.
+pub fn open(path: &Path, line: Option<usize>) -> io::Result<ExitStatus> {
+    let editor = configured().unwrap_or_else(|| vec![DEFAULT_EDITOR.to_string()]);
+    command(&editor, path, line).status()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(editor: &str, line: Option<usize>) -> Vec<String> {
+        let editor: Vec<String> = editor.split_whitespace().map(String::from).collect();
+        let command = command(&editor, Path::new("exercises/intro/intro1.rs"), line);
+        std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn jumps_to_line_where_supported() {
+        assert_eq!(args("/usr/bin/nvim", Some(7)), ["/usr/bin/nvim", "+7", "exercises/intro/intro1.rs"]);
+        assert_eq!(args("code --wait", Some(7)), ["code", "--wait", "exercises/intro/intro1.rs"]);
+        assert_eq!(args("nano", None), ["nano", "exercises/intro/intro1.rs"]);
+    }
+}
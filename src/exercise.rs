@@ -1,32 +1,201 @@
+use crate::backtrace;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{self, Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
-const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
+// The edition every exercise is compiled with
+pub const EDITION: &str = "2021";
+const RUSTC_EDITION_ARGS: &[&str] = &["--edition", EDITION];
 const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
-const CONTEXT: usize = 2;
+// A line holding nothing but the marker
+const MARKER_LINE_REGEX: &str = r"^\s*///?\s*I\s+AM\s+NOT\s+DONE\s*$";
+// A line starting a function, such as `pub(crate) async fn parse(`
+const FN_LINE_REGEX: &str = r#"^\s*(pub(\([^)]*\))?\s+)?((const|async|unsafe|extern(\s+"[^"]*")?)\s+)*fn\s"#;
 const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
 const BUILD_SCRIPT_CARGO_TOML_PATH: &str = "./exercises/tests/Cargo.toml";
+// Where cargo builds the packages of cargo-test exercises. They share it,
+// so that a dependency is only built once for all of them.
+const CARGO_TARGET_DIR: &str = "./target/exercises";
+// How long a compiled exercise may run unless info.toml or `--timeout` says otherwise
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+// The lints clippy exercises are checked with unless info.toml says otherwise
+const DEFAULT_CLIPPY_ARGS: &[&str] = &["-D", "warnings", "-D", "clippy::float_cmp"];
+// The directory holding reference solutions, mirroring `exercises/`
+pub const SOLUTIONS_DIR: &str = "solutions";
 
-// Get a temporary file name that is hopefully unique
-#[inline]
-fn temp_file() -> String {
-    let thread_id: String = format!("{:?}", std::thread::current().id())
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .collect();
+// Let the compiler color its messages unless colors are turned off.
+// Its output is captured, so it would never color them by itself.
+fn rustc_color_args() -> [&'static str; 2] {
+    color_args(console::colors_enabled())
+}
+
+fn color_args(colors: bool) -> [&'static str; 2] {
+    ["--color", if colors { "always" } else { "never" }]
+}
+
+// The colors of a test harness, like those of the compiler, unless
+// the arguments passed on to it choose them already
+fn harness_color_args(mode: Mode, test_args: &[String], colors: bool) -> Vec<&'static str> {
+    let chosen = test_args.iter().any(|arg| arg.starts_with("--color"));
+    match mode {
+        Mode::Test | Mode::CargoTest if !chosen => color_args(colors).to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+/// How many bytes of their output and of their errors are kept of the
+/// exercises that run, unless `--output-limit` says otherwise
+pub const DEFAULT_OUTPUT_LIMIT: usize = 64 * 1024;
+
+static OUTPUT_LIMIT: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Keep at most `limit` bytes of each of the outputs of the exercises that
+/// run from now on, or all of them with `None`. Only the first call counts.
+pub fn set_output_limit(limit: Option<usize>) {
+    let _ = OUTPUT_LIMIT.set(limit);
+}
+
+fn output_limit() -> Option<usize> {
+    *OUTPUT_LIMIT.get_or_init(|| Some(DEFAULT_OUTPUT_LIMIT))
+}
+
+/// How many lines before and after the `I AM NOT DONE` comment are shown
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Context {
+    pub before: usize,
+    pub after: usize,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context { before: 2, after: 2 }
+    }
+}
+
+// `N` for as many lines before the comment as after it, or `BEFORE,AFTER`
+impl std::str::FromStr for Context {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let count = |n: &str| {
+            n.trim()
+                .parse()
+                .map_err(|_| format!("invalid context `{s}`, expected a number of lines or `BEFORE,AFTER`"))
+        };
+        match s.split_once(',') {
+            Some((before, after)) => Ok(Context { before: count(before)?, after: count(after)? }),
+            None => count(s).map(|n| Context { before: n, after: n }),
+        }
+    }
+}
+
+static CONTEXT: OnceLock<Context> = OnceLock::new();
+
+/// Show this many lines around the `I AM NOT DONE` comment from now on.
+/// Only the first call counts.
+pub fn set_context(context: Context) {
+    let _ = CONTEXT.set(context);
+}
+
+fn context() -> Context {
+    *CONTEXT.get_or_init(Context::default)
+}
+
+/// Where the exercises are compiled to unless `--temp-dir` or
+/// $RUSTLINGS_TEMP_DIR says otherwise
+pub const DEFAULT_TEMP_DIR: &str = "target/rustlings-tmp";
+
+static TEMP_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Compile the exercises to `dir` from now on. Only the first call counts.
+pub fn set_temp_dir(dir: PathBuf) {
+    let _ = TEMP_DIR.set(dir);
+}
 
-    format!("./temp_{}_{thread_id}", process::id())
+/// The directory compiled exercises are written to
+pub fn temp_dir() -> &'static Path {
+    TEMP_DIR.get_or_init(|| PathBuf::from(DEFAULT_TEMP_DIR))
 }
 
+// A new temporary file name for the given exercise, different from every
+// other one handed out, even for the same exercise in another rustlings
+// running at the same time. The counter separates the compilations of this
+// process, and the time those of processes with the same recycled id.
+// Windows only runs it with the `.exe` suffix.
+fn temp_file(name: &str) -> String {
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+    let count = COUNT.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+
+    format!(
+        "{}/{}_{count}_{nanos:08x}_{name}{}",
+        temp_dir().display(),
+        process::id(),
+        env::consts::EXE_SUFFIX
+    )
+}
+
+// The processes started for exercises that haven't finished yet, killed
+// when rustlings is interrupted. `true` marks compiled exercises, which
+// lead a process group of their own.
+static RUNNING: Mutex<Vec<(u32, bool)>> = Mutex::new(Vec::new());
+
+fn running() -> std::sync::MutexGuard<'static, Vec<(u32, bool)>> {
+    RUNNING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Like `Command::output`, but killed by `kill_running`
+fn output(command: &mut Command) -> io::Result<Output> {
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let id = child.id();
+    running().push((id, false));
+    let output = child.wait_with_output();
+    running().retain(|&(running, _)| running != id);
+    output
+}
+
+/// Kill the compilers and exercises that are still running
+pub fn kill_running() {
+    for &(id, group) in running().iter() {
+        #[cfg(unix)]
+        unsafe {
+            let id = id as libc::pid_t;
+            libc::kill(if group { -id } else { id }, libc::SIGKILL);
+        }
+        #[cfg(not(unix))]
+        let _ = (id, group);
+    }
+}
+
+/// Remove the binaries compiled by this process that are still around,
+/// complete or not
+pub fn remove_temp_files() {
+    let pattern = format!("{}/{}_*", glob::Pattern::escape(&temp_dir().display().to_string()), process::id());
+    for file in glob::glob(&pattern).into_iter().flatten().flatten() {
+        let _ = remove_file(file);
+    }
+}
+
+// The clippy and build script exercises share one Cargo.toml per mode,
+// so only one of them may be compiled at a time
+static CARGO_TOML_LOCK: Mutex<()> = Mutex::new(());
+
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
@@ -37,6 +206,17 @@ pub enum Mode {
     Clippy,
     // Indicates that the exercise should be run using cargo with build script
     BuildScript,
+    // Indicates that the exercise is a package of its own, whose tests
+    // are built by cargo so that it can use crates from crates.io
+    #[serde(rename = "cargo-test")]
+    CargoTest,
+}
+
+// The highest difficulty an exercise can have in info.toml
+pub const MAX_DIFFICULTY: u8 = 5;
+
+fn default_points() -> u32 {
+    1
 }
 
 #[derive(Deserialize)]
@@ -44,18 +224,246 @@ pub struct ExerciseList {
     pub exercises: Vec<Exercise>,
 }
 
+impl ExerciseList {
+    // Read the info.toml at `path`, followed by the exercises of every
+    // file it includes with `include = ["packs/os/info.toml"]`, in order.
+    // Paths in an included file are relative to the directory it lives in.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut exercises = Vec::new();
+        let mut origins = Vec::new();
+        Self::load_into(path, &mut Vec::new(), &mut exercises, &mut origins)?;
+        Self::new(exercises, &origins)
+    }
+
+    // Check the exercises merged from all files, `origins` naming
+    // the file each of them came from
+    fn new(exercises: Vec<Exercise>, origins: &[String]) -> Result<Self, String> {
+        for (i, exercise) in exercises.iter().enumerate() {
+            if let Some(first) = exercises[..i].iter().position(|e| e.name == exercise.name) {
+                return Err(format!(
+                    "The exercise {} is defined twice, in {} and in {}",
+                    exercise.name, origins[first], origins[i]
+                ));
+            }
+        }
+        let list = ExerciseList { exercises };
+        list.check_requires()?;
+        Ok(list)
+    }
+
+    // Add the exercises of the file at `path` and of the files it includes
+    // to `exercises`, and the name of the file each came from to `origins`.
+    // `including` are the files that led to this one, to catch cycles.
+    fn load_into(
+        path: &Path,
+        including: &mut Vec<PathBuf>,
+        exercises: &mut Vec<Exercise>,
+        origins: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let file_name = path.display().to_string();
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if including.contains(&canonical) {
+            return Err(format!("{file_name} includes itself"));
+        }
+        let toml_str = fs::read_to_string(path).map_err(|e| format!("Could not read {file_name}: {e}"))?;
+        let (parsed, includes) = Self::parse_file(&toml_str, &file_name)?;
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for mut exercise in parsed {
+            exercise.path = dir.join(&exercise.path);
+            for file in &mut exercise.additional_files {
+                *file = dir.join(&*file);
+            }
+            for file in [&mut exercise.stdin_file, &mut exercise.manifest].into_iter().flatten() {
+                *file = dir.join(&*file);
+            }
+            exercises.push(exercise);
+            origins.push(file_name.clone());
+        }
+        including.push(canonical);
+        for include in includes {
+            Self::load_into(&dir.join(include), including, exercises, origins)?;
+        }
+        including.pop();
+        Ok(())
+    }
+
+    // Parse the contents of an info.toml file, returning its exercises and
+    // the files it includes. Exercises are parsed one by one, so that errors
+    // can name the exercise they are about.
+    fn parse_file(toml_str: &str, file_name: &str) -> Result<(Vec<Exercise>, Vec<PathBuf>), String> {
+        let value: toml::Value = toml::from_str(toml_str).map_err(|e| format!("{file_name} is invalid: {e}"))?;
+        let includes: Vec<PathBuf> = match value.get("include") {
+            Some(include) => include
+                .clone()
+                .try_into()
+                .map_err(|_| format!("The include of {file_name} must be a list of paths"))?,
+            None => Vec::new(),
+        };
+        let entries = match value.get("exercises") {
+            Some(toml::Value::Array(entries)) => entries.as_slice(),
+            // A file may just collect others
+            None if !includes.is_empty() => &[],
+            _ => return Err(format!("{file_name} doesn't list any [[exercises]]")),
+        };
+        let exercises = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let name = match entry.get("name").and_then(toml::Value::as_str) {
+                    Some(name) => name.to_string(),
+                    None => format!("number {}", i + 1),
+                };
+                let exercise: Exercise = entry
+                    .clone()
+                    .try_into()
+                    .map_err(|e| format!("The exercise {name} in {file_name} is invalid: {e}"))?;
+                if matches!(exercise.mode, Mode::CargoTest) && exercise.manifest.is_none() {
+                    return Err(format!("The cargo-test exercise {name} in {file_name} needs a manifest"));
+                }
+                if exercise.difficulty > MAX_DIFFICULTY {
+                    return Err(format!(
+                        "The exercise {name} in {file_name} has difficulty {}, but it must be between 0 (unknown) and {MAX_DIFFICULTY}",
+                        exercise.difficulty
+                    ));
+                }
+                if exercise.points == 0 {
+                    return Err(format!(
+                        "The exercise {name} in {file_name} is worth 0 points, but it must be worth at least 1"
+                    ));
+                }
+                Ok(exercise)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((exercises, includes))
+    }
+
+    // Every exercise named in `requires` has to exist, and
+    // no exercise may require itself, not even indirectly
+    fn check_requires(&self) -> Result<(), String> {
+        let index_of = |name: &str| self.exercises.iter().position(|e| e.name == name);
+        for exercise in &self.exercises {
+            if let Some(missing) = exercise.requires.iter().find(|r| index_of(r).is_none()) {
+                return Err(format!(
+                    "The exercise {} in info.toml requires {missing}, but there is no such exercise",
+                    exercise.name
+                ));
+            }
+        }
+
+        // A depth first search, keeping the exercises on the current path
+        // to report the cycle with. `visited` means not part of any cycle.
+        fn visit(list: &ExerciseList, i: usize, path: &mut Vec<usize>, visited: &mut [bool]) -> Result<(), String> {
+            if let Some(start) = path.iter().position(|&p| p == i) {
+                let cycle: Vec<&str> = path[start..]
+                    .iter()
+                    .chain([&i])
+                    .map(|&p| list.exercises[p].name.as_str())
+                    .collect();
+                return Err(format!("The exercises in info.toml require each other: {}", cycle.join(" -> ")));
+            }
+            if visited[i] {
+                return Ok(());
+            }
+            path.push(i);
+            for required in &list.exercises[i].requires {
+                let r = list.exercises.iter().position(|e| &e.name == required).unwrap();
+                visit(list, r, path, visited)?;
+            }
+            path.pop();
+            visited[i] = true;
+            Ok(())
+        }
+        let mut visited = vec![false; self.exercises.len()];
+        for i in 0..self.exercises.len() {
+            visit(self, i, &mut Vec::new(), &mut visited)?;
+        }
+        Ok(())
+    }
+
+    // The exercises the exercise at `index` builds on, directly or
+    // through others, in info.toml order
+    pub fn prerequisites(exercises: &[Exercise], index: usize) -> Vec<usize> {
+        let mut found = vec![false; exercises.len()];
+        let mut pending = vec![index];
+        while let Some(i) = pending.pop() {
+            for required in &exercises[i].requires {
+                if let Some(r) = exercises.iter().position(|e| &e.name == required) {
+                    if !found[r] {
+                        found[r] = true;
+                        pending.push(r);
+                    }
+                }
+            }
+        }
+        (0..exercises.len()).filter(|&i| found[i] && i != index).collect()
+    }
+}
+
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, PartialEq, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
-    // The path to the file containing the exercise's source code
+    // The path to the file containing the exercise's source code.
+    // For an exercise spanning several files this is the crate root.
     pub path: PathBuf,
+    // The other files of a multi-file exercise, such as the modules
+    // the crate root declares with `mod`
+    #[serde(default)]
+    pub additional_files: Vec<PathBuf>,
     // The mode of the exercise (Test, Compile, or Clippy)
     pub mode: Mode,
     // The hint text associated with the exercise
     pub hint: String,
+    // Translations of the hint by language, such as
+    // `hints.zh-CN = "..."` in info.toml
+    #[serde(default)]
+    pub hints: BTreeMap<String, String>,
+    // How many seconds the compiled exercise may run before it is killed
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    // A file piped into the compiled exercise as its standard input.
+    // Without one, the exercise gets a closed standard input.
+    #[serde(default)]
+    pub stdin_file: Option<PathBuf>,
+    // Environment variables set for the compiled exercise only, such as
+    // `env = { MODE = "test" }` in info.toml
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    // Set for exercises that ask for tests to be written or changed,
+    // whose tests aren't checked against the ones rustlings was built with
+    #[serde(default)]
+    pub editable_tests: bool,
+    // Free form keywords, such as "ownership" or "lifetimes"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // From 1 (easy) to MAX_DIFFICULTY, or 0 if unknown
+    #[serde(default)]
+    pub difficulty: u8,
+    // Compiled with optimizations, for exercises whose tests would take
+    // too long without them. `rustlings run --release` sets it, too.
+    #[serde(default)]
+    pub release: bool,
+    // What passing the exercise is worth when grading, at least 1
+    #[serde(default = "default_points")]
+    pub points: u32,
+    // The names of the exercises this one builds on
+    #[serde(default)]
+    pub requires: Vec<String>,
+    // The lint flags clippy runs a clippy exercise with,
+    // instead of DEFAULT_CLIPPY_ARGS
+    #[serde(default)]
+    pub clippy_args: Option<Vec<String>>,
+    // The Cargo.toml of the package of a cargo-test exercise,
+    // whose `path` is then a file of that package
+    #[serde(default)]
+    pub manifest: Option<PathBuf>,
+    // Configuration options a compile or test exercise is compiled with,
+    // such as `cfg = ["included"]` in info.toml for `--cfg included`
+    #[serde(default)]
+    pub cfg: Vec<String>,
 }
 
 // An enum to track of the state of an Exercise.
@@ -69,7 +477,7 @@ pub enum State {
 }
 
 // The context information of a pending exercise
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
 pub struct ContextLine {
     // The source code that is still pending completion
     pub line: String,
@@ -82,13 +490,20 @@ pub struct ContextLine {
 // The result of compiling an exercise
 pub struct CompiledExercise<'a> {
     exercise: &'a Exercise,
-    _handle: FileHandle,
+    // The binary built by this compilation, and no other
+    binary: FileHandle,
 }
 
 impl<'a> CompiledExercise<'a> {
     // Run the compiled exercise
     pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+        self.exercise.run(&self.binary.0, &[])
+    }
+
+    // Run the compiled exercise, passing `args` on to the test harness
+    // of a test exercise, e.g. a test name filter or `--nocapture`
+    pub fn run_with_args(&self, args: &[String]) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.exercise.run(&self.binary.0, args)
     }
 }
 
@@ -99,30 +514,162 @@ pub struct ExerciseOutput {
     pub stdout: String,
     // The textual contents of the standard error of the binary
     pub stderr: String,
+    // Set if the binary was killed for running longer than this
+    pub timed_out: Option<Duration>,
+}
+
+// Read a pipe to its end, keeping at most `limit` bytes of it. The rest is
+// read and thrown away, so that an exercise printing without end neither
+// blocks nor fills up the memory, and a note on what is missing is added.
+fn read_lossy(pipe: &mut impl Read, limit: Option<usize>, name: &str) -> String {
+    let mut kept = Vec::new();
+    let mut omitted = 0;
+    let mut buffer = [0; 8192];
+    loop {
+        match pipe.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                let room = limit.map_or(n, |limit| limit.saturating_sub(kept.len()).min(n));
+                kept.extend_from_slice(&buffer[..room]);
+                omitted += n - room;
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(_) => break,
+        }
+    }
+    if omitted == 0 {
+        return String::from_utf8_lossy(&kept).to_string();
+    }
+    let whole = whole_chars(&kept);
+    omitted += kept.len() - whole;
+    kept.truncate(whole);
+    let mut output = String::from_utf8_lossy(&kept).to_string();
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str(&format!(
+        "… output truncated ({omitted} bytes omitted), run `rustlings run {name}` to see everything\n"
+    ));
+    output
+}
+
+// How many of the bytes are left once a UTF-8 character the end cut in
+// two is dropped
+fn whole_chars(bytes: &[u8]) -> usize {
+    // The first byte of the last character, skipping its continuation bytes
+    let Some(start) = (bytes.len().saturating_sub(4)..bytes.len()).rev().find(|&i| bytes[i] & 0xc0 != 0x80) else {
+        return bytes.len();
+    };
+    let len = match bytes[start] {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    };
+    if bytes.len() - start < len {
+        start
+    } else {
+        bytes.len()
+    }
+}
+
+// Wait for the child to exit, killing it (and its process group) once
+// `timeout` has passed. Returns `None` if it had to be killed.
+fn wait_timeout(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait().expect("Failed to wait for the exercise") {
+            return Some(status);
+        }
+        if Instant::now() >= deadline {
+            kill(child);
+            let _ = child.wait();
+            return None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[cfg(unix)]
+fn kill(child: &mut Child) {
+    // The child leads its own process group, see `Exercise::run`
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+}
+
+// The index of the line with the signature of the function the line at
+// `index` is in, if any: the closest function above it whose body is still
+// open there. Braces in strings and comments are counted, too, which is
+// close enough for the exercises.
+fn enclosing_fn(lines: &[&str], index: usize) -> Option<usize> {
+    let re = Regex::new(FN_LINE_REGEX).unwrap();
+    let ends_item = |line: &str| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with("//") || line.ends_with(['{', '}', ';'])
+    };
+    let mut depth = 0;
+    for i in (0..index).rev() {
+        depth += lines[i].matches('{').count() as isize - lines[i].matches('}').count() as isize;
+        if depth <= 0 {
+            continue;
+        }
+        // A block the line is in opens here. The signature of a function
+        // may be spread over the lines before, down to its brace.
+        let mut start = i;
+        while start > 0 && !ends_item(lines[start - 1]) {
+            start -= 1;
+        }
+        if let Some(signature) = (start..=i).find(|&j| re.is_match(lines[j])) {
+            return Some(signature);
+        }
+        depth = 0;
+    }
+    None
 }
 
-struct FileHandle;
+// A compiled exercise, removed when this is dropped
+struct FileHandle(String);
 
 impl Drop for FileHandle {
     fn drop(&mut self) {
-        clean();
+        let _ignored = remove_file(&self.0);
     }
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        // Removes whatever was compiled unless it is handed on, even
+        // when compiling fails or panics
+        let handle = FileHandle(temp_file(&self.name));
+        let binary = handle.0.clone();
+        if let Err(e) = fs::create_dir_all(temp_dir()) {
+            return Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: format!("Couldn't create {}: {e}", temp_dir().display()),
+                timed_out: None,
+            });
+        }
         let cmd = match self.mode {
-            Mode::Compile => Command::new("rustc")
-                .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+            Mode::Compile => output(Command::new("rustc")
+                .args([self.path.to_str().unwrap(), "-o", &binary])
+                .args(rustc_color_args())
                 .args(RUSTC_EDITION_ARGS)
-                .output(),
-            Mode::Test => Command::new("rustc")
-                .args(&["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .args(self.rustc_profile_args())
+                .args(self.cfg_args())),
+            Mode::Test => output(Command::new("rustc")
+                .args(["--test", self.path.to_str().unwrap(), "-o", &binary])
+                .args(rustc_color_args())
                 .args(RUSTC_EDITION_ARGS)
-                .output(),
+                .args(self.rustc_profile_args())
+                .args(self.cfg_args())),
             Mode::Clippy => {
+                let _lock = CARGO_TOML_LOCK.lock().unwrap_or_else(|e| e.into_inner());
                 let cargo_toml = format!(
                     r#"[package]
 name = "{}"
@@ -143,28 +690,28 @@ path = "{}.rs""#,
                 // an executable, in addition to running clippy. With a
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
-                Command::new("rustc")
-                    .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
+                output(Command::new("rustc")
+                    .args([self.path.to_str().unwrap(), "-o", &binary])
+                    .args(rustc_color_args())
                     .args(RUSTC_EDITION_ARGS)
-                    .output()
+                    .args(self.rustc_profile_args()))
                     .expect("Failed to compile!");
                 // Due to an issue with Clippy, a cargo clean is required to catch all lints.
                 // See https://github.com/rust-lang/rust-clippy/issues/2604
                 // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
                 // https://github.com/rust-lang/rust-clippy/issues/3837
-                Command::new("cargo")
-                    .args(&["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
+                output(Command::new("cargo")
+                    .args(["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
+                    .args(rustc_color_args()))
                     .expect("Failed to run 'cargo clean'");
-                Command::new("cargo")
-                    .args(&["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(&["--", "-D", "warnings", "-D", "clippy::float_cmp"])
-                    .output()
+                output(Command::new("cargo")
+                    .args(["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
+                    .args(rustc_color_args())
+                    .arg("--")
+                    .args(self.clippy_args()))
             },
             Mode::BuildScript => {
+                let _lock = CARGO_TOML_LOCK.lock().unwrap_or_else(|e| e.into_inner());
                 let cargo_toml = format!(
                     r#"[package]
 name = "{}"
@@ -182,55 +729,229 @@ path = "{}.rs""#,
                 };
                 fs::write(BUILD_SCRIPT_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
 
-                Command::new("cargo")
-                    .args(&["test", "--manifest-path", BUILD_SCRIPT_CARGO_TOML_PATH])
-                    .output()
+                output(Command::new("cargo")
+                    .args(["test", "--manifest-path", BUILD_SCRIPT_CARGO_TOML_PATH])
+                    .args(rustc_color_args())
+                    .args(self.cargo_profile_args())
+                    .envs(&self.env))
             }
+            // Diagnostics are rendered to stderr as usual, stdout lists
+            // the built artifacts as JSON
+            Mode::CargoTest => output(Command::new("cargo")
+                .args(["test", "--no-run", "--message-format=json-render-diagnostics", "--manifest-path"])
+                .arg(self.manifest.as_deref().unwrap_or(Path::new("Cargo.toml")))
+                .args(["--target-dir", CARGO_TARGET_DIR])
+                .args(rustc_color_args())
+                .args(self.cargo_profile_args())),
         }
         .expect("Failed to run 'compile' command.");
 
+        // The test harness cargo built is run like the one of a test exercise
+        let copied = match self.mode {
+            Mode::CargoTest if cmd.status.success() => self.copy_test_binary(&cmd.stdout, &binary),
+            _ => Ok(()),
+        };
+        if let Err(e) = copied {
+            return Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: e,
+                timed_out: None,
+            });
+        }
+
         if cmd.status.success() {
             Ok(CompiledExercise {
                 exercise: self,
-                _handle: FileHandle,
+                binary: handle,
             })
         } else {
-            clean();
             Err(ExerciseOutput {
-                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
+                stdout: match self.mode {
+                    Mode::CargoTest => String::new(),
+                    _ => String::from_utf8_lossy(&cmd.stdout).to_string(),
+                },
                 stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+                timed_out: None,
             })
         }
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
+    // Copy the test harness built from the exercise's file to `binary`,
+    // given the JSON messages of `cargo test --no-run`
+    fn copy_test_binary(&self, messages: &[u8], binary: &str) -> Result<(), String> {
+        let own_path = self.path.canonicalize().ok();
+        let mut harnesses = Vec::new();
+        for line in String::from_utf8_lossy(messages).lines() {
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+            if message["reason"] != "compiler-artifact" || message["profile"]["test"] != true {
+                continue;
+            }
+            if let Some(executable) = message["executable"].as_str() {
+                let source = message["target"]["src_path"].as_str().map(|p| Path::new(p).canonicalize().ok());
+                harnesses.push((source.flatten() == own_path, PathBuf::from(executable)));
+            }
+        }
+        // Prefer the harness of the exercise's own file, a package
+        // with a single test target needn't point `path` at it
+        let harness = match harnesses.iter().find(|(own, _)| *own) {
+            Some((_, harness)) => harness,
+            None => match harnesses.as_slice() {
+                [(_, harness)] => harness,
+                _ => return Err(format!("cargo built no tests from {}", self.path.display())),
+            },
+        };
+        fs::copy(harness, binary)
+            .map(|_| ())
+            .map_err(|e| format!("Couldn't copy the tests of {}: {e}", self.path.display()))
+    }
+
+    // Every file of the exercise, starting with the crate root
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.path.as_path()).chain(self.additional_files.iter().map(PathBuf::as_path))
+    }
+
+    // The lint flags of a clippy exercise
+    pub fn clippy_args(&self) -> Vec<&str> {
+        match &self.clippy_args {
+            Some(args) => args.iter().map(String::as_str).collect(),
+            None => DEFAULT_CLIPPY_ARGS.to_vec(),
+        }
+    }
+
+    fn cfg_args(&self) -> impl Iterator<Item = &str> {
+        self.cfg.iter().flat_map(|cfg| ["--cfg", cfg.as_str()])
+    }
+
+    // The hint translated to `lang`, such as `zh-CN`, or the English
+    // one if there is no such translation. A translation to `zh` also
+    // serves `zh-CN`, and the other way round.
+    pub fn hint_in(&self, lang: &str) -> &str {
+        let primary = |tag: &str| tag.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        let exact = self.hints.iter().find(|(tag, _)| tag.eq_ignore_ascii_case(lang));
+        let close = || self.hints.iter().find(|(tag, _)| primary(tag) == primary(lang));
+        exact
+            .or_else(close)
+            .map(|(_, hint)| hint.as_str())
+            .filter(|hint| !hint.trim().is_empty())
+            .unwrap_or(&self.hint)
+    }
+
+    // Whether the exercise is tagged with `tag`, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    // The profile the exercise is compiled with, "release" or "debug"
+    pub fn profile(&self) -> &'static str {
+        if self.release {
+            "release"
+        } else {
+            "debug"
+        }
+    }
+
+    fn rustc_profile_args(&self) -> &'static [&'static str] {
+        if self.release {
+            &["-C", "opt-level=3"]
+        } else {
+            &[]
+        }
+    }
+
+    fn cargo_profile_args(&self) -> &'static [&'static str] {
+        if self.release {
+            &["--release"]
+        } else {
+            &[]
+        }
+    }
+
+    // How long the compiled exercise may run
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+
+    fn run(&self, binary: &str, test_args: &[String]) -> Result<ExerciseOutput, ExerciseOutput> {
         let arg = match self.mode {
-            Mode::Test => "--show-output",
+            Mode::Test | Mode::CargoTest => "--show-output",
             Mode::BuildScript => return Ok(ExerciseOutput {
                 stdout: "".to_string(),
                 stderr: "".to_string(),
+                timed_out: None,
             }),
             _ => "",
         };
-        println!("pa={}", temp_file());
-        let cmd = Command::new(&temp_file())
+        // A closed stdin makes reads return at once instead of waiting
+        // for input nobody is going to type
+        let stdin = match &self.stdin_file {
+            Some(path) => match File::open(path) {
+                Ok(file) => Stdio::from(file),
+                Err(e) => return Err(ExerciseOutput {
+                    stdout: String::new(),
+                    stderr: format!("Couldn't open the input {}: {e}", path.display()),
+                    timed_out: None,
+                }),
+            },
+            None => Stdio::null(),
+        };
+        let mut command = Command::new(binary);
+        command
             .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
+            .args(harness_color_args(self.mode, test_args, console::colors_enabled()))
+            .args(test_args)
+            .envs(backtrace::rust_backtrace().map(|value| ("RUST_BACKTRACE", value)))
+            .envs(&self.env)
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // Run in a process group of its own, so that a timeout also takes
+        // down whatever the exercise spawned
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        let mut child = command.spawn().expect("Failed to run 'run' command");
+        running().push((child.id(), cfg!(unix)));
+
+        // Drain the pipes while waiting, a chatty exercise would block otherwise
+        let mut stdout = child.stdout.take().unwrap();
+        let mut stderr = child.stderr.take().unwrap();
+        let (limit, name) = (output_limit(), self.name.clone());
+        let stdout = thread::spawn(move || read_lossy(&mut stdout, limit, &name));
+        let name = self.name.clone();
+        let stderr = thread::spawn(move || read_lossy(&mut stderr, limit, &name));
 
-        let output = ExerciseOutput {
-            stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+        let timeout = self.timeout();
+        let status = wait_timeout(&mut child, timeout);
+        running().retain(|&(id, _)| id != child.id());
+        let mut output = ExerciseOutput {
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+            timed_out: if status.is_none() { Some(timeout) } else { None },
         };
+        // Panics are in the output of the test harness and on the standard
+        // error of other exercises
+        if backtrace::condensing() {
+            output.stdout = backtrace::condense(&output.stdout);
+            output.stderr = backtrace::condense(&output.stderr);
+        }
 
-        if cmd.status.success() {
-            Ok(output)
-        } else {
-            Err(output)
+        match status {
+            Some(status) if status.success() => Ok(output),
+            _ => Err(output),
         }
     }
 
     pub fn state(&self) -> State {
+        self.state_with(context())
+    }
+
+    // The state, with `context` lines around the `I AM NOT DONE` comment
+    // and the signature of the function it is in, if it is in one
+    fn state_with(&self, context: Context) -> State {
         let mut source_file =
             File::open(&self.path).expect("We were unable to open the exercise file!");
 
@@ -248,19 +969,20 @@ path = "{}.rs""#,
             return State::Done;
         }
 
-        let matched_line_index = source
-            .lines()
-            .enumerate()
-            .find_map(|(i, line)| if re.is_match(line) { Some(i) } else { None })
+        let lines: Vec<&str> = source.lines().collect();
+        let matched_line_index = lines
+            .iter()
+            .position(|line| re.is_match(line))
             .expect("This should not happen at all");
 
-        let min_line = ((matched_line_index as i32) - (CONTEXT as i32)).max(0) as usize;
-        let max_line = matched_line_index + CONTEXT;
+        let min_line = matched_line_index.saturating_sub(context.before);
+        let max_line = matched_line_index + context.after;
+        let signature = enclosing_fn(&lines, matched_line_index).filter(|&i| i < min_line);
 
-        let context = source
-            .lines()
+        let context = lines
+            .iter()
             .enumerate()
-            .filter(|&(i, _)| i >= min_line && i <= max_line)
+            .filter(|&(i, _)| (i >= min_line && i <= max_line) || Some(i) == signature)
             .map(|(i, line)| ContextLine {
                 line: line.to_string(),
                 number: i + 1,
@@ -271,6 +993,20 @@ path = "{}.rs""#,
         State::Pending(context)
     }
 
+    // Remove the line with the `I AM NOT DONE` comment from the exercise,
+    // along with a blank line that would otherwise be left doubled.
+    // Lines with anything else on them are left alone.
+    // Returns whether the file was changed.
+    pub fn remove_marker(&self) -> io::Result<bool> {
+        let source = fs::read_to_string(&self.path)?;
+        let stripped = strip_marker(&source);
+        if stripped == source {
+            return Ok(false);
+        }
+        fs::write(&self.path, stripped)?;
+        Ok(true)
+    }
+
     // Check that the exercise looks to be solved using self.state()
     // This is not the best way to check since
     // the user can just remove the "I AM NOT DONE" string from the file
@@ -280,17 +1016,78 @@ path = "{}.rs""#,
     pub fn looks_done(&self) -> bool {
         self.state() == State::Done
     }
+
+    // Where the reference solution of the exercise would be,
+    // e.g. `solutions/algorithm/algorithm1.rs`
+    pub fn solution_path(&self) -> PathBuf {
+        let relative = self.path.strip_prefix("exercises").unwrap_or(&self.path);
+        Path::new(SOLUTIONS_DIR).join(relative)
+    }
+
+    // The topic of the exercise is the directory it lives in below
+    // `exercises/`, e.g. `algorithm` or `error_handling`.
+    // Exercises sitting directly in `exercises/` (the quizzes) use their
+    // file name without the trailing number instead.
+    pub fn topic(&self) -> String {
+        let mut dirs = self
+            .path
+            .parent()
+            .into_iter()
+            .flat_map(|p| p.components())
+            .filter_map(|c| c.as_os_str().to_str())
+            .filter(|c| *c != "." && *c != "exercises");
+        match dirs.next() {
+            Some(dir) => dir.to_string(),
+            None => self
+                .path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .trim_end_matches(|c: char| c.is_ascii_digit())
+                .to_string(),
+        }
+    }
+}
+
+fn strip_marker(source: &str) -> String {
+    let marker = Regex::new(MARKER_LINE_REGEX).unwrap();
+    let is_blank = |line: &str| line.trim().is_empty();
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        if !marker.is_match(lines[i]) {
+            kept.push(lines[i]);
+            i += 1;
+            continue;
+        }
+        // At the top of the file or between blank lines, the blank line
+        // after the marker goes as well
+        let after_blank = kept.last().is_none_or(|line| is_blank(line));
+        if after_blank && lines.get(i + 1).is_some_and(|line| is_blank(line)) {
+            i += 1;
+        }
+        i += 1;
+    }
+    kept.concat()
 }
 
 impl Display for Exercise {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self.path.to_str().unwrap())
+        write!(f, "{}", display_path(&self.path))
     }
 }
 
-#[inline]
-fn clean() {
-    let _ignored = remove_file(&temp_file());
+// `path` with `/` replaced by `separator`
+fn with_separator(path: &str, separator: char) -> String {
+    path.chars().map(|c| if c == '/' { separator } else { c }).collect()
+}
+
+/// `path` as it is shown to the user, with the separators of the platform
+/// only. Paths from info.toml use `/`, even on Windows, while those joined
+/// to them use `\`, and a mix of both can't be pasted into every editor.
+pub fn display_path(path: &Path) -> String {
+    with_separator(&path.display().to_string(), std::path::MAIN_SEPARATOR)
 }
 
 #[cfg(test)]
@@ -298,18 +1095,67 @@ mod test {
     use super::*;
     use std::path::Path;
 
+    // Parse a single info.toml, ignoring its includes
+    fn parse(toml_str: &str) -> Result<ExerciseList, String> {
+        let (exercises, _) = ExerciseList::parse_file(toml_str, "info.toml")?;
+        let origins = vec!["info.toml".to_string(); exercises.len()];
+        ExerciseList::new(exercises, &origins)
+    }
+
     #[test]
     fn test_clean() {
-        File::create(&temp_file()).unwrap();
         let exercise = Exercise {
             name: String::from("example"),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
+            additional_files: Vec::new(),
             mode: Mode::Compile,
             hint: String::from(""),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
         };
         let compiled = exercise.compile().unwrap();
+        let binary = compiled.binary.0.clone();
+        assert!(Path::new(&binary).exists());
         drop(compiled);
-        assert!(!Path::new(&temp_file()).exists());
+        assert!(!Path::new(&binary).exists());
+    }
+
+    #[test]
+    fn test_temp_file_per_exercise() {
+        assert_ne!(temp_file("enums1"), temp_file("enums2"));
+        assert_ne!(temp_file("enums1"), temp_file("enums1"));
+        let other_thread = std::thread::spawn(|| temp_file("enums1")).join().unwrap();
+        assert_ne!(temp_file("enums1"), other_thread);
+        assert!(temp_file("enums1").ends_with(&format!("enums1{}", env::consts::EXE_SUFFIX)));
+    }
+
+    #[test]
+    fn test_harness_colors_follow_the_choice() {
+        assert_eq!(harness_color_args(Mode::Test, &[], true), ["--color", "always"]);
+        assert_eq!(harness_color_args(Mode::CargoTest, &[], false), ["--color", "never"]);
+        assert!(harness_color_args(Mode::Compile, &[], true).is_empty());
+        assert!(harness_color_args(Mode::Clippy, &[], true).is_empty());
+        let chosen = ["--color=never".to_string()];
+        assert!(harness_color_args(Mode::Test, &chosen, true).is_empty());
+    }
+
+    #[test]
+    fn test_paths_use_one_separator() {
+        assert_eq!(with_separator(r"packs\os/exercises/os1.rs", '\\'), r"packs\os\exercises\os1.rs");
+        assert_eq!(with_separator("exercises/intro/intro2.rs", '/'), "exercises/intro/intro2.rs");
+        let native = format!("exercises{0}intro{0}intro2.rs", std::path::MAIN_SEPARATOR);
+        assert_eq!(display_path(Path::new("exercises/intro/intro2.rs")), native);
     }
 
     #[test]
@@ -317,8 +1163,22 @@ mod test {
         let exercise = Exercise {
             name: "pending_exercise".into(),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
+            additional_files: Vec::new(),
             mode: Mode::Compile,
             hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
         };
 
         let state = exercise.state();
@@ -353,27 +1213,358 @@ mod test {
         assert_eq!(state, State::Pending(expected));
     }
 
+    #[test]
+    fn test_context_around_the_marker() {
+        let exercise = Exercise {
+            path: PathBuf::from("tests/fixture/state/pending_in_fn.rs"),
+            ..ExerciseList::load(Path::new("tests/fixture/state/info.toml")).unwrap().exercises[0].clone()
+        };
+        let numbers = |context: Context| match exercise.state_with(context) {
+            State::Pending(lines) => lines.iter().map(|l| (l.number, l.important)).collect::<Vec<_>>(),
+            State::Done => panic!("the exercise is pending"),
+        };
+
+        // The signature of the function the comment is in is always shown
+        assert_eq!(
+            numbers(Context { before: 1, after: 2 }),
+            [(3, false), (9, false), (10, true), (11, false), (12, false)]
+        );
+        assert_eq!(numbers(Context { before: 0, after: 0 }), [(3, false), (10, true)]);
+        assert_eq!(numbers(Context { before: 7, after: 0 }).first(), Some(&(3, false)));
+        assert_eq!(numbers(Context { before: 7, after: 0 }).len(), 8);
+    }
+
+    #[test]
+    fn test_output_is_cut_between_characters() {
+        let output = read_lossy(&mut "aé".repeat(1000).as_bytes(), Some(5), "chatty");
+        assert_eq!(
+            output,
+            "aéa\n… output truncated (2996 bytes omitted), run `rustlings run chatty` to see everything\n"
+        );
+        assert_eq!(read_lossy(&mut "aé".as_bytes(), Some(3), "chatty"), "aé");
+        assert_eq!(read_lossy(&mut "aé".repeat(1000).as_bytes(), None, "chatty").len(), 3000);
+        assert_eq!(whole_chars("€".as_bytes()), 3);
+        assert_eq!(whole_chars(&"€".as_bytes()[..2]), 0);
+    }
+
+    #[test]
+    fn test_context_parsing() {
+        assert_eq!("5".parse(), Ok(Context { before: 5, after: 5 }));
+        assert_eq!("2,8".parse(), Ok(Context { before: 2, after: 8 }));
+        assert!("2,".parse::<Context>().is_err());
+        assert!("many".parse::<Context>().is_err());
+    }
+
     #[test]
     fn test_finished_exercise() {
         let exercise = Exercise {
             name: "finished_exercise".into(),
             path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
+            additional_files: Vec::new(),
             mode: Mode::Compile,
             hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
         };
 
         assert_eq!(exercise.state(), State::Done);
     }
 
+    #[test]
+    fn test_topic() {
+        let topic = |path: &str| Exercise {
+            name: String::new(),
+            path: PathBuf::from(path),
+            additional_files: Vec::new(),
+            mode: Mode::Compile,
+            hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
+        }
+        .topic();
+
+        assert_eq!(topic("exercises/algorithm/algorithm1.rs"), "algorithm");
+        assert_eq!(topic("exercises/error_handling/errors2.rs"), "error_handling");
+        assert_eq!(topic("exercises/quiz1.rs"), "quiz");
+        assert_eq!(topic("compSuccess.rs"), "compSuccess");
+    }
+
+    #[test]
+    fn test_solution_path() {
+        let exercise = Exercise {
+            name: "algorithm1".into(),
+            path: PathBuf::from("exercises/algorithm/algorithm1.rs"),
+            additional_files: Vec::new(),
+            mode: Mode::Test,
+            hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
+        };
+        assert_eq!(exercise.solution_path(), Path::new("solutions/algorithm/algorithm1.rs"));
+    }
+
     #[test]
     fn test_exercise_with_output() {
         let exercise = Exercise {
             name: "exercise_with_output".into(),
             path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
+            additional_files: Vec::new(),
             mode: Mode::Test,
             hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
         };
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
     }
+
+    #[test]
+    fn concurrent_compilations_run_their_own_binary() {
+        let exercise = Exercise {
+            name: "concurrent".into(),
+            path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
+            additional_files: Vec::new(),
+            mode: Mode::Test,
+            hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
+        };
+        let binaries: Vec<String> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..3)
+                            .map(|_| {
+                                let compiled = exercise.compile().unwrap();
+                                let out = compiled.run().unwrap();
+                                assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
+                                compiled.binary.0.clone()
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+        });
+        let mut unique = binaries.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), binaries.len());
+        assert!(binaries.iter().all(|binary| !Path::new(binary).exists()));
+
+        // Panicking while running it still removes the binary
+        let binary = Mutex::new(String::new());
+        let panicked = std::panic::catch_unwind(|| {
+            let compiled = exercise.compile().unwrap();
+            *binary.lock().unwrap() = compiled.binary.0.clone();
+            panic!("while running {}", compiled.binary.0);
+        });
+        assert!(panicked.is_err());
+        assert!(!Path::new(&*binary.lock().unwrap()).exists());
+    }
+
+    #[test]
+    fn test_translated_hints() {
+        let info = r#"
+            [[exercises]]
+            name = "intro2"
+            path = "exercises/intro/intro2.rs"
+            mode = "compile"
+            hint = "Add an argument after the format string."
+            hints.zh-CN = "在格式字符串后面添加一个参数。"
+            hints.fr = ""
+        "#;
+        let exercise = &parse(info).unwrap().exercises[0];
+        assert_eq!(exercise.hint_in("zh-CN"), "在格式字符串后面添加一个参数。");
+        assert_eq!(exercise.hint_in("zh_cn"), "在格式字符串后面添加一个参数。");
+        assert_eq!(exercise.hint_in("zh"), "在格式字符串后面添加一个参数。");
+        // Missing or empty translations fall back to English
+        assert_eq!(exercise.hint_in("de"), exercise.hint);
+        assert_eq!(exercise.hint_in("fr"), exercise.hint);
+
+        let written = toml::to_string(&toml::from_str::<toml::Value>(info).unwrap()).unwrap();
+        assert_eq!(&parse(&written).unwrap().exercises[0], exercise);
+    }
+
+    #[test]
+    fn test_parse_names_malformed_exercise() {
+        let info = r#"
+            [[exercises]]
+            name = "lifetimes1"
+            path = "exercises/lifetimes/lifetimes1.rs"
+            mode = "compile"
+            hint = ""
+            tags = ["lifetimes", "borrowing"]
+            difficulty = 3
+
+            [[exercises]]
+            name = "lifetimes2"
+            path = "exercises/lifetimes/lifetimes2.rs"
+            mode = "compile"
+            hint = ""
+            tags = "lifetimes"
+        "#;
+        let error = parse(info).err().unwrap();
+        assert!(error.starts_with("The exercise lifetimes2 in info.toml is invalid"), "{error}");
+
+        let exercises = parse(&info.replace(r#"tags = "lifetimes""#, "")).unwrap().exercises;
+        assert!(exercises[0].has_tag("Lifetimes"));
+        assert_eq!(exercises[0].difficulty, 3);
+        assert!(exercises[1].tags.is_empty());
+        assert_eq!(exercises[1].difficulty, 0);
+
+        let error = parse(&info.replace("difficulty = 3", "difficulty = 6")).err().unwrap();
+        assert!(error.contains("lifetimes1"), "{error}");
+        assert!(error.contains("between 0 (unknown) and 5"), "{error}");
+    }
+
+    #[test]
+    fn test_points_default_to_one() {
+        let info = r#"
+            [[exercises]]
+            name = "algorithm1"
+            path = "exercises/algorithm/algorithm1.rs"
+            mode = "test"
+            hint = ""
+            points = 5
+
+            [[exercises]]
+            name = "intro2"
+            path = "exercises/intro/intro2.rs"
+            mode = "compile"
+            hint = ""
+        "#;
+        let exercises = parse(info).unwrap().exercises;
+        assert_eq!(exercises[0].points, 5);
+        assert_eq!(exercises[1].points, 1);
+
+        let error = parse(&info.replace("points = 5", "points = 0")).err().unwrap();
+        assert_eq!(
+            error,
+            "The exercise algorithm1 in info.toml is worth 0 points, but it must be worth at least 1"
+        );
+    }
+
+    #[test]
+    fn test_cargo_test_needs_manifest() {
+        let info = r#"
+            [[exercises]]
+            name = "serde1"
+            path = "exercises/ext/serde1/src/lib.rs"
+            manifest = "exercises/ext/serde1/Cargo.toml"
+            mode = "cargo-test"
+            hint = ""
+        "#;
+        let exercises = parse(info).unwrap().exercises;
+        assert!(matches!(exercises[0].mode, Mode::CargoTest));
+        assert_eq!(exercises[0].manifest.as_deref(), Some(Path::new("exercises/ext/serde1/Cargo.toml")));
+
+        let error = parse(&info.replace("manifest = ", "# manifest = ")).err().unwrap();
+        assert!(error.contains("serde1 in info.toml needs a manifest"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_checks_requires() {
+        let entry = |name: &str, requires: &str| {
+            format!("[[exercises]]\nname = \"{name}\"\npath = \"{name}.rs\"\nmode = \"compile\"\nhint = \"\"\nrequires = [{requires}]\n")
+        };
+        let info = entry("queue1", "") + &entry("graph1", "\"queue1\"") + &entry("graph2", "\"graph1\"");
+        let exercises = parse(&info).unwrap().exercises;
+        assert_eq!(ExerciseList::prerequisites(&exercises, 2), [0, 1]);
+        assert!(ExerciseList::prerequisites(&exercises, 0).is_empty());
+
+        let error = parse(&(info.clone() + &entry("graph3", "\"graph9\""))).err().unwrap();
+        assert!(error.contains("graph3 in info.toml requires graph9"), "{error}");
+
+        let cyclic = entry("a", "\"c\"") + &entry("b", "\"a\"") + &entry("c", "\"b\"");
+        let error = parse(&cyclic).err().unwrap();
+        assert!(error.ends_with("a -> c -> b -> a"), "{error}");
+    }
+
+    #[test]
+    fn test_load_follows_includes() {
+        let list = ExerciseList::load(Path::new("tests/fixture/include/info.toml")).unwrap();
+        let paths: Vec<&Path> = list.exercises.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("tests/fixture/include/builtIn.rs"),
+                Path::new("tests/fixture/include/pack/fromPack.rs")
+            ]
+        );
+
+        let dir = std::env::temp_dir().join(format!("rustlings_include_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("info.toml"), "include = [\"info.toml\"]\n").unwrap();
+        let error = ExerciseList::load(&dir.join("info.toml")).err().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(error.ends_with("info.toml includes itself"), "{error}");
+    }
+
+    #[test]
+    fn test_strip_marker_keeps_layout() {
+        let source = "// intro1.rs\n//\n// Execute `rustlings hint intro1`.\n\n// I AM NOT DONE\n\nfn main() {}\n";
+        assert_eq!(strip_marker(source), "// intro1.rs\n//\n// Execute `rustlings hint intro1`.\n\nfn main() {}\n");
+        assert_eq!(strip_marker("//I  AM NOT DONE\r\nfn main() {}\r\n"), "fn main() {}\r\n");
+        assert_eq!(strip_marker("  /// I AM NOT DONE\n\nfn main() {}\n"), "fn main() {}\n");
+        assert_eq!(strip_marker("use std::fmt;\n// I AM NOT DONE\n\nfn main() {}\n"), "use std::fmt;\n\nfn main() {}\n");
+        // Only lines holding nothing but the marker are removed
+        let source = "// I AM NOT DONE, says the comment\nfn main() {}\n";
+        assert_eq!(strip_marker(source), source);
+    }
 }
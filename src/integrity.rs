@@ -0,0 +1,49 @@
+use crate::exercise::Exercise;
+use crate::test_blocks::tests_hash;
+use crate::test_hashes::TEST_HASHES;
+use std::fs;
+use std::path::Path;
+
+/// Where the hashes of the tests as they came are kept, relative to the
+/// rustlings directory. It is checked in rather than worked out when
+/// rustlings is built, as a build of a student's repository would only see
+/// the tests as the student left them.
+pub const TEST_HASHES_FILE: &str = "src/test_hashes.rs";
+
+fn recorded(file: &Path) -> Option<u64> {
+    let key: Vec<String> = file
+        .components()
+        .filter(|c| c.as_os_str() != ".")
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let key = key.join("/");
+    TEST_HASHES.iter().find(|(path, _)| *path == key).map(|&(_, hash)| hash)
+}
+
+/// Whether the tests of the exercise differ from the ones recorded in
+/// `TEST_HASHES_FILE`, or are gone. Exercises that ask for tests to be
+/// written (`editable_tests` in info.toml) are never modified, and neither
+/// are the files that have no recorded tests.
+pub fn tests_modified(exercise: &Exercise) -> bool {
+    if exercise.editable_tests {
+        return false;
+    }
+    exercise.files().any(|file| {
+        let Some(hash) = recorded(file) else { return false };
+        // A missing file is left to the compiler to report
+        fs::read_to_string(file).is_ok_and(|source| tests_hash(&source) != Some(hash))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dev;
+
+    #[test]
+    fn recorded_hashes_are_up_to_date() {
+        let recorded = fs::read_to_string(TEST_HASHES_FILE).unwrap();
+        let current = dev::test_hashes(Path::new("exercises"));
+        assert!(recorded == current, "the tests changed, run `rustlings dev hash-tests`");
+    }
+}
@@ -1,30 +1,56 @@
-use crate::exercise::{Exercise, ExerciseList};
+use crate::completions::Shell;
+use crate::exercise::{Exercise, ExerciseList, Mode, State, SOLUTIONS_DIR};
+use crate::integrity::TEST_HASHES_FILE;
+use crate::progress::{Progress, PROGRESS_FILE};
 use crate::project::RustAnalyzerProject;
 use crate::run::{reset, run};
-use crate::verify::verify;
+use crate::ui::{format_duration, set_color_choice, ColorChoice};
+use crate::verify::{
+    first_error_line, passes, status, verify, verify_keep_going, verify_parallel, Failure, PromptHistory, Status,
+};
 use argh::FromArgs;
 use console::Emoji;
 use notify::DebouncedEvent;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use std::cell::{OnceCell, RefCell};
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, prelude::*};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::time::{UNIX_EPOCH, SystemTime};
 
 #[macro_use]
 mod ui;
 
+mod autocommit;
+mod backtrace;
+mod completions;
+mod dev;
+mod diff;
+mod editor;
 mod exercise;
+mod integrity;
+mod markdown;
+mod notification;
+mod originals;
+mod progress;
 mod project;
+mod quiz;
+mod report;
 mod run;
+mod score;
+mod signals;
+mod test_blocks;
+mod test_hashes;
+mod tui;
 mod verify;
 
 // In sync with crate version
@@ -39,6 +65,42 @@ struct Args {
     /// show the executable version
     #[argh(switch, short = 'v')]
     version: bool,
+    /// kill exercises running longer than this many seconds, instead of the
+    /// `timeout_secs` from info.toml or the default of 30
+    #[argh(option)]
+    timeout: Option<u64>,
+    /// when to color the output: `always`, `auto` (the default,
+    /// only on a terminal unless NO_COLOR is set) or `never`
+    #[argh(option, default = "ColorChoice::Auto")]
+    color: ColorChoice,
+    /// never color the output, the same as `--color never`
+    #[argh(switch)]
+    no_color: bool,
+    /// show the hints in this language where they have been translated,
+    /// e.g. `zh-CN`. Defaults to $RUSTLINGS_LANG.
+    #[argh(option)]
+    lang: Option<String>,
+    /// compile the exercises to this directory instead of
+    /// target/rustlings-tmp. Defaults to $RUSTLINGS_TEMP_DIR.
+    #[argh(option)]
+    temp_dir: Option<PathBuf>,
+    /// load info.toml and the exercises from this directory instead of
+    /// the current one. Defaults to $RUSTLINGS_DIR.
+    #[argh(option)]
+    exercise_dir: Option<PathBuf>,
+    /// how many lines to show around the I AM NOT DONE comment, either as
+    /// many before as after it, e.g. 5, or before and after, e.g. 2,8.
+    /// Defaults to $RUSTLINGS_CONTEXT or 2.
+    #[argh(option)]
+    context: Option<exercise::Context>,
+    /// keep at most this many bytes of what an exercise prints, or all of
+    /// it with 0. Defaults to 65536, and to all of it for rustlings run.
+    #[argh(option)]
+    output_limit: Option<usize>,
+    /// show the whole backtrace of a panicking exercise, instead of only
+    /// the frames of the exercise itself
+    #[argh(switch)]
+    full_backtrace: bool,
     #[argh(subcommand)]
     nested: Option<Subcommands>,
 }
@@ -49,11 +111,20 @@ enum Subcommands {
     Verify(VerifyArgs),
     Watch(WatchArgs),
     Run(RunArgs),
+    Edit(EditArgs),
     Reset(ResetArgs),
     Hint(HintArgs),
     List(ListArgs),
     Lsp(LspArgs),
-    CicvVerify(CicvVerifyArgs)
+    Solution(SolutionArgs),
+    Score(ScoreArgs),
+    Report(ReportArgs),
+    Quiz(QuizArgs),
+    Stats(StatsArgs),
+    Clean(CleanArgs),
+    Dev(DevArgs),
+    CicvVerify(CicvVerifyArgs),
+    Completions(CompletionsArgs),
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -63,7 +134,40 @@ struct CicvVerifyArgs {}
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "verify")]
 /// Verifies all exercises according to the recommended order
-struct VerifyArgs {}
+struct VerifyArgs {
+    /// how many exercises to verify at the same time,
+    /// defaults to the number of CPU cores
+    #[argh(option, short = 'j')]
+    jobs: Option<usize>,
+    /// verify every exercise again, even those that passed
+    /// before and haven't changed since
+    #[argh(switch)]
+    force_recheck: bool,
+    /// start verifying at this exercise, skipping the ones before it in info.toml
+    #[argh(option)]
+    from: Option<String>,
+    /// only verify the exercises of this topic, or those whose name
+    /// or path matches this glob, e.g. `algorithm*`
+    #[argh(option)]
+    only: Option<String>,
+    /// only verify the exercises with this tag
+    #[argh(option)]
+    tag: Option<String>,
+    /// print a single plain line per exercise for CI logs, without progress
+    /// bar or emoji. This is the default when stdout isn't a terminal.
+    #[argh(switch)]
+    ci: bool,
+    /// the same as --ci
+    #[argh(switch, short = 'q')]
+    quiet: bool,
+    /// show the progress bar and full output even when stdout isn't a terminal
+    #[argh(switch)]
+    interactive: bool,
+    /// verify every exercise instead of stopping at the first failure,
+    /// then sum up the failures by topic
+    #[argh(switch)]
+    keep_going: bool,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "watch")]
@@ -72,6 +176,26 @@ struct WatchArgs {
     /// show hints on success
     #[argh(switch)]
     success_hints: bool,
+    /// verify every exercise again instead of resuming
+    /// from the progress saved by earlier runs
+    #[argh(switch)]
+    force_recheck: bool,
+    /// send a desktop notification when the current exercise
+    /// starts passing or failing
+    #[argh(switch)]
+    notify: bool,
+    /// keep the output of earlier runs instead of clearing
+    /// the screen before every run
+    #[argh(switch)]
+    no_clear: bool,
+    /// refuse to jump to exercises whose prerequisites
+    /// aren't done yet, instead of only warning
+    #[argh(switch)]
+    strict_order: bool,
+    /// commit every exercise you complete to git, as
+    /// "rustlings: complete <name>"
+    #[argh(switch)]
+    git_autocommit: bool,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -79,26 +203,152 @@ struct WatchArgs {
 /// Runs/Tests a single exercise
 struct RunArgs {
     #[argh(positional)]
-    /// the name of the exercise
-    name: String,
+    /// the name of the exercise, the current one if omitted, then the
+    /// arguments for its tests, e.g. `rustlings run hashmaps3 -- some_test`
+    args: Vec<String>,
+    /// let the tests print their output as they run, passed on to them
+    #[argh(switch)]
+    nocapture: bool,
+    /// compile the exercise with optimizations, as if info.toml asked for them
+    #[argh(switch)]
+    release: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "edit")]
+/// Opens an exercise in $VISUAL or $EDITOR
+struct EditArgs {
+    #[argh(positional)]
+    /// the name of the exercise, the current one if omitted
+    name: Option<String>,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "reset")]
-/// Resets a single exercise using "git stash -- <filename>"
+/// Restores exercises to the state they were in when rustlings first ran
 struct ResetArgs {
     #[argh(positional)]
     /// the name of the exercise
-    name: String,
+    name: Option<String>,
+    /// reset every exercise
+    #[argh(switch)]
+    all: bool,
+    /// don't ask for confirmation
+    #[argh(switch, short = 'f')]
+    force: bool,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "hint")]
 /// Returns a hint for the given exercise
 struct HintArgs {
+    #[argh(positional)]
+    /// the name of the exercise, the current one if omitted,
+    /// or the topic with --all
+    name: Option<String>,
+    /// print the hints of every exercise of the topic
+    #[argh(switch)]
+    all: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "solution")]
+/// Shows the reference solution of the given exercise
+struct SolutionArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    /// show the differences between your code and the solution
+    #[argh(switch, short = 'd')]
+    diff: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "score")]
+/// Verifies every exercise without stopping and reports the result as JSON
+struct ScoreArgs {
+    /// write the report to this file instead of printing it
+    #[argh(option, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "report")]
+/// Writes the recorded progress as a Markdown or HTML report
+struct ReportArgs {
+    /// the format of the report: `md` (the default) or `html`
+    #[argh(option, default = "report::Format::Markdown")]
+    format: report::Format,
+    /// write the report to this file instead of printing it
+    #[argh(option, short = 'o')]
+    output: Option<PathBuf>,
+    /// verify the exercises that changed since they were last verified first
+    #[argh(switch)]
+    verify: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "quiz")]
+/// Asks for solved exercises again, in a random order
+struct QuizArgs {
+    /// how many exercises to ask for, 5 by default
+    #[argh(option, short = 'n', default = "quiz::DEFAULT_COUNT")]
+    count: usize,
+    /// ask only for exercises of this topic, e.g. `iterators`
+    #[argh(option)]
+    topic: Option<String>,
+    /// the seed of the random order, to be asked the same again
+    #[argh(option)]
+    seed: Option<u64>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "stats")]
+/// Shows which exercises took the most attempts
+struct StatsArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "clean")]
+/// Removes compiled exercises left behind by interrupted runs
+struct CleanArgs {
+    /// also forget the recorded progress: which exercises passed,
+    /// the time spent on them and the attempts they took
+    #[argh(switch)]
+    progress: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "dev")]
+/// Tools for the authors of exercises
+struct DevArgs {
+    #[argh(subcommand)]
+    nested: DevCommands,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+enum DevCommands {
+    Check(CheckArgs),
+    HashTests(HashTestsArgs),
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "check")]
+/// Checks info.toml and the exercises it lists for mistakes
+struct CheckArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "hash-tests")]
+/// Records the test blocks of the exercises in src/test_hashes.rs
+struct HashTestsArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "completions")]
+/// Prints a shell completion script
+struct CompletionsArgs {
+    #[argh(positional)]
+    /// the shell to complete in: bash, zsh, fish or powershell
+    shell: Shell,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -126,6 +376,67 @@ struct ListArgs {
     #[argh(switch, short = 's')]
     /// display only exercises that have been solved
     solved: bool,
+    #[argh(option, short = 't')]
+    /// display only exercises of the given topic, e.g. `algorithm`
+    topic: Option<String>,
+    #[argh(option)]
+    /// sort the exercises by `status`, `name` or `path`
+    /// instead of the recommended order
+    sort: Option<ListSort>,
+    #[argh(switch)]
+    /// print the exercises as a JSON array and nothing else
+    json: bool,
+    #[argh(option)]
+    /// display only exercises with the given tag, e.g. `lifetimes`
+    tag: Option<String>,
+    #[argh(option)]
+    /// display only exercises whose difficulty is at most this, from 0 (unknown) to 5
+    max_difficulty: Option<u8>,
+    #[argh(option)]
+    /// display only the prerequisites of the given exercise that aren't done yet
+    prerequisites: Option<String>,
+    #[argh(switch)]
+    /// also show what every exercise is worth when grading
+    points: bool,
+    #[argh(switch)]
+    /// browse the exercises in the terminal, running, editing and showing
+    /// the hint of the selected one
+    interactive: bool,
+}
+
+// An entry of `rustlings list --json`
+#[derive(Serialize)]
+struct ListEntry<'a> {
+    name: &'a str,
+    path: &'a Path,
+    // Only listed for multi-file exercises
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    additional_files: &'a [PathBuf],
+    mode: Mode,
+    topic: String,
+    hint: &'a str,
+    status: Status,
+    seconds_spent: u64,
+}
+
+#[derive(PartialEq, Debug)]
+enum ListSort {
+    Status,
+    Name,
+    Path,
+}
+
+impl FromStr for ListSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "status" => Ok(ListSort::Status),
+            "name" => Ok(ListSort::Name),
+            "path" => Ok(ListSort::Path),
+            _ => Err(format!("unknown sort key `{s}`, expected `status`, `name` or `path`")),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize)]
@@ -151,7 +462,11 @@ pub struct  ExerciseStatistics {
 
 #[tokio::main]
 async fn main() {
-    let args: Args = argh::from_env();
+    let mut args: Args = argh::from_env();
+
+    let color = if args.no_color { ColorChoice::Never } else { args.color };
+    set_color_choice(color);
+    ui::enable_ansi(color);
 
     if args.version {
         println!("v{VERSION}");
@@ -162,6 +477,17 @@ async fn main() {
         println!("\n{WELCOME}\n");
     }
 
+    // Completion scripts are installed once, from anywhere
+    if let Some(Subcommands::Completions(subargs)) = &args.nested {
+        print!("{}", completions::script(subargs.shell));
+        std::process::exit(0);
+    }
+
+    let exercise_dir = args.exercise_dir.clone().or_else(|| std::env::var_os("RUSTLINGS_DIR").map(PathBuf::from));
+    if let Some(dir) = exercise_dir.filter(|dir| !dir.as_os_str().is_empty()) {
+        enter_exercise_dir(&dir, &mut args);
+    }
+
     if !Path::new("info.toml").exists() {
         println!(
             "{} must be run from the rustlings directory",
@@ -171,6 +497,67 @@ async fn main() {
         std::process::exit(1);
     }
 
+    let temp_dir = args.temp_dir.clone().or_else(|| std::env::var_os("RUSTLINGS_TEMP_DIR").map(PathBuf::from));
+    exercise::set_temp_dir(temp_dir.filter(|dir| !dir.as_os_str().is_empty()).unwrap_or_else(|| PathBuf::from(exercise::DEFAULT_TEMP_DIR)));
+
+    let context = match args.context {
+        Some(context) => Some(context),
+        None => match std::env::var("RUSTLINGS_CONTEXT") {
+            Ok(value) if !value.is_empty() => match value.parse() {
+                Ok(context) => Some(context),
+                Err(e) => {
+                    println!("RUSTLINGS_CONTEXT: {e}");
+                    std::process::exit(1);
+                }
+            },
+            _ => None,
+        },
+    };
+    exercise::set_context(context.unwrap_or_default());
+    // `rustlings run` is where the whole output of an exercise can be seen
+    let output_limit = match args.output_limit {
+        Some(limit) => limit,
+        None if matches!(args.nested, Some(Subcommands::Run(_))) => 0,
+        None => exercise::DEFAULT_OUTPUT_LIMIT,
+    };
+    exercise::set_output_limit((output_limit > 0).then_some(output_limit));
+    backtrace::set_full(args.full_backtrace);
+
+    // Cleaning up works even when info.toml is broken
+    if let Some(Subcommands::Clean(subargs)) = &args.nested {
+        clean(subargs.progress);
+        std::process::exit(0);
+    }
+
+    // info.toml is checked as it is, even when it can't be loaded
+    if let Some(Subcommands::Dev(DevArgs {
+        nested: DevCommands::Check(_),
+    })) = &args.nested
+    {
+        let violations = dev::check(Path::new("info.toml"));
+        for violation in &violations {
+            println!("{violation}");
+        }
+        if !violations.is_empty() {
+            let plural = if violations.len() == 1 { "" } else { "s" };
+            println!("Found {} problem{plural}.", violations.len());
+            std::process::exit(1);
+        }
+        println!("No problems found.");
+        std::process::exit(0);
+    }
+    if let Some(Subcommands::Dev(DevArgs {
+        nested: DevCommands::HashTests(_),
+    })) = &args.nested
+    {
+        if let Err(e) = fs::write(TEST_HASHES_FILE, dev::test_hashes(Path::new("exercises"))) {
+            println!("Could not write {TEST_HASHES_FILE}: {e}");
+            std::process::exit(1);
+        }
+        println!("Recorded the test blocks in {TEST_HASHES_FILE}, rebuild rustlings to use them.");
+        std::process::exit(0);
+    }
+
     if !rustc_exists() {
         println!("We cannot find `rustc`.");
         println!("Try running `rustc --version` to diagnose your problem.");
@@ -178,8 +565,14 @@ async fn main() {
         std::process::exit(1);
     }
 
-    let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
+    let lang = args.lang.clone().or_else(|| std::env::var("RUSTLINGS_LANG").ok()).filter(|lang| !lang.is_empty());
+    let exercises = match load_exercises(args.timeout, lang.as_deref()) {
+        Ok(exercises) => exercises,
+        Err(e) => {
+            println!("{e}");
+            std::process::exit(1);
+        }
+    };
     let verbose = args.nocapture;
 
     let command = args.nested.unwrap_or_else(|| {
@@ -188,83 +581,427 @@ async fn main() {
     });
     match command {
         Subcommands::List(subargs) => {
-            if !subargs.paths && !subargs.names {
-                println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
+            if let Some(name) = &subargs.prerequisites {
+                let exercise = find_exercise(name, &exercises);
+                let index = exercises.iter().position(|e| std::ptr::eq(e, exercise)).unwrap();
+                let mut state = Progress::load(PROGRESS_FILE, &rustc_version());
+                let unmet: Vec<&Exercise> = ExerciseList::prerequisites(&exercises, index)
+                    .into_iter()
+                    .map(|i| &exercises[i])
+                    .filter(|e| cached_status(e, &mut state) != Status::Done)
+                    .collect();
+                if unmet.is_empty() {
+                    println!("All prerequisites of {name} are done.");
+                }
+                for e in unmet {
+                    println!("{:<17}\t{}", e.name, exercise::display_path(&e.path));
+                }
+                std::process::exit(0);
             }
-            let mut exercises_done: u16 = 0;
+            if subargs.interactive && !console::Term::stdout().is_term() {
+                println!("rustlings list --interactive needs a terminal.");
+                std::process::exit(1);
+            }
+            if !subargs.paths && !subargs.names && !subargs.json && !subargs.interactive {
+                let points = if subargs.points { format!("\t{:>6}", "Points") } else { String::new() };
+                println!("{:<17}\t{:<46}\t{:<7}\t{:>7}{points}", "Name", "Path", "Status", "Time");
+            }
+            let state = RefCell::new(Progress::load(PROGRESS_FILE, &rustc_version()));
             let filters = subargs.filter.clone().unwrap_or_default().to_lowercase();
-            exercises.iter().for_each(|e| {
-                let fname = format!("{}", e.path.display());
-                let filter_cond = filters
-                    .split(',')
-                    .filter(|f| !f.trim().is_empty())
-                    .any(|f| e.name.contains(&f) || fname.contains(&f));
-                let status = if e.looks_done() {
-                    exercises_done += 1;
-                    "Done"
+            let mut listed: Vec<usize> = (0..exercises.len())
+                .filter(|&i| {
+                    let e = &exercises[i];
+                    let fnames: Vec<String> = e.files().map(|f| f.display().to_string()).collect();
+                    let filter_cond = filters
+                        .split(',')
+                        .filter(|f| !f.trim().is_empty())
+                        .any(|f| e.name.contains(f) || fnames.iter().any(|fname| fname.contains(f)));
+                    let topic_cond = match &subargs.topic {
+                        Some(topic) => e.topic().eq_ignore_ascii_case(topic),
+                        None => true,
+                    };
+                    let tag_cond = subargs.tag.as_ref().is_none_or(|tag| e.has_tag(tag));
+                    let difficulty_cond = subargs.max_difficulty.is_none_or(|max| e.difficulty <= max);
+                    topic_cond && tag_cond && difficulty_cond && (filter_cond || subargs.filter.is_none())
+                })
+                .collect();
+
+            // Working out whether an exercise is Done or Failing means
+            // compiling it, so only do so for the exercises that are
+            // actually listed, at most once each, and not at all when the
+            // progress cache knows it passed.
+            let statuses: Vec<OnceCell<Status>> = exercises.iter().map(|_| OnceCell::new()).collect();
+            let status_of = |i: usize| *statuses[i].get_or_init(|| cached_status(&exercises[i], &mut state.borrow_mut()));
+
+            if subargs.solved || subargs.unsolved {
+                listed.retain(|&i| {
+                    let done = status_of(i) == Status::Done;
+                    (done && subargs.solved) || (!done && subargs.unsolved)
+                });
+            }
+            match subargs.sort {
+                Some(ListSort::Status) => listed.sort_by_key(|&i| status_of(i)),
+                Some(ListSort::Name) => listed.sort_by(|&a, &b| exercises[a].name.cmp(&exercises[b].name)),
+                Some(ListSort::Path) => listed.sort_by(|&a, &b| exercises[a].path.cmp(&exercises[b].path)),
+                None => {}
+            }
+
+            if subargs.interactive {
+                if let Err(e) = tui::list(&exercises, listed, &state.borrow(), verbose) {
+                    println!("The terminal stopped working: {e}");
+                    std::process::exit(1);
+                }
+                std::process::exit(0);
+            }
+
+            if subargs.json {
+                let entries: Vec<ListEntry> = listed
+                    .iter()
+                    .map(|&i| {
+                        let e = &exercises[i];
+                        ListEntry {
+                            name: &e.name,
+                            path: &e.path,
+                            additional_files: &e.additional_files,
+                            mode: e.mode,
+                            topic: e.topic(),
+                            hint: &e.hint,
+                            status: status_of(i),
+                            seconds_spent: state.borrow().time_spent(e).as_secs(),
+                        }
+                    })
+                    .collect();
+                let stdout = std::io::stdout();
+                let mut handle = stdout.lock();
+                serde_json::to_writer_pretty(&mut handle, &entries)
+                    .map_err(io::Error::from)
+                    .and_then(|_| writeln!(handle))
+                    .unwrap_or_else(|e| match e.kind() {
+                        std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+                        _ => std::process::exit(1),
+                    });
+                std::process::exit(0);
+            }
+
+            for i in listed {
+                let e = &exercises[i];
+                let fname = exercise::display_path(&e.path);
+                let line = if subargs.paths {
+                    format!("{fname}\n")
+                } else if subargs.names {
+                    format!("{}\n", e.name)
                 } else {
-                    "Pending"
-                };
-                let solve_cond = {
-                    (e.looks_done() && subargs.solved)
-                        || (!e.looks_done() && subargs.unsolved)
-                        || (!subargs.solved && !subargs.unsolved)
-                };
-                if solve_cond && (filter_cond || subargs.filter.is_none()) {
-                    let line = if subargs.paths {
-                        format!("{fname}\n")
-                    } else if subargs.names {
-                        format!("{}\n", e.name)
-                    } else {
-                        format!("{:<17}\t{fname:<46}\t{status:<7}\n", e.name)
+                    let status = status_of(i).to_string();
+                    let time = match state.borrow().time_spent(e) {
+                        Duration::ZERO => "-".to_string(),
+                        time => format_duration(time),
                     };
-                    // Somehow using println! leads to the binary panicking
-                    // when its output is piped.
-                    // So, we're handling a Broken Pipe error and exiting with 0 anyway
-                    let stdout = std::io::stdout();
-                    {
-                        let mut handle = stdout.lock();
-                        handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
-                            match e.kind() {
-                                std::io::ErrorKind::BrokenPipe => std::process::exit(0),
-                                _ => std::process::exit(1),
-                            };
-                        });
-                    }
+                    let points = if subargs.points { format!("\t{:>6}", e.points) } else { String::new() };
+                    format!("{:<17}\t{fname:<46}\t{status:<7}\t{time:>7}{points}\n", e.name)
+                };
+                // Somehow using println! leads to the binary panicking
+                // when its output is piped.
+                // So, we're handling a Broken Pipe error and exiting with 0 anyway
+                let stdout = std::io::stdout();
+                {
+                    let mut handle = stdout.lock();
+                    handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
+                        match e.kind() {
+                            std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+                            _ => std::process::exit(1),
+                        };
+                    });
                 }
-            });
-            let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
+            }
+            // Plain lists of names or paths are meant for scripts and shell completion
+            if subargs.paths || subargs.names {
+                std::process::exit(0);
+            }
+            // Exercises that weren't listed are counted by their marker alone.
+            let done: Vec<&Exercise> = exercises
+                .iter()
+                .zip(&statuses)
+                .filter(|(e, status)| match status.get() {
+                    Some(status) => *status == Status::Done,
+                    None => e.looks_done(),
+                })
+                .map(|(e, _)| e)
+                .collect();
+            let percentage_progress = done.len() as f32 / exercises.len() as f32 * 100.0;
             println!(
                 "Progress: You completed {} / {} exercises ({:.1} %).",
-                exercises_done,
+                done.len(),
                 exercises.len(),
                 percentage_progress
             );
+            if subargs.points {
+                let earned: u32 = done.iter().map(|e| e.points).sum();
+                let total: u32 = exercises.iter().map(|e| e.points).sum();
+                println!("Points: You earned {earned} / {total} points.");
+            }
             std::process::exit(0);
         }
 
         Subcommands::Run(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
-            run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+            let mut test_args = subargs.args;
+            let name = (!test_args.is_empty()).then(|| test_args.remove(0));
+            let mut exercise = find_exercise_or_current(name.as_deref(), &exercises).clone();
+            exercise.release |= subargs.release;
+            let exercise = &exercise;
+            // A run of only some of the tests isn't an attempt at the exercise
+            let filtered = !test_args.is_empty();
+            if subargs.nocapture {
+                test_args.push("--nocapture".to_string());
+            }
+            let result = run(exercise, verbose, &test_args);
+            if !filtered {
+                Progress::load(PROGRESS_FILE, &rustc_version()).count_attempt(exercise, result.is_ok());
+            }
+            result.unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Edit(subargs) => {
+            let exercise = find_exercise_or_current(subargs.name.as_deref(), &exercises);
+            let line = match exercise.state() {
+                State::Pending(context) => context.iter().find(|l| l.important).map(|l| l.number),
+                State::Done => None,
+            };
+            match editor::open(&exercise.path, line) {
+                Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+                Err(_) => {
+                    println!("Couldn't start an editor, set $VISUAL or $EDITOR to the one you use.");
+                    println!("The exercise is at {exercise}");
+                    std::process::exit(1);
+                }
+            }
         }
 
         Subcommands::Reset(subargs) => {
+            let to_reset: Vec<&Exercise> = match (&subargs.name, subargs.all) {
+                (Some(name), false) => vec![find_exercise(name, &exercises)],
+                (None, true) => exercises.iter().collect(),
+                _ => {
+                    eprintln!("Provide either the name of an exercise or --all to reset.");
+                    std::process::exit(1);
+                }
+            };
+            let what = match &to_reset[..] {
+                [exercise] if exercise.additional_files.is_empty() => format!("{exercise}"),
+                [exercise] => {
+                    let files: Vec<String> = exercise.files().map(|f| f.display().to_string()).collect();
+                    files.join(", ")
+                }
+                _ => format!("all {} exercises", to_reset.len()),
+            };
+            if !subargs.force && !confirm(&format!("Reset {what} to the original state? Your changes will be lost.")) {
+                println!("Nothing was reset.");
+                std::process::exit(1);
+            }
+
+            let mut state = Progress::load(PROGRESS_FILE, &rustc_version());
+            let mut failed = false;
+            for exercise in to_reset {
+                state.forget(exercise);
+                failed |= reset(exercise).is_err();
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Solution(subargs) => {
             let exercise = find_exercise(&subargs.name, &exercises);
+            let solution_path = exercise.solution_path();
+            let solution = fs::read_to_string(&solution_path).unwrap_or_else(|_| {
+                println!("There is no solution for {} yet.", exercise.name);
+                std::process::exit(1)
+            });
 
-            reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+            if subargs.diff {
+                let current = fs::read_to_string(&exercise.path).unwrap_or_default();
+                let diff = diff::unified(
+                    &current,
+                    &solution,
+                    &exercise.path.display().to_string(),
+                    &solution_path.display().to_string(),
+                );
+                if diff.is_empty() {
+                    println!("{exercise} is the same as the solution.");
+                } else {
+                    print!("{diff}");
+                }
+            } else {
+                print!("{solution}");
+            }
         }
 
         Subcommands::Hint(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            if !subargs.all {
+                let exercise = find_exercise_or_current(subargs.name.as_deref(), &exercises);
+                markdown::print(&exercise.hint);
+                return;
+            }
+
+            let topic = subargs.name.unwrap_or_else(|| {
+                println!("Provide the topic to print all hints of, e.g. `rustlings hint --all algorithm`.");
+                std::process::exit(1)
+            });
+            let in_topic: Vec<&Exercise> = exercises.iter().filter(|e| e.topic().eq_ignore_ascii_case(&topic)).collect();
+            if in_topic.is_empty() {
+                println!("No topic found for '{topic}'!");
+                let mut topics: Vec<String> = exercises.iter().map(Exercise::topic).collect();
+                topics.dedup();
+                print_close_matches(&topic, topics.iter().map(String::as_str));
+                std::process::exit(1);
+            }
+            for exercise in in_topic {
+                println!("{}", console::style(&exercise.name).bold().underlined());
+                markdown::print(exercise.hint.trim());
+                println!();
+            }
+        }
+
+        Subcommands::Verify(subargs) => {
+            let mut state = Progress::load(PROGRESS_FILE, &rustc_version()).force_recheck(subargs.force_recheck);
+            let jobs = subargs
+                .jobs
+                .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+            let in_scope = verification_scope(
+                &exercises,
+                subargs.from.as_deref(),
+                subargs.only.as_deref(),
+                subargs.tag.as_deref(),
+            );
+            if subargs.keep_going {
+                let failures = verify_keep_going(&in_scope, jobs, &mut state);
+                print_failure_summary(&in_scope, &failures, exercises.len() - in_scope.len());
+                std::process::exit(if failures.is_empty() { 0 } else { 1 });
+            }
+            let plain = subargs.ci || subargs.quiet || (!subargs.interactive && !console::user_attended());
+            let result = verify_parallel(&in_scope, jobs, verbose, plain, &mut state);
+            let skipped = exercises.len() - in_scope.len();
+            if plain {
+                let passed = match result {
+                    Ok(()) => in_scope.len(),
+                    Err(failed) => in_scope.iter().position(|e| std::ptr::eq(*e, failed)).unwrap(),
+                };
+                let failed = usize::from(result.is_err());
+                let unverified = in_scope.len() - passed - failed;
+                println!("Summary: {passed} passed, {failed} failed, {unverified} not verified, {skipped} skipped");
+                if result.is_err() {
+                    std::process::exit(1);
+                }
+                return;
+            }
+            result.unwrap_or_else(|_| std::process::exit(1));
+            if skipped == 0 {
+                success!("All {} exercises passed!", exercises.len());
+            } else {
+                let scope = format!("{} of {} exercises", in_scope.len(), exercises.len());
+                success!("{} passed, {skipped} were skipped and not verified.", scope);
+            }
+        }
+
+        // Always exits successfully for graders: the report carries the result
+        Subcommands::Score(subargs) => {
+            let report = score::score(&exercises);
+            let json = serde_json::to_string_pretty(&report).unwrap();
+            match subargs.output {
+                Some(path) => {
+                    if let Err(e) = fs::write(&path, json + "\n") {
+                        println!("Failed to write the report to {}: {e}", path.display());
+                        std::process::exit(1);
+                    }
+                    for (topic, section) in &report.sections {
+                        println!(
+                            "{topic:<17}\t{}/{}\t{}/{} points",
+                            section.passed, section.total, section.points, section.total_points
+                        );
+                    }
+                    println!(
+                        "Score: {}/{} ({:.1} %), {}/{} points, written to {}",
+                        report.passed,
+                        report.total,
+                        report.percentage,
+                        report.points,
+                        report.total_points,
+                        path.display()
+                    );
+                }
+                None => println!("{json}"),
+            }
+        }
+
+        Subcommands::Report(subargs) => {
+            let mut state = Progress::load(PROGRESS_FILE, &rustc_version());
+            if subargs.verify {
+                for exercise in &exercises {
+                    if !state.is_up_to_date(exercise) {
+                        state.record(exercise, status(exercise) == Status::Done);
+                    }
+                }
+            }
+            let report = report::render(&exercises, &state, subargs.format, SystemTime::now());
+            match subargs.output {
+                Some(path) => {
+                    if let Err(e) = fs::write(&path, report) {
+                        println!("Failed to write the report to {}: {e}", path.display());
+                        std::process::exit(1);
+                    }
+                    println!("Report written to {}", path.display());
+                }
+                None => print!("{report}"),
+            }
+        }
 
-            println!("{}", exercise.hint);
+        Subcommands::Quiz(subargs) => {
+            if let Err(e) = quiz::quiz(&exercises, subargs.count, subargs.topic.as_deref(), subargs.seed) {
+                println!("Error: Could not run the quiz: {e:?}");
+                std::process::exit(1);
+            }
         }
 
-        Subcommands::Verify(_subargs) => {
-            verify(&exercises, (0, exercises.len()), verbose, false)
-                .unwrap_or_else(|_| std::process::exit(1));
+        Subcommands::Stats(_) => {
+            let state = Progress::load(PROGRESS_FILE, "");
+            let mut rows: Vec<(&Exercise, progress::Attempts, Duration)> = exercises
+                .iter()
+                .map(|e| (e, state.attempts(e), state.time_spent(e)))
+                .filter(|(_, attempts, time)| attempts.failures > 0 || attempts.saves > 0 || !time.is_zero())
+                .collect();
+            if rows.is_empty() {
+                println!("No attempts recorded yet, `rustlings watch`, `verify` and `run` count them.");
+                std::process::exit(0);
+            }
+            // Stable, so exercises with as many attempts stay in course order
+            rows.sort_by_key(|(_, attempts, time)| std::cmp::Reverse((attempts.failures, *time)));
+            println!("{:<17}\t{:>8}\t{:>5}\t{:>7}\tTopic", "Name", "Attempts", "Saves", "Time");
+            let show = |time: Duration| match time {
+                Duration::ZERO => "-".to_string(),
+                time => format_duration(time),
+            };
+            for (e, attempts, time) in &rows {
+                println!(
+                    "{:<17}\t{:>8}\t{:>5}\t{:>7}\t{}",
+                    e.name,
+                    attempts.failures,
+                    attempts.saves,
+                    show(*time),
+                    e.topic()
+                );
+            }
+            println!(
+                "{:<17}\t{:>8}\t{:>5}\t{:>7}",
+                "Total",
+                rows.iter().map(|(_, a, _)| a.failures).sum::<u32>(),
+                rows.iter().map(|(_, a, _)| a.saves).sum::<u32>(),
+                show(rows.iter().map(|(_, _, t)| *t).sum())
+            );
         }
 
+        Subcommands::Completions(_) => unreachable!("completions are printed before loading info.toml"),
+        Subcommands::Dev(_) => unreachable!("the dev commands run before info.toml is loaded"),
+        Subcommands::Clean(_) => unreachable!("cleaning up happens before loading info.toml"),
+
         Subcommands::CicvVerify(_subargs) => {
             // let toml_str = &fs::read_to_string("info.toml").unwrap();
             // exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
@@ -291,9 +1028,9 @@ async fn main() {
                 let inner_exercise = exercise;
                 let c_mutex = Arc::clone(&rights);
                 let exercise_check_list_ref = Arc::clone(&exercise_check_list);
-                let _verbose = verbose.clone();
+                let _verbose = verbose;
                 let t = tokio::task::spawn( async move {
-                    match run(&inner_exercise, true) {
+                    match run(&inner_exercise, true, &[]) {
                     // match verify(vec![&inner_exercise], (0, 1), true, true) {
                         Ok(_) => {
                             *c_mutex.lock().unwrap() += 1;
@@ -337,9 +1074,7 @@ async fn main() {
             project
                 .get_sysroot_src()
                 .expect("Couldn't find toolchain path, do you have `rustc` installed?");
-            project
-                .exercises_to_json()
-                .expect("Couldn't parse rustlings exercises files");
+            project.exercises_to_json(&exercises);
 
             if project.crates.is_empty() {
                 println!("Failed find any exercises, make sure you're in the `rustlings` folder");
@@ -351,7 +1086,7 @@ async fn main() {
             }
         }
 
-        Subcommands::Watch(_subargs) => match watch(&exercises, verbose, _subargs.success_hints) {
+        Subcommands::Watch(subargs) => match watch(exercises, verbose, &subargs, args.timeout, lang.as_deref()) {
             Err(e) => {
                 println!(
                     "Error: Could not watch your progress. Error message was {:?}.",
@@ -365,6 +1100,8 @@ async fn main() {
                     "{emoji} All exercises completed! {emoji}",
                     emoji = Emoji("🎉", "★")
                 );
+                let spent = Progress::load(PROGRESS_FILE, "").total_time_spent();
+                println!("You spent {} on the exercises.", format_duration(spent));
                 println!("\n{FENISH_LINE}\n");
             }
             Ok(WatchStatus::Unfinished) => {
@@ -375,36 +1112,209 @@ async fn main() {
     }
 }
 
+// Make `dir` the current directory, so that info.toml and the paths in it
+// are found there. The paths given on the command line stay relative to
+// where rustlings was run.
+fn enter_exercise_dir(dir: &Path, args: &mut Args) {
+    let outputs = match &mut args.nested {
+        Some(Subcommands::Score(subargs)) => subargs.output.as_mut(),
+        Some(Subcommands::Report(subargs)) => subargs.output.as_mut(),
+        _ => None,
+    };
+    for path in args.temp_dir.iter_mut().chain(outputs) {
+        if let Ok(absolute) = std::path::absolute(&*path) {
+            *path = absolute;
+        }
+    }
+
+    let info = dir.join("info.toml");
+    if !info.is_file() {
+        let looked_at = std::path::absolute(&info).unwrap_or(info);
+        println!("The exercise directory {} has no info.toml, there is no {}", dir.display(), looked_at.display());
+        std::process::exit(1);
+    }
+    if let Err(e) = std::env::set_current_dir(dir) {
+        println!("Could not change to the exercise directory {}: {e}", dir.display());
+        std::process::exit(1);
+    }
+}
+
+// Read the exercises from info.toml, killing them after `timeout`
+// seconds if given, and keep copies of any new ones
+fn load_exercises(timeout: Option<u64>, lang: Option<&str>) -> Result<Vec<Exercise>, String> {
+    let mut exercises = ExerciseList::load(Path::new("info.toml"))?.exercises;
+    if let Some(timeout) = timeout {
+        for exercise in &mut exercises {
+            exercise.timeout_secs = Some(timeout);
+        }
+    }
+    // Everything showing a hint shows the translated one
+    if let Some(lang) = lang {
+        for exercise in &mut exercises {
+            exercise.hint = exercise.hint_in(lang).to_string();
+        }
+    }
+    // Solutions must never be verified, and thus graded, as exercises
+    if let Some(e) = exercises.iter().find(|e| e.path.starts_with(SOLUTIONS_DIR)) {
+        return Err(format!("The exercise {} in info.toml points into the {SOLUTIONS_DIR} directory.", e.name));
+    }
+    // Keep a pristine copy of every exercise for `rustlings reset`.
+    // Without it only resetting is impossible, so failures are ignored.
+    let _ = originals::snapshot(&exercises);
+    // Pick up exercises added to info.toml since `rustlings lsp` ran
+    project::refresh(&exercises);
+    Ok(exercises)
+}
+
+// The results of `verify --keep-going`: how many exercises of every topic
+// passed and failed, and why each failure did
+fn print_failure_summary(verified: &[&Exercise], failures: &[Failure], skipped: usize) {
+    let mut topics: Vec<(String, usize, usize)> = Vec::new();
+    for exercise in verified {
+        let failed = failures.iter().any(|f| std::ptr::eq(f.exercise, *exercise));
+        let topic = exercise.topic();
+        let index = match topics.iter().position(|(t, _, _)| *t == topic) {
+            Some(index) => index,
+            None => {
+                topics.push((topic, 0, 0));
+                topics.len() - 1
+            }
+        };
+        if failed {
+            topics[index].2 += 1;
+        } else {
+            topics[index].1 += 1;
+        }
+    }
+    println!();
+    println!("{:<17}\t{:>6}\t{:>6}", "Topic", "Passed", "Failed");
+    for (topic, passed, failed) in &topics {
+        println!("{topic:<17}\t{passed:>6}\t{failed:>6}");
+    }
+    if !failures.is_empty() {
+        println!();
+        println!("Failed exercises:");
+        for failure in failures {
+            println!("{:<17}\t{}", failure.exercise.name, failure.error);
+        }
+    }
+    println!();
+    let failed = failures.len();
+    println!("Summary: {} passed, {failed} failed, {skipped} skipped", verified.len() - failed);
+}
+
+// Remove the compiled exercises of every run, including those left behind
+// in the rustlings directory by older versions, and the progress file too
+// if `progress` is set
+fn clean(progress: bool) {
+    let mut removed = Vec::new();
+    let temp_dir = exercise::temp_dir();
+    if temp_dir.exists() {
+        match fs::remove_dir_all(temp_dir) {
+            Ok(()) => removed.push(temp_dir.to_path_buf()),
+            Err(e) => println!("Could not remove {}: {e}", temp_dir.display()),
+        }
+    }
+    for file in glob::glob("temp_*").into_iter().flatten().flatten().filter(|f| f.is_file()) {
+        if fs::remove_file(&file).is_ok() {
+            removed.push(file);
+        }
+    }
+    if progress && Path::new(PROGRESS_FILE).exists() {
+        match fs::remove_file(PROGRESS_FILE) {
+            Ok(()) => removed.push(PathBuf::from(PROGRESS_FILE)),
+            Err(e) => println!("Could not remove {PROGRESS_FILE}: {e}"),
+        }
+    }
+    if removed.is_empty() {
+        println!("Nothing to clean up.");
+    }
+    for path in removed {
+        println!("Removed {}", path.display());
+    }
+}
+
+// Navigation requests typed into the watch shell,
+// carried out by the watch loop
+enum WatchCommand {
+    // Remove the `I AM NOT DONE` comment of the current exercise
+    Done,
+    Skip,
+    Goto(String),
+    Prev,
+    Next,
+    ToggleClear,
+}
+
+fn print_watch_help() {
+    println!("Commands available to you in watch mode:");
+    println!("  hint         - prints the current exercise's hint");
+    println!("  done         - removes the `I AM NOT DONE` comment once the exercise passes");
+    println!("  skip         - defers the current exercise until the others are done");
+    println!("  next         - moves on to the next unsolved exercise");
+    println!("  prev         - goes back to the previous unsolved exercise");
+    println!("  goto <name>  - jumps to the given exercise");
+    println!("  clear        - clears the screen");
+    println!("  toggle clear - switches clearing the screen before every run on or off");
+    println!("  quit         - quits watch mode");
+    println!("  !<cmd>       - executes a command, like `!rustc --explain E0381`");
+    println!("  help         - displays this help message");
+    println!();
+    println!("Watch mode automatically re-evaluates the current exercise");
+    println!("when you edit a file's contents.")
+}
+
 fn spawn_watch_shell(
     failed_exercise_hint: &Arc<Mutex<Option<String>>>,
     should_quit: Arc<AtomicBool>,
+    commands: Sender<WatchCommand>,
 ) {
     let failed_exercise_hint = Arc::clone(failed_exercise_hint);
     println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
     thread::spawn(move || loop {
         let mut input = String::new();
         match io::stdin().read_line(&mut input) {
+            // stdin was closed, there are no more commands to read
+            Ok(0) => return,
             Ok(_) => {
                 let input = input.trim();
-                if input == "hint" {
+                let command = if input.is_empty() {
+                    None
+                } else if input == "hint" {
                     if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{hint}");
+                        markdown::print(hint);
                     }
+                    None
                 } else if input == "clear" {
-                    println!("\x1B[2J\x1B[1;1H");
+                    if ui::ansi() {
+                        println!("\x1B[2J\x1B[1;1H");
+                    }
+                    None
                 } else if input.eq("quit") {
                     should_quit.store(true, Ordering::SeqCst);
                     println!("Bye!");
+                    None
                 } else if input.eq("help") {
-                    println!("Commands available to you in watch mode:");
-                    println!("  hint   - prints the current exercise's hint");
-                    println!("  clear  - clears the screen");
-                    println!("  quit   - quits watch mode");
-                    println!("  !<cmd> - executes a command, like `!rustc --explain E0381`");
-                    println!("  help   - displays this help message");
-                    println!();
-                    println!("Watch mode automatically re-evaluates the current exercise");
-                    println!("when you edit a file's contents.")
+                    print_watch_help();
+                    None
+                } else if input.eq("done") {
+                    Some(WatchCommand::Done)
+                } else if input.eq("skip") {
+                    Some(WatchCommand::Skip)
+                } else if input.eq("next") {
+                    Some(WatchCommand::Next)
+                } else if input.eq("prev") {
+                    Some(WatchCommand::Prev)
+                } else if input.eq("toggle clear") {
+                    Some(WatchCommand::ToggleClear)
+                } else if let Some(name) = input.strip_prefix("goto") {
+                    match name.trim() {
+                        "" => {
+                            println!("usage: goto <exercise name>");
+                            None
+                        }
+                        name => Some(WatchCommand::Goto(name.to_string())),
+                    }
                 } else if let Some(cmd) = input.strip_prefix('!') {
                     let parts: Vec<&str> = cmd.split_whitespace().collect();
                     if parts.is_empty() {
@@ -412,8 +1322,16 @@ fn spawn_watch_shell(
                     } else if let Err(e) = Command::new(parts[0]).args(&parts[1..]).status() {
                         println!("failed to execute command `{}`: {}", cmd, e);
                     }
+                    None
                 } else {
                     println!("unknown command: {input}");
+                    print_watch_help();
+                    None
+                };
+                if let Some(command) = command {
+                    if commands.send(command).is_err() {
+                        return;
+                    }
                 }
             }
             Err(error) => println!("error reading command: {error}"),
@@ -421,6 +1339,27 @@ fn spawn_watch_shell(
     });
 }
 
+// Ask a yes/no question on the terminal, defaulting to no
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+// The status of the exercise, without compiling it if it passed and didn't
+// change since. Otherwise it is worked out and recorded for next time.
+fn cached_status(exercise: &Exercise, state: &mut Progress) -> Status {
+    if state.is_up_to_date(exercise) {
+        return Status::Done;
+    }
+    let status = status(exercise);
+    if matches!(status, Status::Done | Status::Failing) {
+        state.record(exercise, status == Status::Done);
+    }
+    status
+}
+
 fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
     if name.eq("next") {
         exercises
@@ -442,89 +1381,581 @@ fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
     }
 }
 
+// The exercises `verify --from <name> --only <topic or glob> --tag <tag>`
+// covers, in info.toml order. Exits with suggestions if any matches nothing.
+fn verification_scope<'a>(
+    exercises: &'a [Exercise],
+    from: Option<&str>,
+    only: Option<&str>,
+    tag: Option<&str>,
+) -> Vec<&'a Exercise> {
+    let start = match from {
+        Some(name) => exercises.iter().position(|e| e.name == name).unwrap_or_else(|| {
+            println!("No exercise found for '{name}'!");
+            print_close_matches(name, exercises.iter().map(|e| e.name.as_str()));
+            std::process::exit(1)
+        }),
+        None => 0,
+    };
+    let mut in_scope: Vec<&Exercise> = match only {
+        Some(only) => {
+            let pattern = glob::Pattern::new(only).unwrap_or_else(|e| {
+                println!("'{only}' is not a valid pattern: {e}");
+                std::process::exit(1)
+            });
+            exercises[start..]
+                .iter()
+                .filter(|e| {
+                    e.topic().eq_ignore_ascii_case(only)
+                        || pattern.matches(&e.name)
+                        || pattern.matches_path(&e.path)
+                })
+                .collect()
+        }
+        None => exercises[start..].iter().collect(),
+    };
+    if let (Some(only), true) = (only, in_scope.is_empty()) {
+        println!("No exercise matches '{only}'!");
+        let mut candidates: Vec<String> = exercises.iter().map(Exercise::topic).collect();
+        candidates.dedup();
+        candidates.extend(exercises.iter().map(|e| e.name.clone()));
+        print_close_matches(only, candidates.iter().map(String::as_str));
+        std::process::exit(1);
+    }
+    if let Some(tag) = tag {
+        in_scope.retain(|e| e.has_tag(tag));
+        if in_scope.is_empty() {
+            println!("No exercise to verify is tagged '{tag}'!");
+            let mut tags: Vec<&str> = exercises.iter().flat_map(|e| &e.tags).map(String::as_str).collect();
+            tags.sort_unstable();
+            tags.dedup();
+            print_close_matches(tag, tags);
+            std::process::exit(1);
+        }
+    }
+    in_scope
+}
+
+// The candidates that are at most a few typos away from `name`
+// or contain it, closest first
+fn close_matches<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(2);
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let lowercase = candidate.to_lowercase();
+            let distance = edit_distance(&name, &lowercase);
+            (distance <= max_distance || lowercase.contains(&name)).then_some((distance, candidate))
+        })
+        .collect();
+    matches.sort();
+    matches.dedup();
+    matches.into_iter().map(|(_, candidate)| candidate).take(5).collect()
+}
+
+fn print_close_matches<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) {
+    let matches = close_matches(name, candidates);
+    if !matches.is_empty() {
+        println!("Did you mean: {}?", matches.join(", "));
+    }
+}
+
+// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+// The exercise `run` and `hint` pick without a name: the first one
+// still marked `I AM NOT DONE`, or else the first one that fails.
+// Exercises that passed unchanged before aren't compiled again.
+fn current_exercise<'a>(exercises: &'a [Exercise], state: &Progress) -> Option<&'a Exercise> {
+    exercises.iter().find(|e| !e.looks_done()).or_else(|| {
+        exercises
+            .iter()
+            .find(|e| !state.is_up_to_date(e) && status(e) == Status::Failing)
+    })
+}
+
+fn find_exercise_or_current<'a>(name: Option<&str>, exercises: &'a [Exercise]) -> &'a Exercise {
+    if let Some(name) = name {
+        return find_exercise(name, exercises);
+    }
+    let state = Progress::load(PROGRESS_FILE, &rustc_version());
+    let exercise = current_exercise(exercises, &state).unwrap_or_else(|| {
+        println!("🎉 Congratulations! You have done all the exercises!");
+        std::process::exit(1)
+    });
+    println!("Current exercise: {exercise}");
+    exercise
+}
+
+// Whether watch mode may move on to the exercise at `target`. Jumping
+// ahead of its prerequisites only gets a warning, unless `strict` is set.
+fn may_jump_to(exercises: &[Exercise], target: usize, state: &Progress, strict: bool) -> bool {
+    let unmet: Vec<&str> = ExerciseList::prerequisites(exercises, target)
+        .into_iter()
+        .filter(|&i| !state.passed(&exercises[i]))
+        .map(|i| exercises[i].name.as_str())
+        .collect();
+    if unmet.is_empty() {
+        return true;
+    }
+    let name = &exercises[target].name;
+    if strict {
+        warn!("{} builds on exercises that aren't done yet, so it has to wait:", name);
+    } else {
+        warn!("{} builds on exercises that aren't done yet, you may want to do them first:", name);
+    }
+    println!("  {}", unmet.join(", "));
+    !strict
+}
+
+// Idle gaps longer than this, say overnight, only count this much
+// towards the time spent on an exercise
+const IDLE_LIMIT: Duration = Duration::from_secs(10 * 60);
+
+// Measures the time spent between the user's actions in watch mode.
+// Clones share the same laps, so that an interrupt can record the last one.
+#[derive(Clone)]
+struct Stopwatch {
+    last_activity: Arc<Mutex<Instant>>,
+}
+
+impl Stopwatch {
+    fn start() -> Self {
+        Stopwatch {
+            last_activity: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    // The time since the last lap, capped at IDLE_LIMIT
+    fn lap(&self) -> Duration {
+        let now = Instant::now();
+        let mut last_activity = self.last_activity.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = now.duration_since(*last_activity);
+        *last_activity = now;
+        elapsed.min(IDLE_LIMIT)
+    }
+}
+
+// On Ctrl-C, stop whatever is being built or run, clean up after it and
+// record the time spent on the exercise being worked on
+fn save_on_interrupt(stopwatch: Stopwatch, working_on: Arc<Mutex<String>>, toolchain: String) {
+    signals::on_interrupt(move || {
+        exercise::kill_running();
+        exercise::remove_temp_files();
+        let name = working_on.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        Progress::load(PROGRESS_FILE, &toolchain).add_time(&name, stopwatch.lap());
+        ui::restore_terminal();
+        println!();
+        println!("Progress saved, run `rustlings watch` to resume.");
+    });
+}
+
 enum WatchStatus {
     Finished,
     Unfinished,
 }
 
+// How long watch mode waits for a file to settle before rebuilding
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+// The exercises that were modified by a batch of file events, in the
+// order they were first touched. Editing any file of a multi-file
+// exercise counts as modifying the exercise. Anything that isn't an
+// exercise listed in info.toml is ignored, which takes care of editor
+// swap and backup files such as `.algorithm1.rs.swp`, `4913` or `algorithm1.rs~`.
+fn exercises_changed_by(events: &[DebouncedEvent], exercises: &[Exercise]) -> Vec<usize> {
+    let mut changed = Vec::new();
+    for event in events {
+        let path = match event {
+            DebouncedEvent::Create(path)
+            | DebouncedEvent::Write(path)
+            | DebouncedEvent::Chmod(path)
+            | DebouncedEvent::Rename(_, path) => path,
+            // Editors saving by replacing the file, as many do on Windows,
+            // may leave nothing but its removal once events are debounced
+            DebouncedEvent::Remove(path) if path.exists() => path,
+            _ => continue,
+        };
+        let hidden = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_none_or(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        // Declared files need not be Rust sources, like the input of an
+        // `include_str!`, and may be shared by several exercises
+        let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+        for (index, _) in exercises.iter().enumerate().filter(|(_, e)| e.files().any(|file| path.ends_with(file))) {
+            if !changed.contains(&index) {
+                changed.push(index);
+            }
+        }
+    }
+    changed
+}
+
+// The order in which watch mode verifies exercises: `first` if given,
+// then every unsolved exercise from `start` onwards, wrapping around
+// to the beginning, and finally the skipped exercises in the order
+// they were skipped.
+fn verification_order(
+    len: usize,
+    is_done: impl Fn(usize) -> bool,
+    first: Option<usize>,
+    start: usize,
+    skipped: &[usize],
+) -> Vec<usize> {
+    let unsolved = (start..len)
+        .chain(0..start.min(len))
+        .filter(|&i| !is_done(i) && !skipped.contains(&i));
+    // Skipped exercises are kept until they have been verified, even if
+    // their marker has been removed in the meantime
+    let rest = unsolved
+        .chain(skipped.iter().copied())
+        .filter(|&i| Some(i) != first);
+    first.into_iter().chain(rest).collect()
+}
+
+// Whether a batch of file events touched info.toml, or a file it
+// includes. The Cargo.toml files of exercises don't count.
+fn info_toml_changed(events: &[DebouncedEvent]) -> bool {
+    events.iter().any(|event| match event {
+        DebouncedEvent::Create(path)
+        | DebouncedEvent::Write(path)
+        | DebouncedEvent::Remove(path)
+        | DebouncedEvent::Rename(_, path) => {
+            path.extension() == Some(OsStr::new("toml")) && path.file_name() != Some(OsStr::new("Cargo.toml"))
+        }
+        _ => false,
+    })
+}
+
+// The directories watch mode watches besides `exercises/`: the one
+// holding info.toml, and those of exercises included from packs
+// outside of `exercises/`
+fn watched_dirs(exercises: &[Exercise]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = exercises
+        .iter()
+        .flat_map(Exercise::files)
+        .filter(|file| !file.starts_with("exercises"))
+        .filter_map(Path::parent)
+        .map(|dir| Path::new(".").join(dir))
+        .collect();
+    dirs.push(PathBuf::from("."));
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+// Where `current` and `skipped`, indices into `old`, point in `new`.
+// Exercises are followed by name. If the current exercise is gone, the
+// next one after it that is still there takes its place.
+fn carry_over(old: &[Exercise], new: &[Exercise], current: usize, skipped: &[usize]) -> (usize, Vec<usize>) {
+    let position = |old_index: usize| new.iter().position(|e| e.name == old[old_index].name);
+    let current = (current..old.len()).find_map(position).unwrap_or(0);
+    let skipped = skipped.iter().filter_map(|&i| position(i)).collect();
+    (current, skipped)
+}
+
+// Print what changed between two versions of the exercise list,
+// returning whether anything did, their order included
+fn report_reload(old: &[Exercise], new: &[Exercise]) -> bool {
+    let names = |list: &[Exercise], other: &[Exercise]| -> Vec<String> {
+        list.iter()
+            .filter(|e| !other.iter().any(|o| o.name == e.name))
+            .map(|e| e.name.clone())
+            .collect()
+    };
+    let added = names(new, old);
+    let removed = names(old, new);
+    let changed: Vec<&str> = new
+        .iter()
+        .filter(|e| old.iter().any(|o| o.name == e.name && o != *e))
+        .map(|e| e.name.as_str())
+        .collect();
+    for (what, names) in [("Added", added.join(", ")), ("Removed", removed.join(", ")), ("Changed", changed.join(", "))] {
+        if !names.is_empty() {
+            println!("{what}: {names}");
+        }
+    }
+    old != new
+}
+
 fn watch(
-    exercises: &[Exercise],
+    mut exercises: Vec<Exercise>,
     verbose: bool,
-    success_hints: bool,
+    args: &WatchArgs,
+    timeout: Option<u64>,
+    lang: Option<&str>,
 ) -> notify::Result<WatchStatus> {
     /* Clears the terminal with an ANSI escape code.
-    Works in UNIX and newer Windows terminals. */
+    Works in UNIX and newer Windows terminals, the others are left as they are. */
     fn clear_screen() {
-        println!("\x1Bc");
+        if ui::ansi() {
+            println!("\x1Bc");
+        }
+    }
+
+    // Marks the start of a new run when the screen isn't cleared
+    fn print_run_separator(exercise: &Exercise) {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() % 86400;
+        let time = format!("{:02}:{:02}:{:02} UTC", secs / 3600, secs / 60 % 60, secs % 60);
+        println!();
+        println!("{}", console::style(format!("========== {} @ {time} ==========", exercise.name)).bold());
     }
 
     let (tx, rx) = channel();
+    let (command_tx, command_rx) = channel();
     let should_quit = Arc::new(AtomicBool::new(false));
 
-    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, DEBOUNCE)?;
     watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+    for dir in watched_dirs(&exercises) {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    let mut clear = !args.no_clear;
+    if clear {
+        clear_screen();
+    }
 
-    clear_screen();
+    let toolchain = rustc_version();
+    let mut state = Progress::load(PROGRESS_FILE, &toolchain).force_recheck(args.force_recheck);
+    let mut history = PromptHistory::default();
+    let stopwatch = Stopwatch::start();
+    // Exercises that passed in an earlier run and haven't been edited
+    // since don't need to be verified again
+    let resume_point = state.resume_point(&exercises);
+    let working_on = Arc::new(Mutex::new(exercises[resume_point.min(exercises.len() - 1)].name.clone()));
+    save_on_interrupt(stopwatch.clone(), Arc::clone(&working_on), toolchain);
 
     let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(
-        exercises.iter(),
-        (0, exercises.len()),
+    let index_of = |exercises: &[Exercise], t: &Exercise| exercises.iter().position(|e| std::ptr::eq(e, t)).unwrap();
+    let mut current = match verify(
+        exercises[resume_point..].iter(),
+        (resume_point, exercises.len()),
         verbose,
-        success_hints,
+        args.success_hints,
+        &mut state,
+        &mut history,
     ) {
         Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
+        Err(exercise) => index_of(&exercises, exercise),
     };
-    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit));
+    *working_on.lock().unwrap() = exercises[current].name.clone();
+    let failed_exercise_hint = Arc::new(Mutex::new(Some(to_owned_hint(&exercises[current]))));
+    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit), command_tx);
+    // Exercises deferred with `skip`, revisited once everything else is done
+    let mut skipped: Vec<usize> = Vec::new();
     loop {
-        match rx.recv_timeout(Duration::from_secs(1)) {
-            Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
-                        let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .find(|e| filepath.ends_with(&e.path))
-                            .into_iter()
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        let num_done = exercises.iter().filter(|e| e.looks_done()).count();
-                        clear_screen();
-                        match verify(
-                            pending_exercises,
-                            (num_done, exercises.len()),
-                            verbose,
-                            success_hints,
-                        ) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
+        let events = match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(event) => {
+                // Editors tend to produce a burst of events per save,
+                // collect them all before deciding what to rebuild
+                let mut events = vec![event];
+                while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+                    events.push(event);
+                }
+                events
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                // the timeout expired, just check for commands and the `should_quit` variable below then loop again
+                Vec::new()
+            }
+            Err(e) => {
+                println!("watch error: {e:?}");
+                Vec::new()
+            }
+        };
+        // A broken info.toml leaves the exercises as they were
+        let mut reloaded = false;
+        if info_toml_changed(&events) {
+            match load_exercises(timeout, lang) {
+                Ok(new) if new.is_empty() => println!("info.toml lists no exercises, keeping the ones from before."),
+                Ok(new) => {
+                    if report_reload(&exercises, &new) {
+                        println!("Reloaded info.toml.");
+                        (current, skipped) = carry_over(&exercises, &new, current, &skipped);
+                        exercises = new;
+                        for dir in watched_dirs(&exercises) {
+                            watcher.watch(dir, RecursiveMode::NonRecursive)?;
                         }
+                        *failed_exercise_hint.lock().unwrap() = Some(to_owned_hint(&exercises[current]));
+                        *working_on.lock().unwrap() = exercises[current].name.clone();
+                        reloaded = true;
+                    }
+                }
+                Err(e) => println!("{e}\nKeeping the exercises from before the change to info.toml."),
+            }
+        }
+
+        let exercises = &exercises;
+        let is_done = |i: usize| exercises[i].looks_done();
+        let order = exercises_changed_by(&events, exercises)
+            .first()
+            .map(|&changed| verification_order(exercises.len(), is_done, Some(changed), 0, &skipped))
+            .or_else(|| reloaded.then(|| verification_order(exercises.len(), is_done, Some(current), current, &skipped)));
+        let order = order.or_else(|| match command_rx.try_recv().ok()? {
+            WatchCommand::Done => {
+                let exercise = &exercises[current];
+                if is_done(current) {
+                    println!("{exercise} has no `I AM NOT DONE` comment left.");
+                    return None;
+                }
+                if !passes(exercise) {
+                    println!("{exercise} doesn't pass yet, so its `I AM NOT DONE` comment stays.");
+                    return None;
+                }
+                match exercise.remove_marker() {
+                    // Verified once more, to record that it passes
+                    Ok(_) if is_done(current) => {
+                        Some(verification_order(exercises.len(), is_done, Some(current), current, &skipped))
+                    }
+                    Ok(_) => {
+                        println!("The `I AM NOT DONE` comment of {exercise} isn't on a line of its own, please remove it yourself.");
+                        None
+                    }
+                    Err(e) => {
+                        println!("Could not remove the `I AM NOT DONE` comment of {exercise}: {e}");
+                        None
                     }
                 }
-                _ => {}
+            }
+            WatchCommand::Skip => {
+                if !skipped.contains(&current) {
+                    skipped.push(current);
+                }
+                Some(verification_order(exercises.len(), is_done, None, current + 1, &skipped))
+            }
+            WatchCommand::Next => match (current + 1..exercises.len()).find(|&i| !is_done(i)) {
+                Some(next) => may_jump_to(exercises, next, &state, args.strict_order)
+                    .then(|| verification_order(exercises.len(), is_done, Some(next), next, &skipped)),
+                None => {
+                    println!("There is no unsolved exercise after {}.", exercises[current].name);
+                    None
+                }
             },
-            Err(RecvTimeoutError::Timeout) => {
-                // the timeout expired, just check the `should_quit` variable below then loop again
+            WatchCommand::Prev => match (0..current).rev().find(|&i| !is_done(i)) {
+                Some(prev) => may_jump_to(exercises, prev, &state, args.strict_order)
+                    .then(|| verification_order(exercises.len(), is_done, Some(prev), prev, &skipped)),
+                None => {
+                    println!("There is no unsolved exercise before {}.", exercises[current].name);
+                    None
+                }
+            },
+            WatchCommand::ToggleClear => {
+                clear = !clear;
+                println!("The screen will {}be cleared before every run.", if clear { "" } else { "no longer " });
+                None
+            }
+            WatchCommand::Goto(name) => match exercises.iter().position(|e| e.name == name) {
+                Some(target) => may_jump_to(exercises, target, &state, args.strict_order)
+                    .then(|| verification_order(exercises.len(), is_done, Some(target), target, &skipped)),
+                None => {
+                    println!("No exercise found for '{name}'!");
+                    None
+                }
+            },
+        });
+
+        if let Some(order) = order {
+            // Whatever led here was the work on the current exercise
+            state.add_time_spent(&exercises[current], stopwatch.lap());
+            let num_done = exercises.iter().filter(|e| e.looks_done()).count();
+            let passed_before: Vec<bool> = exercises.iter().map(|e| state.passed(e)).collect();
+            let previous = current;
+            if clear {
+                clear_screen();
+                history.forget();
+            } else if let Some(&first) = order.first() {
+                print_run_separator(&exercises[first]);
+            }
+            let result = verify(
+                order.iter().map(|&i| &exercises[i]),
+                (num_done, exercises.len()),
+                verbose,
+                args.success_hints,
+                &mut state,
+                &mut history,
+            );
+            if args.git_autocommit {
+                let completed = order.iter().filter(|&&i| !passed_before[i] && state.passed(&exercises[i]));
+                for exercise in completed.map(|&i| &exercises[i]) {
+                    match autocommit::commit(exercise) {
+                        Ok(()) => println!("Committed {exercise} to git."),
+                        Err(e) => warn!("Not committing {}", format!("{exercise}: {e}")),
+                    }
+                }
+            }
+            if args.notify {
+                // The exercise being worked on went from pending to done
+                if !passed_before[previous] && state.passed(&exercises[previous]) {
+                    notification::send(
+                        &format!("{} is done!", exercises[previous].name),
+                        "Keep it up!",
+                    );
+                }
+                // An exercise that used to pass is failing now
+                if let Err(exercise) = result {
+                    if passed_before[index_of(exercises, exercise)] {
+                        notification::send(
+                            &format!("{} is failing", exercise.name),
+                            &first_error_line(exercise).unwrap_or_default(),
+                        );
+                    }
+                }
+            }
+            match result {
+                Ok(_) => return Ok(WatchStatus::Finished),
+                Err(exercise) => {
+                    current = index_of(exercises, exercise);
+                    // Everything verified before the failing exercise passed
+                    let passed = &order[..order.iter().position(|&i| i == current).unwrap()];
+                    skipped.retain(|i| !passed.contains(i));
+                    if skipped.contains(&current) {
+                        println!("Revisiting skipped exercise {}.", exercise.name);
+                    }
+                    let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
+                    *failed_exercise_hint = Some(to_owned_hint(exercise));
+                    *working_on.lock().unwrap() = exercise.name.clone();
+                }
             }
-            Err(e) => println!("watch error: {e:?}"),
         }
         // Check if we need to exit
         if should_quit.load(Ordering::SeqCst) {
+            state.add_time_spent(&exercises[current], stopwatch.lap());
             return Ok(WatchStatus::Unfinished);
         }
     }
 }
 
+// The version of the toolchain, which cached results are only valid for
+fn rustc_version() -> String {
+    Command::new("rustc")
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
 fn rustc_exists() -> bool {
     Command::new("rustc")
-        .args(&["--version"])
+        .args(["--version"])
         .stdout(Stdio::null())
         .spawn()
         .and_then(|mut child| child.wait())
@@ -594,3 +2025,203 @@ const WELCOME: &str = r#"       welcome to...
  | |  | |_| \__ \ |_| | | | | | (_| \__ \
  |_|   \__,_|___/\__|_|_|_| |_|\__, |___/
                                |___/"#;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn exercises() -> Vec<Exercise> {
+        ["variables1", "variables2"]
+            .iter()
+            .map(|name| Exercise {
+                name: name.to_string(),
+                path: PathBuf::from(format!("exercises/variables/{name}.rs")),
+                additional_files: Vec::new(),
+                mode: Mode::Compile,
+                hint: String::new(),
+                hints: BTreeMap::new(),
+                env: BTreeMap::new(),
+                editable_tests: false,
+                timeout_secs: None,
+                stdin_file: None,
+                tags: Vec::new(),
+                difficulty: 0,
+                release: false,
+                points: 1,
+                requires: Vec::new(),
+                clippy_args: None,
+                manifest: None,
+                cfg: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn editor_save_schedules_a_single_rebuild() {
+        let dir = PathBuf::from("/home/student/rustlings/exercises/variables");
+        // roughly what vim does when saving with `backupcopy=no`
+        let events = [
+            DebouncedEvent::Create(dir.join("4913")),
+            DebouncedEvent::Write(dir.join(".variables2.rs.swp")),
+            DebouncedEvent::Rename(dir.join("variables2.rs"), dir.join("variables2.rs~")),
+            DebouncedEvent::Create(dir.join("variables2.rs")),
+            DebouncedEvent::Write(dir.join("variables2.rs")),
+            DebouncedEvent::Chmod(dir.join("variables2.rs")),
+            DebouncedEvent::Remove(dir.join("variables2.rs~")),
+        ];
+        assert_eq!(exercises_changed_by(&events, &exercises()), [1]);
+    }
+
+    #[test]
+    fn atomic_rename_save_is_seen() {
+        let dir = PathBuf::from("/home/student/rustlings/exercises/variables");
+        let events = [
+            DebouncedEvent::Create(dir.join("variables1.rs.tmp")),
+            DebouncedEvent::Rename(dir.join("variables1.rs.tmp"), dir.join("variables1.rs")),
+        ];
+        assert_eq!(exercises_changed_by(&events, &exercises()), [0]);
+    }
+
+    #[test]
+    fn replacing_save_is_seen() {
+        let dir = std::env::temp_dir().join(format!("rustlings_replace_{}", std::process::id()));
+        fs::create_dir_all(dir.join("exercises/variables")).unwrap();
+        let file = dir.join("exercises/variables/variables1.rs");
+        fs::write(&file, "fn main() {}\n").unwrap();
+        let changed = exercises_changed_by(&[DebouncedEvent::Remove(file.clone())], &exercises());
+        fs::remove_file(&file).unwrap();
+        let removed = exercises_changed_by(&[DebouncedEvent::Remove(file)], &exercises());
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(changed, [0]);
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn module_file_belongs_to_its_exercise() {
+        let mut exercises = exercises();
+        exercises[1].additional_files = vec![PathBuf::from("exercises/variables/helper.rs")];
+        let events = [DebouncedEvent::Write(PathBuf::from(
+            "/home/student/rustlings/exercises/variables/helper.rs",
+        ))];
+        assert_eq!(exercises_changed_by(&events, &exercises), [1]);
+    }
+
+    #[test]
+    fn shared_and_non_rust_files_belong_to_every_exercise_declaring_them() {
+        let mut exercises = exercises();
+        let input = PathBuf::from("exercises/variables/input.txt");
+        exercises[0].additional_files = vec![input.clone()];
+        exercises[1].additional_files = vec![input];
+        let events = [DebouncedEvent::Write(PathBuf::from(
+            "/home/student/rustlings/exercises/variables/input.txt",
+        ))];
+        assert_eq!(exercises_changed_by(&events, &exercises), [0, 1]);
+    }
+
+    #[test]
+    fn unrelated_files_are_ignored() {
+        let events = [
+            DebouncedEvent::Write(PathBuf::from("/home/student/rustlings/exercises/variables/notes.rs")),
+            DebouncedEvent::Write(PathBuf::from("/home/student/rustlings/exercises/variables/README.md")),
+            DebouncedEvent::Write(PathBuf::from("/home/student/rustlings/exercises/variables/.variables1.rs")),
+            DebouncedEvent::NoticeWrite(PathBuf::from("/home/student/rustlings/exercises/variables/variables1.rs")),
+        ];
+        assert!(exercises_changed_by(&events, &exercises()).is_empty());
+    }
+
+    #[test]
+    fn reload_follows_exercises_by_name() {
+        let old = exercises();
+        let mut new = exercises();
+        let mut added = new[0].clone();
+        added.name = "variables0".to_string();
+        new.insert(0, added);
+        assert!(report_reload(&old, &new));
+        assert_eq!(carry_over(&old, &new, 1, &[0]), (2, vec![1]));
+
+        // The current exercise is gone, the one after it takes over
+        new.remove(1);
+        assert_eq!(carry_over(&old, &new, 0, &[0]), (1, vec![]));
+        assert!(!report_reload(&old, &old.clone()));
+    }
+
+    #[test]
+    fn only_info_toml_triggers_reload() {
+        let root = PathBuf::from("/home/student/rustlings");
+        assert!(info_toml_changed(&[DebouncedEvent::Write(root.join("info.toml"))]));
+        assert!(info_toml_changed(&[DebouncedEvent::Create(root.join("packs/os/os.toml"))]));
+        assert!(!info_toml_changed(&[DebouncedEvent::Write(root.join("exercises/clippy/Cargo.toml"))]));
+        assert!(!info_toml_changed(&[DebouncedEvent::Write(root.join(".rustlings-state.json"))]));
+    }
+
+    #[test]
+    fn verification_order_wraps_around() {
+        let done = |i: usize| i == 1 || i == 4;
+        assert_eq!(verification_order(6, done, None, 3, &[]), [3, 5, 0, 2]);
+        assert_eq!(verification_order(6, done, Some(4), 0, &[]), [4, 0, 2, 3, 5]);
+    }
+
+    #[test]
+    fn skipped_exercises_come_last() {
+        let done = |i: usize| i == 0;
+        assert_eq!(verification_order(5, done, None, 2, &[2]), [3, 4, 1, 2]);
+        assert_eq!(verification_order(5, done, None, 0, &[3, 1]), [2, 4, 3, 1]);
+        // even when they look done
+        assert_eq!(verification_order(3, done, None, 1, &[0]), [1, 2, 0]);
+    }
+
+    #[test]
+    fn first_exercise_is_not_repeated() {
+        let done = |_| false;
+        assert_eq!(verification_order(3, done, Some(1), 1, &[1]), [1, 2, 0]);
+        assert_eq!(verification_order(3, done, Some(0), 5, &[]), [0, 1, 2]);
+    }
+
+    #[test]
+    fn close_matches_tolerate_typos() {
+        let names = ["variables1", "variables2", "vecs1", "algorithm1"];
+        assert_eq!(close_matches("varaibles1", names), ["variables1", "variables2"]);
+        assert_eq!(close_matches("algo", names), ["algorithm1"]);
+        assert!(close_matches("iterators1", names).is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn completions_match_arguments() {
+        let long_options = |help: &str| -> Vec<String> {
+            let mut options: Vec<String> = help
+                .lines()
+                .skip_while(|l| !l.starts_with("Options:"))
+                .take_while(|l| !l.starts_with("Commands:"))
+                .filter_map(|l| l.split_whitespace().find(|w| w.starts_with("--")))
+                .map(str::to_string)
+                .collect();
+            options.sort();
+            options
+        };
+        let help = |args: &[&str]| Args::from_args(&["rustlings"], args).unwrap_err().output;
+        let sorted = |options: &[&str]| {
+            let mut options: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+            options.sort();
+            options
+        };
+
+        assert_eq!(long_options(&help(&["--help"])), sorted(completions::GLOBAL_OPTIONS));
+        let commands: Vec<&str> = completions::COMMANDS.iter().map(|(name, _, _)| *name).collect();
+        let top_level = help(&["--help"]);
+        let listed: Vec<&str> = top_level
+            .lines()
+            .skip_while(|l| !l.starts_with("Commands:"))
+            .skip(1)
+            .filter(|l| l.starts_with("  ") && !l.starts_with("    "))
+            .filter_map(|l| l.split_whitespace().next())
+            .collect();
+        assert_eq!(listed, commands);
+        for (name, _, options) in completions::COMMANDS {
+            assert_eq!(long_options(&help(&[name, "--help"])), sorted(options), "{name}");
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use console::{measure_text_width, style, Term};
+
+// Lines are never wrapped narrower than this, however narrow the terminal
+const MIN_WIDTH: usize = 20;
+
+/// Print a hint, or any other markdown text from info.toml.
+/// On a terminal with colors it is styled and wrapped to the terminal's
+/// width; otherwise, as when piped, it is printed as written.
+pub fn print(text: &str) {
+    let term = Term::stdout();
+    if term.is_term() && console::colors_enabled() {
+        let width = term.size_checked().map(|(_, columns)| columns as usize);
+        print!("{}", render(text, width));
+    } else {
+        println!("{text}");
+    }
+}
+
+// A block of text: a paragraph or a list item. Hard wrapped lines
+// of a block are joined, so they can be wrapped again.
+struct Block {
+    // Printed on the first line of the block, e.g. `  • `
+    marker: String,
+    // The width of the marker, by which the other lines are indented
+    indent: usize,
+    text: String,
+}
+
+/// Render `text` with basic ANSI styling: `**bold**`, `*emphasis*`,
+/// highlighted `` `code` `` and underlined links, with list items
+/// indented under a bullet. Lines are wrapped at `width` if given.
+pub fn render(text: &str, width: Option<usize>) -> String {
+    let mut blocks: Vec<Option<Block>> = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let depth = (line.len() - trimmed.len()) / 2;
+        if trimmed.is_empty() {
+            // A blank line, printed as such
+            blocks.push(None);
+        } else if let Some((marker, rest)) = list_marker(trimmed) {
+            let marker = format!("{}{marker} ", "  ".repeat(depth + 1));
+            blocks.push(Some(Block {
+                indent: measure_text_width(&marker),
+                marker,
+                text: rest.to_string(),
+            }));
+        } else if let Some(Some(block)) = blocks.last_mut() {
+            block.text.push(' ');
+            block.text.push_str(trimmed);
+        } else {
+            blocks.push(Some(Block {
+                marker: String::new(),
+                indent: 0,
+                text: trimmed.to_string(),
+            }));
+        }
+    }
+
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Some(block) => wrap(&block, width, &mut out),
+            None => out.push('\n'),
+        }
+    }
+    out
+}
+
+// The bullet or number starting a list item, and the item's text
+fn list_marker(line: &str) -> Option<(String, &str)> {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(("•".to_string(), rest.trim_start()));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then(|| (line[..=digits].to_string(), rest.trim_start()))
+}
+
+// Append the block to `out`, broken into lines of at most `width` columns
+fn wrap(block: &Block, width: Option<usize>, out: &mut String) {
+    let width = width.map(|w| w.saturating_sub(block.indent).max(MIN_WIDTH));
+    let mut line_width = 0;
+    out.push_str(&block.marker);
+    for word in words(&block.text) {
+        let word_width = measure_text_width(&word);
+        if line_width > 0 {
+            if width.is_some_and(|width| line_width + 1 + word_width > width) {
+                out.push('\n');
+                out.push_str(&" ".repeat(block.indent));
+                line_width = 0;
+            } else {
+                out.push(' ');
+                line_width += 1;
+            }
+        }
+        out.push_str(&word);
+        line_width += word_width;
+    }
+    out.push('\n');
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Kind {
+    Plain,
+    Bold,
+    Emphasis,
+    Code,
+    Link,
+    Url,
+}
+
+// The text split into styled spans
+fn spans(text: &str) -> Vec<(Kind, &str)> {
+    // The span starting with `open` at the start of `rest` if it is closed
+    fn delimited<'a>(rest: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+        let inner = rest.strip_prefix(open)?;
+        let end = inner.find(close)?;
+        (end > 0).then(|| (&inner[..end], open.len() + end + close.len()))
+    }
+
+    let mut spans = Vec::new();
+    let mut plain_start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let found = if let Some((code, len)) = delimited(rest, "`", "`") {
+            Some((vec![(Kind::Code, code)], len))
+        } else if let Some((bold, len)) = delimited(rest, "**", "**") {
+            Some((vec![(Kind::Bold, bold)], len))
+        } else if let Some((emphasis, len)) = delimited(rest, "*", "*").filter(|(e, _)| !e.starts_with(' ')) {
+            Some((vec![(Kind::Emphasis, emphasis)], len))
+        } else if let Some((label, label_len)) = delimited(rest, "[", "]") {
+            delimited(&rest[label_len..], "(", ")")
+                .map(|(url, url_len)| (vec![(Kind::Link, label), (Kind::Url, url)], label_len + url_len))
+        } else {
+            None
+        };
+        match found {
+            Some((found, len)) => {
+                if plain_start < i {
+                    spans.push((Kind::Plain, &text[plain_start..i]));
+                }
+                spans.extend(found);
+                i += len;
+                plain_start = i;
+            }
+            None => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if plain_start < text.len() {
+        spans.push((Kind::Plain, &text[plain_start..]));
+    }
+    spans
+}
+
+// The styled words of the text. Every word is styled on its own so
+// that wrapping never carries a style over to the indentation.
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (kind, span) in spans(text) {
+        let span = match kind {
+            Kind::Url => format!("({span})"),
+            _ => span.to_string(),
+        };
+        let mut pieces = span.split(' ').peekable();
+        if kind == Kind::Url && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        while let Some(piece) = pieces.next() {
+            if !piece.is_empty() {
+                let styled = match kind {
+                    Kind::Plain => style(piece),
+                    Kind::Bold => style(piece).bold(),
+                    Kind::Emphasis => style(piece).italic(),
+                    Kind::Code => style(piece).cyan(),
+                    Kind::Link => style(piece).underlined(),
+                    Kind::Url => style(piece).dim(),
+                };
+                word.push_str(&styled.to_string());
+            }
+            // A space ends the word
+            if pieces.peek().is_some() && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn plain(text: &str, width: Option<usize>) -> String {
+        console::set_colors_enabled(false);
+        render(text, width)
+    }
+
+    #[test]
+    fn lists_are_indented() {
+        let hint = "So the end goal is to:\n   - get rid of `vec0`, then\n     create a new vec\n   - fix `main`\n\n1. done";
+        assert_eq!(
+            plain(hint, None),
+            "So the end goal is to:\n    • get rid of vec0, then create a new vec\n    • fix main\n\n  1. done\n"
+        );
+    }
+
+    #[test]
+    fn long_lines_are_wrapped() {
+        let hint = "- one two three four five six seven eight nine ten eleven twelve";
+        assert_eq!(
+            plain(hint, Some(30)),
+            "  • one two three four five\n    six seven eight nine ten\n    eleven twelve\n"
+        );
+    }
+
+    #[test]
+    fn inline_markup_is_removed() {
+        assert_eq!(
+            plain("Read the **Into** `trait` [docs](https://doc.rust-lang.org/std), *now*.", None),
+            "Read the Into trait docs (https://doc.rust-lang.org/std), now.\n"
+        );
+        // Unclosed markers are kept
+        assert_eq!(plain("2 * 3 and a `tick", None), "2 * 3 and a `tick\n");
+    }
+}
@@ -0,0 +1,62 @@
+use crate::exercise::Exercise;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The directory keeping a copy of every exercise as it was
+/// the first time rustlings saw it, relative to the rustlings directory
+pub const ORIGINALS_DIR: &str = ".originals";
+
+// Where the original copy of an exercise's file is kept
+fn original_path(file: &Path) -> PathBuf {
+    Path::new(ORIGINALS_DIR).join(file)
+}
+
+/// Copy every exercise file that doesn't have an original copy yet,
+/// so that exercises added to info.toml later are covered as well
+pub fn snapshot(exercises: &[Exercise]) -> io::Result<()> {
+    for file in exercises.iter().flat_map(Exercise::files) {
+        let original = original_path(file);
+        if original.exists() || !file.exists() {
+            continue;
+        }
+        if let Some(dir) = original.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::copy(file, original)?;
+    }
+    Ok(())
+}
+
+/// Whether every file of the exercise has an original copy
+pub fn exist(exercise: &Exercise) -> bool {
+    exercise.files().all(|file| original_path(file).exists())
+}
+
+/// Overwrite every file of the exercise with its original copy.
+/// Nothing is touched unless all of the copies exist.
+pub fn restore(exercise: &Exercise) -> io::Result<()> {
+    if let Some(missing) = exercise.files().find(|file| !original_path(file).exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no original copy of {}", missing.display()),
+        ));
+    }
+    for file in exercise.files() {
+        fs::copy(original_path(file), file)?;
+    }
+    Ok(())
+}
+
+/// Copy the original copies of the exercise's files into `dir`, under
+/// the same relative paths, leaving the exercise itself alone
+pub fn copy_to(exercise: &Exercise, dir: &Path) -> io::Result<()> {
+    for file in exercise.files() {
+        let copy = dir.join(file);
+        if let Some(parent) = copy.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(original_path(file), copy)?;
+    }
+    Ok(())
+}
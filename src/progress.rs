@@ -0,0 +1,515 @@
+use crate::exercise::Exercise;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+/// The file the verification progress is persisted to,
+/// relative to the rustlings directory
+pub const PROGRESS_FILE: &str = ".rustlings-state.json";
+
+// Bump this whenever the layout of the file or the meaning of its
+// hashes changes. Files written with another version are ignored.
+const FORMAT_VERSION: u32 = 2;
+
+/// The last verification result of every exercise, together with a hash
+/// of everything the result depends on: the source file, the exercise's
+/// mode and path in info.toml, and the rustc version.
+/// Also the time spent on every exercise in watch mode, and how many
+/// attempts it took.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Progress {
+    version: u32,
+    exercises: BTreeMap<String, Entry>,
+    // In milliseconds. Unlike the results this can't be worked out again,
+    // so it is kept even from files written in another format version.
+    #[serde(default)]
+    time_spent: BTreeMap<String, u64>,
+    // Kept like the time spent
+    #[serde(default)]
+    attempts: BTreeMap<String, Attempts>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    toolchain: String,
+    #[serde(skip)]
+    force_recheck: bool,
+}
+
+// All that is read from files written in another format version
+#[derive(Deserialize)]
+struct Statistics {
+    #[serde(default)]
+    time_spent: BTreeMap<String, u64>,
+    #[serde(default)]
+    attempts: BTreeMap<String, Attempts>,
+}
+
+/// How many tries an exercise took. Only versions of the exercise that
+/// weren't verified before count, the first one seen being where it
+/// started from.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct Attempts {
+    /// The versions of the exercise that failed to compile or run
+    pub failures: u32,
+    /// The versions verified until it passed for the first time
+    pub saves: u32,
+    #[serde(default)]
+    solved: bool,
+    // The version verified last
+    #[serde(default)]
+    hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct Entry {
+    passed: bool,
+    hash: String,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress {
+            version: FORMAT_VERSION,
+            exercises: BTreeMap::new(),
+            time_spent: BTreeMap::new(),
+            attempts: BTreeMap::new(),
+            path: None,
+            toolchain: String::new(),
+            force_recheck: false,
+        }
+    }
+}
+
+impl Progress {
+    /// Load the progress from `path`, starting afresh if the file
+    /// is missing, corrupt or was written in an older format.
+    /// Changes are written back to the same file.
+    /// `toolchain` identifies the compiler, e.g. the output of `rustc --version`;
+    /// results obtained with another compiler are not up to date.
+    pub fn load(path: impl AsRef<Path>, toolchain: &str) -> Self {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).unwrap_or_default();
+        let progress = serde_json::from_str::<Progress>(&contents)
+            .ok()
+            .filter(|p| p.version == FORMAT_VERSION)
+            .unwrap_or_else(|| {
+                let statistics = serde_json::from_str::<Statistics>(&contents).ok();
+                let (time_spent, attempts) = statistics.map(|s| (s.time_spent, s.attempts)).unwrap_or_default();
+                Progress {
+                    time_spent,
+                    attempts,
+                    ..Progress::default()
+                }
+            });
+        Progress {
+            path: Some(path.to_path_buf()),
+            toolchain: toolchain.to_string(),
+            ..progress
+        }
+    }
+
+    /// Treat every exercise as out of date for as long as this lives,
+    /// while still recording new results
+    pub fn force_recheck(self, force_recheck: bool) -> Self {
+        Progress {
+            force_recheck,
+            ..self
+        }
+    }
+
+    /// Whether the exercise passed last time and neither it
+    /// nor the toolchain changed since
+    pub fn is_up_to_date(&self, exercise: &Exercise) -> bool {
+        if self.force_recheck {
+            return false;
+        }
+        match (self.exercises.get(&exercise.name), self.hash(exercise)) {
+            (Some(entry), Some(hash)) => entry.passed && entry.hash == hash,
+            _ => false,
+        }
+    }
+
+    /// Whether the exercise passed the last time it was verified
+    pub fn passed(&self, exercise: &Exercise) -> bool {
+        self.exercises.get(&exercise.name).is_some_and(|e| e.passed)
+    }
+
+    /// Whether the exercise passed the last time it was verified,
+    /// `None` if it never was
+    pub fn verified(&self, exercise: &Exercise) -> Option<bool> {
+        self.exercises.get(&exercise.name).map(|e| e.passed)
+    }
+
+    /// The index of the first exercise that has to be verified again,
+    /// or `exercises.len()` if all of them are up to date
+    pub fn resume_point(&self, exercises: &[Exercise]) -> usize {
+        exercises
+            .iter()
+            .position(|e| !self.is_up_to_date(e))
+            .unwrap_or(exercises.len())
+    }
+
+    /// Record the verification result of an exercise and persist it
+    pub fn record(&mut self, exercise: &Exercise, passed: bool) {
+        let hash = match self.hash(exercise) {
+            Some(hash) => hash,
+            None => return,
+        };
+        let entry = Entry { passed, hash };
+        if self.exercises.get(&exercise.name) == Some(&entry) {
+            return;
+        }
+        self.exercises.insert(exercise.name.clone(), entry);
+        // Failing to persist the progress only costs a recheck next time
+        let _ = self.save();
+    }
+
+    /// Count an attempt at the exercise, that compiled and ran successfully
+    /// or not, unless this version of it was counted already
+    pub fn count_attempt(&mut self, exercise: &Exercise, passed: bool) {
+        let hash = match self.hash(exercise) {
+            Some(hash) => hash,
+            None => return,
+        };
+        let attempts = match self.attempts.get_mut(&exercise.name) {
+            Some(attempts) if attempts.hash == hash => return,
+            Some(attempts) => attempts,
+            // Where the exercise started from, not an attempt yet
+            None => {
+                let attempts = Attempts {
+                    solved: passed,
+                    hash,
+                    ..Attempts::default()
+                };
+                self.attempts.insert(exercise.name.clone(), attempts);
+                let _ = self.save();
+                return;
+            }
+        };
+        attempts.hash = hash;
+        if !attempts.solved {
+            attempts.saves += 1;
+        }
+        if passed {
+            attempts.solved = true;
+        } else {
+            attempts.failures += 1;
+        }
+        let _ = self.save();
+    }
+
+    /// The attempts counted for the exercise so far
+    pub fn attempts(&self, exercise: &Exercise) -> Attempts {
+        self.attempts.get(&exercise.name).cloned().unwrap_or_default()
+    }
+
+    /// The time spent on the exercise in watch mode so far
+    pub fn time_spent(&self, exercise: &Exercise) -> Duration {
+        Duration::from_millis(self.time_spent.get(&exercise.name).copied().unwrap_or(0))
+    }
+
+    /// The time spent on all exercises together
+    pub fn total_time_spent(&self) -> Duration {
+        Duration::from_millis(self.time_spent.values().sum())
+    }
+
+    /// Count more time spent on the exercise and persist it
+    pub fn add_time_spent(&mut self, exercise: &Exercise, time: Duration) {
+        self.add_time(&exercise.name, time);
+    }
+
+    /// Add to the time spent on the exercise called `name`
+    pub fn add_time(&mut self, name: &str, time: Duration) {
+        let millis = time.as_millis() as u64;
+        if millis == 0 {
+            return;
+        }
+        *self.time_spent.entry(name.to_string()).or_default() += millis;
+        let _ = self.save();
+    }
+
+    /// Drop everything recorded for the exercise: its verification
+    /// result, its attempts and the time spent on it
+    pub fn forget(&mut self, exercise: &Exercise) {
+        let result = self.exercises.remove(&exercise.name);
+        let attempts = self.attempts.remove(&exercise.name);
+        let time_spent = self.time_spent.remove(&exercise.name);
+        if result.is_some() || attempts.is_some() || time_spent.is_some() {
+            let _ = self.save();
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        // Write to a temporary file first so that a concurrent reader
+        // never sees a half-written file
+        let temp = path.with_extension(format!("json.{}", process::id()));
+        fs::write(&temp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&temp, path)
+    }
+
+    // A 64 bit FNV-1a hash of the exercise's sources, input and manifest, its info.toml
+    // entry (the lints of a clippy exercise, the environment variables, the profile and
+    // the cfg options included) and the toolchain. It is stable across Rust versions,
+    // unlike the std hashers. Files the sources read through `#[path]` or `include!` are
+    // only covered if they are in `additional_files`, which `rustlings dev check` makes
+    // sure of.
+    fn hash(&self, exercise: &Exercise) -> Option<String> {
+        let mut contents = Vec::new();
+        let extra = [&exercise.stdin_file, &exercise.manifest].into_iter().flatten().map(PathBuf::as_path);
+        for file in exercise.files().chain(extra) {
+            contents.extend(fs::read(file).ok()?);
+            contents.push(0);
+        }
+        let files: Vec<String> = exercise.files().map(|f| f.display().to_string()).collect();
+        let mut entry = format!("{:?}\0{}\0{}\0{}", exercise.mode, files.join("\0"), self.toolchain, exercise.profile());
+        if let Some(args) = &exercise.clippy_args {
+            entry.push('\0');
+            entry.push_str(&args.join(" "));
+        }
+        for (key, value) in &exercise.env {
+            entry.push_str(&format!("\0{key}={value}"));
+        }
+        for cfg in &exercise.cfg {
+            entry.push_str(&format!("\0--cfg {cfg}"));
+        }
+        let hash = contents
+            .iter()
+            .chain(entry.as_bytes())
+            .fold(0xcbf29ce484222325_u64, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+        Some(format!("{hash:016x}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    const TOOLCHAIN: &str = "rustc 1.70.0 (90c541806 2023-05-31)";
+
+    // A scratch directory holding two exercises, removed on drop
+    struct Scratch {
+        dir: PathBuf,
+        exercises: Vec<Exercise>,
+    }
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("rustlings_{name}_{}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let exercises = ["first", "second"]
+                .iter()
+                .map(|name| {
+                    let path = dir.join(format!("{name}.rs"));
+                    fs::write(&path, "fn main() {}\n").unwrap();
+                    Exercise {
+                        name: name.to_string(),
+                        path,
+                        additional_files: Vec::new(),
+                        mode: Mode::Compile,
+                        hint: String::new(),
+                        hints: BTreeMap::new(),
+                        env: BTreeMap::new(),
+                        editable_tests: false,
+                        timeout_secs: None,
+                        stdin_file: None,
+                        tags: Vec::new(),
+                        difficulty: 0,
+                        release: false,
+                        points: 1,
+                        requires: Vec::new(),
+                        clippy_args: None,
+                        manifest: None,
+                        cfg: Vec::new(),
+                    }
+                })
+                .collect();
+            Scratch { dir, exercises }
+        }
+
+        fn state_file(&self) -> PathBuf {
+            self.dir.join(PROGRESS_FILE)
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn resumes_after_passed_exercise() {
+        let scratch = Scratch::new("resume");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert_eq!(progress.resume_point(&scratch.exercises), 0);
+
+        progress.record(&scratch.exercises[0], true);
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert_eq!(progress.resume_point(&scratch.exercises), 1);
+    }
+
+    #[test]
+    fn forgotten_exercise_is_rechecked() {
+        let scratch = Scratch::new("forgotten");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[0], true);
+        progress.forget(&scratch.exercises[0]);
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert!(!progress.is_up_to_date(&scratch.exercises[0]));
+    }
+
+    #[test]
+    fn attempts_count_new_versions_only() {
+        let scratch = Scratch::new("attempts");
+        let exercise = &scratch.exercises[0];
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        // The version it started from, verified twice
+        progress.count_attempt(exercise, false);
+        progress.count_attempt(exercise, false);
+        assert_eq!(progress.attempts(exercise).saves, 0);
+
+        for (source, passed) in [("fn main() { 1 }", false), ("fn main() { 2 }", false), ("fn main() {}", true)] {
+            fs::write(&exercise.path, source).unwrap();
+            progress.count_attempt(exercise, passed);
+            progress.count_attempt(exercise, passed);
+        }
+        // Only failures count once it passed
+        fs::write(&exercise.path, "fn main() { 3 }").unwrap();
+        progress.count_attempt(exercise, false);
+
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        let attempts = progress.attempts(exercise);
+        assert_eq!((attempts.failures, attempts.saves), (3, 3));
+
+        let mut progress = progress;
+        progress.add_time_spent(exercise, Duration::from_secs(5));
+        progress.forget(exercise);
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert_eq!(progress.attempts(exercise).failures, 0);
+        assert_eq!(progress.time_spent(exercise), Duration::ZERO);
+    }
+
+    #[test]
+    fn failed_exercise_is_rechecked() {
+        let scratch = Scratch::new("failed");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[0], true);
+        progress.record(&scratch.exercises[0], false);
+        assert_eq!(progress.resume_point(&scratch.exercises), 0);
+    }
+
+    #[test]
+    fn edited_exercise_is_rechecked() {
+        let scratch = Scratch::new("edited");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[0], true);
+        progress.record(&scratch.exercises[1], true);
+        assert_eq!(progress.resume_point(&scratch.exercises), 2);
+
+        fs::write(&scratch.exercises[0].path, "fn main() { }\n").unwrap();
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert_eq!(progress.resume_point(&scratch.exercises), 0);
+        assert!(progress.is_up_to_date(&scratch.exercises[1]));
+    }
+
+    #[test]
+    fn changed_lints_are_rechecked() {
+        let mut scratch = Scratch::new("lints");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[0], true);
+
+        scratch.exercises[0].clippy_args = Some(vec!["-D".to_string(), "clippy::pedantic".to_string()]);
+        assert!(!progress.is_up_to_date(&scratch.exercises[0]));
+    }
+
+    #[test]
+    fn corrupt_or_old_files_are_ignored() {
+        let scratch = Scratch::new("corrupt");
+        for contents in [
+            "{ not json",
+            r#"{"version":1,"exercises":{"first":{"passed":true,"hash":"0"}}}"#,
+            r#"{"first":true}"#,
+        ] {
+            fs::write(scratch.state_file(), contents).unwrap();
+            let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+            assert_eq!(progress.resume_point(&scratch.exercises), 0);
+
+            // and get replaced by a valid file on the next update
+            progress.record(&scratch.exercises[0], true);
+            let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+            assert_eq!(progress.resume_point(&scratch.exercises), 1);
+        }
+    }
+
+    #[test]
+    fn time_spent_accumulates_across_sessions() {
+        let scratch = Scratch::new("time");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.add_time_spent(&scratch.exercises[0], Duration::from_secs(90));
+        progress.record(&scratch.exercises[0], true);
+
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.add_time_spent(&scratch.exercises[0], Duration::from_millis(500));
+        progress.add_time_spent(&scratch.exercises[1], Duration::from_secs(10));
+        assert_eq!(progress.time_spent(&scratch.exercises[0]), Duration::from_millis(90_500));
+        assert_eq!(progress.total_time_spent(), Duration::from_millis(100_500));
+    }
+
+    #[test]
+    fn time_spent_survives_other_versions() {
+        let scratch = Scratch::new("time_version");
+        fs::write(
+            scratch.state_file(),
+            r#"{"version":9,"exercises":[],"time_spent":{"first":1500},"new_field":true}"#,
+        )
+        .unwrap();
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert_eq!(progress.time_spent(&scratch.exercises[0]), Duration::from_millis(1500));
+        assert!(!progress.passed(&scratch.exercises[0]));
+    }
+
+    #[test]
+    fn cache_hit() {
+        let scratch = Scratch::new("hit");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[1], true);
+
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert!(progress.is_up_to_date(&scratch.exercises[1]));
+        assert!(!progress.is_up_to_date(&scratch.exercises[0]));
+        let progress = progress.force_recheck(true);
+        assert!(!progress.is_up_to_date(&scratch.exercises[1]));
+    }
+
+    #[test]
+    fn toolchain_change_invalidates() {
+        let scratch = Scratch::new("toolchain");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[0], true);
+
+        let progress = Progress::load(scratch.state_file(), "rustc 1.71.0 (8ede3aae2 2023-07-12)");
+        assert!(!progress.is_up_to_date(&scratch.exercises[0]));
+    }
+
+    #[test]
+    fn mode_change_invalidates() {
+        let mut scratch = Scratch::new("mode");
+        let mut progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        progress.record(&scratch.exercises[0], true);
+
+        scratch.exercises[0].mode = Mode::Test;
+        let progress = Progress::load(scratch.state_file(), TOOLCHAIN);
+        assert!(!progress.is_up_to_date(&scratch.exercises[0]));
+    }
+}
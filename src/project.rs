@@ -1,14 +1,21 @@
-use glob::glob;
+use crate::exercise::{Exercise, Mode, EDITION};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::error::Error;
-use std::path::PathBuf;
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 
+/// Where `rustlings lsp` writes the project, relative to the rustlings directory
+pub const PROJECT_FILE: &str = "rust-project.json";
+
 /// Contains the structure of resulting rust-project.json file
 /// and functions to build the data required to create the file
 #[derive(Serialize, Deserialize)]
 pub struct RustAnalyzerProject {
+    // Unknown when RUST_SRC_PATH is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sysroot: Option<String>,
     sysroot_src: String,
     pub crates: Vec<Crate>,
 }
@@ -24,6 +31,7 @@ pub struct Crate {
 impl RustAnalyzerProject {
     pub fn new() -> RustAnalyzerProject {
         RustAnalyzerProject {
+            sysroot: None,
             sysroot_src: String::new(),
             crates: Vec::new(),
         }
@@ -32,40 +40,32 @@ impl RustAnalyzerProject {
     /// Write rust-project.json to disk
     pub fn write_to_disk(&self) -> Result<(), std::io::Error> {
         std::fs::write(
-            "./rust-project.json",
+            PROJECT_FILE,
             serde_json::to_vec(&self).expect("Failed to serialize to JSON"),
         )?;
         Ok(())
     }
 
-    /// If path contains .rs extension, add a crate to `rust-project.json`
-    fn path_to_json(&mut self, path: PathBuf) -> Result<(), Box<dyn Error>> {
-        if let Some(ext) = path.extension() {
-            if ext == "rs" {
-                self.crates.push(Crate {
-                    root_module: path.display().to_string(),
-                    edition: "2021".to_string(),
-                    deps: Vec::new(),
+    /// Add a crate to `rust-project.json` for every exercise, rooted at
+    /// the exercise's main file. The other files of multi-file exercises
+    /// are found by rust-analyzer through their `mod` declarations.
+    pub fn exercises_to_json(&mut self, exercises: &[Exercise]) {
+        for exercise in exercises {
+            self.crates.push(Crate {
+                root_module: exercise.path.display().to_string(),
+                edition: EDITION.to_string(),
+                deps: Vec::new(),
+                cfg: match exercise.mode {
                     // This allows rust_analyzer to work inside #[test] blocks
-                    cfg: vec!["test".to_string()],
-                })
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Parse the exercises folder for .rs files, any matches will create
-    /// a new `crate` in rust-project.json which allows rust-analyzer to
-    /// treat it like a normal binary
-    pub fn exercises_to_json(&mut self) -> Result<(), Box<dyn Error>> {
-        for path in glob("./exercises/**/*")? {
-            self.path_to_json(path?)?;
+                    Mode::Test | Mode::CargoTest => vec!["test".to_string()],
+                    _ => Vec::new(),
+                },
+            })
         }
-        Ok(())
     }
 
-    /// Use `rustc` to determine the default toolchain
+    /// Ask the active toolchain's `rustc` for its sysroot,
+    /// unless RUST_SRC_PATH points to the standard library's sources
     pub fn get_sysroot_src(&mut self) -> Result<(), Box<dyn Error>> {
         // check if RUST_SRC_PATH is set
         if let Ok(path) = env::var("RUST_SRC_PATH") {
@@ -73,20 +73,16 @@ impl RustAnalyzerProject {
             return Ok(());
         }
 
-        let toolchain = Command::new("rustc")
-            .arg("--print")
-            .arg("sysroot")
-            .output()?
-            .stdout;
-
-        let toolchain = String::from_utf8_lossy(&toolchain);
-        let mut whitespace_iter = toolchain.split_whitespace();
-
-        let toolchain = whitespace_iter.next().unwrap_or(&toolchain);
+        let output = Command::new("rustc").arg("--print").arg("sysroot").output()?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().into());
+        }
+        // The path may well contain spaces, only the line break is trimmed
+        let sysroot = String::from_utf8(output.stdout)?.trim_end().to_string();
 
-        println!("Determined toolchain: {}\n", &toolchain);
+        println!("Determined toolchain: {}\n", &sysroot);
 
-        self.sysroot_src = (std::path::Path::new(&*toolchain)
+        self.sysroot_src = (Path::new(&sysroot)
             .join("lib")
             .join("rustlib")
             .join("src")
@@ -94,6 +90,55 @@ impl RustAnalyzerProject {
             .join("library")
             .to_string_lossy())
         .to_string();
+        self.sysroot = Some(sysroot);
         Ok(())
     }
 }
+
+/// Write rust-project.json again if info.toml changed since it was
+/// written by `rustlings lsp`, so that new exercises are picked up
+pub fn refresh(exercises: &[Exercise]) {
+    let modified = |path: &str| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let stale = match (modified(PROJECT_FILE), modified("info.toml")) {
+        (Some(project), Some(info)) => info > project,
+        _ => false,
+    };
+    if !stale {
+        return;
+    }
+    let mut project = RustAnalyzerProject::new();
+    // Keep the sysroot found before, running rustc again isn't needed
+    let old = fs::read(PROJECT_FILE)
+        .ok()
+        .and_then(|json| serde_json::from_slice::<RustAnalyzerProject>(&json).ok());
+    if let Some(old) = old {
+        project.sysroot = old.sysroot;
+        project.sysroot_src = old.sysroot_src;
+    }
+    project.exercises_to_json(exercises);
+    let _ = project.write_to_disk();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::ExerciseList;
+
+    #[test]
+    fn every_exercise_is_one_crate() {
+        let exercises = ExerciseList::load(Path::new("info.toml")).unwrap().exercises;
+        let mut project = RustAnalyzerProject::new();
+        project.exercises_to_json(&exercises);
+        let json = serde_json::to_vec(&project).unwrap();
+        let parsed: RustAnalyzerProject = serde_json::from_slice(&json).unwrap();
+
+        for exercise in &exercises {
+            let path = exercise.path.display().to_string();
+            let crates: Vec<&Crate> = parsed.crates.iter().filter(|c| c.root_module == path).collect();
+            assert_eq!(crates.len(), 1, "{path}");
+            assert_eq!(crates[0].edition, EDITION);
+            assert_eq!(crates[0].cfg.contains(&"test".to_string()), matches!(exercise.mode, Mode::Test | Mode::CargoTest), "{path}");
+        }
+        assert_eq!(parsed.crates.len(), exercises.len());
+    }
+}
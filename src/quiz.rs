@@ -0,0 +1,254 @@
+use crate::exercise::{Exercise, Mode};
+use crate::markdown;
+use crate::originals;
+use crate::run::run;
+use crate::ui::format_duration;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How many exercises a quiz asks for unless `--count` says otherwise
+pub const DEFAULT_COUNT: usize = 5;
+
+// How long to wait for a file to settle before rebuilding
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+// A SplitMix64 generator, small and giving the same numbers
+// for a seed on every platform
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number from 0 to `n` - 1
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+// Up to `count` of the candidates, in an order decided by `seed`
+fn pick<T>(mut candidates: Vec<T>, count: usize, seed: u64) -> Vec<T> {
+    let mut rng = Rng(seed);
+    for i in (1..candidates.len()).rev() {
+        candidates.swap(i, rng.below(i + 1));
+    }
+    candidates.truncate(count);
+    candidates
+}
+
+// The seed of a quiz without `--seed`
+fn random_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    // Short enough to type
+    (nanos ^ u64::from(process::id())) % 1_000_000
+}
+
+// The exercises a quiz may ask for: solved ones, with original copies
+// to start from. Only those rustc builds on their own can be solved
+// elsewhere, the others are tied to a Cargo.toml next to them.
+fn candidates<'a>(exercises: &'a [Exercise], topic: Option<&str>) -> Vec<&'a Exercise> {
+    exercises
+        .iter()
+        .filter(|e| matches!(e.mode, Mode::Compile | Mode::Test))
+        .filter(|e| topic.is_none_or(|topic| e.topic().eq_ignore_ascii_case(topic)))
+        .filter(|e| e.files().all(Path::is_relative) && originals::exist(e))
+        .filter(|e| e.looks_done())
+        .collect()
+}
+
+// The directory a quiz is taken in, removed when the quiz ends
+struct Workspace(PathBuf);
+
+impl Workspace {
+    fn create() -> io::Result<Self> {
+        let dir = env::temp_dir().join(format!("rustlings-quiz-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        // Watch events carry canonical paths
+        Ok(Workspace(dir.canonicalize()?))
+    }
+
+    // A copy of the exercise in the workspace, started over from its
+    // original copy and marked as not done
+    fn copy(&self, exercise: &Exercise) -> io::Result<Exercise> {
+        originals::copy_to(exercise, &self.0)?;
+        let mut copy = exercise.clone();
+        copy.path = self.0.join(&exercise.path);
+        copy.additional_files = exercise.additional_files.iter().map(|f| self.0.join(f)).collect();
+        if copy.looks_done() {
+            let source = fs::read_to_string(&copy.path)?;
+            fs::write(&copy.path, format!("// I AM NOT DONE\n\n{source}"))?;
+        }
+        Ok(copy)
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// What can be typed during a quiz
+enum QuizCommand {
+    Hint,
+    Skip,
+    Quit,
+}
+
+fn spawn_quiz_shell(commands: Sender<QuizCommand>) {
+    thread::spawn(move || loop {
+        let mut input = String::new();
+        let command = match io::stdin().read_line(&mut input) {
+            // stdin was closed, there are no more commands to read
+            Ok(0) | Err(_) => return,
+            Ok(_) => match input.trim() {
+                "" => continue,
+                "hint" => QuizCommand::Hint,
+                "skip" => QuizCommand::Skip,
+                "quit" => QuizCommand::Quit,
+                other => {
+                    println!("unknown command: {other}, type `hint`, `skip` or `quit`");
+                    continue;
+                }
+            },
+        };
+        if commands.send(command).is_err() {
+            return;
+        }
+    });
+}
+
+// Whether a batch of file events touched a file of the exercise
+fn touched(events: &[DebouncedEvent], exercise: &Exercise) -> bool {
+    events.iter().any(|event| match event {
+        DebouncedEvent::Create(path) | DebouncedEvent::Write(path) | DebouncedEvent::Rename(_, path) => {
+            exercise.files().any(|file| file == path)
+        }
+        _ => false,
+    })
+}
+
+// Print how long each exercise took, `None` for the skipped ones
+fn report(times: &[(String, Option<Duration>)]) {
+    println!();
+    println!("Quiz results:");
+    let width = times.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+    for (name, time) in times {
+        let time = time.map_or_else(|| "skipped".to_string(), format_duration);
+        println!("  {name:<width$}  {time}");
+    }
+    let solved: Vec<Duration> = times.iter().filter_map(|(_, time)| *time).collect();
+    println!(
+        "Solved {} of {} in {}",
+        solved.len(),
+        times.len(),
+        format_duration(solved.iter().sum())
+    );
+}
+
+/// Ask for up to `count` solved exercises again, in a random order, from
+/// copies of their original state in a temporary directory. The files in
+/// `exercises/` and the recorded progress are left alone.
+pub fn quiz(exercises: &[Exercise], count: usize, topic: Option<&str>, seed: Option<u64>) -> notify::Result<()> {
+    let seed = seed.unwrap_or_else(random_seed);
+    let picked = pick(candidates(exercises, topic), count, seed);
+    if picked.is_empty() {
+        match topic {
+            Some(topic) => println!("You haven't solved any exercise of {topic} to be quizzed on yet."),
+            None => println!("You haven't solved any exercise to be quizzed on yet."),
+        }
+        return Ok(());
+    }
+
+    let workspace = Workspace::create()?;
+    let copies = picked
+        .iter()
+        .map(|exercise| workspace.copy(exercise))
+        .collect::<io::Result<Vec<_>>>()?;
+    let plural = if copies.len() == 1 { "" } else { "s" };
+    println!("Quiz of {} exercise{plural} with seed {seed}, `--seed {seed}` asks for them again.", copies.len());
+    println!("They are copies in {}, your own solutions stay as they are.", workspace.0.display());
+    println!("Type `hint` for a hint, `skip` to move on or `quit` to stop.");
+
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, DEBOUNCE)?;
+    watcher.watch(&workspace.0, RecursiveMode::Recursive)?;
+    let (command_tx, command_rx) = channel();
+    spawn_quiz_shell(command_tx);
+
+    let mut times = Vec::new();
+    for (i, exercise) in copies.iter().enumerate() {
+        println!();
+        println!(
+            "{}",
+            console::style(format!("========== {}/{}: {} ==========", i + 1, copies.len(), exercise.name)).bold()
+        );
+        let start = Instant::now();
+        let mut changed = true;
+        let time = loop {
+            if changed && run(exercise, false, &[]).is_ok() {
+                if exercise.looks_done() {
+                    break Some(start.elapsed());
+                }
+                println!("Remove the `I AM NOT DONE` comment of {exercise} to move on.");
+            }
+            changed = match rx.recv_timeout(Duration::from_millis(200)) {
+                Ok(event) => {
+                    let mut events = vec![event];
+                    while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+                        events.push(event);
+                    }
+                    touched(&events, exercise)
+                }
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => break None,
+            };
+            match command_rx.try_recv() {
+                Ok(QuizCommand::Hint) => markdown::print(&exercise.hint),
+                Ok(QuizCommand::Skip) => break None,
+                Ok(QuizCommand::Quit) => {
+                    times.push((exercise.name.clone(), None));
+                    report(&times);
+                    return Ok(());
+                }
+                Err(_) => {}
+            }
+        };
+        times.push((exercise.name.clone(), time));
+    }
+    report(&times);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_quiz() {
+        let names: Vec<usize> = (0..20).collect();
+        let quiz = pick(names.clone(), 5, 42);
+        assert_eq!(quiz, pick(names.clone(), 5, 42));
+        assert_ne!(quiz, pick(names.clone(), 5, 43));
+        assert_eq!(quiz.len(), 5);
+
+        let mut all = pick(names.clone(), 50, 7);
+        all.sort();
+        assert_eq!(all, names);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::exercise::Exercise;
+use crate::progress::Progress;
+use crate::ui::format_duration;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The formats `rustlings report` can write
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!("unknown format `{s}`, expected `md` or `html`")),
+        }
+    }
+}
+
+// Where an exercise stands according to the recorded progress
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Standing {
+    Done,
+    Failing,
+    InProgress,
+    NotStarted,
+}
+
+impl Standing {
+    fn of(exercise: &Exercise, state: &Progress) -> Self {
+        let attempts = state.attempts(exercise);
+        match state.verified(exercise) {
+            Some(true) => Standing::Done,
+            Some(false) if exercise.looks_done() => Standing::Failing,
+            Some(false) => Standing::InProgress,
+            None if attempts.failures > 0 || attempts.saves > 0 || !state.time_spent(exercise).is_zero() => {
+                Standing::InProgress
+            }
+            None => Standing::NotStarted,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Standing::Done => "done",
+            Standing::Failing => "failing",
+            Standing::InProgress => "in progress",
+            Standing::NotStarted => "not started",
+        }
+    }
+}
+
+struct Row<'a> {
+    exercise: &'a Exercise,
+    standing: Standing,
+    time: Duration,
+    attempts: u32,
+}
+
+// The rows of every topic, topics and exercises in course order
+fn sections<'a>(exercises: &'a [Exercise], state: &Progress) -> Vec<(String, Vec<Row<'a>>)> {
+    let mut sections: Vec<(String, Vec<Row>)> = Vec::new();
+    for exercise in exercises {
+        let row = Row {
+            exercise,
+            standing: Standing::of(exercise, state),
+            time: state.time_spent(exercise),
+            attempts: state.attempts(exercise).failures,
+        };
+        let topic = exercise.topic();
+        match sections.iter_mut().find(|(t, _)| *t == topic) {
+            Some((_, rows)) => rows.push(row),
+            None => sections.push((topic, vec![row])),
+        }
+    }
+    sections
+}
+
+fn show_time(time: Duration) -> String {
+    match time {
+        Duration::ZERO => "-".to_string(),
+        time => format_duration(time),
+    }
+}
+
+// `2024-04-01 09:30 UTC`, worked out by hand to spare a dependency.
+// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let minutes = secs % 86400 / 60;
+    format!("{year}-{month:02}-{day:02} {:02}:{:02} UTC", minutes / 60, minutes % 60)
+}
+
+// The summary line above the tables
+fn overall(exercises: &[Exercise], state: &Progress) -> (usize, usize, f64) {
+    let done = exercises.iter().filter(|e| Standing::of(e, state) == Standing::Done).count();
+    let total = exercises.len();
+    let percentage = if total == 0 { 0.0 } else { done as f64 * 100.0 / total as f64 };
+    (done, total, percentage)
+}
+
+// The points earned by the exercises that are done, out of the points of all of them
+fn points<'a>(exercises: impl IntoIterator<Item = (&'a Exercise, Standing)>) -> (u32, u32) {
+    exercises.into_iter().fold((0, 0), |(earned, total), (exercise, standing)| {
+        let earned = earned + if standing == Standing::Done { exercise.points } else { 0 };
+        (earned, total + exercise.points)
+    })
+}
+
+fn row_points(rows: &[Row]) -> (u32, u32) {
+    points(rows.iter().map(|row| (row.exercise, row.standing)))
+}
+
+fn markdown(exercises: &[Exercise], state: &Progress, generated: SystemTime) -> String {
+    let (done, total, percentage) = overall(exercises, state);
+    let (earned, total_points) = points(exercises.iter().map(|e| (e, Standing::of(e, state))));
+    let mut out = String::new();
+    let _ = writeln!(out, "# Rustlings progress\n");
+    let _ = writeln!(
+        out,
+        "{done} of {total} exercises done ({percentage:.1} %), {earned} of {total_points} points, {} spent in total. Generated {}.",
+        show_time(state.total_time_spent()),
+        timestamp(generated)
+    );
+    for (topic, rows) in sections(exercises, state) {
+        let (earned, total_points) = row_points(&rows);
+        let _ = writeln!(out, "\n## {topic}\n");
+        let _ = writeln!(out, "{earned} of {total_points} points.\n");
+        let _ = writeln!(out, "| Exercise | Status | Time spent | Attempts |");
+        let _ = writeln!(out, "| --- | --- | ---: | ---: |");
+        for row in rows {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                row.exercise.name,
+                row.standing.label(),
+                show_time(row.time),
+                row.attempts
+            );
+        }
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 50em; margin: 2em auto; color: #222; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1.5em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+td.number { text-align: right; }
+.done { color: #1a7f37; } .failing { color: #cf222e; } .in-progress { color: #9a6700; } .not-started { color: #888; }";
+
+fn html(exercises: &[Exercise], state: &Progress, generated: SystemTime) -> String {
+    let (done, total, percentage) = overall(exercises, state);
+    let (earned, total_points) = points(exercises.iter().map(|e| (e, Standing::of(e, state))));
+    let mut out = String::new();
+    let _ = writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<title>Rustlings progress</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>");
+    let _ = writeln!(out, "<h1>Rustlings progress</h1>");
+    let _ = writeln!(
+        out,
+        "<p>{done} of {total} exercises done ({percentage:.1} %), {earned} of {total_points} points, {} spent in total. Generated {}.</p>",
+        show_time(state.total_time_spent()),
+        timestamp(generated)
+    );
+    for (topic, rows) in sections(exercises, state) {
+        let (earned, total_points) = row_points(&rows);
+        let _ = writeln!(out, "<h2>{}</h2>\n<p>{earned} of {total_points} points.</p>\n<table>", escape(&topic));
+        let _ = writeln!(out, "<tr><th>Exercise</th><th>Status</th><th>Time spent</th><th>Attempts</th></tr>");
+        for row in rows {
+            let label = row.standing.label();
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"{}\">{label}</td><td class=\"number\">{}</td><td class=\"number\">{}</td></tr>",
+                escape(&row.exercise.name),
+                label.replace(' ', "-"),
+                show_time(row.time),
+                row.attempts
+            );
+        }
+        let _ = writeln!(out, "</table>");
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+/// The progress recorded for `exercises` in `state`, one table per topic,
+/// as of `generated`. Nothing is verified again.
+pub fn render(exercises: &[Exercise], state: &Progress, format: Format, generated: SystemTime) -> String {
+    match format {
+        Format::Markdown => markdown(exercises, state, generated),
+        Format::Html => html(exercises, state, generated),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_utc_dates() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01 00:00 UTC");
+        assert_eq!(timestamp(UNIX_EPOCH + Duration::from_secs(1_709_251_199)), "2024-02-29 23:59 UTC");
+        assert_eq!(timestamp(UNIX_EPOCH + Duration::from_secs(1_711_963_800)), "2024-04-01 09:30 UTC");
+    }
+
+    #[test]
+    fn unrecorded_exercises_are_not_started() {
+        let exercises = crate::exercise::ExerciseList::load(std::path::Path::new("tests/fixture/success/info.toml"))
+            .unwrap()
+            .exercises;
+        let report = render(&exercises, &Progress::default(), Format::Markdown, UNIX_EPOCH);
+        assert!(report.contains("0 of 2 exercises done (0.0 %), 0 of 4 points"), "{report}");
+        assert!(report.contains("| compSuccess | not started | - | 0 |"), "{report}");
+        assert!(report.contains("| testSuccess | not started | - | 0 |"), "{report}");
+    }
+}
@@ -1,33 +1,52 @@
-use std::process::Command;
-
 use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
+use crate::originals;
+use crate::verify::{input_note, test};
+use console::style;
 use indicatif::ProgressBar;
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
 // The verbose argument helps determine whether or not to show
-// the output from the test harnesses (if the mode of the exercise is test)
-pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
+// the output from the test harnesses (if the mode of the exercise is test).
+// `test_args` are passed on to the test harness, and ignored with
+// a warning by exercises that don't have one.
+pub fn run(exercise: &Exercise, verbose: bool, test_args: &[String]) -> Result<(), ()> {
+    if !test_args.is_empty() && !matches!(exercise.mode, Mode::Test | Mode::CargoTest) {
+        warn!("{} isn't a test exercise, ignoring the arguments for the tests", exercise);
+    }
+    println!("{}", style(profile_note(exercise)).dim());
     match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
+        // The output of tests that were asked for is always shown
+        Mode::Test | Mode::CargoTest => test(exercise, verbose || !test_args.is_empty(), test_args)?,
         Mode::Compile => compile_and_run(exercise)?,
         Mode::Clippy => compile_and_run(exercise)?,
-        Mode::BuildScript => test(exercise, verbose)?,
+        Mode::BuildScript => test(exercise, verbose, &[])?,
     }
     Ok(())
 }
 
-// Resets the exercise by stashing the changes.
-pub fn reset(exercise: &Exercise) -> Result<(), ()> {
-    let command = Command::new("git")
-        .args(["stash", "--"])
-        .arg(&exercise.path)
-        .spawn();
+// Which profile the exercise is compiled with, so that a slow run of
+// an unoptimized one doesn't come as a surprise
+fn profile_note(exercise: &Exercise) -> String {
+    if exercise.release {
+        format!("Compiling {exercise} with optimizations (release profile)")
+    } else {
+        format!("Compiling {exercise} without optimizations (debug profile), `rustlings run --release` optimizes it")
+    }
+}
 
-    match command {
-        Ok(_) => Ok(()),
-        Err(_) => Err(()),
+// Resets the exercise by restoring the copy of it that was
+// taken the first time rustlings ran.
+pub fn reset(exercise: &Exercise) -> Result<(), ()> {
+    match originals::restore(exercise) {
+        Ok(()) => {
+            success!("Reset {} to its original state", exercise);
+            Ok(())
+        }
+        Err(_) => {
+            warn!("Could not reset {}, no original copy of it was found", exercise);
+            Err(())
+        }
     }
 }
 
@@ -67,7 +86,14 @@ fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
             println!("{}", output.stdout);
             println!("{}", output.stderr);
 
-            warn!("Ran {} with errors", exercise);
+            match output.timed_out {
+                Some(timeout) => {
+                    let secs = timeout.as_secs();
+                    warn!("{} timed out after {secs}s", exercise);
+                    println!("{}", input_note(exercise));
+                }
+                None => warn!("Ran {} with errors", exercise),
+            }
             Err(())
         }
     }
@@ -0,0 +1,81 @@
+use crate::exercise::{Exercise, Mode};
+use crate::verify::{status, Status};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+/// The grade of a whole run of `rustlings score`
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub passed: usize,
+    pub total: usize,
+    pub percentage: f64,
+    // The points of the passed exercises, out of the points of all of them
+    pub points: u32,
+    pub total_points: u32,
+    // Keyed by topic, e.g. `algorithm`
+    pub sections: BTreeMap<String, Section>,
+    pub exercises: Vec<ExerciseScore>,
+}
+
+#[derive(Serialize, Default, Debug)]
+pub struct Section {
+    pub passed: usize,
+    pub total: usize,
+    pub points: u32,
+    pub total_points: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExerciseScore {
+    pub name: String,
+    pub mode: Mode,
+    pub passed: bool,
+    // What the exercise is worth, whether it passed or not
+    pub points: u32,
+    pub status: Status,
+    pub duration_secs: f64,
+}
+
+/// Check every exercise the way `verify` does, without printing or
+/// prompting anything and without stopping at failures.
+/// An exercise only passes once it is `Done`; anything that goes wrong
+/// while checking it, up to rustlings itself panicking, fails it.
+pub fn score(exercises: &[Exercise]) -> Report {
+    let mut sections: BTreeMap<String, Section> = BTreeMap::new();
+    let mut scores = Vec::with_capacity(exercises.len());
+    for exercise in exercises {
+        let start = Instant::now();
+        let status = panic::catch_unwind(AssertUnwindSafe(|| status(exercise))).unwrap_or(Status::Failing);
+        let passed = status == Status::Done;
+
+        let section = sections.entry(exercise.topic()).or_default();
+        section.total += 1;
+        section.passed += usize::from(passed);
+        section.total_points += exercise.points;
+        if passed {
+            section.points += exercise.points;
+        }
+        scores.push(ExerciseScore {
+            name: exercise.name.clone(),
+            mode: exercise.mode,
+            passed,
+            points: exercise.points,
+            status,
+            duration_secs: start.elapsed().as_secs_f64(),
+        });
+    }
+
+    let passed = scores.iter().filter(|s| s.passed).count();
+    let total = scores.len();
+    Report {
+        passed,
+        total,
+        percentage: if total == 0 { 0.0 } else { passed as f64 * 100.0 / total as f64 },
+        points: scores.iter().filter(|s| s.passed).map(|s| s.points).sum(),
+        total_points: scores.iter().map(|s| s.points).sum(),
+        sections,
+        exercises: scores,
+    }
+}
@@ -0,0 +1,64 @@
+// Ctrl-C and friends, handled outside of the signal handler itself.
+// The handler only writes to a pipe, a thread waiting on the other end
+// does the actual cleaning up.
+
+/// The exit status of a process stopped by Ctrl-C
+pub const INTERRUPTED: i32 = 130;
+
+#[cfg(unix)]
+mod imp {
+    use super::INTERRUPTED;
+    use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+    use std::thread;
+
+    static PIPE: AtomicI32 = AtomicI32::new(-1);
+    static RECEIVED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn handle(_: libc::c_int) {
+        // A second Ctrl-C doesn't wait for the cleanup to finish
+        if RECEIVED.fetch_add(1, Ordering::SeqCst) > 0 {
+            unsafe { libc::_exit(INTERRUPTED) };
+        }
+        let byte = 0u8;
+        unsafe { libc::write(PIPE.load(Ordering::SeqCst), (&byte as *const u8).cast(), 1) };
+    }
+
+    pub fn on_interrupt(cleanup: impl FnOnce() + Send + 'static) {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        PIPE.store(fds[1], Ordering::SeqCst);
+        thread::spawn(move || {
+            let mut byte = 0u8;
+            loop {
+                let read = unsafe { libc::read(fds[0], (&mut byte as *mut u8).cast(), 1) };
+                if read == 1 {
+                    break;
+                }
+                if read < 0 && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return;
+            }
+            cleanup();
+            std::process::exit(INTERRUPTED);
+        });
+        let handler = handle as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        unsafe {
+            libc::signal(libc::SIGINT, handler);
+            libc::signal(libc::SIGTERM, handler);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    pub fn on_interrupt(_cleanup: impl FnOnce() + Send + 'static) {}
+}
+
+/// Run `cleanup` and exit with status 130 on SIGINT or SIGTERM. A second
+/// signal while `cleanup` runs exits right away.
+pub fn on_interrupt(cleanup: impl FnOnce() + Send + 'static) {
+    imp::on_interrupt(cleanup);
+}
@@ -0,0 +1,118 @@
+// `rustlings dev hash-tests` records the test blocks of the exercises with
+// this, so that rustlings can tell when they were edited.
+
+/// A 64 bit FNV-1a hash of the `#[cfg(test)]` modules in `source`, or `None`
+/// if it has none. Whitespace and comments are left out, so reformatting the
+/// tests or editing their comments doesn't change it.
+pub fn tests_hash(source: &str) -> Option<u64> {
+    let mut hash = None;
+    let mut rest = source;
+    while let Some(start) = rest.find("#[cfg(test)]") {
+        let item = &rest[start + "#[cfg(test)]".len()..];
+        let Some(open) = item.find(['{', ';']) else { break };
+        let is_mod = item[..open].split_whitespace().any(|word| word == "mod");
+        if !is_mod || !item[open..].starts_with('{') {
+            rest = item;
+            continue;
+        }
+        let (len, text) = significant(&item[open..]);
+        hash = Some(text.bytes().fold(hash.unwrap_or(0xcbf29ce484222325_u64), |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        }));
+        rest = &item[open + len..];
+    }
+    hash
+}
+
+// `block`, which starts with `{`, up to its matching `}` without
+// whitespace and comments, and how many bytes of `block` that took
+fn significant(block: &str) -> (usize, String) {
+    let chars: Vec<(usize, char)> = block.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let mut text = String::new();
+    let mut depth = 0;
+    let mut i = 0;
+    while let Some(c) = at(i) {
+        match (c, at(i + 1)) {
+            ('/', Some('/')) => {
+                while at(i).is_some_and(|c| c != '\n') {
+                    i += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while at(i).is_some() && (at(i), at(i + 1)) != (Some('*'), Some('/')) {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            // Strings and characters are kept as they are, braces and all
+            ('"', _) => {
+                text.push('"');
+                i += 1;
+                while let Some(c) = at(i) {
+                    text.push(c);
+                    i += 1;
+                    match c {
+                        '\\' => {
+                            text.extend(at(i));
+                            i += 1;
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            // Such as '\n', '\'' or '\u{7f}'
+            ('\'', Some('\\')) => {
+                let end = (i + 3..chars.len()).find(|&j| at(j) == Some('\'')).unwrap_or(chars.len() - 1);
+                text.extend(chars[i..=end].iter().map(|&(_, c)| c));
+                i = end + 1;
+                continue;
+            }
+            // A character, unlike a lifetime, is closed right after it
+            ('\'', Some(character)) if at(i + 2) == Some('\'') => {
+                text.extend(['\'', character, '\'']);
+                i += 3;
+                continue;
+            }
+            ('{', _) => depth += 1,
+            ('}', _) => depth -= 1,
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        text.push(c);
+        i += 1;
+        if depth == 0 {
+            return (chars[i - 1].0 + c.len_utf8(), text);
+        }
+    }
+    (block.len(), text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXERCISE: &str = "fn double(x: i32) -> i32 {\n    x * 2\n}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn doubles() {\n        // Braces in strings and characters don't count\n        assert_eq!(double(2), 4, \"}}\");\n        assert_ne!('}', '\\'');\n    }\n}\n\nfn after() {}\n";
+
+    #[test]
+    fn only_the_code_of_the_tests_counts() {
+        let hash = tests_hash(EXERCISE);
+        assert!(hash.is_some());
+        assert_eq!(tests_hash(&EXERCISE.replace("x * 2", "x + x")), hash);
+        assert_eq!(tests_hash(&EXERCISE.replace("fn after() {}", "")), hash);
+        assert_eq!(tests_hash(&EXERCISE.replace("    ", "\t").replace("// Braces", "// The braces")), hash);
+
+        assert_ne!(tests_hash(&EXERCISE.replace("double(2), 4", "double(2), double(2)")), hash);
+        assert_ne!(tests_hash(&EXERCISE.replace("\"}}\"", "\"}\"")), hash);
+        assert_eq!(tests_hash(&EXERCISE.replace("#[cfg(test)]", "")), None);
+        assert_eq!(tests_hash("#[cfg(test)]\nuse std::fmt;\nfn main() {}\n"), None);
+    }
+}
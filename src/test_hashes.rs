@@ -0,0 +1,98 @@
+// The hash of the test block of every exercise file that has one, keyed by its
+// path with `/` separators. Written by `rustlings dev hash-tests`, don't edit it.
+pub const TEST_HASHES: &[(&str, u64)] = &[
+    ("exercises/algorithm/algorithm1.rs", 0x4ae56705462b40c9),
+    ("exercises/algorithm/algorithm10.rs", 0xddbe7c5518406315),
+    ("exercises/algorithm/algorithm11.rs", 0xcdf17274213ecfc5),
+    ("exercises/algorithm/algorithm12.rs", 0x72a214705eab9492),
+    ("exercises/algorithm/algorithm13.rs", 0x2c01faa62078fab1),
+    ("exercises/algorithm/algorithm14.rs", 0xeddf78eb591874c3),
+    ("exercises/algorithm/algorithm15.rs", 0x44bfedc01a7619b6),
+    ("exercises/algorithm/algorithm16.rs", 0x88c2ae70cd648a3b),
+    ("exercises/algorithm/algorithm17.rs", 0xe8abe50ad1ece4d5),
+    ("exercises/algorithm/algorithm18.rs", 0xe2b8de65da9e79b0),
+    ("exercises/algorithm/algorithm19.rs", 0xcc6f76a6a5c43624),
+    ("exercises/algorithm/algorithm2.rs", 0xcde56ade3805937d),
+    ("exercises/algorithm/algorithm20.rs", 0x2184cc94ff9aeb22),
+    ("exercises/algorithm/algorithm21.rs", 0x55db9de286d16690),
+    ("exercises/algorithm/algorithm3.rs", 0xb9073bf9597ef696),
+    ("exercises/algorithm/algorithm4.rs", 0xbeff1048f7df8175),
+    ("exercises/algorithm/algorithm5.rs", 0x304f437aaaf97c29),
+    ("exercises/algorithm/algorithm6.rs", 0xddef7ec37b8d9251),
+    ("exercises/algorithm/algorithm7.rs", 0x3c536d564c70080d),
+    ("exercises/algorithm/algorithm8.rs", 0x9b8846c02d6404b4),
+    ("exercises/algorithm/algorithm9.rs", 0x026afdc67ed11a24),
+    ("exercises/async/async1.rs", 0xfe9b91dc9c398adf),
+    ("exercises/async/async2.rs", 0x3482ef7c71897828),
+    ("exercises/async/async3.rs", 0x9ac39b4256c37971),
+    ("exercises/conversions/as_ref_mut.rs", 0x452e232c92c3ece1),
+    ("exercises/conversions/cow_str.rs", 0x89d1439b688476c7),
+    ("exercises/conversions/from_into.rs", 0x1f659591aa958c52),
+    ("exercises/conversions/from_str.rs", 0x2fcad11446fed401),
+    ("exercises/conversions/try_from_into.rs", 0xe797600aff8dd6cd),
+    ("exercises/conversions/using_as.rs", 0x1fd24728c370fba2),
+    ("exercises/enums/enums3.rs", 0x7f59bffe4ccd3909),
+    ("exercises/enums/enums4.rs", 0x03d8bbb9256343f2),
+    ("exercises/env/env1.rs", 0xa37670c03f25a747),
+    ("exercises/error_handling/errors1.rs", 0x91703c47e0a2bb24),
+    ("exercises/error_handling/errors2.rs", 0x506584fb8e3ae078),
+    ("exercises/error_handling/errors6.rs", 0x1a5309aa01d67b30),
+    ("exercises/error_handling/errors7.rs", 0x3c444c104c7add22),
+    ("exercises/ext/serde1/src/lib.rs", 0x299458127dcedefe),
+    ("exercises/ext/serde2/src/lib.rs", 0xbedc463aec10f21e),
+    ("exercises/generics/generics2.rs", 0x627b3027c9091519),
+    ("exercises/generics/generics3.rs", 0x12a5193d3557b3b3),
+    ("exercises/hashmaps/hashmaps1.rs", 0x26948b44140fed13),
+    ("exercises/hashmaps/hashmaps2.rs", 0xe234b327021c17e3),
+    ("exercises/hashmaps/hashmaps3.rs", 0x58faf233668fa700),
+    ("exercises/hashmaps/hashmaps4.rs", 0xc11f24f2873daadc),
+    ("exercises/if/if1.rs", 0x4a9df95bdfc3252b),
+    ("exercises/if/if2.rs", 0xa9622ce95fc37c12),
+    ("exercises/if/if3.rs", 0xd3ae5cd5bda2a601),
+    ("exercises/iterators/iterators2.rs", 0x15650b1da797af58),
+    ("exercises/iterators/iterators3.rs", 0x7e57a76aaa24519b),
+    ("exercises/iterators/iterators4.rs", 0xfa5868952e97f3d8),
+    ("exercises/iterators/iterators5.rs", 0x7f6cc6dd71001431),
+    ("exercises/iterators/iterators6.rs", 0xd968180b52c1ae22),
+    ("exercises/lifetimes/lifetimes4.rs", 0x0ba2bfe290d6fb1b),
+    ("exercises/macros/macros5.rs", 0xd6ad02c0a569bffa),
+    ("exercises/modules/modules4/main.rs", 0x0ff3fb39c8412c99),
+    ("exercises/options/options1.rs", 0x5da7e25b6006476d),
+    ("exercises/options/options2.rs", 0x778928ecf7f72817),
+    ("exercises/quiz2.rs", 0xb283a919b917b3bf),
+    ("exercises/quiz3.rs", 0xc3dbd739942abfce),
+    ("exercises/smart_pointers/box1.rs", 0x054d155662413be8),
+    ("exercises/smart_pointers/cow1.rs", 0x1dc2f08dd4012927),
+    ("exercises/smart_pointers/rc2.rs", 0x861007e462580a18),
+    ("exercises/smart_pointers/weak1.rs", 0x43e86e59360c66e8),
+    ("exercises/strings/strings3.rs", 0xfbd6f1f509a8c932),
+    ("exercises/strings/strings5.rs", 0xecffc8ab57ae7805),
+    ("exercises/structs/structs1.rs", 0xf6268aeec121d7c5),
+    ("exercises/structs/structs2.rs", 0xeccfaf4b9e2383f1),
+    ("exercises/structs/structs3.rs", 0x426fcdd1084636d4),
+    ("exercises/structs/structs4.rs", 0xc6722cd42253afb9),
+    ("exercises/tests/tests1.rs", 0x6085c6378feb360f),
+    ("exercises/tests/tests10.rs", 0xc7023f98555ee8bf),
+    ("exercises/tests/tests2.rs", 0x6a1229ee2a6e48c9),
+    ("exercises/tests/tests3.rs", 0xbc18fb0463f8b264),
+    ("exercises/tests/tests4.rs", 0xeab6db6a113b6d72),
+    ("exercises/tests/tests5.rs", 0xb763cc1fe23ffe17),
+    ("exercises/tests/tests6.rs", 0xa02220e6950e2525),
+    ("exercises/tests/tests7.rs", 0xd84e477d5c9647f9),
+    ("exercises/tests/tests8.rs", 0x37e90e3a5146956e),
+    ("exercises/tests/tests9.rs", 0xe4ac326214648b34),
+    ("exercises/threads/threads4.rs", 0xa635975f6db20454),
+    ("exercises/threads/threads5.rs", 0x9d99fc988b5ea23f),
+    ("exercises/traits/traits1.rs", 0xce30d3ffe2e916e5),
+    ("exercises/traits/traits2.rs", 0x752987511d93413c),
+    ("exercises/traits/traits3.rs", 0x4b4e13d778950f62),
+    ("exercises/traits/traits4.rs", 0xff606e503f98d4a5),
+    ("exercises/traits/traits6.rs", 0x1d25beefd3017769),
+    ("exercises/traits/traits7.rs", 0x636b1294081fce5d),
+    ("exercises/unsafe/unsafe1.rs", 0xf76d5b73a1c18499),
+    ("exercises/unsafe/unsafe2.rs", 0xd651b946d02b4771),
+    ("exercises/unsafe/unsafe3.rs", 0x30887514c76998c8),
+    ("exercises/unsafe/unsafe4.rs", 0x7ba9eda2d2d0a312),
+    ("exercises/vecs/vecs1.rs", 0xfda069cb9698a21a),
+    ("exercises/vecs/vecs2.rs", 0xe152a2bac10daa5a),
+];
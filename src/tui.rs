@@ -0,0 +1,333 @@
+use crate::editor;
+use crate::exercise::{self, Exercise, State};
+use crate::markdown;
+use crate::progress::Progress;
+use crate::run;
+use crate::signals;
+use crate::verify::{status, Status};
+use console::{style, truncate_str, Key, Term};
+use std::cell::OnceCell;
+use std::io;
+use std::panic;
+
+// Switch to the alternate screen of the terminal, which keeps what was
+// on it before for when the alternate one is left again
+const ENTER_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_SCREEN: &str = "\x1b[?1049l";
+
+const KEYS: &str = "↑↓ move  / search  Enter run  h hint  e edit  q quit";
+
+fn enter_screen(term: &Term) -> io::Result<()> {
+    term.write_str(ENTER_SCREEN)?;
+    term.hide_cursor()
+}
+
+fn leave_screen() {
+    let term = Term::stdout();
+    let _ = term.write_str(LEAVE_SCREEN);
+    let _ = term.show_cursor();
+}
+
+// The alternate screen for as long as this lives. Dropping it, as when
+// unwinding from a panic, gives the terminal back as it was.
+struct Screen;
+
+impl Screen {
+    fn enter(term: &Term) -> io::Result<Self> {
+        // The message of a panic would be lost on the alternate screen
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            leave_screen();
+            hook(info);
+        }));
+        signals::on_interrupt(|| {
+            exercise::kill_running();
+            exercise::remove_temp_files();
+            leave_screen();
+        });
+        enter_screen(term)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        leave_screen();
+    }
+}
+
+// What is chosen from and how it is shown, apart from the terminal
+struct Browser<'a> {
+    exercises: &'a [Exercise],
+    // The exercises `rustlings list` would show, by index
+    listed: Vec<usize>,
+    // Those of them matching `query`
+    shown: Vec<usize>,
+    query: String,
+    // Whether keys are typed into `query` rather than commands
+    searching: bool,
+    // The index in `shown` of the selected exercise
+    selected: usize,
+    // The index in `shown` of the first exercise on the screen
+    top: usize,
+    hint: bool,
+    // Resolved as the exercises are selected, and forgotten when they
+    // are run or edited
+    statuses: Vec<OnceCell<Status>>,
+}
+
+impl<'a> Browser<'a> {
+    fn new(exercises: &'a [Exercise], listed: Vec<usize>) -> Self {
+        let mut browser = Browser {
+            exercises,
+            shown: Vec::new(),
+            listed,
+            query: String::new(),
+            searching: false,
+            selected: 0,
+            top: 0,
+            hint: false,
+            statuses: exercises.iter().map(|_| OnceCell::new()).collect(),
+        };
+        browser.search();
+        browser
+    }
+
+    // Show the exercises whose name or topic contains the query,
+    // keeping the selection on the same exercise if it still matches
+    fn search(&mut self) {
+        let query = self.query.to_lowercase();
+        let before = self.shown.get(self.selected).copied();
+        self.shown = self
+            .listed
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let e = &self.exercises[i];
+                e.name.to_lowercase().contains(&query) || e.topic().to_lowercase().contains(&query)
+            })
+            .collect();
+        self.selected = before.and_then(|i| self.shown.iter().position(|&s| s == i)).unwrap_or(0);
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let last = self.shown.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    // Scroll so that the selected exercise is one of `rows` on the screen
+    fn scroll(&mut self, rows: usize) {
+        let rows = rows.max(1);
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + rows {
+            self.top = self.selected + 1 - rows;
+        }
+    }
+
+    fn current(&self) -> Option<&'a Exercise> {
+        self.shown.get(self.selected).map(|&i| &self.exercises[i])
+    }
+
+    // The status as far as it is known without compiling anything
+    fn known_status(&self, i: usize, state: &Progress) -> Option<Status> {
+        let exercise = &self.exercises[i];
+        if let Some(status) = self.statuses[i].get() {
+            Some(*status)
+        } else if !exercise.looks_done() {
+            Some(Status::Pending)
+        } else if state.is_up_to_date(exercise) {
+            Some(Status::Done)
+        } else {
+            None
+        }
+    }
+}
+
+fn glyph(status: Option<Status>) -> String {
+    match status {
+        Some(Status::Done) => style("✓").green().to_string(),
+        Some(Status::Pending) => style("○").yellow().to_string(),
+        Some(Status::Failing) => style("✗").red().to_string(),
+        Some(Status::ModifiedTests) => style("!").red().to_string(),
+        None => style("·").dim().to_string(),
+    }
+}
+
+fn draw(term: &Term, browser: &mut Browser, state: &Progress) -> io::Result<()> {
+    let (height, width) = term.size();
+    let (height, width) = (height as usize, width as usize);
+    let hint = match browser.current() {
+        Some(exercise) if browser.hint => {
+            let title = style(format!("Hint for {}", exercise.name)).bold().to_string();
+            let text = markdown::render(&exercise.hint, Some(width));
+            std::iter::once(title).chain(text.lines().map(String::from)).collect()
+        }
+        _ => Vec::new(),
+    };
+    let hint: Vec<String> = hint.into_iter().take(height / 2).collect();
+    let rows = height.saturating_sub(2 + hint.len());
+    browser.scroll(rows);
+
+    let done = (0..browser.exercises.len())
+        .filter(|&i| browser.known_status(i, state) == Some(Status::Done))
+        .count();
+    let mut lines = vec![format!(
+        "{} {done} of {} exercises done, {} not checked yet",
+        style("Rustlings").bold(),
+        browser.exercises.len(),
+        glyph(None)
+    )];
+    for (row, &i) in browser.shown.iter().enumerate().skip(browser.top).take(rows) {
+        let exercise = &browser.exercises[i];
+        let status = browser.known_status(i, state);
+        let label = status.map_or_else(String::new, |s| s.to_string());
+        let line = format!(" {} {:<20} {:<18} {label}", glyph(status), exercise.name, exercise.topic());
+        let line = truncate_str(&line, width, "…").into_owned();
+        lines.push(if row == browser.selected { style(line).reverse().to_string() } else { line });
+    }
+    if browser.shown.is_empty() {
+        lines.push(style(" No exercise matches the search").dim().to_string());
+    }
+    lines.resize(height.saturating_sub(1 + hint.len()), String::new());
+    lines.extend(hint);
+    lines.push(if browser.searching {
+        format!("/{}█", browser.query)
+    } else if !browser.query.is_empty() {
+        format!("/{}  ({} of {})  Esc clears the search", browser.query, browser.shown.len(), browser.listed.len())
+    } else {
+        style(KEYS).dim().to_string()
+    });
+
+    let screen: Vec<String> = lines
+        .iter()
+        .take(height)
+        .map(|line| format!("{}\x1b[K", truncate_str(line, width, "…")))
+        .collect();
+    term.move_cursor_to(0, 0)?;
+    term.write_str(&screen.join("\n"))?;
+    term.write_str("\x1b[J")
+}
+
+// Leave the list to do something with the exercise, and come back to it
+fn away(term: &Term, action: impl FnOnce()) -> io::Result<()> {
+    leave_screen();
+    action();
+    enter_screen(term)
+}
+
+/// Browse the `listed` exercises until `q` is pressed, running, editing
+/// and showing the hint of the selected one on request
+pub fn list(exercises: &[Exercise], listed: Vec<usize>, state: &Progress, verbose: bool) -> io::Result<()> {
+    let term = Term::stdout();
+    let _screen = Screen::enter(&term)?;
+    let mut browser = Browser::new(exercises, listed);
+    loop {
+        draw(&term, &mut browser, state)?;
+        // Statuses are only worked out for the exercise that is selected,
+        // as that means compiling it
+        if let Some(&i) = browser.shown.get(browser.selected) {
+            if browser.known_status(i, state).is_none() {
+                let _ = browser.statuses[i].set(status(&exercises[i]));
+                draw(&term, &mut browser, state)?;
+            }
+        }
+
+        let key = term.read_key()?;
+        if browser.searching {
+            match key {
+                Key::Enter | Key::Escape => browser.searching = false,
+                Key::Backspace => {
+                    browser.query.pop();
+                    browser.search();
+                }
+                Key::Char(c) if !c.is_control() => {
+                    browser.query.push(c);
+                    browser.search();
+                }
+                _ => {}
+            }
+            continue;
+        }
+        let page = term.size().0 as isize / 2;
+        match key {
+            Key::ArrowUp | Key::Char('k') => browser.move_by(-1),
+            Key::ArrowDown | Key::Char('j') => browser.move_by(1),
+            Key::PageUp => browser.move_by(-page),
+            Key::PageDown => browser.move_by(page),
+            Key::Home | Key::Char('g') => browser.selected = 0,
+            Key::End | Key::Char('G') => browser.move_by(isize::MAX),
+            Key::Char('/') => browser.searching = true,
+            Key::Escape if !browser.query.is_empty() => {
+                browser.query.clear();
+                browser.search();
+            }
+            Key::Char('h') => browser.hint = !browser.hint,
+            Key::Char('q') | Key::Escape => return Ok(()),
+            Key::Enter | Key::Char('e') => {
+                let Some(&i) = browser.shown.get(browser.selected) else { continue };
+                let exercise = &exercises[i];
+                away(&term, || {
+                    if key == Key::Enter {
+                        let _ = run::run(exercise, verbose, &[]);
+                        println!("\nPress any key to go back to the list.");
+                        let _ = term.read_key();
+                    } else {
+                        let line = match exercise.state() {
+                            State::Pending(context) => context.iter().find(|l| l.important).map(|l| l.number),
+                            State::Done => None,
+                        };
+                        if editor::open(&exercise.path, line).is_err() {
+                            println!("Couldn't start an editor, set $VISUAL or $EDITOR to the one you use.");
+                            let _ = term.read_key();
+                        }
+                    }
+                })?;
+                browser.statuses[i] = OnceCell::new();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn search_keeps_the_selection() {
+        let exercises = crate::exercise::ExerciseList::load(Path::new("tests/fixture/state/info.toml"))
+            .unwrap()
+            .exercises;
+        let mut browser = Browser::new(&exercises, vec![0, 1, 2]);
+        browser.move_by(1);
+        assert_eq!(browser.current().unwrap().name, "pending_test_exercise");
+
+        browser.query = "PENDING".to_string();
+        browser.search();
+        assert_eq!(browser.shown, [0, 1]);
+        assert_eq!(browser.current().unwrap().name, "pending_test_exercise");
+
+        browser.query = "finished".to_string();
+        browser.search();
+        assert_eq!(browser.shown, [2]);
+        assert_eq!(browser.current().unwrap().name, "finished_exercise");
+        browser.move_by(5);
+        assert_eq!(browser.selected, 0);
+    }
+
+    #[test]
+    fn selection_stays_on_screen() {
+        let exercises = crate::exercise::ExerciseList::load(Path::new("tests/fixture/state/info.toml"))
+            .unwrap()
+            .exercises;
+        let mut browser = Browser::new(&exercises, vec![0, 1, 2]);
+        browser.move_by(isize::MAX);
+        browser.scroll(2);
+        assert_eq!((browser.selected, browser.top), (2, 1));
+        browser.move_by(-2);
+        browser.scroll(2);
+        assert_eq!((browser.selected, browser.top), (0, 0));
+    }
+}
@@ -31,3 +31,188 @@ macro_rules! success {
         }
     }};
 }
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// When to color the output, set with `--color`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ColorChoice {
+    Always,
+    // Only when writing to a terminal and NO_COLOR isn't set
+    Auto,
+    Never,
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(ColorChoice::Always),
+            "auto" => Ok(ColorChoice::Auto),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("unknown color choice `{s}`, expected `always`, `auto` or `never`")),
+        }
+    }
+}
+
+// Turn colors on or off for everything printed from now on, including
+// the messages of the compiler. Every style applied with `console`
+// checks this setting, progress bars included.
+pub fn set_color_choice(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        // See https://no-color.org
+        ColorChoice::Auto if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) => false,
+        ColorChoice::Auto => return,
+    };
+    console::set_colors_enabled(enabled);
+    console::set_colors_enabled_stderr(enabled);
+}
+
+static ANSI: AtomicBool = AtomicBool::new(true);
+
+// Whether the terminal understands ANSI escape codes, such as the one
+// clearing the screen
+pub fn ansi() -> bool {
+    ANSI.load(Ordering::Relaxed)
+}
+
+// Windows consoles only understand ANSI escape codes once virtual
+// terminal processing is turned on, which older ones can't do. Without
+// it, colors are turned off unless `--color always` asked for them.
+pub fn enable_ansi(choice: ColorChoice) {
+    if !enable_virtual_terminal() {
+        ANSI.store(false, Ordering::Relaxed);
+        if choice != ColorChoice::Always {
+            set_color_choice(ColorChoice::Never);
+        }
+    }
+}
+
+#[cfg(windows)]
+fn enable_virtual_terminal() -> bool {
+    use std::ffi::c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(handle: u32) -> *mut c_void;
+        fn GetConsoleMode(console: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: *mut c_void, mode: u32) -> i32;
+    }
+
+    unsafe {
+        let console = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut mode = 0;
+        // Not a console but a pipe or file, which take escape codes as they are
+        if GetConsoleMode(console, &mut mode) == 0 {
+            return true;
+        }
+        mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+            || SetConsoleMode(console, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+#[cfg(not(windows))]
+fn enable_virtual_terminal() -> bool {
+    true
+}
+
+// Show the cursor a progress bar may have hidden and drop the colors
+// of a message cut short
+pub fn restore_terminal() {
+    for term in [console::Term::stdout(), console::Term::stderr()] {
+        if term.features().is_attended() {
+            let _ = term.show_cursor();
+            let _ = term.write_str("\x1b[0m");
+        }
+    }
+}
+
+// A coarse human readable duration, such as `1h 05m` or `3m 20s`
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs / 60 % 60),
+    }
+}
+
+// Terminals narrower than this only get the counters of the progress line
+const NARROW_WIDTH: usize = 60;
+
+// Where verify is at, as shown by `progress_line`
+pub struct VerifyProgress<'a> {
+    // The position of the exercise being verified, counting from 1
+    pub position: usize,
+    pub total: usize,
+    pub passed: usize,
+    // The topic and file name of the exercise being verified
+    pub section: String,
+    pub file: &'a str,
+    // How long the remaining exercises will probably take,
+    // unknown until the first one is done
+    pub eta: Option<std::time::Duration>,
+}
+
+// The line verify keeps updated while it works through the exercises, e.g.
+// `[  73/110 ] algorithm • algorithm9.rs | 72 passed | ETA 2m 10s`,
+// cut to `width` columns. Narrow terminals get `[  73/110 ] 72 passed`.
+pub fn progress_line(progress: &VerifyProgress, width: usize) -> String {
+    let digits = progress.total.to_string().len();
+    // Padded, so the line doesn't shift as the position grows
+    let counter = format!("[ {:>digits$}/{} ]", progress.position, progress.total);
+    let passed = format!("{} passed", progress.passed);
+    if width < NARROW_WIDTH {
+        return console::truncate_str(&format!("{counter} {passed}"), width, "").into_owned();
+    }
+
+    let mut line = format!(
+        "{} {} • {} | {}",
+        console::style(counter).bold(),
+        progress.section,
+        console::style(progress.file).bold(),
+        console::style(passed).green()
+    );
+    if let Some(eta) = progress.eta {
+        line.push_str(&format!(" | ETA {}", format_duration(eta)));
+    }
+    console::truncate_str(&line, width, "…").into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn durations_are_coarse() {
+        assert_eq!(format_duration(Duration::from_millis(59_900)), "59s");
+        assert_eq!(format_duration(Duration::from_secs(200)), "3m 20s");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1h 05m");
+    }
+
+    #[test]
+    fn progress_line_fits_the_terminal() {
+        console::set_colors_enabled(false);
+        let progress = VerifyProgress {
+            position: 73,
+            total: 110,
+            passed: 72,
+            section: "algorithm".to_string(),
+            file: "algorithm9.rs",
+            eta: Some(Duration::from_secs(130)),
+        };
+        assert_eq!(
+            progress_line(&progress, 80),
+            "[  73/110 ] algorithm • algorithm9.rs | 72 passed | ETA 2m 10s"
+        );
+        assert_eq!(progress_line(&progress, 45), "[  73/110 ] 72 passed");
+        assert_eq!(progress_line(&progress, 62).chars().count(), 62);
+        assert!(progress_line(&progress, 62).ends_with('…'));
+    }
+}
@@ -1,158 +1,584 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
-use console::style;
+use crate::diff;
+use crate::exercise::{ContextLine, Exercise, ExerciseOutput, Mode, State};
+use crate::integrity;
+use crate::progress::Progress;
+use crate::ui;
+use console::{style, Term};
 use indicatif::{ProgressBar, ProgressStyle};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
 // If the Exercise being verified is a test, the verbose boolean
 // determines whether or not the test harness outputs are displayed.
+// Every result is recorded in `state` so that it survives the process,
+// and exercises `state` considers up to date are not compiled again.
+// `history` keeps unchanged prompts from being printed over and over.
 pub fn verify<'a>(
     exercises: impl IntoIterator<Item = &'a Exercise>,
     progress: (usize, usize),
     verbose: bool,
     success_hints: bool,
+    state: &mut Progress,
+    history: &mut PromptHistory,
 ) -> Result<(), &'a Exercise> {
     let (num_done, total) = progress;
-    let bar = ProgressBar::new(total as u64);
-    let mut percentage = num_done as f32 / total as f32 * 100.0;
-    bar.set_style(ProgressStyle::default_bar()
-        .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
-        .progress_chars("#>-")
-    );
-    bar.set_position(num_done as u64);
-    bar.set_message(format!("({:.1} %)", percentage));
+    let mut tracker = Tracker::new(num_done, total, false);
 
     for exercise in exercises {
-        let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-            Mode::Compile => compile_and_run_interactively(exercise, success_hints),
-            Mode::Clippy => compile_only(exercise, success_hints),
-            Mode::BuildScript => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
+        tracker.show(exercise);
+        if state.is_up_to_date(exercise) {
+            report_cached(exercise);
+            tracker.advance();
+            continue;
+        }
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_message(match exercise.mode {
+            Mode::Test | Mode::BuildScript | Mode::CargoTest => format!("Testing {exercise}..."),
+            Mode::Compile | Mode::Clippy => format!("Compiling {exercise}..."),
+        });
+        spinner.enable_steady_tick(100);
+        let outcome = evaluate(exercise);
+        spinner.finish_and_clear();
+        state.count_attempt(exercise, matches!(outcome, Outcome::Passed(_)));
 
-        };
-        if !compile_result.unwrap_or(false) {
+        if !matches!(outcome, Outcome::Passed(_)) {
+            tracker.finalize();
+        }
+        let passed = report(exercise, outcome, verbose, success_hints, history);
+        state.record(exercise, passed);
+        if !passed {
             return Err(exercise);
         }
-        percentage += 100.0 / total as f32;
-        bar.inc(1);
-        bar.set_message(format!("({:.1} %)", percentage));
+        tracker.advance();
     }
     Ok(())
 }
 
-enum RunMode {
-    Interactive,
-    NonInteractive,
+// Compile and run the exercises that aren't `cached` on up to `jobs`
+// threads, handing the index of every exercise with its outcome and how
+// long it took, None for cached ones, to `handle` in the given order. Only `handle`
+// prints anything, so the output of exercises verified at the same time
+// never interleaves. Stops at the first exercise `handle` returns false for.
+fn evaluate_in_order<'a>(
+    exercises: &[&'a Exercise],
+    jobs: usize,
+    cached: &[bool],
+    mut handle: impl FnMut(usize, Option<(Outcome, Duration)>) -> bool,
+) -> Result<(), &'a Exercise> {
+    let total = exercises.len();
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, total.max(1)) {
+            let tx = tx.clone();
+            let (next, stop) = (&next, &stop);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= total {
+                        break;
+                    }
+                    let outcome = (!cached[index]).then(|| {
+                        let start = Instant::now();
+                        let outcome = evaluate(exercises[index]);
+                        (outcome, start.elapsed())
+                    });
+                    if tx.send((index, outcome)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Outcomes that arrived before those of earlier exercises
+        let mut finished = BTreeMap::new();
+        for (index, &exercise) in exercises.iter().enumerate() {
+            let outcome = loop {
+                match finished.remove(&index) {
+                    Some(outcome) => break outcome,
+                    None => {
+                        let (i, outcome) = rx.recv().expect("a verification worker stopped unexpectedly");
+                        finished.insert(i, outcome);
+                    }
+                }
+            };
+            if !handle(index, outcome) {
+                stop.store(true, Ordering::Relaxed);
+                return Err(exercise);
+            }
+        }
+        Ok(())
+    })
 }
 
-// Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose, false)?;
-    Ok(())
+// Like `verify`, but compiles and runs up to `jobs` exercises at the same
+// time. The results are still reported one exercise at a time in the
+// given order, stopping at the first failure.
+// With `plain` set, every exercise gets a single line such as
+// `PASS variables1 (0.3s)` instead, without progress bar or prompts.
+pub fn verify_parallel<'a>(
+    exercises: &[&'a Exercise],
+    jobs: usize,
+    verbose: bool,
+    plain: bool,
+    state: &mut Progress,
+) -> Result<(), &'a Exercise> {
+    let mut tracker = Tracker::new(0, exercises.len(), plain);
+    let mut history = PromptHistory::default();
+    let cached: Vec<bool> = exercises.iter().map(|e| state.is_up_to_date(e)).collect();
+    if let Some(&first) = exercises.first() {
+        tracker.show(first);
+    }
+    evaluate_in_order(exercises, jobs, &cached, |index, outcome| {
+        let exercise = exercises[index];
+        // Show the exercise waited for next
+        let advance = |tracker: &mut Tracker| {
+            tracker.advance();
+            if let Some(&next) = exercises.get(index + 1) {
+                tracker.show(next);
+            }
+        };
+        let (outcome, elapsed) = match outcome {
+            Some(outcome) => outcome,
+            None if plain => {
+                println!("PASS {} (cached)", exercise.name);
+                return true;
+            }
+            None => {
+                report_cached(exercise);
+                advance(&mut tracker);
+                return true;
+            }
+        };
+        state.count_attempt(exercise, matches!(outcome, Outcome::Passed(_)));
+        let passed = if plain {
+            report_plain(exercise, &outcome, elapsed)
+        } else {
+            if !matches!(outcome, Outcome::Passed(_)) {
+                tracker.finalize();
+            }
+            report(exercise, outcome, verbose, false, &mut history)
+        };
+        state.record(exercise, passed);
+        if passed {
+            advance(&mut tracker);
+        }
+        passed
+    })
 }
 
-// Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+/// An exercise `verify --keep-going` found not to pass,
+/// with the line of its output telling why
+pub struct Failure<'a> {
+    pub exercise: &'a Exercise,
+    pub error: String,
+}
 
-    let _ = compile(exercise, &progress_bar)?;
-    progress_bar.finish_and_clear();
+// Like `verify_parallel` with `plain` set, but going on past failures,
+// which are returned in the given order
+pub fn verify_keep_going<'a>(exercises: &[&'a Exercise], jobs: usize, state: &mut Progress) -> Vec<Failure<'a>> {
+    let cached: Vec<bool> = exercises.iter().map(|e| state.is_up_to_date(e)).collect();
+    let mut failures = Vec::new();
+    let _ = evaluate_in_order(exercises, jobs, &cached, |index, outcome| {
+        let exercise = exercises[index];
+        let (outcome, elapsed) = match outcome {
+            Some(outcome) => outcome,
+            None => {
+                println!("PASS {} (cached)", exercise.name);
+                return true;
+            }
+        };
+        state.count_attempt(exercise, matches!(outcome, Outcome::Passed(_)));
+        let passed = report_plain(exercise, &outcome, elapsed);
+        state.record(exercise, passed);
+        if let Some(error) = plain_failure(exercise, &outcome) {
+            failures.push(Failure { exercise, error });
+        }
+        true
+    });
+    failures
+}
 
-    Ok(prompt_for_completion(exercise, None, success_hints))
+// Keeps the progress line of `verify` up to date, see `ui::progress_line`
+struct Tracker {
+    bar: ProgressBar,
+    position: usize,
+    total: usize,
+    // How many exercises passed in this run
+    passed: usize,
+    start: Instant,
 }
 
-// Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+impl Tracker {
+    // `num_done` exercises are already done, before this run
+    fn new(num_done: usize, total: usize, hidden: bool) -> Self {
+        let bar = if hidden { ProgressBar::hidden() } else { ProgressBar::new(total as u64) };
+        bar.set_style(ProgressStyle::default_bar().template("{msg}"));
+        Tracker {
+            bar,
+            position: num_done,
+            total,
+            passed: 0,
+            start: Instant::now(),
+        }
+    }
 
-    let compilation = compile(exercise, &progress_bar)?;
+    // Show that `exercise` is being verified
+    fn show(&self, exercise: &Exercise) {
+        let remaining = self.total.saturating_sub(self.position);
+        // Extrapolated from the average time of the exercises done so far
+        let eta = (self.passed > 0).then(|| self.start.elapsed() / self.passed as u32 * remaining as u32);
+        let file = exercise.path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+        let progress = ui::VerifyProgress {
+            position: (self.position + 1).min(self.total),
+            total: self.total,
+            passed: self.passed,
+            section: exercise.topic(),
+            file,
+            eta,
+        };
+        let width = Term::stderr().size_checked().map_or(80, |(_, columns)| columns as usize);
+        self.bar.set_message(ui::progress_line(&progress, width));
+    }
 
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+    // The exercise shown last passed
+    fn advance(&mut self) {
+        self.position += 1;
+        self.passed += 1;
+        self.bar.inc(1);
+    }
 
-    let output = match result {
-        Ok(output) => output,
-        Err(output) => {
-            warn!("Ran {} with errors", exercise);
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
-            return Err(());
+    // Leave the progress line on screen above the output of a failure
+    fn finalize(&self) {
+        self.bar.abandon();
+    }
+}
+
+// The status of an exercise as reported by `rustlings list`.
+#[derive(Serialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    // The marker has been removed, but the exercise's tests aren't
+    // the ones it came with
+    #[serde(rename = "modified-tests")]
+    ModifiedTests,
+    // The "I AM NOT DONE" marker has been removed but the exercise
+    // doesn't compile or its tests don't pass
+    Failing,
+    // The exercise still contains the "I AM NOT DONE" marker
+    Pending,
+    // The marker has been removed and the exercise passes
+    Done,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Status::ModifiedTests => "Modified tests",
+            Status::Failing => "Failing",
+            Status::Pending => "Pending",
+            Status::Done => "Done",
+        })
+    }
+}
+
+// Work out the status of the given Exercise.
+// Exercises that still have the marker are Pending without compiling
+// anything; the others are checked the same way `verify` checks them,
+// but silently.
+pub fn status(exercise: &Exercise) -> Status {
+    if !exercise.looks_done() {
+        return Status::Pending;
+    }
+    if integrity::tests_modified(exercise) {
+        return Status::ModifiedTests;
+    }
+    if check(exercise).is_ok() {
+        Status::Done
+    } else {
+        Status::Failing
+    }
+}
+
+// The first line of the error that keeps the given Exercise from passing,
+// or None if it passes
+pub fn first_error_line(exercise: &Exercise) -> Option<String> {
+    check(exercise).err().map(|output| error_line(&output))
+}
+
+// The line of the output that best describes why it failed
+fn error_line(output: &ExerciseOutput) -> String {
+    if let Some(timeout) = output.timed_out {
+        return format!("timed out after {}s", timeout.as_secs());
+    }
+    let colors = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+    let text = colors.replace_all(&output.stderr, "").into_owned()
+        + &colors.replace_all(&output.stdout, "");
+    let lines = || text.lines().map(str::trim).filter(|l| !l.is_empty());
+    lines()
+        .find(|l| l.starts_with("error") || l.contains("panicked at"))
+        .or_else(|| lines().next())
+        .unwrap_or("failed without any output")
+        .to_string()
+}
+
+// Whether the given Exercise compiles and runs successfully,
+// regardless of its `I AM NOT DONE` comment
+pub fn passes(exercise: &Exercise) -> bool {
+    check(exercise).is_ok()
+}
+
+// Compile and run the given Exercise the way `verify` does,
+// without printing anything
+fn check(exercise: &Exercise) -> Result<(), ExerciseOutput> {
+    match evaluate(exercise) {
+        Outcome::Passed(_) => Ok(()),
+        Outcome::CompileFailed(output) | Outcome::RunFailed(output) | Outcome::TimedOut(output) => {
+            Err(output)
         }
-    };
+        Outcome::TestsModified => Err(ExerciseOutput {
+            stdout: String::new(),
+            stderr: "modified tests".to_string(),
+            timed_out: None,
+        }),
+    }
+}
 
-    Ok(prompt_for_completion(exercise, Some(output.stdout), success_hints))
+// What compiling and running an exercise produced
+enum Outcome {
+    CompileFailed(ExerciseOutput),
+    RunFailed(ExerciseOutput),
+    // The binary was killed for running longer than the exercise's timeout
+    TimedOut(ExerciseOutput),
+    // Its tests were edited, so passing them proves nothing
+    TestsModified,
+    Passed(ExerciseOutput),
 }
 
-// Compile the given Exercise as a test harness and display
-// the output if verbose is set to true
-fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, success_hints: bool) -> Result<bool, ()> {
+// Compile the given Exercise and, unless it is only meant to be
+// compiled, run it. Nothing is printed, so this can run on any thread.
+fn evaluate(exercise: &Exercise) -> Outcome {
+    evaluate_with_args(exercise, &[])
+}
+
+// Like `evaluate`, passing `test_args` on to the test harness
+fn evaluate_with_args(exercise: &Exercise, test_args: &[String]) -> Outcome {
+    if integrity::tests_modified(exercise) {
+        return Outcome::TestsModified;
+    }
+    let compilation = match exercise.compile() {
+        Ok(compilation) => compilation,
+        Err(output) => return Outcome::CompileFailed(output),
+    };
+    match exercise.mode {
+        Mode::Clippy => Outcome::Passed(ExerciseOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            timed_out: None,
+        }),
+        Mode::Compile | Mode::Test | Mode::BuildScript | Mode::CargoTest => match compilation.run_with_args(test_args) {
+            Ok(output) => Outcome::Passed(output),
+            Err(output) if output.timed_out.is_some() => Outcome::TimedOut(output),
+            Err(output) => Outcome::RunFailed(output),
+        },
+    }
+}
+
+// Compile and run the resulting test harness of the given Exercise,
+// with `test_args` such as a test name filter
+pub fn test(exercise: &Exercise, verbose: bool, test_args: &[String]) -> Result<(), ()> {
     let progress_bar = ProgressBar::new_spinner();
     progress_bar.set_message(format!("Testing {exercise}..."));
     progress_bar.enable_steady_tick(100);
-
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
+    let outcome = evaluate_with_args(exercise, test_args);
     progress_bar.finish_and_clear();
 
-    match result {
-        Ok(output) => {
+    match outcome {
+        Outcome::Passed(output) => {
             if verbose {
                 println!("{}", output.stdout);
             }
-            if let RunMode::Interactive = run_mode {
-                Ok(prompt_for_completion(exercise, None, success_hints))
-            } else {
-                Ok(true)
-            }
+            Ok(())
         }
-        Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stdout);
+        failure => {
+            report_failure(exercise, &failure);
             Err(())
         }
     }
 }
 
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a, 'b>(
-    exercise: &'a Exercise,
-    progress_bar: &'b ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
+// Tell the user how the given Exercise fared, prompting them to
+// move on if it passed. Returns whether the exercise is completed.
+// If the Exercise is a test, the test harness output is displayed
+// when verbose is set to true
+fn report(
+    exercise: &Exercise,
+    outcome: Outcome,
+    verbose: bool,
+    success_hints: bool,
+    history: &mut PromptHistory,
+) -> bool {
+    let output = match outcome {
+        Outcome::Passed(output) => output,
+        failure => {
+            report_failure(exercise, &failure);
+            return false;
+        }
+    };
+    match exercise.mode {
+        Mode::Test | Mode::BuildScript | Mode::CargoTest => {
+            if verbose {
+                println!("{}", output.stdout);
+            }
+            prompt_for_completion(exercise, None, success_hints, history)
+        }
+        Mode::Compile => prompt_for_completion(exercise, Some(output.stdout), success_hints, history),
+        Mode::Clippy => prompt_for_completion(exercise, None, success_hints, history),
+    }
+}
 
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
+// Print a single plain line telling how the given Exercise fared,
+// returning whether it is completed
+fn report_plain(exercise: &Exercise, outcome: &Outcome, elapsed: Duration) -> bool {
+    match plain_failure(exercise, outcome) {
+        None => {
+            println!("PASS {} ({:.1}s)", exercise.name, elapsed.as_secs_f32());
+            true
+        }
+        Some(error) => {
+            println!("FAIL {}: {error}", exercise.name);
+            false
+        }
+    }
+}
+
+// Why the exercise isn't completed, in a single line
+fn plain_failure(exercise: &Exercise, outcome: &Outcome) -> Option<String> {
+    match outcome {
+        Outcome::Passed(_) if exercise.looks_done() => None,
+        Outcome::Passed(_) => Some("still marked `I AM NOT DONE`".to_string()),
+        Outcome::CompileFailed(output) | Outcome::RunFailed(output) | Outcome::TimedOut(output) => {
+            Some(error_line(output))
+        }
+        Outcome::TestsModified => Some("modified tests".to_string()),
+    }
+}
+
+fn report_cached(exercise: &Exercise) {
+    println!("{} {exercise}", style("cached ✓").green());
+}
+
+// Why an exercise reading its standard input can still run forever
+pub fn input_note(exercise: &Exercise) -> String {
+    match &exercise.stdin_file {
+        Some(path) => format!(
+            "Its standard input is {}, a loop reading past its end gets nothing more.",
+            path.display()
+        ),
+        None => "Its standard input is closed, a loop waiting for input gets nothing and never ends.".to_string(),
+    }
+}
+
+fn report_failure(exercise: &Exercise, outcome: &Outcome) {
+    match outcome {
+        Outcome::CompileFailed(output) => {
             warn!(
                 "Compiling of {} failed! Please try again. Here's the output:",
                 exercise
             );
             println!("{}", output.stderr);
-            Err(())
         }
+        Outcome::TimedOut(output) => {
+            let secs = output.timed_out.unwrap_or_default().as_secs();
+            warn!("{} timed out after {secs}s! Is there an endless loop?", exercise);
+            println!("{}", output.stdout);
+            println!("{}", output.stderr);
+            println!("{}", input_note(exercise));
+        }
+        Outcome::RunFailed(output) if matches!(exercise.mode, Mode::Compile) => {
+            warn!("Ran {} with errors", exercise);
+            println!("{}", output.stdout);
+            println!("{}", output.stderr);
+        }
+        Outcome::RunFailed(output) => {
+            warn!(
+                "Testing of {} failed! Please try again. Here's the output:",
+                exercise
+            );
+            println!("{}", output.stdout);
+            print_assertion_diffs(&output.stdout);
+        }
+        Outcome::TestsModified => {
+            warn!("The tests of {} have been modified! They have to pass as they came.", exercise);
+            println!("`rustlings reset {}` restores the whole exercise, tests included.", exercise.name);
+        }
+        Outcome::Passed(_) => {}
     }
 }
 
-fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, success_hints: bool) -> bool {
+// Highlight what differs between the two sides of every failed `assert_eq!`.
+// Only people reading a terminal benefit, so pipes get the raw output alone.
+fn print_assertion_diffs(test_output: &str) {
+    if !console::user_attended() {
+        return;
+    }
+    for diff in diff::assertions(test_output)
+        .iter()
+        .filter_map(|(left, right)| diff::word_diff(left, right))
+    {
+        println!("{diff}");
+    }
+}
+
+// Remembers the last "I AM NOT DONE" context that was shown, so that
+// rebuilds that don't change it don't print it again.
+// Forgetting it makes the next prompt print everything.
+#[derive(Default)]
+pub struct PromptHistory {
+    last: Option<(PathBuf, Vec<ContextLine>)>,
+}
+
+impl PromptHistory {
+    pub fn forget(&mut self) {
+        self.last = None;
+    }
+
+    // Remember the context shown for the exercise,
+    // returning whether it is the same as last time
+    fn is_repeat(&mut self, exercise: &Exercise, context: &[ContextLine]) -> bool {
+        let repeat = matches!(&self.last, Some((path, last)) if *path == exercise.path && last == context);
+        self.last = Some((exercise.path.clone(), context.to_vec()));
+        repeat
+    }
+}
+
+fn prompt_for_completion(
+    exercise: &Exercise,
+    prompt_output: Option<String>,
+    success_hints: bool,
+    history: &mut PromptHistory,
+) -> bool {
     let context = match exercise.state() {
         State::Done => return true,
         State::Pending(context) => context,
     };
     match exercise.mode {
         Mode::Compile => success!("Successfully ran {}!", exercise),
-        Mode::Test => success!("Successfully tested {}!", exercise),
+        Mode::Test | Mode::CargoTest => success!("Successfully tested {}!", exercise),
         Mode::Clippy => success!("Successfully compiled {}!", exercise),
         Mode::BuildScript => success!("Successfully compiled {}!", exercise),
     }
@@ -167,7 +593,7 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
 
     let success_msg = match exercise.mode {
         Mode::Compile => "The code is compiling!",
-        Mode::Test => "The code is compiling, and the tests pass!",
+        Mode::Test | Mode::CargoTest => "The code is compiling, and the tests pass!",
         Mode::Clippy => clippy_success_msg,
         Mode::BuildScript => "Build script works!",
     };
@@ -194,13 +620,30 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
         println!();
     }
 
+    if history.is_repeat(exercise, &context) {
+        let line = context.iter().find(|l| l.important).map_or(0, |l| l.number);
+        println!(
+            "(still waiting for the {} comment on line {line} to be removed)",
+            style("`I AM NOT DONE`").bold()
+        );
+        return false;
+    }
+
     println!("You can keep working on this exercise,");
     println!(
         "or jump into the next one by removing the {} comment:",
         style("`I AM NOT DONE`").bold()
     );
     println!();
+    // Wide enough for the last line number, so that the lines stay aligned
+    let width = context.last().map_or(0, |l| l.number.to_string().len()).max(2);
+    let mut previous = None;
     for context_line in context {
+        // Between the signature of the function and the lines around the comment
+        if previous.is_some_and(|number| number + 1 < context_line.number) {
+            println!("{} {}", style(format!("{:>width$}", "…")).blue(), style("|").blue());
+        }
+        previous = Some(context_line.number);
         let formatted_line = if context_line.important {
             format!("{}", style(context_line.line).bold())
         } else {
@@ -208,8 +651,8 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
         };
 
         println!(
-            "{:>2} {}  {}",
-            style(context_line.number).blue().bold(),
+            "{} {}  {}",
+            style(format!("{:>width$}", context_line.number)).blue().bold(),
             style("|").blue(),
             formatted_line
         );
@@ -221,3 +664,53 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
 fn separator() -> console::StyledObject<&'static str> {
     style("====================").bold()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn exercise(path: &str, mode: Mode) -> Exercise {
+        Exercise {
+            name: String::from("example"),
+            path: PathBuf::from(path),
+            additional_files: Vec::new(),
+            mode,
+            hint: String::new(),
+            hints: BTreeMap::new(),
+            env: BTreeMap::new(),
+            editable_tests: false,
+            timeout_secs: None,
+            stdin_file: None,
+            tags: Vec::new(),
+            difficulty: 0,
+            release: false,
+            points: 1,
+            requires: Vec::new(),
+            clippy_args: None,
+            manifest: None,
+            cfg: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn first_error_line_of_compile_failure() {
+        let exercise = exercise("tests/fixture/failure/compFailure.rs", Mode::Compile);
+        let line = first_error_line(&exercise).unwrap();
+        assert!(line.starts_with("error"), "{line}");
+        assert!(!line.contains('\x1b'), "{line}");
+    }
+
+    #[test]
+    fn first_error_line_of_failing_test() {
+        let exercise = exercise("tests/fixture/failure/testNotPassed.rs", Mode::Test);
+        let line = first_error_line(&exercise).unwrap();
+        assert!(line.contains("panicked at"), "{line}");
+    }
+
+    #[test]
+    fn no_error_line_when_passing() {
+        let exercise = exercise("tests/fixture/success/testSuccess.rs", Mode::Test);
+        assert_eq!(first_error_line(&exercise), None);
+    }
+}
@@ -0,0 +1,14 @@
+pub fn greet(name: &str) -> String {
+    shout::shout(&format!("hello {name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_loudly() {
+        println!("THE DEPENDENCY WAS BUILT");
+        assert_eq!(greet("crate"), "HELLO CRATE!");
+    }
+}
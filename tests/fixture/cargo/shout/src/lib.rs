@@ -0,0 +1,13 @@
+pub fn shout(text: &str) -> String {
+    format!("{}!", text.to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_case() {
+        assert_eq!(shout("hi"), "hi!");
+    }
+}
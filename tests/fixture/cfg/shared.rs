@@ -0,0 +1,12 @@
+pub fn answer() -> u32 {
+    42
+}
+
+#[cfg(test)]
+#[cfg(not(included))]
+mod tests {
+    #[test]
+    fn only_in_shared() {
+        assert_eq!(super::answer(), 42);
+    }
+}
@@ -0,0 +1,7 @@
+#[path = "shared.rs"]
+mod shared;
+
+#[test]
+fn only_in_user() {
+    assert_eq!(shared::answer(), 42);
+}
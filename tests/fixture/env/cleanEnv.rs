@@ -0,0 +1,4 @@
+#[test]
+fn doesnt_see_the_variable() {
+    assert!(std::env::var("RUSTLINGS_FIXTURE").is_err());
+}
@@ -0,0 +1,4 @@
+#[test]
+fn sees_the_variable() {
+    assert_eq!(std::env::var("RUSTLINGS_FIXTURE").as_deref(), Ok("injected"));
+}
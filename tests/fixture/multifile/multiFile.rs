@@ -0,0 +1,6 @@
+mod helper;
+
+#[test]
+fn uses_helper() {
+    assert_eq!(helper::answer(), 42);
+}
@@ -0,0 +1,5 @@
+fn main() {
+    for i in 0..100_000 {
+        println!("line {i} ünïcödé");
+    }
+}
@@ -0,0 +1,8 @@
+fn first_word(text: &str) -> &str {
+    text.split_whitespace().next().unwrap()
+}
+
+#[test]
+fn finds_the_first_word() {
+    assert_eq!(first_word(""), "");
+}
@@ -0,0 +1,4 @@
+#[test]
+fn is_optimized() {
+    assert!(!cfg!(debug_assertions));
+}
@@ -0,0 +1,14 @@
+// fake_exercise
+
+pub fn parse(
+    input: &str,
+) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    for word in input.split_whitespace() {
+        numbers.push(word.len() as u32);
+    }
+    // I AM NOT DONE
+    numbers
+}
+
+fn main() {}
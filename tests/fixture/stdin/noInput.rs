@@ -0,0 +1,8 @@
+use std::io::{self, Read};
+
+fn main() {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input).unwrap();
+    assert!(input.is_empty());
+    println!("NOTHING TO READ");
+}
@@ -0,0 +1,7 @@
+use std::io::{self, BufRead};
+
+fn main() {
+    let lines: Vec<String> = io::stdin().lock().lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["first line", "second line"]);
+    println!("READ {} LINES", lines.len());
+}
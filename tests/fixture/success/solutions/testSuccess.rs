@@ -0,0 +1,5 @@
+#[test]
+fn passing() {
+    println!("THIS TEST TOO SHALL PASS");
+    assert_eq!(1 + 1, 2);
+}
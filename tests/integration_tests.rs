@@ -40,11 +40,25 @@ fn verify_fails_if_some_fails() {
         .code(1);
 }
 
+#[test]
+fn verify_keep_going_sums_up_every_failure() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--keep-going", "--jobs", "3"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("FAIL compFailure"))
+        .stdout(predicates::str::contains("FAIL testFailure"))
+        .stdout(predicates::str::contains("compTimeout      \ttimed out after 1s"))
+        .stdout(predicates::str::contains("Summary: 0 passed, 3 failed, 0 skipped"));
+}
+
 #[test]
 fn run_single_compile_success() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "compSuccess"])
+        .args(["run", "compSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .success();
@@ -54,7 +68,7 @@ fn run_single_compile_success() {
 fn run_single_compile_failure() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "compFailure"])
+        .args(["run", "compFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
@@ -64,7 +78,7 @@ fn run_single_compile_failure() {
 fn run_single_test_success() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testSuccess"])
+        .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .success();
@@ -74,17 +88,61 @@ fn run_single_test_success() {
 fn run_single_test_failure() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testFailure"])
+        .args(["run", "testFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
 }
 
+#[test]
+fn run_single_compile_timeout() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compTimeout"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("compTimeout.rs timed out after 1s"));
+}
+
+#[test]
+fn timeout_flag_overrides_info_toml() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--timeout", "2", "run", "compTimeout"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("timed out after 2s"));
+}
+
+#[test]
+fn hint_all_prints_every_hint_of_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "--all", "testfailure"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .success()
+        .stdout("testFailure\nHello!\n\n");
+}
+
+#[test]
+fn hint_all_unknown_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "--all", "testfail"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Did you mean: testFailure?"));
+}
+
 #[test]
 fn run_single_test_not_passed() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testNotPassed.rs"])
+        .args(["run", "testNotPassed.rs"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
@@ -104,7 +162,7 @@ fn run_single_test_no_filename() {
 fn run_single_test_no_exercise() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "compNoExercise.rs"])
+        .args(["run", "compNoExercise.rs"])
         .current_dir("tests/fixture/failure")
         .assert()
         .code(1);
@@ -114,7 +172,7 @@ fn run_single_test_no_exercise() {
 fn reset_single_exercise() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["reset", "intro1"])
+        .args(["reset", "intro1"])
         .assert()
         .code(0);
 }
@@ -127,7 +185,7 @@ fn reset_no_exercise() {
         .assert()
         .code(1)
         .stderr(predicates::str::contains(
-            "positional arguments not provided",
+            "Provide either the name of an exercise or --all",
         ));
 }
 
@@ -135,7 +193,7 @@ fn reset_no_exercise() {
 fn get_hint_for_single_test() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["hint", "testFailure"])
+        .args(["hint", "testFailure"])
         .current_dir("tests/fixture/failure")
         .assert()
         .code(0)
@@ -171,18 +229,72 @@ fn all_exercises_require_confirmation() {
 fn run_compile_exercise_does_not_prompt() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "pending_exercise"])
+        .args(["run", "pending_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
 }
 
+#[test]
+fn run_without_name_picks_pending_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("Current exercise: pending_exercise.rs"));
+}
+
+#[test]
+fn hint_without_name_picks_failing_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success()
+        .stdout("Current exercise: compFailure.rs\n\n");
+}
+
+#[test]
+fn edit_opens_exercise_in_editor() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "pending_exercise"])
+        .env_remove("VISUAL")
+        .env("EDITOR", "echo --")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout("-- pending_exercise.rs\n");
+}
+
+#[test]
+fn edit_reports_editor_failure() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "pending_exercise"])
+        .env("VISUAL", "false")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "pending_exercise"])
+        .env("VISUAL", "no-such-editor-rustlings")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("The exercise is at pending_exercise.rs"));
+}
+
 #[test]
 fn run_test_exercise_does_not_prompt() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "pending_test_exercise"])
+        .args(["run", "pending_test_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
         .code(0)
@@ -193,18 +305,209 @@ fn run_test_exercise_does_not_prompt() {
 fn run_single_test_success_with_output() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["--nocapture", "run", "testSuccess"])
+        .args(["--nocapture", "run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PASS"));
 }
 
+#[test]
+fn run_passes_arguments_to_the_tests() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testSuccess", "--", "passing", "--nocapture"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("test passing ... THIS TEST TOO SHALL PASS"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testSuccess", "--", "no_such_test"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("1 filtered out"));
+}
+
+#[test]
+fn run_ignores_test_arguments_of_compiled_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compSuccess", "--nocapture"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("isn't a test exercise, ignoring the arguments"));
+}
+
+#[test]
+fn run_pipes_stdin_file_into_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "readsInput"])
+        .current_dir("tests/fixture/stdin/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("READ 2 LINES"));
+}
+
+#[test]
+fn exercise_env_is_set_for_that_exercise_only() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "2"])
+        .current_dir("tests/fixture/env/")
+        .env_remove("RUSTLINGS_FIXTURE")
+        .assert()
+        .success();
+}
+
+#[test]
+fn release_exercises_are_optimized() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "optimized"])
+        .current_dir("tests/fixture/release/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("(release profile)"));
+}
+
+#[test]
+fn run_release_optimizes_any_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "unoptimized"])
+        .current_dir("tests/fixture/release/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("(debug profile)"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "--release", "unoptimized"])
+        .current_dir("tests/fixture/release/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("(release profile)"));
+}
+
+#[test]
+fn runaway_output_is_truncated() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--output-limit", "1000", "run", "chatty"])
+        .current_dir("tests/fixture/output/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("line 10 ").and(predicates::str::contains("line 1000 ").not()))
+        .stdout(predicates::str::contains("bytes omitted), run `rustlings run chatty` to see everything"));
+}
+
+#[test]
+fn run_shows_all_of_the_output() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "chatty"])
+        .current_dir("tests/fixture/output/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("line 99999 ").and(predicates::str::contains("output truncated").not()));
+}
+
+#[test]
+fn panics_show_the_frames_of_the_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "unwrapNone"])
+        .current_dir("tests/fixture/panic/")
+        .env_remove("RUST_BACKTRACE")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::is_match(r"stack backtrace \(.*\):\n +\d+: unwrapNone::first_word\n").unwrap())
+        .stdout(predicates::str::contains("core::panicking").not());
+}
+
+#[test]
+fn full_backtrace_shows_every_frame() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--full-backtrace", "run", "unwrapNone"])
+        .current_dir("tests/fixture/panic/")
+        .env_remove("RUST_BACKTRACE")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("unwrapNone::first_word"))
+        .stdout(predicates::str::contains("panic_fmt"));
+}
+
+#[test]
+fn run_closes_stdin_without_stdin_file() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "noInput"])
+        .current_dir("tests/fixture/stdin/")
+        .stdin(File::open("tests/fixture/stdin/input.txt").unwrap())
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("NOTHING TO READ"));
+}
+
+#[test]
+fn cargo_test_exercises_build_with_cargo() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--nocapture", "run", "cargoSuccess"])
+        .current_dir("tests/fixture/cargo/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("THE DEPENDENCY WAS BUILT"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "cargoFailure"])
+        .current_dir("tests/fixture/cargo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("keeps_the_case"));
+}
+
+#[test]
+fn quiz_leaves_solved_exercises_alone() {
+    let first = |output: &[u8]| {
+        String::from_utf8_lossy(output)
+            .lines()
+            .find(|l| l.contains("1/2: "))
+            .map(str::to_string)
+    };
+    let quiz = || {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["quiz", "--seed", "3"])
+            .current_dir("tests/fixture/success/")
+            .stdin(File::open("tests/fixture/quiz_commands.txt").unwrap())
+            .output()
+            .unwrap()
+    };
+    let output = quiz();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("with seed 3"), "{stdout}");
+    assert!(stdout.contains("Quiz results"), "{stdout}");
+    assert_eq!(first(&output.stdout), first(&quiz().stdout));
+
+    let mut source = String::new();
+    File::open("tests/fixture/success/compSuccess.rs")
+        .unwrap()
+        .read_to_string(&mut source)
+        .unwrap();
+    assert!(!source.contains("I AM NOT DONE"));
+}
+
 #[test]
 fn run_single_test_success_without_output() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testSuccess"])
+        .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .code(0)
@@ -215,17 +518,142 @@ fn run_single_test_success_without_output() {
 fn run_rustlings_list() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success();
 }
 
+#[test]
+fn exercise_dir_is_used_instead_of_the_current_one() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--exercise-dir", "success", "run", "compSuccess"])
+        .current_dir("tests/fixture")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Successfully ran compSuccess.rs"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names"])
+        .env("RUSTLINGS_DIR", "state")
+        .current_dir("tests/fixture")
+        .assert()
+        .success()
+        .stdout("pending_exercise\npending_test_exercise\nfinished_exercise\n");
+}
+
+#[test]
+fn exercise_dir_without_info_toml_says_where_it_looked() {
+    let dir = std::fs::canonicalize("tests/fixture").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--exercise-dir", "tests/fixture", "list"])
+        .assert()
+        .code(1)
+        .stdout(format!(
+            "The exercise directory tests/fixture has no info.toml, there is no {}\n",
+            dir.join("info.toml").display()
+        ));
+}
+
+#[test]
+fn concurrent_runs_of_one_exercise_all_pass() {
+    let runs: Vec<std::process::Child> = (0..6)
+        .map(|_| {
+            Command::cargo_bin("rustlings")
+                .unwrap()
+                .args(["run", "testSuccess"])
+                .current_dir("tests/fixture/success")
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut run in runs {
+        assert!(run.wait().unwrap().success());
+    }
+}
+
+#[test]
+fn edited_tests_keep_an_exercise_from_passing() {
+    let dir = std::env::temp_dir().join(format!("rustlings_edited_tests_{}", std::process::id()));
+    let exercise = "exercises/hashmaps/hashmaps1.rs";
+    std::fs::create_dir_all(dir.join("exercises/hashmaps")).unwrap();
+    let source = std::fs::read_to_string(exercise).unwrap().replace("I AM NOT DONE", "");
+    let (code, _) = source.split_once("#[cfg(test)]").unwrap();
+    let edited = format!("{code}#[cfg(test)]\nmod tests {{\n    #[test]\n    fn nothing() {{}}\n}}\n");
+    std::fs::write(dir.join(exercise), edited).unwrap();
+    let info = format!("[[exercises]]\nname = \"hashmaps1\"\npath = \"{exercise}\"\nmode = \"test\"\nhint = \"\"\n");
+    std::fs::write(dir.join("info.toml"), &info).unwrap();
+
+    let score = Command::cargo_bin("rustlings").unwrap().arg("score").current_dir(&dir).output().unwrap();
+    let verify = Command::cargo_bin("rustlings").unwrap().arg("verify").current_dir(&dir).output().unwrap();
+    std::fs::write(dir.join("info.toml"), info + "editable_tests = true\n").unwrap();
+    let editable = Command::cargo_bin("rustlings").unwrap().arg("score").current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let score = String::from_utf8_lossy(&score.stdout);
+    assert!(score.contains(r#""status": "modified-tests""#), "{score}");
+    assert!(!verify.status.success());
+    assert!(String::from_utf8_lossy(&verify.stdout).contains("FAIL hashmaps1: modified tests"));
+    assert!(!String::from_utf8_lossy(&editable.stdout).contains("modified-tests"));
+}
+
+#[test]
+fn committed_edits_to_tests_are_still_caught() {
+    let dir = std::env::temp_dir().join(format!("rustlings_committed_tests_{}", std::process::id()));
+    let exercise = "exercises/hashmaps/hashmaps1.rs";
+    std::fs::create_dir_all(dir.join("exercises/hashmaps")).unwrap();
+    let source = std::fs::read_to_string(exercise).unwrap().replace("I AM NOT DONE", "");
+    let edited = source.replacen(">= 5", ">= 0", 1);
+    std::fs::write(dir.join(exercise), edited).unwrap();
+    let info = format!("[[exercises]]\nname = \"hashmaps1\"\npath = \"{exercise}\"\nmode = \"test\"\nhint = \"\"\n");
+    std::fs::write(dir.join("info.toml"), info).unwrap();
+    // The student's own repository, whose last commit has the edited tests
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=student", "-c", "user.email=student@example.com"])
+            .args(args)
+            .current_dir(&dir)
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?}");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "Solve hashmaps1"]);
+
+    let score = Command::cargo_bin("rustlings").unwrap().arg("score").current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let score = String::from_utf8_lossy(&score.stdout);
+    assert!(score.contains(r#""status": "modified-tests""#), "{score}");
+}
+
+#[test]
+fn compiler_output_is_colored_only_when_asked() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--color", "always", "run", "compFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("\x1b[91merror"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("\x1b[").not());
+}
+
 #[test]
 fn run_rustlings_list_no_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success()
@@ -236,7 +664,7 @@ fn run_rustlings_list_no_pending() {
 fn run_rustlings_list_both_done_and_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(["list"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -247,7 +675,7 @@ fn run_rustlings_list_both_done_and_pending() {
 fn run_rustlings_list_without_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list", "--solved"])
+        .args(["list", "--solved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -258,9 +686,707 @@ fn run_rustlings_list_without_pending() {
 fn run_rustlings_list_without_done() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list", "--unsolved"])
+        .args(["list", "--unsolved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn run_rustlings_list_failing() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Failing").and(predicates::str::contains("Done").not()));
+}
+
+#[test]
+fn run_rustlings_list_failing_is_not_solved() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--solved"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("compFailure").not());
+}
+
+#[test]
+fn run_rustlings_list_by_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--topic", "algorithm"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("algorithm10").and(predicates::str::contains("variables1").not()));
+}
+
+#[test]
+fn run_rustlings_list_topic_and_unsolved() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--unsolved", "--topic", "pending_test_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("pending_test_exercise")
+                .and(predicates::str::contains("pending_exercise\n").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_sorted() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--sort", "name"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with(
+            "finished_exercise\npending_exercise\npending_test_exercise\n",
+        ));
+}
+
+#[test]
+fn run_rustlings_list_sorted_by_status() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--paths", "--sort", "status"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with(
+            "pending_exercise.rs\npending_test_exercise.rs\nfinished_exercise.rs\n",
+        ));
+}
+
+#[test]
+fn run_rustlings_list_unknown_sort() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--sort", "size"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn run_rustlings_list_json() {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--json", "--topic", "algorithm"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = entries.as_array().unwrap();
+    // As many as info.toml has, so that adding an exercise doesn't break this
+    let info: toml::Value = toml::from_str(&std::fs::read_to_string("info.toml").unwrap()).unwrap();
+    let algorithms = info["exercises"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|e| e["path"].as_str().unwrap().starts_with("exercises/algorithm/"))
+        .count();
+    assert!(algorithms > 0);
+    assert_eq!(entries.len(), algorithms);
+    for entry in entries {
+        let entry = entry.as_object().unwrap();
+        // additional_files is only there for exercises that have some
+        let mut keys: Vec<&str> = entry.keys().map(String::as_str).filter(|&k| k != "additional_files").collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["hint", "mode", "name", "path", "seconds_spent", "status", "topic"]);
+        assert_eq!(entry["topic"], "algorithm");
+        assert!(entry["path"].as_str().unwrap().starts_with("exercises/algorithm/"));
+        assert!(["compile", "test", "clippy", "buildscript"].contains(&entry["mode"].as_str().unwrap()));
+        assert!(["done", "pending", "failing"].contains(&entry["status"].as_str().unwrap()));
+    }
+}
+
+#[test]
+fn run_rustlings_list_json_status() {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--json"])
+        .current_dir("tests/fixture/state")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let statuses: Vec<&str> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["pending", "pending", "done"]);
+}
+
+#[test]
+fn verify_only_reports_skipped_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--interactive", "--only", "test*"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("1 of 2 exercises passed, 1 were skipped")
+                .and(predicates::str::contains("compSuccess").not()),
+        );
+}
+
+#[test]
+fn verify_from_starts_at_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--interactive", "--from", "testFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Compiling of testFailure.rs failed"));
+}
+
+#[test]
+fn verify_from_unknown_exercise_suggests_names() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--from", "testSucess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(1)
+        .stdout("No exercise found for 'testSucess'!\nDid you mean: testSuccess?\n");
+}
+
+#[test]
+fn score_never_fails() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("score")
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains(r#""passed": 0,"#)
+                .and(predicates::str::contains(r#""name": "compTimeout""#))
+                .and(predicates::str::contains(r#""status": "failing""#)),
+        );
+}
+
+#[test]
+fn score_writes_report() {
+    let report = std::env::temp_dir().join(format!("rustlings_score_{}.json", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["score", "--output"])
+        .arg(&report)
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Score: 2/2 (100.0 %), 4/4 points"));
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    std::fs::remove_file(&report).unwrap();
+    assert_eq!(json["passed"], 2);
+    assert_eq!(json["total"], 2);
+    assert_eq!(json["sections"]["compSuccess"]["passed"], 1);
+    assert_eq!(json["exercises"][1]["mode"], "test");
+    assert_eq!(json["points"], 4);
+    assert_eq!(json["total_points"], 4);
+    assert_eq!(json["sections"]["testSuccess"]["total_points"], 3);
+    assert_eq!(json["exercises"][1]["points"], 3);
+}
+
+#[test]
+fn list_points_shows_weights() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--points"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Points"))
+        .stdout(predicates::str::contains("Points: You earned 1 / 3 points."));
+}
+
+#[test]
+fn verify_tag_limits_scope() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--interactive", "--tag", "Testing"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("1 of 2 exercises passed"));
+}
+
+#[test]
+fn list_by_tag_and_difficulty() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--tag", "assertions"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout("testSuccess\n");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--max-difficulty", "1"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout("compSuccess\n");
+}
+
+#[test]
+fn list_unmet_prerequisites() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--prerequisites", "testFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success()
+        .stdout("compFailure      \tcompFailure.rs\n");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--prerequisites", "compFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success()
+        .stdout("All prerequisites of compFailure are done.\n");
+}
+
+#[test]
+fn completions_outside_rustlings_directory() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["completions", "bash"])
+        .current_dir("tests")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("complete -F _rustlings rustlings"));
+}
+
+#[test]
+fn completions_unknown_shell() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn color_never_prints_no_escape_codes() {
+    for args in [&["--color", "never", "verify"][..], &["--no-color", "run", "compFailure"]] {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir("tests/fixture/failure")
+            .assert()
+            .code(1)
+            .stdout(predicates::str::contains("compFailure").and(predicates::str::contains("\x1b[").not()));
+    }
+}
+
+#[test]
+fn color_always_colors_compiler_errors() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--color", "always", "verify", "--interactive"])
+        .current_dir("tests/fixture/failure")
+        .env_remove("NO_COLOR")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("\x1b["));
+}
+
+#[test]
+fn no_color_env_var_disables_colors() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir("tests/fixture/failure")
+        .env("NO_COLOR", "1")
+        .env("CLICOLOR_FORCE", "1")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("\x1b[").not());
+}
+
+#[test]
+fn verify_in_parallel_success() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "4"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success();
+}
+
+#[test]
+fn verify_in_parallel_reports_first_failure_in_order() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--interactive", "--jobs", "4"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("Compiling of compFailure.rs failed")
+                .and(predicates::str::contains("testFailure").not()),
+        );
+}
+
+// A copy of a fixture directory that tests may modify, removed on drop
+struct ScratchFixture(std::path::PathBuf);
+
+impl ScratchFixture {
+    fn new(fixture: &str, name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("rustlings_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for entry in std::fs::read_dir(format!("tests/fixture/{fixture}")).unwrap() {
+            let entry = entry.unwrap();
+            // leave out state written by other tests, and directories
+            if entry.file_name().to_string_lossy().starts_with('.') || entry.path().is_dir() {
+                continue;
+            }
+            std::fs::copy(entry.path(), dir.join(entry.file_name())).unwrap();
+        }
+        ScratchFixture(dir)
+    }
+}
+
+impl Drop for ScratchFixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn verify_skips_cached_exercises() {
+    let scratch = ScratchFixture::new("success", "cached");
+    let verify = || {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["verify", "--interactive"])
+            .current_dir(&scratch.0)
+            .assert()
+            .success()
+    };
+
+    verify().stdout(predicates::str::contains("cached").not());
+    verify().stdout(
+        predicates::str::contains("cached ✓ compSuccess.rs")
+            .and(predicates::str::contains("cached ✓ testSuccess.rs")),
+    );
+
+    std::fs::write(
+        scratch.0.join("compSuccess.rs"),
+        "fn main() {\n    println!(\"edited\");\n}\n",
+    )
+    .unwrap();
+    verify().stdout(
+        predicates::str::contains("cached ✓ compSuccess.rs")
+            .not()
+            .and(predicates::str::contains("cached ✓ testSuccess.rs")),
+    );
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--force-recheck"])
+        .current_dir(&scratch.0)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("cached").not());
+}
+
+#[test]
+fn list_records_results_for_verify() {
+    let scratch = ScratchFixture::new("success", "list_cache");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir(&scratch.0)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Done"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--interactive"])
+        .current_dir(&scratch.0)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("cached ✓ compSuccess.rs")
+                .and(predicates::str::contains("cached ✓ testSuccess.rs")),
+        );
+}
+
+#[test]
+fn verify_without_terminal_prints_plain_lines() {
+    let scratch = ScratchFixture::new("success", "plain");
+    let verify = || {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .arg("verify")
+            .current_dir(&scratch.0)
+            .assert()
+            .success()
+    };
+
+    verify().stdout(
+        predicates::str::contains("PASS compSuccess (")
+            .and(predicates::str::contains("PASS testSuccess ("))
+            .and(predicates::str::contains(
+                "Summary: 2 passed, 0 failed, 0 not verified, 0 skipped",
+            )),
+    );
+    verify().stdout(predicates::str::contains("PASS testSuccess (cached)"));
+}
+
+#[test]
+fn verify_ci_reports_failure_on_one_line() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--ci"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("FAIL compFailure: error")
+                .and(predicates::str::contains("Summary: 0 passed, 1 failed"))
+                .and(predicates::str::contains("Compiling of").not()),
+        );
+}
+
+#[test]
+fn cfg_options_leave_out_included_tests() {
+    let run = |name: &str| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["--nocapture", "run", name])
+            .current_dir("tests/fixture/cfg")
+            .assert()
+            .success()
+    };
+    run("user").stdout(
+        predicates::str::contains("1 passed")
+            .and(predicates::str::contains("only_in_user"))
+            .and(predicates::str::contains("only_in_shared").not()),
+    );
+    run("shared").stdout(predicates::str::contains("only_in_shared"));
+}
+
+#[test]
+fn multi_file_exercise_is_one_unit() {
+    let scratch = ScratchFixture::new("multifile", "multifile");
+    let rustlings = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rustlings").unwrap();
+        cmd.args(args).current_dir(&scratch.0);
+        cmd
+    };
+    let helper = scratch.0.join("helper.rs");
+    let original = std::fs::read_to_string(&helper).unwrap();
+
+    rustlings(&["verify", "--interactive"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("All 1 exercises passed!"));
+
+    // breaking the module breaks the exercise, even though its root is unchanged
+    std::fs::write(&helper, "pub fn answer() -> u32 {\n    41\n}\n").unwrap();
+    rustlings(&["verify", "--interactive"])
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("cached").not());
+
+    rustlings(&["list", "--json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("\"additional_files\": [\n      \"helper.rs\"\n    ]"));
+
+    rustlings(&["reset", "multiFile"])
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Reset multiFile.rs, helper.rs to the original state?"));
+    rustlings(&["reset", "multiFile", "--force"]).assert().success();
+    assert_eq!(std::fs::read_to_string(&helper).unwrap(), original);
+}
+
+#[test]
+fn included_exercises_follow_built_in_ones() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--paths"])
+        .current_dir("tests/fixture/include")
+        .assert()
+        .success()
+        .stdout("builtIn.rs\npack/fromPack.rs\n");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--ci"])
+        .current_dir("tests/fixture/include")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("PASS fromPack ("));
+}
+
+#[test]
+fn included_duplicate_is_an_error() {
+    let scratch = ScratchFixture::new("include", "include_duplicate");
+    std::fs::create_dir(scratch.0.join("pack")).unwrap();
+    std::fs::write(
+        scratch.0.join("pack/info.toml"),
+        "[[exercises]]\nname = \"builtIn\"\npath = \"builtIn.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir(&scratch.0)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "The exercise builtIn is defined twice, in info.toml and in pack/info.toml",
+        ));
+}
+
+#[test]
+fn lsp_project_follows_info_toml() {
+    let scratch = ScratchFixture::new("success", "lsp");
+    let rustlings = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rustlings").unwrap();
+        cmd.args(args).current_dir(&scratch.0);
+        cmd
+    };
+    let crates = || {
+        let json = std::fs::read(scratch.0.join("rust-project.json")).unwrap();
+        let project: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        project["crates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| (c["root_module"].as_str().unwrap().to_string(), c["cfg"].to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    rustlings(&["lsp"]).assert().success();
+    assert_eq!(
+        crates(),
+        [
+            ("compSuccess.rs".to_string(), "[]".to_string()),
+            ("testSuccess.rs".to_string(), "[\"test\"]".to_string())
+        ]
+    );
+
+    let info = scratch.0.join("info.toml");
+    let mut toml = std::fs::read_to_string(&info).unwrap();
+    toml.push_str("\n[[exercises]]\nname = \"added\"\npath = \"added.rs\"\nmode = \"compile\"\nhint = \"\"\n");
+    std::fs::write(&info, toml).unwrap();
+    let later = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+    File::options().write(true).open(&info).unwrap().set_modified(later).unwrap();
+    rustlings(&["list", "--names"]).assert().success();
+    assert_eq!(crates().len(), 3);
+}
+
+#[test]
+fn reset_restores_original() {
+    let scratch = ScratchFixture::new("success", "reset");
+    let exercise = scratch.0.join("compSuccess.rs");
+    let original = std::fs::read_to_string(&exercise).unwrap();
+    let rustlings = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rustlings").unwrap();
+        cmd.args(args).current_dir(&scratch.0);
+        cmd
+    };
+
+    // the first run takes the snapshot
+    rustlings(&["list"]).assert().success();
+    std::fs::write(&exercise, "fn main() { broken }").unwrap();
+
+    // declining, or not being able to answer, keeps the changes
+    rustlings(&["reset", "compSuccess"])
+        .with_stdin()
+        .buffer("n\n")
+        .assert()
+        .code(1);
+    assert_eq!(std::fs::read_to_string(&exercise).unwrap(), "fn main() { broken }");
+
+    rustlings(&["reset", "compSuccess"])
+        .with_stdin()
+        .buffer("y\n")
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(&exercise).unwrap(), original);
+
+    std::fs::write(&exercise, "fn main() { broken }").unwrap();
+    rustlings(&["reset", "--all", "--force"]).assert().success();
+    assert_eq!(std::fs::read_to_string(&exercise).unwrap(), original);
+}
+
+#[test]
+fn reset_forgets_progress() {
+    let scratch = ScratchFixture::new("success", "reset_progress");
+    let rustlings = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rustlings").unwrap();
+        cmd.args(args).current_dir(&scratch.0);
+        cmd
+    };
+
+    rustlings(&["verify", "--interactive"]).assert().success();
+    rustlings(&["reset", "--force", "testSuccess"]).assert().success();
+    rustlings(&["verify", "--interactive"])
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("cached ✓ compSuccess.rs")
+                .and(predicates::str::contains("cached ✓ testSuccess.rs").not()),
+        );
+}
+
+#[test]
+fn solution_is_printed() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "testSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("assert_eq!(1 + 1, 2);"));
+}
+
+#[test]
+fn solution_diff() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "--diff", "testSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("--- testSuccess.rs")
+                .and(predicates::str::contains("+++ solutions/testSuccess.rs"))
+                .and(predicates::str::contains("-    assert!(true);"))
+                .and(predicates::str::contains("+    assert_eq!(1 + 1, 2);")),
+        );
+}
+
+#[test]
+fn missing_solution() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "compSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("There is no solution for compSuccess"));
+}